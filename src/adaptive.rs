@@ -0,0 +1,97 @@
+use crate::models::{Difficulty, Question};
+
+/// Difficulty tier that follows the answers in adaptive mode
+/// Starts at medium, goes up a tier after `CLIMB_STREAK` correct answers in a
+/// row and down a tier after any miss, staying put at either end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TierLadder {
+    tier: Difficulty,
+    /// Correct answers in a row at the current tier
+    streak: usize,
+    /// The tier each graded question was asked at, in order
+    history: Vec<Difficulty>,
+}
+
+impl Default for TierLadder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TierLadder {
+    pub const CLIMB_STREAK: usize = 2;
+
+    pub fn new() -> Self {
+        Self {
+            tier: Difficulty::Medium,
+            streak: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Rebuilds the ladder from the outcomes so far, e.g. for a restored session
+    pub fn replay(outcomes: impl IntoIterator<Item = bool>) -> Self {
+        let mut ladder = Self::new();
+        for correct in outcomes {
+            ladder.record(correct);
+        }
+        ladder
+    }
+
+    /// Records a graded question asked at the current tier; returns the tier
+    /// the next one should come from
+    pub fn record(&mut self, correct: bool) -> Difficulty {
+        self.history.push(self.tier);
+        let from = self.tier;
+        if !correct {
+            self.streak = 0;
+            self.tier = easier(self.tier);
+        } else {
+            self.streak += 1;
+            if self.streak >= Self::CLIMB_STREAK {
+                self.streak = 0;
+                self.tier = harder(self.tier);
+            }
+        }
+        if self.tier != from {
+            tracing::info!(%from, to = %self.tier, "adaptive tier changed");
+        }
+        self.tier
+    }
+
+    pub fn tier(&self) -> Difficulty {
+        self.tier
+    }
+
+    pub fn history(&self) -> &[Difficulty] {
+        &self.history
+    }
+}
+
+fn harder(tier: Difficulty) -> Difficulty {
+    match tier {
+        Difficulty::Easy => Difficulty::Medium,
+        Difficulty::Medium | Difficulty::Hard => Difficulty::Hard,
+    }
+}
+
+fn easier(tier: Difficulty) -> Difficulty {
+    match tier {
+        Difficulty::Hard => Difficulty::Medium,
+        Difficulty::Medium | Difficulty::Easy => Difficulty::Easy,
+    }
+}
+
+/// Index in `pool` of the question to ask at `tier`: the first one rated at
+/// that tier, or else the first at the nearest tier; questions without a
+/// difficulty count as medium
+pub fn pick(pool: &[Question], tier: Difficulty) -> Option<usize> {
+    let distance = |question: &Question| {
+        let rated = question.difficulty.unwrap_or(Difficulty::Medium);
+        (rated as i8 - tier as i8).abs()
+    };
+    pool.iter()
+        .enumerate()
+        .min_by_key(|(_, question)| distance(question))
+        .map(|(index, _)| index)
+}
@@ -0,0 +1,160 @@
+use crate::models::Question;
+use crate::question_repository::{QuestionRepository, RepositoryError};
+use clap::ValueEnum;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// Lines Anki's text importer reads before the notes: tab-separated fields
+/// with HTML, tags in the third column
+const HEADER: &str = "#separator:tab\n#html:true\n#tags column:3\n";
+
+/// Between the question and its context on the front, and the answer and its
+/// notes on the back
+const SECTION_BREAK: &str = "<hr>";
+
+/// Tag prefix that carries the question's domain
+const DOMAIN_TAG: &str = "domain::";
+
+/// Which questions `export-anki --only` keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AnkiOnly {
+    /// Questions whose latest saved outcome is a fail, skip or reveal
+    Missed,
+}
+
+/// What `export-anki` writes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnkiOptions {
+    pub only: Option<AnkiOnly>,
+}
+
+/// Fetches the questions whose identities are in `missed`, by id, and lists
+/// the missed identities the bank no longer has
+pub fn fetch_missed(
+    repository: &dyn QuestionRepository,
+    missed: &HashSet<String>,
+) -> Result<(Vec<Question>, Vec<String>), RepositoryError> {
+    let mut uids: Vec<String> = missed.iter().cloned().collect();
+    uids.sort();
+    let (mut questions, gone) = match repository.get_questions_by_uids(&uids) {
+        Err(RepositoryError::MissingUids(gone)) => {
+            uids.retain(|uid| !gone.contains(uid));
+            (repository.get_questions_by_uids(&uids)?, gone)
+        }
+        result => (result?, Vec::new()),
+    };
+    questions.sort_by_key(|question| question.id);
+    Ok((questions, gone))
+}
+
+/// Renders questions as an Anki import file
+/// One note per line: the question (and its context) on the front, the answer
+/// (and its notes) on the back, the tags and domain in the third column.
+pub fn to_tsv(questions: &[Question]) -> String {
+    let mut out = String::from(HEADER);
+    for question in questions {
+        let front = join_sections(&question.question, question.context.as_deref());
+        let back = join_sections(&question.answer, question.notes.as_deref());
+        let tags: Vec<String> = question
+            .tags
+            .iter()
+            .map(|tag| tag_word(tag))
+            .chain(
+                question
+                    .category
+                    .iter()
+                    .map(|category| format!("{}{}", DOMAIN_TAG, tag_word(category))),
+            )
+            .collect();
+        // A leading `#` would make the importer take the line for a header
+        let front = match front.strip_prefix('#') {
+            Some(rest) => format!("&#35;{}", rest),
+            None => front,
+        };
+        out.push_str(&format!("{}\t{}\t{}\n", front, back, tags.join(" ")));
+    }
+    out
+}
+
+pub fn write_tsv(out: &mut impl Write, questions: &[Question]) -> io::Result<()> {
+    out.write_all(to_tsv(questions).as_bytes())
+}
+
+/// Reads an Anki text export back into questions, numbered from 1 in file order
+/// Header lines and blank lines are skipped; the time limit is left at 0 for
+/// the bank's author to set.
+pub fn from_tsv(text: &str) -> Result<Vec<Question>, String> {
+    let mut questions = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [front, back, rest @ ..] = fields.as_slice() else {
+            return Err(format!("line {} has no back field", index + 1));
+        };
+        let (question, context) = split_sections(front);
+        let (answer, notes) = split_sections(back);
+        let mut tags = Vec::new();
+        let mut category = None;
+        for tag in rest.first().map_or("", |tags| *tags).split_whitespace() {
+            match tag.strip_prefix(DOMAIN_TAG) {
+                Some(domain) => category = Some(domain.replace('_', " ")),
+                None => tags.push(tag.to_string()),
+            }
+        }
+        questions.push(Question {
+            id: questions.len() + 1,
+            question,
+            context,
+            answer,
+            notes,
+            tags,
+            category,
+            ..Default::default()
+        });
+    }
+    Ok(questions)
+}
+
+fn join_sections(main: &str, extra: Option<&str>) -> String {
+    match extra {
+        Some(extra) => format!("{}{}{}", escape(main), SECTION_BREAK, escape(extra)),
+        None => escape(main),
+    }
+}
+
+fn split_sections(field: &str) -> (String, Option<String>) {
+    match field.split_once(SECTION_BREAK) {
+        Some((main, extra)) => (unescape(main), Some(unescape(extra))),
+        None => (unescape(field), None),
+    }
+}
+
+/// HTML for one field: markup characters as entities, tabs as `&#9;` and line
+/// breaks as `<br>`, so a note stays on one line of the file
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\t', "&#9;")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn unescape(field: &str) -> String {
+    field
+        .replace("<br>", "\n")
+        .replace("&#9;", "\t")
+        .replace("&#35;", "#")
+        .replace("&quot;", "\"")
+        .replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&")
+}
+
+/// Anki tags can't contain spaces
+fn tag_word(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join("_")
+}
@@ -1,63 +1,1225 @@
-use crate::question_repository::QuestionRepository;
-use crate::quiz_state::{HintState, QuizState};
-use crate::ui::QuizUI;
-use crossterm::event::{self, Event, KeyCode};
+use crate::audio::{Cue, SoundPlayer};
+use crate::autosave::Autosaver;
+use crate::bank_updates::BankUpdate;
+use crate::cluster::{self, ClusterSettings};
+use crate::config::{Mode, QuizConfig};
+use crate::editor::{self, EditorSettings};
+use crate::events::{self, terminal_events, EventSource, Input};
+use crate::goal::{self, DailyGoal, DayProgress};
+use crate::i18n::Messages;
+use crate::keymap::{Action, KeyBinding, KeyBindings};
+use crate::mastery::{MasteryLoop, MasteryState};
+use crate::models::Question;
+use crate::paging::{self, PageReceiver};
+use crate::profile::ProfileName;
+use crate::question_repository::{QuestionRepository, RepositoryError};
+use crate::quiz_state::{
+    AnswerInput, Confidence, ContentTab, ContentTabState, QuestionStatus, RevealAnimation,
+};
+use crate::results::SessionResult;
+use crate::scheduler;
+use crate::scorer::AnswerScorer;
+use crate::session::{Session, SessionAction};
+use crate::shell::{self, ExamShell, ShellSettings};
+use crate::stats::{self, HintStats, PersonalBests, RunComparison};
+use crate::theme::Theme;
+use crate::timer::{Countdown, FrameRate, IdleWatch};
+use crate::ui::{BreakView, ScreenLayout, StartView, SummaryView, View};
+use chrono::Local;
+use crossterm::event::{Event, KeyCode, KeyEvent};
 use ratatui::{backend::Backend, Terminal};
-use std::io;
+use std::io::{self, Write};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
 
-/// Application coordinator that orchestrates quiz logic (Dependency Inversion Principle)
-/// Depends on the QuestionRepository abstraction, not concrete implementations
+/// Why the event loop stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    /// `q` or Ctrl+C
+    Quit,
+    /// SIGTERM or SIGHUP, by name
+    Signal(&'static str),
+    /// Terminal input failed, e.g. stdin closed or the terminal went away
+    InputLost,
+    /// The shell or editor key was pressed; `run` opens it and carries on, so
+    /// only `run_with` callers see this
+    Suspend(Suspension),
+}
+
+/// What the TUI steps aside for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suspension {
+    Shell,
+    /// `$EDITOR` on a file whose contents become the submitted answer
+    Editor,
+    /// Applying the question's setup manifests with kubectl
+    PrepCluster,
+}
+
+/// What the event loop should do after a key or action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Screen state changed
+    Redraw,
+    /// Nothing applied (e.g. `Next` before the question is resolved)
+    Unchanged,
+    Exit,
+    /// Leave the TUI for a shell or editor and come back
+    Suspend(Suspension),
+}
+
+impl Outcome {
+    fn from_changed(changed: bool) -> Self {
+        if changed {
+            Outcome::Redraw
+        } else {
+            Outcome::Unchanged
+        }
+    }
+}
+
+/// Terminal front-end that drives a Session
+/// Depends on the QuestionRepository abstraction, not concrete implementations;
+/// quiz rules live in the Session, this only holds screen state.
 pub struct App {
-    quiz_state: QuizState,
-    hint_state: HintState,
+    session: Session,
+    tab_state: ContentTabState,
+    answer_input: AnswerInput,
+    jump_input: AnswerInput,
+    /// Answer held back, with the clock paused, until its confidence is rated
+    pending_answer: Option<String>,
+    /// Time since the last key press, when idle auto-pause is on
+    idle: Option<IdleWatch>,
+    /// The clock was paused for lack of input; the next key resumes it
+    idle_paused: bool,
+    reveal: RevealAnimation,
+    /// Lines of the question's context block scrolled past
+    context_scroll: u16,
+    /// The question block shows only the prompt's first line
+    question_collapsed: bool,
+    /// One-line message for the controls bar, cleared on the next key press
+    notice: Option<String>,
+    /// Drilling the session's one question until it's right enough times in a row
+    mastery: Option<MasteryLoop>,
+    theme: Theme,
+    layout: ScreenLayout,
+    keymap: KeyBindings,
+    messages: Messages,
+    personal_bests: PersonalBests,
+    /// Question source and count, shown in the header's corner
+    source: String,
+    /// Shown in the header so it's clear whose stats are being recorded
+    profile: Option<ProfileName>,
+    goal: DailyGoal,
+    /// Today's progress from saved sessions, before this one
+    goal_progress: DayProgress,
+    /// The last complete session, compared with this one on the summary
+    previous_run: Option<SessionResult>,
+    /// Hint usage in saved sessions, for each question's hint rate on the summary
+    hint_stats: HintStats,
+    shell: ShellSettings,
+    editor: EditorSettings,
+    sounds: SoundPlayer,
+    cluster: ClusterSettings,
+    /// Questions whose practice namespace was set up during this quiz
+    prepared: Vec<usize>,
+    autosave: Option<Autosaver>,
+    /// Pages of a large bank still loading in the background
+    loading: Option<PageReceiver>,
+    /// Bank sources the load left out because they didn't parse
+    skipped_sources: Vec<String>,
+    /// The background bank update check, until it finishes
+    bank_check: Option<JoinHandle<Option<BankUpdate>>>,
+    /// The start screen, until its countdown runs out and the first clock starts
+    start: Option<Countdown>,
+    /// A break between questions, until it runs out or Enter ends it
+    on_break: Option<Countdown>,
+    /// Tick period during the reveal animation and the final-seconds flash
+    frame_rate: FrameRate,
+    expiry_handled: bool,
+    /// Header flash phase while the clock runs out; `None` when not flashing
+    flash: Option<bool>,
+    last_frame: Option<FrameKey>,
+}
+
+/// Snapshot of what the clock can change on screen; see `App::tick`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FrameKey {
+    finished: bool,
+    index: usize,
+    elapsed_secs: u64,
+    remaining_secs: u64,
+    session_secs: u64,
+    expired: bool,
+    flash: Option<bool>,
+    revealed: Option<usize>,
+    idle_paused: bool,
+    /// Start screen: `Some(None)` while waiting for Enter, then the count
+    start: Option<Option<u64>>,
+    /// Seconds left in a break
+    on_break: Option<u64>,
 }
 
 impl App {
+    /// Tick period while nothing animates; the countdown only shows whole seconds
+    const IDLE_TICK: Duration = Duration::from_millis(250);
+
     /// Creates a new App instance using dependency injection
     /// This follows the Dependency Inversion Principle - we depend on the
     /// QuestionRepository trait (abstraction) rather than concrete implementations
-    pub fn new(repository: Box<dyn QuestionRepository>) -> Self {
-        let questions = repository.get_questions();
+    pub fn new(
+        repository: Box<dyn QuestionRepository>,
+        theme: Theme,
+        keymap: KeyBindings,
+        config: QuizConfig,
+        personal_bests: PersonalBests,
+    ) -> Result<Self, RepositoryError> {
+        if paging::can_start_early(repository.as_ref(), &config) {
+            let repository: Arc<dyn QuestionRepository> = Arc::from(repository);
+            let start = paging::start(Arc::clone(&repository), &config)?;
+            let source = describe_source(repository.as_ref(), start.total);
+            let mut session = Session::new(start.first, config)?;
+            session.expect_total(start.total);
+            let mut app = Self::from_session(session, source, theme, keymap, personal_bests);
+            app.loading = Some(start.rest);
+            app.skipped_sources = repository.skipped_sources();
+            return Ok(app);
+        }
+        let questions = scheduler::build_session(repository.as_ref(), &config)?;
+        let source = describe_source(repository.as_ref(), questions.len());
+        let session = Session::new(questions, config)?;
+        let mut app = Self::from_session(session, source, theme, keymap, personal_bests);
+        app.skipped_sources = repository.skipped_sources();
+        Ok(app)
+    }
+
+    /// Wraps an existing session, e.g. one recovered from an autosave
+    /// `source` is the header text, see [`describe_source`]
+    pub fn from_session(
+        session: Session,
+        source: String,
+        theme: Theme,
+        keymap: KeyBindings,
+        personal_bests: PersonalBests,
+    ) -> Self {
+        let idle = session.config().idle_pause().map(|threshold| {
+            IdleWatch::with_clock(threshold, session.quiz_state().timer().clock())
+        });
+        let mastery = match session.config().mode {
+            Mode::Study => None,
+            _ => session
+                .config()
+                .mastery
+                .map(|mastery| MasteryLoop::new(mastery.streak)),
+        };
+        let reveal = RevealAnimation::with_clock(session.quiz_state().timer().clock());
         Self {
-            quiz_state: QuizState::new(questions),
-            hint_state: HintState::new(),
+            idle,
+            idle_paused: false,
+            session,
+            tab_state: ContentTabState::new(),
+            answer_input: AnswerInput::new(),
+            jump_input: AnswerInput::new(),
+            reveal,
+            context_scroll: 0,
+            question_collapsed: false,
+            notice: None,
+            mastery,
+            theme,
+            layout: ScreenLayout::default(),
+            keymap,
+            messages: Messages::english(),
+            personal_bests,
+            source,
+            profile: None,
+            goal: DailyGoal::default(),
+            goal_progress: DayProgress::default(),
+            previous_run: None,
+            hint_stats: HintStats::default(),
+            shell: ShellSettings::default(),
+            editor: EditorSettings::default(),
+            sounds: SoundPlayer::default(),
+            cluster: ClusterSettings::default(),
+            prepared: Vec::new(),
+            autosave: None,
+            loading: None,
+            skipped_sources: Vec::new(),
+            bank_check: None,
+            start: None,
+            on_break: None,
+            frame_rate: FrameRate::default(),
+            expiry_handled: false,
+            flash: None,
+            last_frame: None,
+            pending_answer: None,
+        }
+    }
+
+    /// Opens on a screen with the session's parameters, the first question's
+    /// clock held until Enter and a countdown of `countdown_secs`; study mode,
+    /// which has no clock, starts at once
+    pub fn with_start_screen(mut self, countdown_secs: u64) -> Self {
+        if self.session.config().mode == Mode::Study {
+            return self;
+        }
+        self.session.pause_clock();
+        let clock = self.session.quiz_state().timer().clock();
+        self.start = Some(Countdown::with_clock(countdown_secs, clock));
+        self
+    }
+
+    /// Whether the start screen is still up
+    pub fn is_starting(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// Draws with `layout`; the accessible layout turns colors off as well
+    pub fn with_layout(mut self, layout: ScreenLayout) -> Self {
+        if layout == ScreenLayout::Accessible {
+            self.theme = Theme::new(false);
+        }
+        self.layout = layout;
+        self
+    }
+
+    /// Redraws animations at `frame_rate` instead of the default 20 fps
+    pub fn with_frame_rate(mut self, frame_rate: FrameRate) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    /// Keeps an autosave of the session while it runs, for crash recovery
+    pub fn with_autosave(mut self, autosave: Autosaver) -> Self {
+        self.autosave = Some(autosave);
+        self
+    }
+
+    /// Grades answers with `scorer`, e.g. one backed by kubectl
+    pub fn with_scorer(mut self, scorer: AnswerScorer) -> Self {
+        self.session = self.session.with_scorer(scorer);
+        self
+    }
+
+    /// Screen text in another language than English
+    pub fn with_messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Profile named in the header
+    pub fn with_profile(mut self, profile: Option<ProfileName>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Tracks `goal` on top of today's saved sessions; the launch notice says
+    /// how many of the last 7 days met it
+    pub fn with_goal(mut self, goal: DailyGoal, history: &[SessionResult]) -> Self {
+        if goal.is_set() {
+            let today = Local::now().date_naive();
+            self.goal_progress = goal::daily_progress(history, &Local)
+                .get(&today)
+                .copied()
+                .unwrap_or_default();
+            self.notice = Some(goal::week_summary(history, &goal, today, &Local));
+        }
+        self.goal = goal;
+        self
+    }
+
+    /// Compares the summary with the latest complete session in `history`
+    pub fn with_history(mut self, history: &[SessionResult]) -> Self {
+        self.previous_run = stats::previous_run(history).cloned();
+        self.hint_stats = HintStats::from_history(history);
+        self
+    }
+
+    /// Shell opened by the shell key (`$SHELL` unless configured)
+    pub fn with_shell(mut self, shell: ShellSettings) -> Self {
+        self.shell = shell;
+        self
+    }
+
+    /// Editor opened for manifest answers (`$EDITOR` unless configured)
+    pub fn with_editor(mut self, editor: EditorSettings) -> Self {
+        self.editor = editor;
+        self
+    }
+
+    pub fn with_sounds(mut self, sounds: SoundPlayer) -> Self {
+        self.sounds = sounds;
+        self
+    }
+
+    pub fn with_cluster(mut self, cluster: ClusterSettings) -> Self {
+        self.cluster = cluster;
+        self
+    }
+
+    /// Shows the bank update `check` finds as a notice once it finishes
+    pub fn with_bank_check(mut self, check: JoinHandle<Option<BankUpdate>>) -> Self {
+        self.bank_check = Some(check);
+        self
+    }
+
+    /// Bank sources the load left out because they didn't parse, for the
+    /// caller to report before the terminal is taken over
+    pub fn skipped_sources(&self) -> &[String] {
+        &self.skipped_sources
+    }
+
+    /// Ids of the questions whose practice namespace was set up, for teardown
+    pub fn prepared_questions(&self) -> &[usize] {
+        &self.prepared
+    }
+
+    /// Main event loop for the application, reading keys from the terminal
+    pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<ExitReason> {
+        loop {
+            // A fresh event stream after each suspension, so nothing reads the
+            // terminal while the shell or editor owns it
+            match self.run_with(terminal, terminal_events()).await? {
+                ExitReason::Suspend(Suspension::Shell) => self.open_shell(terminal).await?,
+                ExitReason::Suspend(Suspension::Editor) => self.open_editor(terminal).await?,
+                ExitReason::Suspend(Suspension::PrepCluster) => self.open_prep(terminal).await?,
+                exit => return Ok(exit),
+            }
+            // Time in the shell or editor was spent working, not away
+            self.note_input();
+        }
+    }
+
+    /// Runs the shell with the clock paused (in exam mode it may keep running)
+    async fn open_shell<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        let pause = self.session.config().mode != Mode::Exam;
+        if pause {
+            self.session.pause_clock();
         }
+        let status = shell::run(&self.shell).await;
+        if pause {
+            self.session.resume_clock();
+        }
+        // The shell drew over everything; forget what ratatui thinks is on screen
+        terminal.clear()?;
+        if let Err(err) = status? {
+            tracing::warn!(error = %err, "shell failed to start");
+            self.notice = Some(format!("could not start shell: {}", err));
+        }
+        Ok(())
     }
 
-    /// Main event loop for the application
-    pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+    /// Submits what was written in the editor; the clock is paused meanwhile
+    /// unless the editor is configured as timed
+    async fn open_editor<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        let pause = !self.editor.timed;
+        if pause {
+            self.session.pause_clock();
+        }
+        let question = self.session.quiz_state().current_question().clone();
+        let edited = editor::edit(&self.editor, &question).await;
+        if pause {
+            self.session.resume_clock();
+        }
+        terminal.clear()?;
+        match edited? {
+            Ok(answer) => {
+                if self.submit(answer) == Outcome::Unchanged {
+                    self.notice = Some("time ran out while editing; nothing submitted".to_string());
+                }
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "editor answer not submitted");
+                self.notice = Some(err.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the question's setup outside the TUI with the clock paused;
+    /// setting up is not part of the task
+    async fn open_prep<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        self.session.pause_clock();
+        let question = self.session.quiz_state().current_question().clone();
+        let prepared = cluster::hand_over(&self.cluster, &question).await;
+        self.session.resume_clock();
+        terminal.clear()?;
+        match prepared? {
+            Ok(namespace) => {
+                if !self.prepared.contains(&question.id) {
+                    self.prepared.push(question.id);
+                }
+                self.notice = Some(format!("setup applied in namespace {}", namespace));
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "cluster prep failed");
+                self.notice = Some(err.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Event loop over any event source; waits on events, a timer tick and
+    /// shutdown signals at the same time, and only draws when something visible
+    /// changed. Ends as `Quit` when the source runs dry.
+    pub async fn run_with<B: Backend, E: EventSource>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        mut events: E,
+    ) -> io::Result<ExitReason> {
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
+
+        let mut redraw = true;
         loop {
-            terminal.draw(|f| QuizUI::render(f, &self.quiz_state, &self.hint_state))?;
-
-            if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
-                        KeyCode::Char('q') => return Ok(()),
-                        KeyCode::Char('h') => self.handle_hint_request(),
-                        KeyCode::Char('n') => self.handle_next_question(),
-                        _ => {}
+            if self.tick() || redraw {
+                self.draw(terminal)?;
+            }
+
+            tokio::select! {
+                _ = sleep(self.tick_period()) => redraw = false,
+                input = events::next_input(&mut events) => match input {
+                    Input::Event(Event::Key(key)) => match self.handle_key(key) {
+                        Outcome::Exit => return Ok(ExitReason::Quit),
+                        Outcome::Suspend(suspension) => return Ok(ExitReason::Suspend(suspension)),
+                        outcome => redraw = outcome == Outcome::Redraw,
+                    },
+                    Input::Event(Event::Resize(..)) => redraw = true,
+                    Input::Event(_) => redraw = false,
+                    Input::Ended => return Ok(ExitReason::Quit),
+                    Input::Lost(err) => {
+                        tracing::warn!(error = %err, "terminal input lost");
+                        return Ok(ExitReason::InputLost);
+                    }
+                },
+                page = next_page(&mut self.loading), if self.loading.is_some() => {
+                    redraw = self.receive_page(page);
+                }
+                update = finished_check(&mut self.bank_check), if self.bank_check.is_some() => {
+                    self.bank_check = None;
+                    redraw = update.is_some();
+                    if let Some(update) = update {
+                        self.notice = Some(update.to_string());
                     }
                 }
+                signal = &mut shutdown => {
+                    let signal = signal?;
+                    tracing::warn!(signal, "shutdown signal received");
+                    return Ok(ExitReason::Signal(signal));
+                }
             }
+        }
+    }
 
-            sleep(Duration::from_millis(50)).await;
+    /// Adds a page loaded in the background; the end of the pages, or a page
+    /// that failed, settles the session at the questions it has
+    fn receive_page(&mut self, page: Option<Result<Vec<Question>, RepositoryError>>) -> bool {
+        match page {
+            Some(Ok(questions)) => self.session.append_questions(questions),
+            Some(Err(err)) => {
+                tracing::error!(error = %err, "loading more questions failed");
+                self.notice = Some(format!("stopped loading questions: {}", err));
+                self.session.finish_loading();
+                self.loading = None;
+            }
+            None => {
+                self.session.finish_loading();
+                self.loading = None;
+            }
         }
+        true
     }
 
-    fn handle_hint_request(&mut self) {
-        if !self.quiz_state.timer().is_expired() {
-            self.hint_state.enable_hints();
-            let max_hints = self.quiz_state.current_question().hints.len();
-            self.hint_state.next_hint(max_hints);
+    fn draw<B: Backend>(&self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        let goal = self.goal_text();
+        let diagnosis = self.session.diagnosis();
+        let layout = self.layout;
+        terminal.draw(|f| {
+            if let Some(start) = &self.start {
+                layout.render_start(
+                    f,
+                    &StartView {
+                        quiz_state: self.session.quiz_state(),
+                        config: self.session.config(),
+                        countdown: start.remaining_secs(),
+                        theme: &self.theme,
+                        messages: &self.messages,
+                        keymap: &self.keymap,
+                        source: &self.source,
+                        profile: self.profile.as_ref(),
+                    },
+                );
+                return;
+            }
+            if let Some(countdown) = &self.on_break {
+                layout.render_break(
+                    f,
+                    &BreakView {
+                        quiz_state: self.session.quiz_state(),
+                        remaining_secs: countdown.remaining_secs().unwrap_or_default(),
+                        clock_running: !self.session.config().breaks_pause_clock(),
+                        theme: &self.theme,
+                        messages: &self.messages,
+                        keymap: &self.keymap,
+                        source: &self.source,
+                        profile: self.profile.as_ref(),
+                    },
+                );
+                return;
+            }
+            if self.session.is_finished() {
+                let comparison = self
+                    .previous_run
+                    .as_ref()
+                    .map(|previous| RunComparison::between(previous, &self.session.result()));
+                layout.render_summary(
+                    f,
+                    &SummaryView {
+                        quiz_state: self.session.quiz_state(),
+                        theme: &self.theme,
+                        keymap: &self.keymap,
+                        messages: &self.messages,
+                        goal: goal.as_deref(),
+                        comparison: comparison.as_ref(),
+                        hint_stats: &self.hint_stats,
+                        tiers: self.session.tier_history(),
+                        breaks: self.session.break_time(),
+                    },
+                );
+                return;
+            }
+            let view = View {
+                quiz_state: self.session.quiz_state(),
+                hint_state: self.session.hint_state(),
+                tab_state: &self.tab_state,
+                answer_input: &self.answer_input,
+                jump_input: &self.jump_input,
+                reveal: &self.reveal,
+                verdict: self.session.verdict(),
+                diagnosis: diagnosis.as_deref(),
+                context_scroll: self.context_scroll,
+                question_collapsed: self.question_collapsed,
+                notice: self.notice.as_deref(),
+                flash: self.flash,
+                theme: &self.theme,
+                config: self.session.config(),
+                personal_bests: &self.personal_bests,
+                source: &self.source,
+                profile: self.profile.as_ref(),
+                goal: goal.as_deref(),
+                mastery: self.mastery.as_ref(),
+                keymap: &self.keymap,
+                shell_enabled: self.shell_enabled(),
+                confidence_pending: self.pending_answer.is_some(),
+                idle_paused: self.idle_paused,
+                messages: &self.messages,
+            };
+            layout.render(f, &view);
+        })?;
+        Ok(())
+    }
+
+    /// Routes a key press: text prompts take raw keys, everything else goes
+    /// through the keymap to `dispatch`
+    pub fn handle_key(&mut self, key: KeyEvent) -> Outcome {
+        tracing::debug!(code = ?key.code, modifiers = ?key.modifiers, "key event");
+        let had_notice = self.notice.take().is_some();
+        if KeyBinding::QUIT_FALLBACK.matches(&key) {
+            return Outcome::Exit;
+        }
+        if let Some(start) = &mut self.start {
+            // Enter starts the countdown, quitting still works
+            return match key.code {
+                KeyCode::Enter => {
+                    start.begin();
+                    Outcome::Redraw
+                }
+                _ if self.keymap.action_for(&key) == Some(Action::Quit) => Outcome::Exit,
+                _ => Outcome::Unchanged,
+            };
+        }
+        if self.on_break.is_some() {
+            // Enter ends the break early, quitting still works
+            return match key.code {
+                KeyCode::Enter => {
+                    self.end_break();
+                    Outcome::Redraw
+                }
+                _ if self.keymap.action_for(&key) == Some(Action::Quit) => Outcome::Exit,
+                _ => Outcome::Unchanged,
+            };
+        }
+        // The key that ends an idle pause only resumes the clock
+        if self.note_input() {
+            return Outcome::Redraw;
+        }
+        if self.reveal.is_running() {
+            self.reveal.skip();
+            return Outcome::Redraw;
+        }
+
+        let outcome = if self.session.quiz_state().is_armed() && !self.session.is_finished() {
+            // Ready gate: Enter starts the clock, quitting still works
+            match key.code {
+                KeyCode::Enter => self.apply(SessionAction::Start),
+                _ if self.keymap.action_for(&key) == Some(Action::Quit) => Outcome::Exit,
+                _ => Outcome::Unchanged,
+            }
+        } else if self.pending_answer.is_some() {
+            let rating = match key.code {
+                KeyCode::Char(c) => Confidence::from_key(c),
+                _ => None,
+            };
+            match rating {
+                Some(confidence) => self.rate_confidence(confidence),
+                None if self.keymap.action_for(&key) == Some(Action::Quit) => Outcome::Exit,
+                None => Outcome::Unchanged,
+            }
+        } else if self.jump_input.is_active() {
+            match key.code {
+                KeyCode::Enter => self.handle_jump(),
+                KeyCode::Esc => self.jump_input.reset(),
+                KeyCode::Backspace => self.jump_input.backspace(),
+                KeyCode::Char(c) if c.is_ascii_digit() => self.jump_input.push(c),
+                _ => {}
+            }
+            Outcome::Redraw
+        } else if self.answer_input.is_active() {
+            match key.code {
+                KeyCode::Enter => self.handle_submit(),
+                KeyCode::Esc => self.answer_input.reset(),
+                KeyCode::Backspace => self.answer_input.backspace(),
+                KeyCode::Left => self.answer_input.move_left(),
+                KeyCode::Right => self.answer_input.move_right(),
+                KeyCode::Char(c) => self.answer_input.push(c),
+                _ => {}
+            }
+            Outcome::Redraw
+        } else if let Some(action) = self.keymap.action_for(&key) {
+            self.dispatch(action)
+        } else if let KeyCode::Char(c @ '1'..='4') = key.code {
+            self.tab_state.select_index(c as usize - '1' as usize);
+            Outcome::Redraw
+        } else {
+            Outcome::Unchanged
+        };
+
+        if had_notice && outcome == Outcome::Unchanged {
+            Outcome::Redraw
+        } else {
+            outcome
         }
     }
 
-    fn handle_next_question(&mut self) {
-        if self.quiz_state.timer().is_expired() {
-            self.quiz_state.next_question();
-            self.hint_state.reset();
+    /// Reducer for keymap actions: updates state and says whether the screen
+    /// needs redrawing or the loop should exit. Never touches the terminal.
+    pub fn dispatch(&mut self, action: Action) -> Outcome {
+        tracing::debug!(action = action.name(), "dispatch");
+        match action {
+            Action::Quit => return Outcome::Exit,
+            Action::Hint if self.session.out_of_hints() => {
+                self.notice = Some(self.messages.get("hints_none_left").to_string());
+            }
+            Action::Hint => return self.apply(SessionAction::Hint),
+            Action::Next => return self.apply(SessionAction::Next),
+            Action::Skip => return self.apply(SessionAction::Skip),
+            Action::ExtendTime => return self.apply(SessionAction::ExtendTime),
+            Action::RestartTimer => return self.apply(SessionAction::RestartTimer),
+            Action::Answer => return self.handle_answer_request(),
+            Action::Jump => return self.handle_jump_request(),
+            Action::Shell => return self.handle_shell_request(),
+            Action::Editor => return self.handle_editor_request(),
+            Action::NextTab => self.tab_state.next_tab(),
+            Action::PreviousTab => self.tab_state.previous_tab(),
+            Action::ScrollUp => self.tab_state.scroll_up(),
+            Action::ScrollDown => self.tab_state.scroll_down(),
+            Action::ContextUp => return self.scroll_context(-1),
+            Action::ContextDown => return self.scroll_context(1),
+            Action::Related => return self.toggle_related(),
+            Action::PrepCluster => return self.handle_prep_request(),
+            Action::CollapseQuestion => return self.toggle_question_collapsed(),
         }
+        Outcome::Redraw
+    }
+
+    pub fn mastery(&self) -> Option<&MasteryLoop> {
+        self.mastery.as_ref()
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn tab_state(&self) -> &ContentTabState {
+        &self.tab_state
+    }
+
+    pub fn answer_input(&self) -> &AnswerInput {
+        &self.answer_input
     }
+
+    /// Snapshot of the session for export; incomplete if the summary wasn't reached
+    pub fn session_result(&self) -> SessionResult {
+        self.session.result()
+    }
+
+    /// Progress toward today's goal including this session, if a goal is set
+    fn goal_text(&self) -> Option<String> {
+        if !self.goal.is_set() {
+            return None;
+        }
+        let mut session = DayProgress::default();
+        session.add_quiz(self.session.quiz_state());
+        Some(self.goal.progress_text(&self.goal_progress.plus(session)))
+    }
+
+    /// How long the loop may sleep without a frame going stale: quick while
+    /// something animates, relaxed while only the seconds count down
+    fn tick_period(&self) -> Duration {
+        if self.reveal.is_running() || self.flash.is_some() {
+            self.frame_rate.frame_period()
+        } else {
+            Self::IDLE_TICK
+        }
+    }
+
+    /// Runs the time-driven updates and any due autosave; returns true when the
+    /// screen would now look different
+    fn tick(&mut self) -> bool {
+        if self.start.as_ref().is_some_and(Countdown::is_done) {
+            self.start = None;
+            self.session.resume_clock();
+            tracing::info!("start countdown finished");
+            // Time on the start screen isn't idle time
+            if let Some(idle) = &mut self.idle {
+                idle.input();
+            }
+        }
+        if self.on_break.as_ref().is_some_and(Countdown::is_done) {
+            self.end_break();
+        }
+        self.update_timed_state();
+        if self.on_break.is_none() && self.session.break_due() {
+            self.start_break();
+        }
+        self.check_idle();
+        if let Some(autosave) = &mut self.autosave {
+            autosave.save_if_due(&self.session);
+        }
+        let frame = self.frame_key();
+        let changed = self.last_frame != Some(frame);
+        self.last_frame = Some(frame);
+        changed
+    }
+
+    /// Hides the next question behind a break screen, its clock paused unless
+    /// breaks are strict
+    fn start_break(&mut self) {
+        let secs = self.session.config().break_secs;
+        let clock = self.session.quiz_state().timer().clock();
+        let mut countdown = Countdown::with_clock(secs, clock);
+        countdown.begin();
+        if self.session.config().breaks_pause_clock() {
+            self.session.pause_clock();
+        }
+        self.on_break = Some(countdown);
+        tracing::info!(secs, "break started");
+    }
+
+    fn end_break(&mut self) {
+        let Some(countdown) = self.on_break.take() else {
+            return;
+        };
+        let taken = countdown
+            .elapsed()
+            .min(Duration::from_secs(countdown.secs()));
+        self.session.record_break(taken);
+        self.session.resume_clock();
+        // Time on a break isn't idle time
+        if let Some(idle) = &mut self.idle {
+            idle.input();
+        }
+    }
+
+    /// The time-dependent parts of the screen, compared between ticks
+    fn frame_key(&self) -> FrameKey {
+        let quiz_state = self.session.quiz_state();
+        let timer = quiz_state.timer();
+        FrameKey {
+            finished: self.session.is_finished(),
+            index: quiz_state.current_index(),
+            elapsed_secs: timer.elapsed().as_secs(),
+            remaining_secs: timer.remaining().as_secs(),
+            session_secs: quiz_state.session_elapsed().as_secs(),
+            expired: timer.is_expired(),
+            flash: self.flash,
+            revealed: self.reveal.visible_chars(),
+            idle_paused: self.idle_paused,
+            start: self.start.as_ref().map(Countdown::remaining_secs),
+            on_break: self.on_break.as_ref().and_then(Countdown::remaining_secs),
+        }
+    }
+
+    /// Pauses the clock once the idle threshold passes without input, while
+    /// a question is open and its clock running
+    fn check_idle(&mut self) {
+        let Some(idle) = &mut self.idle else {
+            return;
+        };
+        let quiz_state = self.session.quiz_state();
+        let running = !self.session.is_finished()
+            && self.start.is_none()
+            && self.on_break.is_none()
+            && !quiz_state.is_armed()
+            && !quiz_state.is_resolved()
+            && !quiz_state.timer().is_expired()
+            && self.pending_answer.is_none();
+        if idle.check() && running {
+            tracing::info!(
+                index = quiz_state.current_index(),
+                "clock paused while idle"
+            );
+            self.session.pause_clock();
+            self.idle_paused = true;
+        }
+    }
+
+    /// Restarts the idle countdown; true if this ended an idle pause, whose
+    /// clock is resumed
+    fn note_input(&mut self) -> bool {
+        if let Some(idle) = &mut self.idle {
+            idle.input();
+        }
+        let resumed = std::mem::take(&mut self.idle_paused);
+        if resumed {
+            self.session.resume_clock();
+        }
+        resumed
+    }
+
+    /// Time-driven updates: answer lock, flash, expiry bell and auto-advance
+    fn update_timed_state(&mut self) {
+        if self.session.is_finished() {
+            self.flash = None;
+            return;
+        }
+        let quiz_state = self.session.quiz_state();
+        let config = self.session.config();
+        self.reveal.advance();
+        self.tab_state.sync_answer_lock(quiz_state.is_resolved());
+        if config.mode == Mode::Study {
+            return;
+        }
+
+        let timer = quiz_state.timer();
+        self.flash = if config.flash && !quiz_state.is_resolved() {
+            timer.flash_phase()
+        } else {
+            None
+        };
+        if !timer.is_expired() {
+            return;
+        }
+        let unanswered = quiz_state.current_submission().is_none();
+        if !self.expiry_handled {
+            self.expiry_handled = true;
+            tracing::info!(index = quiz_state.current_index(), "timer expired");
+            if let Some(mastery) = &mut self.mastery {
+                mastery.record(false);
+            }
+            if config.bell && unanswered {
+                ring_bell();
+            }
+        }
+        // The typewriter starts once the reveal delay has passed
+        if config.reveal_animation
+            && unanswered
+            && !self.reveal.has_started()
+            && !timer.reveal_pending(config.reveal_delay_secs)
+        {
+            let answer = &quiz_state.current_question().answer;
+            self.reveal.start(answer.chars().count());
+        }
+        if let Some(delay) = config.auto_advance_secs {
+            if timer.overtime() >= Duration::from_secs(delay) && !self.answer_input.is_active() {
+                self.apply(SessionAction::Next);
+            }
+        }
+    }
+
+    /// Feeds an action to the session and refreshes screen state to match
+    fn apply(&mut self, action: SessionAction) -> Outcome {
+        let drilling = self.mastery.as_ref().is_some_and(|m| !m.is_mastered());
+        if drilling && matches!(action, SessionAction::Next | SessionAction::Skip) {
+            return self.step_mastery(action);
+        }
+        let fresh_view = matches!(
+            action,
+            SessionAction::Next
+                | SessionAction::Skip
+                | SessionAction::JumpTo(_)
+                | SessionAction::RestartTimer
+                | SessionAction::Retry
+        );
+        let selects_hints = action == SessionAction::Hint;
+        let grades = matches!(action, SessionAction::Submit(_));
+        match self.session.apply(action) {
+            Ok(true) if fresh_view => self.reset_question_view(),
+            Ok(true) if selects_hints => self.tab_state.select(ContentTab::Hints),
+            Ok(true) if grades && !self.session.quiz_state().is_resolved() => {
+                // A wrong answer left open by --retry-wrong
+                self.sounds.play(Cue::Incorrect);
+                let quiz_state = self.session.quiz_state();
+                let attempt = quiz_state.records()[quiz_state.current_index()]
+                    .attempts
+                    .len()
+                    + 1;
+                self.notice = Some(
+                    self.messages
+                        .format("answer_try_again", &[("attempt", &attempt)]),
+                );
+            }
+            Ok(true) if grades => {
+                if let Some(cue) = Cue::for_status(self.session.quiz_state().current_status()) {
+                    self.sounds.play(cue);
+                }
+                self.record_mastery_attempt();
+            }
+            Ok(true) => {}
+            Ok(false) => return Outcome::Unchanged,
+            Err(err) => self.notice = Some(err.to_string()),
+        }
+        Outcome::Redraw
+    }
+
+    /// Next and Skip while drilling for mastery: Skip gives the attempt up,
+    /// showing the answer, and Next starts another attempt on the same question
+    fn step_mastery(&mut self, action: SessionAction) -> Outcome {
+        let resolved = self.session.quiz_state().is_resolved();
+        let Some(mastery) = &mut self.mastery else {
+            return Outcome::Unchanged;
+        };
+        match action {
+            SessionAction::Skip if !resolved => {
+                mastery.record(false);
+                self.apply(SessionAction::GiveUp)
+            }
+            SessionAction::Next if resolved => {
+                // An attempt left unrecorded ran out of time
+                mastery.record(false);
+                mastery.next_attempt();
+                self.apply(SessionAction::Retry)
+            }
+            _ => Outcome::Unchanged,
+        }
+    }
+
+    /// Counts a graded answer towards the mastery streak; a pass short of the
+    /// target moves straight on to the next attempt, while a miss stays on
+    /// screen with the answer until Next
+    fn record_mastery_attempt(&mut self) {
+        let passed = self.session.quiz_state().current_status() == QuestionStatus::Pass;
+        let Some(mastery) = &mut self.mastery else {
+            return;
+        };
+        let state = mastery.record(passed);
+        let key = match state {
+            MasteryState::Passed => "mastery_passed",
+            MasteryState::Mastered => "mastery_done",
+            _ => return,
+        };
+        let notice = self.messages.format(
+            key,
+            &[
+                ("streak", &mastery.streak()),
+                ("target", &mastery.target()),
+                ("attempts", &mastery.attempts()),
+            ],
+        );
+        if mastery.next_attempt() {
+            self.apply(SessionAction::Retry);
+        }
+        self.notice = Some(notice);
+    }
+
+    fn handle_answer_request(&mut self) -> Outcome {
+        let open = !self.session.is_finished() && !self.session.quiz_state().is_resolved();
+        if open {
+            self.answer_input.open();
+        }
+        Outcome::from_changed(open)
+    }
+
+    fn handle_submit(&mut self) {
+        let answer = self.answer_input.take();
+        self.submit(answer);
+    }
+
+    /// Grades `answer`, or holds it for a confidence rating first when the
+    /// prompt is on; the clock doesn't run while the user decides
+    fn submit(&mut self, answer: String) -> Outcome {
+        if self.session.config().asks_confidence() && !self.session.quiz_state().is_resolved() {
+            self.session.pause_clock();
+            self.pending_answer = Some(answer);
+            return Outcome::Redraw;
+        }
+        self.apply(SessionAction::Submit(answer))
+    }
+
+    fn rate_confidence(&mut self, confidence: Confidence) -> Outcome {
+        let Some(answer) = self.pending_answer.take() else {
+            return Outcome::Unchanged;
+        };
+        self.session.resume_clock();
+        self.apply(SessionAction::RateConfidence(confidence));
+        self.apply(SessionAction::Submit(answer));
+        Outcome::Redraw
+    }
+
+    fn handle_jump_request(&mut self) -> Outcome {
+        let open = !self.session.is_finished();
+        if open {
+            self.jump_input.open();
+        }
+        Outcome::from_changed(open)
+    }
+
+    /// Scrolls the context block, stopping at its last line
+    fn scroll_context(&mut self, delta: i16) -> Outcome {
+        let lines = self
+            .session
+            .quiz_state()
+            .current_question()
+            .context
+            .as_deref()
+            .map_or(0, |context| context.lines().count());
+        let last = lines.saturating_sub(1).min(u16::MAX as usize) as u16;
+        let scrolled = self.context_scroll.saturating_add_signed(delta).min(last);
+        let changed = scrolled != self.context_scroll;
+        self.context_scroll = scrolled;
+        Outcome::from_changed(changed)
+    }
+
+    /// Related commands belong to the answer, so they only toggle once it is revealed
+    fn toggle_related(&mut self) -> Outcome {
+        let question = self.session.quiz_state().current_question();
+        if self.tab_state.answer_locked() || question.related_commands().is_empty() {
+            return Outcome::Unchanged;
+        }
+        self.tab_state.toggle_related();
+        self.tab_state.select(ContentTab::Answer);
+        Outcome::Redraw
+    }
+
+    /// A question waiting for the start key shows no text to collapse, and
+    /// study mode's question column has room to spare
+    fn toggle_question_collapsed(&mut self) -> Outcome {
+        if self.session.quiz_state().is_armed() || self.session.config().mode == Mode::Study {
+            return Outcome::Unchanged;
+        }
+        self.question_collapsed = !self.question_collapsed;
+        Outcome::Redraw
+    }
+
+    /// Whether the shell key opens a shell in this session's mode
+    fn shell_enabled(&self) -> bool {
+        self.session.config().mode != Mode::Exam || self.shell.exam != ExamShell::Disabled
+    }
+
+    fn handle_shell_request(&mut self) -> Outcome {
+        if !self.shell_enabled() {
+            self.notice = Some("the shell is disabled in exam mode".to_string());
+            return Outcome::Redraw;
+        }
+        Outcome::Suspend(Suspension::Shell)
+    }
+
+    /// Only unresolved questions that take a manifest open the editor
+    fn handle_editor_request(&mut self) -> Outcome {
+        let quiz_state = self.session.quiz_state();
+        if self.session.is_finished() || quiz_state.is_resolved() {
+            return Outcome::Unchanged;
+        }
+        if !quiz_state.current_question().yaml_answer {
+            self.notice = Some("this question takes a typed answer".to_string());
+            return Outcome::Redraw;
+        }
+        Outcome::Suspend(Suspension::Editor)
+    }
+
+    fn handle_prep_request(&mut self) -> Outcome {
+        if self.session.is_finished() {
+            return Outcome::Unchanged;
+        }
+        if self.session.quiz_state().current_question().setup.is_none() {
+            self.notice = Some("this question has no setup manifests".to_string());
+            return Outcome::Redraw;
+        }
+        Outcome::Suspend(Suspension::PrepCluster)
+    }
+
+    fn handle_jump(&mut self) {
+        let input = self.jump_input.take();
+        if let Ok(id) = input.trim().parse::<usize>() {
+            self.apply(SessionAction::JumpTo(id));
+        }
+    }
+
+    /// Clears per-question UI state after moving to another question
+    fn reset_question_view(&mut self) {
+        self.reveal.reset();
+        self.context_scroll = 0;
+        self.question_collapsed = false;
+        self.tab_state.reset();
+        self.answer_input.reset();
+        self.expiry_handled = false;
+    }
+}
+
+/// e.g. "built-in (7 Qs)" or "banks/pods.yaml (12 Qs)", counting the session's questions
+pub fn describe_source(repository: &dyn QuestionRepository, count: usize) -> String {
+    format!("{} ({} Qs)", repository.source_description(), count)
+}
+
+/// Resolves when the process is asked to terminate (SIGTERM) or its terminal goes away (SIGHUP)
+#[cfg(unix)]
+pub(crate) async fn shutdown_signal() -> io::Result<&'static str> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut hangup = signal(SignalKind::hangup())?;
+    tokio::select! {
+        _ = terminate.recv() => Ok("SIGTERM"),
+        _ = hangup.recv() => Ok("SIGHUP"),
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn shutdown_signal() -> io::Result<&'static str> {
+    std::future::pending().await
+}
+
+/// The next page, or `None` once loading has ended
+async fn next_page(
+    loading: &mut Option<PageReceiver>,
+) -> Option<Result<Vec<Question>, RepositoryError>> {
+    match loading {
+        Some(pages) => pages.recv().await,
+        None => None,
+    }
+}
+
+/// The bank update check's finding; a check that panicked found nothing
+async fn finished_check(check: &mut Option<JoinHandle<Option<BankUpdate>>>) -> Option<BankUpdate> {
+    match check {
+        Some(handle) => handle.await.ok().flatten(),
+        None => None,
+    }
+}
+
+fn ring_bell() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
 }
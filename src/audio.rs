@@ -0,0 +1,123 @@
+use crate::quiz_state::QuestionStatus;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+
+/// Whether this build can play sounds (the `sounds` cargo feature)
+pub const SUPPORTED: bool = cfg!(feature = "sounds");
+
+/// WAV files played when an answer is graded, from `[sounds]`; only used with `--sounds`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SoundSettings {
+    pub enabled: bool,
+    pub correct: Option<PathBuf>,
+    pub incorrect: Option<PathBuf>,
+}
+
+impl SoundSettings {
+    pub fn path(&self, cue: Cue) -> Option<&Path> {
+        match cue {
+            Cue::Correct => self.correct.as_deref(),
+            Cue::Incorrect => self.incorrect.as_deref(),
+        }
+    }
+
+    /// Enabled with at least one file to play
+    pub fn is_active(&self) -> bool {
+        self.enabled && (self.correct.is_some() || self.incorrect.is_some())
+    }
+}
+
+/// Which sound a verdict calls for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    Correct,
+    Incorrect,
+}
+
+impl Cue {
+    /// Only graded answers make a sound; skips and reveals stay quiet
+    pub fn for_status(status: QuestionStatus) -> Option<Self> {
+        match status {
+            QuestionStatus::Pass => Some(Cue::Correct),
+            QuestionStatus::Fail => Some(Cue::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+/// Plays cues on a background thread that owns the audio output, so the quiz never
+/// waits on it
+/// Silent when sounds are off, the build has no audio support or no device opens.
+#[derive(Debug, Default)]
+pub struct SoundPlayer {
+    settings: SoundSettings,
+    output: Option<Sender<PathBuf>>,
+}
+
+impl SoundPlayer {
+    pub fn new(settings: SoundSettings) -> Self {
+        let output = if settings.is_active() {
+            spawn_output()
+        } else {
+            None
+        };
+        Self { settings, output }
+    }
+
+    pub fn play(&self, cue: Cue) {
+        if let (Some(output), Some(path)) = (&self.output, self.settings.path(cue)) {
+            // A closed channel means the device failed; the thread already logged it
+            let _ = output.send(path.to_path_buf());
+        }
+    }
+}
+
+#[cfg(feature = "sounds")]
+fn spawn_output() -> Option<Sender<PathBuf>> {
+    use rodio::{Decoder, OutputStream, Source};
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::sync::mpsc;
+
+    let (sender, receiver) = mpsc::channel::<PathBuf>();
+    let spawned = std::thread::Builder::new()
+        .name("sounds".to_string())
+        .spawn(move || {
+            // The stream stops playing when dropped, so it lives on this thread
+            let (_stream, handle) = match OutputStream::try_default() {
+                Ok(output) => output,
+                Err(err) => {
+                    tracing::warn!(error = %err, "no audio device; sounds disabled");
+                    return;
+                }
+            };
+            for path in receiver {
+                let played = File::open(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|file| {
+                        Decoder::new(BufReader::new(file)).map_err(|err| err.to_string())
+                    })
+                    .and_then(|sound| {
+                        handle
+                            .play_raw(sound.convert_samples())
+                            .map_err(|err| err.to_string())
+                    });
+                if let Err(err) = played {
+                    tracing::warn!(path = %path.display(), error = %err, "could not play sound");
+                }
+            }
+        });
+    match spawned {
+        Ok(_) => Some(sender),
+        Err(err) => {
+            tracing::warn!(error = %err, "could not start the sound thread");
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "sounds"))]
+fn spawn_output() -> Option<Sender<PathBuf>> {
+    tracing::warn!("built without the sounds feature; --sounds has no effect");
+    None
+}
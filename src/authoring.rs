@@ -0,0 +1,294 @@
+use crate::models::Question;
+use crate::question_repository::{
+    FileQuestionRepository, QuestionFormat, QuestionRepository, RepositoryError,
+};
+use crate::validation;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// Time limit offered when there is no template to copy one from
+pub const DEFAULT_TIME_LIMIT_SECS: u64 = 120;
+
+/// Where `add` saves the new question and what it starts from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddOptions {
+    pub file: PathBuf,
+    /// Id of a question in the same file whose values seed the prompts
+    pub template: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum AddError {
+    Io(io::Error),
+    Load(RepositoryError),
+    TemplateNotFound(usize),
+    Invalid(String),
+    /// Input ended before the question text was entered
+    Cancelled,
+}
+
+impl fmt::Display for AddError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddError::Io(err) => write!(f, "{}", err),
+            AddError::Load(err) => write!(f, "{}", err),
+            AddError::TemplateNotFound(id) => {
+                write!(f, "no question with id {} to use as a template", id)
+            }
+            AddError::Invalid(message) => write!(f, "invalid question: {}", message),
+            AddError::Cancelled => write!(f, "cancelled; nothing was added"),
+        }
+    }
+}
+
+impl std::error::Error for AddError {}
+
+impl From<io::Error> for AddError {
+    fn from(err: io::Error) -> Self {
+        AddError::Io(err)
+    }
+}
+
+/// Prompts for a question and appends it to a bank file
+/// A missing file is created; the new question gets the next free id.
+pub fn run<R: BufRead, W: Write>(
+    options: &AddOptions,
+    input: &mut R,
+    out: &mut W,
+) -> Result<Question, AddError> {
+    let existing = load_existing(&options.file)?;
+    let template = match options.template {
+        Some(id) => Some(
+            existing
+                .iter()
+                .find(|question| question.id == id)
+                .ok_or(AddError::TemplateNotFound(id))?,
+        ),
+        None => None,
+    };
+    let id = next_id(&existing);
+    writeln!(out, "Adding question {} to {}", id, options.file.display())?;
+
+    let question = prompt_question(input, out, id, template)?;
+    validate(&question).map_err(AddError::Invalid)?;
+    append_question(&options.file, &question)?;
+    writeln!(out, "Added question {} to {}", id, options.file.display())?;
+    Ok(question)
+}
+
+/// One more than the highest id in the bank
+pub fn next_id(questions: &[Question]) -> usize {
+    questions
+        .iter()
+        .map(|question| question.id)
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+/// Asks for the text, time limit, hints and answer; with a template, pressing
+/// Enter keeps its value and its tags, category and other details carry over
+pub fn prompt_question<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    id: usize,
+    template: Option<&Question>,
+) -> Result<Question, AddError> {
+    let mut question = template.cloned().unwrap_or_default();
+    question.id = id;
+    question.depends_on = None;
+
+    let text = loop {
+        let label = match template {
+            Some(template) => format!("Question [{}]: ", template.question),
+            None => "Question: ".to_string(),
+        };
+        let Some(line) = prompt(input, out, &label)? else {
+            return Err(AddError::Cancelled);
+        };
+        match (line.trim(), template) {
+            ("", Some(template)) => break template.question.clone(),
+            ("", None) => writeln!(out, "The question text is required.")?,
+            (text, _) => break text.to_string(),
+        }
+    };
+    question.question = text;
+
+    let default_limit =
+        template.map_or(DEFAULT_TIME_LIMIT_SECS, |template| template.time_limit_secs);
+    question.time_limit_secs = loop {
+        let label = format!("Time limit in seconds [{}]: ", default_limit);
+        let Some(line) = prompt(input, out, &label)? else {
+            break default_limit;
+        };
+        match line.trim() {
+            "" => break default_limit,
+            value => match value.parse::<u64>() {
+                Ok(secs) if secs > 0 => break secs,
+                _ => writeln!(out, "Enter a whole number of seconds above 0.")?,
+            },
+        }
+    };
+
+    question.hints = Vec::new();
+    if let Some(template) = template.filter(|template| !template.hints.is_empty()) {
+        let label = format!(
+            "Keep the {} hint(s) from question {}? [Y/n]: ",
+            template.hints.len(),
+            template.id
+        );
+        let keep = prompt(input, out, &label)?.unwrap_or_default();
+        if !keep.trim().eq_ignore_ascii_case("n") {
+            question.hints = template.hints.clone();
+        }
+    }
+    writeln!(out, "Hints, one per line; a blank line finishes:")?;
+    loop {
+        let label = format!("  Hint {}: ", question.hints.len() + 1);
+        match prompt(input, out, &label)? {
+            Some(line) if !line.trim().is_empty() => question.hints.push(line.trim().to_string()),
+            _ => break,
+        }
+    }
+
+    match template {
+        Some(template) => writeln!(
+            out,
+            "Answer, ended by a line with only \".\" (a \".\" right away keeps question {}'s answer):",
+            template.id
+        )?,
+        None => writeln!(out, "Answer, ended by a line with only \".\":")?,
+    }
+    let answer = read_block(input)?;
+    question.answer = match template {
+        Some(template) if answer.is_empty() => template.answer.clone(),
+        _ => answer,
+    };
+    Ok(question)
+}
+
+/// What the quiz needs from a question to run it; the first problem found
+pub fn validate(question: &Question) -> Result<(), String> {
+    match validation::check_question(question).into_iter().next() {
+        Some((_, message)) => Err(message),
+        None => Ok(()),
+    }
+}
+
+/// Adds `question` at the end of the bank. YAML files get the entry appended
+/// as text so existing comments and layout survive, and JSON Lines files a
+/// line; JSON files and YAML that isn't a plain block list are rewritten.
+pub fn append_question(path: &Path, question: &Question) -> Result<(), AddError> {
+    let existing = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let contents = match QuestionFormat::from_path(path) {
+        Some(QuestionFormat::Json) => append_json(&existing, question)?,
+        Some(QuestionFormat::JsonLines) => append_json_line(&existing, question)?,
+        _ => append_yaml(&existing, question)?,
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn append_yaml(existing: &str, question: &Question) -> Result<String, AddError> {
+    let invalid = |err: serde_yaml::Error| AddError::Invalid(err.to_string());
+    let entry = serde_yaml::to_string(&[question]).map_err(invalid)?;
+    if existing.trim().is_empty() {
+        return Ok(entry);
+    }
+
+    let mut appended = existing.to_string();
+    if !appended.ends_with('\n') {
+        appended.push('\n');
+    }
+    appended.push_str(&entry);
+    let count =
+        |text: &str| serde_yaml::from_str::<Vec<serde_yaml::Value>>(text).map(|list| list.len());
+    let before = count(existing).map_err(invalid)?;
+    if count(&appended).ok() == Some(before + 1) {
+        return Ok(appended);
+    }
+
+    // e.g. a flow list (`[...]`): parse and write the whole list instead
+    let mut list: Vec<serde_yaml::Value> = serde_yaml::from_str(existing).map_err(invalid)?;
+    list.push(serde_yaml::to_value(question).map_err(invalid)?);
+    serde_yaml::to_string(&list).map_err(invalid)
+}
+
+fn append_json_line(existing: &str, question: &Question) -> Result<String, AddError> {
+    let line = serde_json::to_string(question).map_err(|err| AddError::Invalid(err.to_string()))?;
+    let mut contents = existing.to_string();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&line);
+    contents.push('\n');
+    Ok(contents)
+}
+
+fn append_json(existing: &str, question: &Question) -> Result<String, AddError> {
+    let invalid = |err: serde_json::Error| AddError::Invalid(err.to_string());
+    let mut list: Vec<serde_json::Value> = if existing.trim().is_empty() {
+        Vec::new()
+    } else {
+        serde_json::from_str(existing).map_err(invalid)?
+    };
+    list.push(serde_json::to_value(question).map_err(invalid)?);
+    let mut contents = serde_json::to_string_pretty(&list).map_err(invalid)?;
+    contents.push('\n');
+    Ok(contents)
+}
+
+/// The bank's questions; a file that doesn't exist yet is an empty bank
+fn load_existing(path: &Path) -> Result<Vec<Question>, AddError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let repository = FileQuestionRepository::new(path.display().to_string());
+    match repository.get_questions() {
+        Ok(questions) => Ok(questions),
+        // An empty file is fine too
+        Err(_) if fs::read_to_string(path).is_ok_and(|text| text.trim().is_empty()) => {
+            Ok(Vec::new())
+        }
+        Err(err) => Err(AddError::Load(err)),
+    }
+}
+
+/// Prints `label` and reads one line without its line ending; `None` at end of input
+fn prompt<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    label: &str,
+) -> io::Result<Option<String>> {
+    write!(out, "{}", label)?;
+    out.flush()?;
+    read_line(input)
+}
+
+fn read_line<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let trimmed = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(trimmed);
+    Ok(Some(line))
+}
+
+/// Lines up to a lone "." or the end of input, newlines and indentation kept
+fn read_block<R: BufRead>(input: &mut R) -> io::Result<String> {
+    let mut lines = Vec::new();
+    while let Some(line) = read_line(input)? {
+        if line.trim() == "." {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok(lines.join("\n").trim_end().to_string())
+}
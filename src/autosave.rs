@@ -0,0 +1,321 @@
+use crate::config::{Mode, QuizConfig};
+use crate::models::{self, Question};
+use crate::question_repository::{QuestionRepository, RepositoryError};
+use crate::quiz_state::{Confidence, QuestionStatus};
+use crate::results::SessionResult;
+use crate::session::Session;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Snapshot format version; older or newer snapshots are not recovered
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// How often a running question is saved when nothing else prompts a save
+pub const INTERVAL: Duration = Duration::from_secs(30);
+
+/// In-progress session state, enough to pick the session up again
+/// Questions are stored by identity; the bank supplies their text on recovery.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub version: u32,
+    pub saved_at: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub mode: Mode,
+    /// Where the questions came from, e.g. "banks/pods.yaml"
+    pub source: String,
+    /// Digest of the session's questions, to notice a bank edited since
+    pub fingerprint: String,
+    /// The session's questions in order, drill repeats included
+    pub questions: Vec<SnapshotQuestion>,
+    pub current_index: usize,
+    /// Time already spent on the current question
+    pub current_elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotQuestion {
+    /// The question's [`Question::identity`], which recovery fetches it by
+    pub uid: String,
+    /// The id it had, to name it if the bank no longer has it
+    pub id: usize,
+    pub status: QuestionStatus,
+    pub submission: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<String>,
+    pub time_spent_secs: Option<u64>,
+    pub hints_used: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints_viewed: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_credit: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+}
+
+impl SessionSnapshot {
+    /// Questions with an outcome so far
+    pub fn answered(&self) -> usize {
+        self.questions
+            .iter()
+            .filter(|question| question.status != QuestionStatus::Pending)
+            .count()
+    }
+}
+
+/// Why a snapshot can't be picked up again
+#[derive(Debug)]
+pub enum RecoveryError {
+    UnsupportedVersion(u32),
+    /// The quiz was started with a different question source this time
+    OtherSource {
+        saved: String,
+        current: String,
+    },
+    Load(RepositoryError),
+    /// The bank no longer has these questions the session used
+    MissingQuestions(Vec<usize>),
+    /// The session's questions were edited since the save
+    BankChanged,
+}
+
+impl fmt::Display for RecoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoveryError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "it was saved in an unsupported format (version {})",
+                    version
+                )
+            }
+            RecoveryError::OtherSource { saved, current } => {
+                write!(f, "it used {} rather than {}", saved, current)
+            }
+            RecoveryError::Load(err) => write!(f, "its questions could not be loaded: {}", err),
+            RecoveryError::MissingQuestions(ids) => {
+                let ids: Vec<String> = ids.iter().map(usize::to_string).collect();
+                match ids.as_slice() {
+                    [id] => write!(f, "question {} is no longer in the bank", id),
+                    _ => write!(
+                        f,
+                        "{} of its questions no longer exist (ids {})",
+                        ids.len(),
+                        ids.join(", ")
+                    ),
+                }
+            }
+            RecoveryError::BankChanged => write!(f, "its questions have changed since"),
+        }
+    }
+}
+
+impl std::error::Error for RecoveryError {}
+
+/// Keeps the autosave current while a session runs
+/// Saves whenever a question gets an outcome or the quiz moves to another
+/// question, and every `INTERVAL` in between so the clock is kept too.
+#[derive(Debug)]
+pub struct Autosaver {
+    path: PathBuf,
+    source: String,
+    last_progress: Option<Progress>,
+    last_saved: Option<Instant>,
+}
+
+/// What changes when a question completes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Progress {
+    answered: usize,
+    current_index: usize,
+    total: usize,
+}
+
+impl Autosaver {
+    /// Saves to `path`; `source` is the repository's description
+    pub fn new(path: PathBuf, source: String) -> Self {
+        Self {
+            path,
+            source,
+            last_progress: None,
+            last_saved: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Saves the session if it progressed or `INTERVAL` passed since the last save;
+    /// returns whether it saved. A failed write is logged and tried again when next due.
+    pub fn save_if_due(&mut self, session: &Session) -> bool {
+        let quiz_state = session.quiz_state();
+        let progress = Progress {
+            answered: quiz_state
+                .records()
+                .iter()
+                .filter(|record| record.status != QuestionStatus::Pending)
+                .count(),
+            current_index: quiz_state.current_index(),
+            total: quiz_state.total_questions(),
+        };
+        let interval_passed = self
+            .last_saved
+            .is_none_or(|saved| saved.elapsed() >= INTERVAL);
+        if self.last_progress == Some(progress) && !interval_passed {
+            return false;
+        }
+        self.last_progress = Some(progress);
+        self.last_saved = Some(Instant::now());
+        match save(&self.path, &session.snapshot(&self.source)) {
+            Ok(()) => {
+                tracing::debug!(path = %self.path.display(), "session autosaved");
+                true
+            }
+            Err(err) => {
+                tracing::warn!(path = %self.path.display(), error = %err, "autosave failed");
+                false
+            }
+        }
+    }
+}
+
+/// `<data_dir>/autosave.json`, one per data directory (and so per profile)
+pub fn autosave_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("autosave.json")
+}
+
+/// Where `write_atomic` stages a file before renaming it into place
+pub fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Writes `contents` to a temp file next to `path` and renames it over `path`,
+/// so a crash mid-write leaves the previous file intact
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = temp_path(path);
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+pub fn save(path: &Path, snapshot: &SessionSnapshot) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(snapshot)?;
+    write_atomic(path, &json)
+}
+
+/// The saved snapshot, if there is a readable one
+/// A temp file left by an interrupted write is removed; the last complete save
+/// is still in place.
+pub fn load(path: &Path) -> Option<SessionSnapshot> {
+    let temp = temp_path(path);
+    if temp.exists() {
+        tracing::warn!(path = %temp.display(), "removing an interrupted autosave write");
+        let _ = fs::remove_file(&temp);
+    }
+    let json = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(snapshot) => Some(snapshot),
+        Err(err) => {
+            tracing::warn!(path = %path.display(), error = %err, "ignoring unreadable autosave");
+            None
+        }
+    }
+}
+
+/// Removes the autosave once the session it belongs to is saved properly
+pub fn discard(path: &Path) {
+    for file in [path.to_path_buf(), temp_path(path)] {
+        match fs::remove_file(&file) {
+            Ok(()) => tracing::debug!(path = %file.display(), "autosave removed"),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                tracing::warn!(path = %file.display(), error = %err, "could not remove autosave")
+            }
+        }
+    }
+}
+
+/// Whether the snapshot is newer than every cleanly saved session, i.e. the
+/// quiz stopped without getting to save it
+pub fn is_unfinished(snapshot: &SessionSnapshot, history: &[SessionResult]) -> bool {
+    history
+        .iter()
+        .all(|result| result.finished_at < snapshot.saved_at)
+}
+
+/// Rebuilds the snapshot's session from the bank it came from
+/// `config` supplies every option except the mode, which is the snapshot's.
+pub fn recover(
+    snapshot: &SessionSnapshot,
+    repository: &dyn QuestionRepository,
+    config: QuizConfig,
+) -> Result<Session, RecoveryError> {
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(RecoveryError::UnsupportedVersion(snapshot.version));
+    }
+    let current = repository.source_description();
+    if current != snapshot.source {
+        return Err(RecoveryError::OtherSource {
+            saved: snapshot.source.clone(),
+            current,
+        });
+    }
+    let uids: Vec<String> = snapshot
+        .questions
+        .iter()
+        .map(|saved| saved.uid.clone())
+        .collect();
+    let questions = repository
+        .get_questions_by_uids(&uids)
+        .map_err(|err| match err {
+            RepositoryError::MissingUids(uids) => RecoveryError::MissingQuestions(
+                uids.iter()
+                    .filter_map(|uid| {
+                        let saved = snapshot.questions.iter().find(|saved| saved.uid == *uid);
+                        saved.map(|saved| saved.id)
+                    })
+                    .collect(),
+            ),
+            err => RecoveryError::Load(err),
+        })?;
+    if fingerprint(&questions) != snapshot.fingerprint {
+        return Err(RecoveryError::BankChanged);
+    }
+    let config = QuizConfig {
+        mode: snapshot.mode,
+        ..config
+    };
+    Session::restore(questions, config, snapshot).map_err(RecoveryError::Load)
+}
+
+/// Digest of the questions' content, ignoring order, ids and hint shuffling
+/// FNV-1a over their JSON, so it stays the same across builds.
+pub fn fingerprint(questions: &[Question]) -> String {
+    let mut unique: Vec<(String, Question)> = Vec::new();
+    for question in questions {
+        let identity = question.identity();
+        if !unique.iter().any(|(seen, _)| *seen == identity) {
+            let mut question = question.clone();
+            question.id = 0;
+            question.hints.sort();
+            unique.push((identity, question));
+        }
+    }
+    unique.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let json = serde_json::to_vec(&unique).unwrap_or_default();
+    format!("{:016x}", models::fnv1a(&json))
+}
@@ -0,0 +1,153 @@
+use crate::autosave;
+use crate::models::Question;
+use crate::question_repository::QuestionRepository;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::task::JoinHandle;
+
+/// How long a check stands before the bank is looked at again
+pub const CHECK_INTERVAL: Duration = Duration::days(1);
+
+/// What the last check saw, per question source
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checks {
+    sources: BTreeMap<String, Check>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Check {
+    checked_at: DateTime<Utc>,
+    /// Each question's [`autosave::fingerprint`], by identity
+    questions: BTreeMap<String, String>,
+}
+
+/// How a bank changed between two checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankUpdate {
+    pub source: String,
+    /// When the earlier check was made
+    pub since: DateTime<Utc>,
+    pub added: usize,
+    pub removed: usize,
+    pub edited: usize,
+}
+
+impl fmt::Display for BankUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let changes: Vec<String> = [
+            ("new", self.added),
+            ("edited", self.edited),
+            ("removed", self.removed),
+        ]
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(change, count)| format!("{}: {}", change, count))
+        .collect();
+        write!(
+            f,
+            "{} changed since {} (questions {})",
+            self.source,
+            self.since.format("%Y-%m-%d"),
+            changes.join(", ")
+        )
+    }
+}
+
+/// Where the checks are recorded
+pub fn checks_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("bank-checks.json")
+}
+
+/// Compares `questions`, the whole bank behind `source`, with what the last
+/// check recorded and records them in its place
+/// `None` when nothing changed, on the first check of a source, when the last
+/// check is less than [`CHECK_INTERVAL`] old, or when the record can't be
+/// read or written; failures are only logged.
+pub fn check(
+    path: &Path,
+    source: &str,
+    questions: &[Question],
+    now: DateTime<Utc>,
+) -> Option<BankUpdate> {
+    let mut checks: Checks = match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|err| {
+            tracing::debug!(path = %path.display(), error = %err, "ignoring unreadable bank checks");
+            Checks::default()
+        }),
+        Err(_) => Checks::default(),
+    };
+    let previous = checks.sources.get(source);
+    if previous.is_some_and(|check| now - check.checked_at < CHECK_INTERVAL) {
+        return None;
+    }
+    let current: BTreeMap<String, String> = questions
+        .iter()
+        .map(|question| {
+            (
+                question.identity(),
+                autosave::fingerprint(std::slice::from_ref(question)),
+            )
+        })
+        .collect();
+    let update = previous.map(|previous| BankUpdate {
+        source: source.to_string(),
+        since: previous.checked_at,
+        added: current
+            .keys()
+            .filter(|identity| !previous.questions.contains_key(*identity))
+            .count(),
+        removed: previous
+            .questions
+            .keys()
+            .filter(|identity| !current.contains_key(*identity))
+            .count(),
+        edited: current
+            .iter()
+            .filter(|(identity, digest)| {
+                previous
+                    .questions
+                    .get(*identity)
+                    .is_some_and(|before| before != *digest)
+            })
+            .count(),
+    });
+    checks.sources.insert(
+        source.to_string(),
+        Check {
+            checked_at: now,
+            questions: current,
+        },
+    );
+    let written = serde_json::to_vec_pretty(&checks)
+        .map_err(std::io::Error::from)
+        .and_then(|json| autosave::write_atomic(path, &json));
+    if let Err(err) = written {
+        tracing::debug!(path = %path.display(), error = %err, "could not record the bank check");
+        return None;
+    }
+    update.filter(|update| update.added + update.removed + update.edited > 0)
+}
+
+/// Runs [`check`] on a blocking task, loading the bank there, so startup
+/// doesn't wait for it; a bank that fails to load is skipped
+pub fn spawn_check(
+    path: PathBuf,
+    repository: Box<dyn QuestionRepository>,
+) -> JoinHandle<Option<BankUpdate>> {
+    tokio::task::spawn_blocking(move || {
+        let questions = repository
+            .get_questions()
+            .inspect_err(|err| tracing::debug!(error = %err, "bank check skipped"))
+            .ok()?;
+        check(
+            &path,
+            &repository.source_description(),
+            &questions,
+            Utc::now(),
+        )
+    })
+}
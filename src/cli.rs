@@ -0,0 +1,408 @@
+use crate::anki::AnkiOnly;
+use crate::config::Mode;
+use crate::list::ListSort;
+use crate::print::PrintFormat;
+use crate::report::ReportFormat;
+use crate::scorer::{Grading, Strictness};
+use crate::settings::{self, Settings, SettingsError};
+use crate::ui::ScreenLayout;
+use clap::{ArgGroup, Parser, Subcommand};
+use std::path::PathBuf;
+use tracing::Level;
+
+/// Command-line arguments, parsed before the terminal enters raw mode
+/// Flags left unset fall back to the config file, then to built-in defaults
+#[derive(Debug, Parser)]
+#[command(
+    name = "ckad-practitioner",
+    version,
+    about = "Timed CKAD practice quiz in your terminal"
+)]
+#[command(group(ArgGroup::new("shuffling").args(["shuffle", "shuffle_hints", "choices"]).multiple(true)))]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Config file to use instead of ~/.config/ckad-practitioner/config.toml
+    #[arg(long, value_name = "PATH", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Load questions from a JSON or YAML file
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        conflicts_with = "dir",
+        global = true
+    )]
+    pub file: Option<PathBuf>,
+
+    /// Load every JSON/YAML question file in a directory
+    #[arg(
+        long,
+        visible_alias = "questions-dir",
+        value_name = "PATH",
+        global = true
+    )]
+    pub dir: Option<PathBuf>,
+
+    /// Shuffle the question order
+    #[arg(long, overrides_with = "no_shuffle")]
+    pub shuffle: bool,
+
+    /// Keep the bank's question order, even if the config file shuffles
+    #[arg(long, overrides_with = "shuffle")]
+    pub no_shuffle: bool,
+
+    /// Run questions in ascending id order, whatever order the files load in
+    #[arg(long, conflicts_with = "shuffle", overrides_with = "no_sort_by_id")]
+    pub sort_by_id: bool,
+
+    /// Keep the order the files load in, even if the config file sorts by id
+    #[arg(long, overrides_with = "sort_by_id")]
+    pub no_sort_by_id: bool,
+
+    /// Run exam-critical questions first: higher `priority` before lower
+    #[arg(long, overrides_with = "no_by_priority")]
+    pub by_priority: bool,
+
+    /// Don't move exam-critical questions first, even if the config file does
+    #[arg(long, overrides_with = "by_priority")]
+    pub no_by_priority: bool,
+
+    /// Only run questions with a priority, highest first
+    #[arg(long, overrides_with = "no_priority_only")]
+    pub priority_only: bool,
+
+    /// Run questions without a priority too, even if the config file leaves them out
+    #[arg(long, overrides_with = "priority_only")]
+    pub no_priority_only: bool,
+
+    /// Shuffle the hints of each question
+    #[arg(long, overrides_with = "no_shuffle_hints")]
+    pub shuffle_hints: bool,
+
+    /// Keep each question's hint order, even if the config file shuffles hints
+    #[arg(long, overrides_with = "shuffle_hints")]
+    pub no_shuffle_hints: bool,
+
+    /// Cycle back to the first hint after the last one
+    #[arg(long, overrides_with = "no_wrap_hints")]
+    pub wrap_hints: bool,
+
+    /// Stop at the last hint, even if the config file wraps around
+    #[arg(long, overrides_with = "wrap_hints")]
+    pub no_wrap_hints: bool,
+
+    /// Rate your confidence (high/medium/low) before each answer is graded
+    #[arg(long, overrides_with = "no_confidence")]
+    pub confidence: bool,
+
+    /// Grade answers without a confidence rating, even if the config file asks for one
+    #[arg(long, overrides_with = "confidence")]
+    pub no_confidence: bool,
+
+    /// Allow only N new hints over the whole quiz
+    #[arg(long, value_name = "N")]
+    pub hint_budget: Option<usize>,
+
+    /// Seed for reproducible shuffling and generated choices
+    #[arg(long, requires = "shuffling")]
+    pub seed: Option<u64>,
+
+    /// Keep stats and saved sessions under this profile (created on first use)
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Only include questions with one of these tags (comma separated)
+    #[arg(long, value_delimiter = ',', value_name = "TAGS", global = true)]
+    pub tags: Vec<String>,
+
+    /// Only run the first N questions after filtering and ordering (all of them if fewer)
+    #[arg(long, value_name = "N", value_parser = parse_limit, global = true)]
+    pub limit: Option<usize>,
+
+    /// Session mode [default: practice]
+    #[arg(long, value_enum)]
+    pub mode: Option<Mode>,
+
+    /// Untimed study mode showing question and answer side by side (same as --mode study)
+    #[arg(long, conflicts_with = "mode")]
+    pub study: bool,
+
+    /// How typed answers are graded [default: text]
+    #[arg(long, value_enum, value_name = "BACKEND")]
+    pub grade: Option<Grading>,
+
+    /// How forgiving answer matching is [default: normal]
+    #[arg(long, value_enum, value_name = "PRESET")]
+    pub strictness: Option<Strictness>,
+
+    /// Accept answers that differ only in case (questions can still insist on case)
+    #[arg(long, overrides_with = "no_ignore_case")]
+    pub ignore_case: bool,
+
+    /// Match answer case exactly, even if the config file turns case_sensitive off
+    #[arg(long, overrides_with = "ignore_case")]
+    pub no_ignore_case: bool,
+
+    /// Run the questions a playlist file lists, in its order and sections
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["shuffle", "sort_by_id", "by_priority", "priority_only", "tags", "daily"]
+    )]
+    pub playlist: Option<PathBuf>,
+
+    /// Leave out questions attempted in the last N days, unless too few would be left
+    #[arg(long, value_name = "N", conflicts_with_all = ["daily", "playlist"])]
+    pub fresh_days: Option<u32>,
+
+    /// Drill the question with this id until you answer it right several times in a row
+    #[arg(
+        long,
+        value_name = "ID",
+        conflicts_with_all = ["daily", "playlist", "fresh_days", "plain", "study"]
+    )]
+    pub master: Option<usize>,
+
+    /// Correct answers in a row that end a --master drill (3 by default)
+    #[arg(long, value_name = "N", requires = "master", value_parser = parse_limit)]
+    pub streak: Option<usize>,
+
+    /// Practice only the question of the day, picked by today's date (the same for everyone on a bank)
+    #[arg(long)]
+    pub daily: bool,
+
+    /// Only allow jumping to questions whose prerequisites are completed
+    #[arg(long, overrides_with = "no_guided")]
+    pub guided: bool,
+
+    /// Allow jumping to any question, even if the config file sets guided
+    #[arg(long, overrides_with = "guided")]
+    pub no_guided: bool,
+
+    /// Keep a question open after a wrong answer while time is left, to try again
+    #[arg(long, overrides_with = "no_retry_wrong")]
+    pub retry_wrong: bool,
+
+    /// Grade the first answer as final, even if the config file sets retry_wrong
+    #[arg(long, overrides_with = "retry_wrong")]
+    pub no_retry_wrong: bool,
+
+    /// Ring the terminal bell when time expires
+    #[arg(long, overrides_with = "no_bell")]
+    pub bell: bool,
+
+    /// Don't ring the bell, even if the config file does
+    #[arg(long, overrides_with = "bell")]
+    pub no_bell: bool,
+
+    /// Play the [sounds] WAV files when an answer is graded
+    #[arg(long, overrides_with = "no_sounds")]
+    pub sounds: bool,
+
+    /// Stay silent, even if the config file enables sounds
+    #[arg(long, overrides_with = "sounds")]
+    pub no_sounds: bool,
+
+    /// Move to the next question this many seconds after time expires
+    #[arg(long, value_name = "SECS")]
+    pub auto_advance: Option<u64>,
+
+    /// Pause the clock after this many seconds without a key press (not in exam mode)
+    #[arg(long, value_name = "SECS")]
+    pub idle_pause: Option<u64>,
+
+    /// Show "Time expired" for this many seconds before an expired question's answer
+    #[arg(long, value_name = "SECS")]
+    pub reveal_delay: Option<u64>,
+
+    /// Wait for Enter before starting each question's clock
+    #[arg(long, overrides_with = "no_ready_gate")]
+    pub ready_gate: bool,
+
+    /// Start each question's clock at once, even if the config file sets ready_gate
+    #[arg(long, overrides_with = "ready_gate")]
+    pub no_ready_gate: bool,
+
+    /// Take a break after every N completed questions
+    #[arg(long, value_name = "N")]
+    pub break_every: Option<usize>,
+
+    /// Length of each break (default 120); Enter ends one early
+    #[arg(long, value_name = "SECS")]
+    pub break_secs: Option<u64>,
+
+    /// Skip the start screen and countdown: the first clock starts at launch
+    #[arg(long, overrides_with = "countdown")]
+    pub no_countdown: bool,
+
+    /// Open on the start screen, even if the config file sets start_screen = false
+    #[arg(long, overrides_with = "no_countdown")]
+    pub countdown: bool,
+
+    /// Allow '+' to add 30 seconds to the current question (ignored in exam mode)
+    #[arg(long, overrides_with = "no_accommodations")]
+    pub accommodations: bool,
+
+    /// Don't allow '+', even if the config file enables accommodations
+    #[arg(long, overrides_with = "accommodations")]
+    pub no_accommodations: bool,
+
+    /// Redraw rate for animations, 5 to 60 (default 20); lower uses less CPU
+    #[arg(long, value_name = "FPS")]
+    pub fps: Option<u32>,
+
+    /// Disable colors (the NO_COLOR environment variable works too)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Screen layout: standard, or accessible for screen readers and braille
+    /// displays (labeled text without borders, colors or animations)
+    #[arg(long, value_enum, value_name = "LAYOUT")]
+    pub layout: Option<ScreenLayout>,
+
+    /// Also write the session results as JSON to this path
+    #[arg(long, value_name = "PATH")]
+    pub results_out: Option<PathBuf>,
+
+    /// Also write per-question timing (id, category, status, time, hints, points) as CSV to this path
+    #[arg(long, value_name = "PATH")]
+    pub export_csv: Option<PathBuf>,
+
+    /// POST the session results as JSON to this URL when the session ends
+    #[arg(long, value_name = "URL", global = true)]
+    pub webhook: Option<String>,
+
+    /// Also write a human-readable report (md or csv) to the data directory
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub report: Option<ReportFormat>,
+
+    /// Run the quiz as plain line-based input/output instead of the TUI
+    #[arg(long, conflicts_with_all = ["print", "print_answers"])]
+    pub plain: bool,
+
+    /// With --plain, offer command questions as N numbered options with generated wrong ones
+    #[arg(long, value_name = "N", requires = "plain", value_parser = clap::value_parser!(u8).range(2..=9))]
+    pub choices: Option<u8>,
+
+    /// Print the selected questions instead of starting the quiz (implied when stdout is not a terminal)
+    #[arg(long)]
+    pub print: bool,
+
+    /// Include hints and answers when printing (implies --print)
+    #[arg(long)]
+    pub print_answers: bool,
+
+    /// Output format for --print
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = PrintFormat::Text)]
+    pub print_format: PrintFormat,
+
+    /// Write a debug log to the data directory (RUST_LOG also enables it)
+    #[arg(long)]
+    pub debug: bool,
+
+    /// Write the log to this file instead of the data directory
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Log level: error, warn, info, debug or trace (overrides RUST_LOG)
+    #[arg(long, value_name = "LEVEL")]
+    pub log_level: Option<Level>,
+}
+
+/// Commands that run instead of the quiz
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Print a table of the questions a session would use (--file/--dir, --tags, --limit apply)
+    #[command(visible_alias = "list-questions")]
+    List {
+        /// Table for reading or JSON for scripts
+        #[arg(long, value_enum, value_name = "FORMAT", default_value_t = PrintFormat::Text)]
+        format: PrintFormat,
+        /// Row order
+        #[arg(long, value_enum, default_value_t = ListSort::Id)]
+        sort: ListSort,
+    },
+    /// Prompt for a new question and append it to the --file bank
+    Add {
+        /// Start from the question with this id in the same file
+        #[arg(long, value_name = "ID")]
+        from_template: Option<usize>,
+    },
+    /// Retype the selected questions' answer commands and measure speed and accuracy
+    Typing,
+    /// Print the selected questions as a tab-separated file for Anki's importer
+    ExportAnki {
+        /// Export only the questions missed the last time they were answered
+        #[arg(long, value_enum, value_name = "WHICH")]
+        only: Option<AnkiOnly>,
+    },
+    /// Convert Markdown exercises (headings, solution code blocks, hint bullets) to a YAML bank
+    ImportMd {
+        /// A Markdown file, or a directory of them
+        #[arg(value_name = "FILE_OR_DIR")]
+        path: PathBuf,
+        /// Write the bank to this file instead of stdout (never overwritten)
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Check a bank against the JSON Schema and the quiz's rules; exits non-zero on problems
+    Validate {
+        /// The JSON or YAML bank to check
+        #[arg(value_name = "FILE", required_unless_present = "schema_out")]
+        bank: Option<PathBuf>,
+        /// Write the bank JSON Schema to this path, e.g. for editor autocomplete
+        #[arg(long, value_name = "PATH")]
+        schema_out: Option<PathBuf>,
+    },
+    /// Review saved sessions in a browser, served on 127.0.0.1 until Ctrl+C
+    Serve {
+        /// Result files or directories of them [default: the saved sessions]
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+    },
+    /// Retry posting results that could not reach the webhook
+    FlushOutbox,
+    /// Print how often saved sessions used hints, per tag and per question
+    Stats,
+    /// Apply a question's setup manifests into a practice namespace on the current context
+    PrepCluster {
+        /// The question whose setup to apply
+        #[arg(value_name = "ID")]
+        id: usize,
+        /// Delete the question's practice namespace instead
+        #[arg(long)]
+        teardown: bool,
+    },
+    /// Work with profiles
+    Profiles {
+        #[command(subcommand)]
+        action: ProfilesCommand,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Subcommand)]
+pub enum ProfilesCommand {
+    /// List the profiles in the data directory
+    List,
+}
+
+/// A question count of at least one
+fn parse_limit(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(limit) => Ok(limit),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Parses the process arguments and merges them with the config file
+/// Invalid arguments exit with a clap error; config problems are returned.
+/// Config warnings are handed back so they can be printed and logged.
+pub fn parse_args() -> Result<(Settings, Vec<String>), SettingsError> {
+    let cli = Cli::parse();
+    let (file_config, warnings) = settings::load_file_config(cli.config.as_deref())?;
+    Ok((Settings::resolve(&cli, &file_config)?, warnings))
+}
@@ -0,0 +1,344 @@
+use crate::kubectl::{self, KubectlError, KubectlRunner, ManifestFile, SystemKubectl};
+use crate::models::Question;
+use crate::terminal::{self, TtyModes};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Kube-contexts never prepared into unless `[cluster] deny_contexts` says otherwise
+pub const DEFAULT_DENY_CONTEXTS: [&str; 2] = ["*prod*", "*live*"];
+
+/// Safety and logging for `prep-cluster` and the prep key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterSettings {
+    /// Context name patterns to refuse, `*` matching any run of characters;
+    /// compared case-insensitively
+    pub deny_contexts: Vec<String>,
+    /// kubectl output is also appended here
+    pub log_file: Option<PathBuf>,
+}
+
+impl Default for ClusterSettings {
+    fn default() -> Self {
+        Self {
+            deny_contexts: DEFAULT_DENY_CONTEXTS.map(str::to_string).to_vec(),
+            log_file: None,
+        }
+    }
+}
+
+/// `prep-cluster`: the question to set up, or to tear down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrepOptions {
+    pub id: usize,
+    pub teardown: bool,
+}
+
+/// Why a namespace was not prepared or removed
+#[derive(Debug)]
+pub enum ClusterError {
+    /// The question has nothing to apply
+    NoSetup(usize),
+    UnknownQuestion(usize),
+    /// The current context matches a deny-list pattern
+    Denied {
+        context: String,
+        pattern: String,
+    },
+    /// The user answered no at the confirmation prompt
+    Declined(String),
+    Kubectl(KubectlError),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClusterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClusterError::NoSetup(id) => write!(f, "question {} has no setup manifests", id),
+            ClusterError::UnknownQuestion(id) => write!(f, "no question with id {}", id),
+            ClusterError::Denied { context, pattern } => write!(
+                f,
+                "refusing to touch context '{}': it matches the deny-list pattern '{}'",
+                context, pattern
+            ),
+            ClusterError::Declined(context) => {
+                write!(f, "nothing was changed on context '{}'", context)
+            }
+            ClusterError::Kubectl(err) => write!(f, "{}", err),
+            ClusterError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClusterError {}
+
+impl From<KubectlError> for ClusterError {
+    fn from(err: KubectlError) -> Self {
+        ClusterError::Kubectl(err)
+    }
+}
+
+impl From<io::Error> for ClusterError {
+    fn from(err: io::Error) -> Self {
+        ClusterError::Io(err)
+    }
+}
+
+/// The namespace a question's setup goes into
+pub fn namespace_for(id: usize) -> String {
+    format!("ckad-practice-{}", id)
+}
+
+/// The first deny-list pattern `context` matches
+pub fn denied_by<'a>(context: &str, patterns: &'a [String]) -> Option<&'a str> {
+    let context = context.to_lowercase();
+    patterns
+        .iter()
+        .find(|pattern| glob_match(&pattern.to_lowercase(), &context))
+        .map(String::as_str)
+}
+
+/// `*` matches any run of characters, everything else itself
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Sets up or tears down a question's practice namespace on the current
+/// context, after the deny-list and a confirmation
+/// Prompts are read from `input`; prompts and kubectl output go to `out`.
+/// Returns the namespace.
+pub fn run<R: BufRead, W: Write>(
+    runner: &dyn KubectlRunner,
+    settings: &ClusterSettings,
+    question: &Question,
+    teardown: bool,
+    input: &mut R,
+    out: &mut W,
+) -> Result<String, ClusterError> {
+    if !teardown && question.setup.is_none() {
+        return Err(ClusterError::NoSetup(question.id));
+    }
+    let context = kubectl::current_context(runner)?;
+    if let Some(pattern) = denied_by(&context, &settings.deny_contexts) {
+        return Err(ClusterError::Denied {
+            context,
+            pattern: pattern.to_string(),
+        });
+    }
+    let namespace = namespace_for(question.id);
+    if teardown {
+        write!(
+            out,
+            "Delete namespace '{}' and everything in it on context '{}'? [y/N] ",
+            namespace, context
+        )?;
+    } else {
+        write!(
+            out,
+            "Apply question {}'s setup into namespace '{}' on context '{}'? [y/N] ",
+            question.id, namespace, context
+        )?;
+    }
+    out.flush()?;
+    let mut reply = String::new();
+    input.read_line(&mut reply)?;
+    if !matches!(reply.trim(), "y" | "Y" | "yes") {
+        return Err(ClusterError::Declined(context));
+    }
+
+    let mut log = Log::open(settings, out)?;
+    if teardown {
+        remove(runner, question.id, &mut log)?;
+    } else {
+        prepare(runner, question, &mut log)?;
+    }
+    Ok(namespace)
+}
+
+/// Creates the question's namespace (an existing one is reused) and applies
+/// its setup manifests into it; no checks or prompts, see [`run`]
+pub fn prepare(
+    runner: &dyn KubectlRunner,
+    question: &Question,
+    log: &mut dyn Write,
+) -> Result<String, ClusterError> {
+    let setup = question
+        .setup
+        .as_deref()
+        .ok_or(ClusterError::NoSetup(question.id))?;
+    let namespace = namespace_for(question.id);
+    match run_logged(runner, &["create", "namespace", &namespace], log) {
+        Err(ClusterError::Kubectl(KubectlError::Failed(stderr)))
+            if stderr.contains("AlreadyExists") => {}
+        result => {
+            result?;
+        }
+    }
+    let manifest = ManifestFile::create(&std::env::temp_dir(), setup)?;
+    let path = manifest.path().display().to_string();
+    run_logged(runner, &["apply", "-n", &namespace, "-f", &path], log)?;
+    Ok(namespace)
+}
+
+/// Deletes the question's namespace without waiting for it to go; a missing
+/// namespace is not an error
+pub fn remove(
+    runner: &dyn KubectlRunner,
+    id: usize,
+    log: &mut dyn Write,
+) -> Result<(), ClusterError> {
+    let namespace = namespace_for(id);
+    run_logged(
+        runner,
+        &[
+            "delete",
+            "namespace",
+            &namespace,
+            "--ignore-not-found",
+            "--wait=false",
+        ],
+        log,
+    )
+}
+
+fn run_logged(
+    runner: &dyn KubectlRunner,
+    args: &[&str],
+    log: &mut dyn Write,
+) -> Result<(), ClusterError> {
+    writeln!(log, "$ kubectl {}", args.join(" "))?;
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    match runner.run(&args) {
+        Ok(output) => {
+            write!(log, "{}", output)?;
+            Ok(())
+        }
+        Err(KubectlError::Failed(stderr)) => {
+            write!(log, "{}", stderr)?;
+            Err(KubectlError::Failed(stderr).into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// kubectl output for the terminal, copied to the log file when one is set
+struct Log<'a, W: Write> {
+    out: &'a mut W,
+    file: Option<fs::File>,
+}
+
+impl<'a, W: Write> Log<'a, W> {
+    fn open(settings: &ClusterSettings, out: &'a mut W) -> io::Result<Self> {
+        let file = match &settings.log_file {
+            Some(path) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                Some(
+                    fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)?,
+                )
+            }
+            None => None,
+        };
+        Ok(Self { out, file })
+    }
+}
+
+impl<W: Write> Write for Log<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.write_all(buf)?;
+        if let Some(file) = &mut self.file {
+            file.write_all(buf)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()?;
+        if let Some(file) = &mut self.file {
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Tears down the namespaces prepared during a quiz, e.g. after it ends;
+/// they were confirmed when prepared, but the deny-list is checked again in
+/// case the context changed since
+pub fn remove_all<W: Write>(
+    runner: &dyn KubectlRunner,
+    settings: &ClusterSettings,
+    ids: &[usize],
+    out: &mut W,
+) -> Result<(), ClusterError> {
+    let context = kubectl::current_context(runner)?;
+    if let Some(pattern) = denied_by(&context, &settings.deny_contexts) {
+        return Err(ClusterError::Denied {
+            context,
+            pattern: pattern.to_string(),
+        });
+    }
+    let mut log = Log::open(settings, out)?;
+    for &id in ids {
+        remove(runner, id, &mut log)?;
+    }
+    Ok(())
+}
+
+/// How long one kubectl call may take; applying can wait on the API server
+pub const KUBECTL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Leaves the TUI to prepare `question` with the real kubectl, so the prompt
+/// and kubectl's output have the terminal, then waits for Enter and comes back
+/// The outer error is about the terminal, the inner one about the prep.
+pub async fn hand_over(
+    settings: &ClusterSettings,
+    question: &Question,
+) -> io::Result<Result<String, ClusterError>> {
+    terminal::leave()?;
+    let modes = TtyModes::save();
+    let (settings, question) = (settings.clone(), question.clone());
+    let prepared = tokio::task::spawn_blocking(move || {
+        let mut input = io::stdin().lock();
+        let mut out = io::stdout();
+        let prepared = SystemKubectl::detect(KUBECTL_TIMEOUT)
+            .map_err(ClusterError::from)
+            .and_then(|kubectl| run(&kubectl, &settings, &question, false, &mut input, &mut out));
+        match &prepared {
+            Ok(namespace) => println!("Setup applied in namespace {}.", namespace),
+            Err(err) => println!("{}", err),
+        }
+        print!("Press Enter to return to the quiz. ");
+        let _ = out.flush();
+        let _ = input.read_line(&mut String::new());
+        prepared
+    })
+    .await
+    .map_err(io::Error::other)?;
+
+    if let Some(modes) = &modes {
+        modes.restore()?;
+    }
+    terminal::enter()?;
+    Ok(prepared)
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// How a quiz session behaves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Hints are disabled, like the real exam
+    Exam,
+    /// The default flow: hints available, answer revealed when time expires
+    #[default]
+    Practice,
+    /// Questions that weren't passed come back at the end of the session
+    Drill,
+    /// Untimed: question and answer side by side, for learning rather than testing
+    Study,
+    /// Like practice, but each next question's difficulty follows the answers so far
+    Adaptive,
+}
+
+/// What loading does when two questions share an id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateIds {
+    /// Refuse to start, naming the ids
+    Error,
+    /// Keep the first question with an id and give later ones unused ids, with a warning
+    #[default]
+    Renumber,
+}
+
+/// Keeps questions attempted in the last `days` out of a session; see
+/// `scheduler::exclude_recent`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Freshness {
+    pub days: u32,
+    pub now: DateTime<Utc>,
+    /// When each question identity was last attempted, from the saved sessions
+    pub last_seen: HashMap<String, DateTime<Utc>>,
+}
+
+/// Drills the question with this id until it is answered right `streak`
+/// times in a row; see `mastery::MasteryLoop`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mastery {
+    pub id: usize,
+    pub streak: usize,
+}
+
+impl Mastery {
+    pub const DEFAULT_STREAK: usize = 3;
+}
+
+/// Runtime options for a quiz session
+/// Assembled at startup and injected into the App, defaults match the original behavior
+#[derive(Debug, Clone)]
+pub struct QuizConfig {
+    pub mode: Mode,
+    /// Shuffle the order of the selected questions
+    pub shuffle_questions: bool,
+    /// Order questions by ascending id, for study guides that cite question numbers
+    pub sort_by_id: bool,
+    /// Run higher-priority questions first, keeping the order within each priority
+    pub by_priority: bool,
+    /// Keep only questions with a priority above 0, highest first
+    pub priority_only: bool,
+    /// Shuffle each question's hints; a hint revealing the full answer stays last
+    pub shuffle_hints: bool,
+    /// Pressing 'h' on the last hint wraps back to the first instead of stopping
+    pub wrap_hints: bool,
+    /// Hints the whole quiz may reveal; unlimited when `None`
+    pub hint_budget: Option<usize>,
+    /// Unneeded commands lower the partial credit of multi-command answers
+    pub penalize_extra_commands: bool,
+    /// Fixed seed for reproducible shuffles
+    pub seed: Option<u64>,
+    /// Only keep questions carrying at least one of these tags (empty keeps all)
+    pub tags: Vec<String>,
+    /// Maximum number of questions in the session
+    pub limit: Option<usize>,
+    /// How loading handles questions that share an id
+    pub duplicate_ids: DuplicateIds,
+    /// Submitted answers must match case; questions can override this
+    pub case_sensitive: bool,
+    /// Offer one-line command questions as this many numbered options (plain mode)
+    pub choices: Option<usize>,
+    /// The timer turns red when fewer seconds than this remain
+    pub warning_secs: u64,
+    /// Ring the terminal bell when a question's time expires
+    pub bell: bool,
+    /// Move on automatically this many seconds after time expires
+    pub auto_advance_secs: Option<u64>,
+    /// Seconds "Time expired" stays up before an expired question's answer appears
+    pub reveal_delay_secs: u64,
+    /// Each question waits for Enter before its clock starts (ignored in study mode)
+    pub ready_gate: bool,
+    /// Guided mode: jumping to a question requires its prerequisites to be completed
+    pub guided: bool,
+    /// A wrong answer with time left is kept as an attempt and the question
+    /// stays open for another try
+    pub retry_wrong: bool,
+    /// Type the answer out over a second when time expires instead of showing it at once
+    pub reveal_animation: bool,
+    /// Flash the header during the last few seconds; off for photosensitive users
+    pub flash: bool,
+    /// Accessibility accommodations: '+' grants extra time (never in exam mode)
+    pub accommodations: bool,
+    /// 'R' restarts the current question's clock and hints (never in exam mode)
+    pub allow_restart: bool,
+    /// Ask how confident the user is before grading an answer (not in study mode)
+    pub confidence_prompt: bool,
+    /// Pause the clock after this many seconds without a key press
+    pub idle_pause_secs: Option<u64>,
+    /// Question of the day: keep only the question picked for this date
+    pub daily: Option<NaiveDate>,
+    /// Playlist section titles by the index of the question each one starts at
+    pub sections: BTreeMap<usize, String>,
+    /// Leave out recently attempted questions
+    pub fresh: Option<Freshness>,
+    /// Take a break after every this many completed questions
+    pub break_every: Option<usize>,
+    /// Length of a break; Enter ends one early
+    pub break_secs: u64,
+    /// In exam mode the clock keeps running through breaks, as a real exam's does
+    pub strict_breaks: bool,
+    /// Practise a single question until mastered instead of a whole session
+    pub mastery: Option<Mastery>,
+}
+
+impl QuizConfig {
+    /// Seconds added per press of the time-extension key
+    pub const EXTENSION_SECS: u64 = 30;
+
+    pub fn allows_time_extension(&self) -> bool {
+        self.accommodations && self.mode != Mode::Exam
+    }
+
+    pub fn allows_timer_restart(&self) -> bool {
+        self.allow_restart && !matches!(self.mode, Mode::Exam | Mode::Study)
+    }
+
+    /// How long without input pauses the clock; never in exam mode, where the
+    /// clock runs like the real one, nor in study mode, which has none
+    pub fn idle_pause(&self) -> Option<Duration> {
+        match (self.mode, self.idle_pause_secs) {
+            (Mode::Exam | Mode::Study, _) | (_, None | Some(0)) => None,
+            (_, Some(secs)) => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    /// Completed questions between breaks; none in study mode, which has no clock
+    pub fn break_interval(&self) -> Option<usize> {
+        match (self.mode, self.break_every) {
+            (Mode::Study, _) | (_, None | Some(0)) => None,
+            (_, every) => every,
+        }
+    }
+
+    /// Whether the clock stops for breaks
+    pub fn breaks_pause_clock(&self) -> bool {
+        !(self.strict_breaks && self.mode == Mode::Exam)
+    }
+
+    /// Study mode shows the answer from the start, so there is nothing to predict
+    pub fn asks_confidence(&self) -> bool {
+        self.confidence_prompt && self.mode != Mode::Study
+    }
+}
+
+impl Default for QuizConfig {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            shuffle_questions: false,
+            sort_by_id: false,
+            by_priority: false,
+            priority_only: false,
+            shuffle_hints: false,
+            wrap_hints: false,
+            hint_budget: None,
+            penalize_extra_commands: false,
+            seed: None,
+            tags: Vec::new(),
+            limit: None,
+            duplicate_ids: DuplicateIds::default(),
+            case_sensitive: true,
+            choices: None,
+            warning_secs: 10,
+            bell: false,
+            auto_advance_secs: None,
+            reveal_delay_secs: 0,
+            ready_gate: false,
+            guided: false,
+            retry_wrong: false,
+            reveal_animation: true,
+            flash: true,
+            accommodations: false,
+            allow_restart: false,
+            confidence_prompt: false,
+            idle_pause_secs: None,
+            daily: None,
+            sections: BTreeMap::new(),
+            fresh: None,
+            break_every: None,
+            break_secs: 120,
+            strict_breaks: false,
+            mastery: None,
+        }
+    }
+}
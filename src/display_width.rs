@@ -0,0 +1,42 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Terminal columns `text` takes up: two for CJK characters and most emoji,
+/// none for combining marks
+pub fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// `text` cut to at most `max` columns, ending in `ellipsis` when it had to
+/// be cut
+/// Cuts between grapheme clusters, so an emoji with a skin tone or an accented
+/// letter is kept or dropped whole, and a wide character that would straddle
+/// the limit is left out rather than split.
+pub fn truncate(text: &str, max: usize, ellipsis: &str) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    // Too narrow for the ellipsis: as much of the text as fits, unmarked
+    let ellipsis = if width(ellipsis) <= max { ellipsis } else { "" };
+    let room = max - width(ellipsis);
+    let mut kept = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = width(grapheme);
+        if used + grapheme_width > room {
+            break;
+        }
+        kept.push_str(grapheme);
+        used += grapheme_width;
+    }
+    format!("{}{}", kept.trim_end(), ellipsis)
+}
+
+/// `text` followed by spaces up to `columns`, for aligning table cells
+pub fn pad(text: &str, columns: usize) -> String {
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(columns.saturating_sub(width(text)))
+    )
+}
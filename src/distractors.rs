@@ -0,0 +1,201 @@
+use crate::scorer::normalize;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Resource kinds that are easy to confuse; a distractor swaps one for another
+/// from the same group
+const RESOURCE_GROUPS: &[&[&str]] = &[
+    &[
+        "pod",
+        "deployment",
+        "replicaset",
+        "statefulset",
+        "daemonset",
+        "job",
+        "cronjob",
+    ],
+    &["configmap", "secret"],
+    &["service", "ingress", "networkpolicy"],
+    &["namespace", "serviceaccount", "role", "rolebinding"],
+];
+
+/// Subcommands and flags with a commonly confused counterpart
+const SWAPS: &[(&str, &str)] = &[
+    ("run", "create"),
+    ("create", "apply"),
+    ("expose", "create"),
+    ("scale", "autoscale"),
+    ("--from-literal", "--from-file"),
+    ("--from-file", "--from-env-file"),
+    ("--port", "--target-port"),
+    ("--target-port", "--port"),
+    ("--dry-run=client", "--dry-run=server"),
+    ("--restart=Never", "--restart=Always"),
+];
+
+/// Images that are often mixed up when no tag is given
+const IMAGE_SWAPS: &[(&str, &str)] = &[
+    ("nginx", "httpd"),
+    ("httpd", "nginx"),
+    ("busybox", "alpine"),
+    ("alpine", "busybox"),
+    ("redis", "memcached"),
+];
+
+/// Plausible wrong versions of a one-line command, for multiple-choice questions
+/// Each distractor changes one thing: a resource kind, a flag, an image tag, a
+/// number, or drops a `--flag=value`. Fewer than `n` come back when the command
+/// doesn't allow that many (none for multi-line answers).
+pub fn generate_distractors(answer: &str, n: usize) -> Vec<String> {
+    generate_distractors_with(answer, n, &mut rand::thread_rng())
+}
+
+/// `generate_distractors` with a caller-supplied (e.g. seeded) random source
+pub fn generate_distractors_with<R: Rng>(answer: &str, n: usize, rng: &mut R) -> Vec<String> {
+    let mut candidates = mutations(answer);
+    candidates.shuffle(rng);
+    candidates.truncate(n);
+    candidates
+}
+
+/// Every distinct single-edit variant of `answer`, excluding the answer itself
+fn mutations(answer: &str) -> Vec<String> {
+    if answer.trim().lines().count() != 1 {
+        return Vec::new();
+    }
+    let words: Vec<&str> = answer.split_whitespace().collect();
+    if words.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::from([normalize(answer)]);
+    let mut variants = Vec::new();
+    for (index, word) in words.iter().enumerate().skip(1) {
+        let mut replacements: Vec<Option<String>> =
+            word_mutations(word).into_iter().map(Some).collect();
+        if word.starts_with("--") && word.contains('=') {
+            replacements.push(None);
+        }
+        for replacement in replacements {
+            let mut variant: Vec<&str> = words.clone();
+            match &replacement {
+                Some(replacement) => variant[index] = replacement,
+                None => {
+                    variant.remove(index);
+                }
+            }
+            let variant = variant.join(" ");
+            if seen.insert(variant.clone()) {
+                variants.push(variant);
+            }
+        }
+    }
+    variants
+}
+
+/// Replacements for a single word of the command
+fn word_mutations(word: &str) -> Vec<String> {
+    let mut replacements = Vec::new();
+    for (from, to) in SWAPS {
+        if word == *from {
+            replacements.push(to.to_string());
+        } else if let Some(value) = word.strip_prefix(&format!("{}=", from)) {
+            replacements.push(format!("{}={}", to, value));
+        }
+    }
+
+    // `pod`, `pods` or `deployment/web`
+    let (kind, rest) = match word.split_once('/') {
+        Some((kind, name)) => (kind, format!("/{}", name)),
+        None => (word, String::new()),
+    };
+    let (singular, plural) = match kind.strip_suffix('s') {
+        Some(singular) if !singular.ends_with('s') => (singular, "s"),
+        _ => (kind, ""),
+    };
+    if let Some(group) = RESOURCE_GROUPS
+        .iter()
+        .find(|group| group.contains(&singular))
+    {
+        replacements.extend(
+            group
+                .iter()
+                .filter(|other| **other != singular)
+                .map(|other| format!("{}{}{}", other, plural, rest)),
+        );
+    }
+
+    if let Some(image) = word.strip_prefix("--image=") {
+        replacements.extend(image_mutations(image).map(|image| format!("--image={}", image)));
+    }
+
+    if let Some((flag, value)) = word.split_once('=') {
+        replacements.extend(number_mutations(value).map(|value| format!("{}={}", flag, value)));
+    } else {
+        replacements.extend(number_mutations(word));
+    }
+    replacements
+}
+
+/// A different tag, or for untagged images a commonly confused image
+fn image_mutations(image: &str) -> impl Iterator<Item = String> {
+    let mut images = Vec::new();
+    match image.rsplit_once(':') {
+        Some((name, tag)) if tag != "latest" => {
+            images.push(format!("{}:latest", name));
+            if let Some((major, minor)) = tag.rsplit_once('.') {
+                if let Ok(minor) = minor.parse::<u32>() {
+                    images.push(format!("{}:{}.{}", name, major, minor + 1));
+                }
+            }
+        }
+        Some(_) => {}
+        None => {
+            if let Some((_, other)) = IMAGE_SWAPS.iter().find(|(from, _)| *from == image) {
+                images.push(other.to_string());
+            }
+        }
+    }
+    images.into_iter()
+}
+
+/// One more and one less, for counts such as replicas or ports
+fn number_mutations(value: &str) -> impl Iterator<Item = String> {
+    let numbers = match value.parse::<u32>() {
+        Ok(number) if number > 1 => vec![number - 1, number + 1],
+        Ok(number) => vec![number + 1],
+        Err(_) => Vec::new(),
+    };
+    numbers.into_iter().map(|number| number.to_string())
+}
+
+/// A command question offered as numbered options, one of them correct
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceSet {
+    pub options: Vec<String>,
+    /// Index of the correct answer in `options`
+    pub correct: usize,
+}
+
+impl ChoiceSet {
+    /// Up to `size` shuffled options: `answer` plus generated distractors.
+    /// `None` when no distractors could be generated.
+    pub fn build<R: Rng>(answer: &str, size: usize, rng: &mut R) -> Option<Self> {
+        let mut options = generate_distractors_with(answer, size.saturating_sub(1), rng);
+        if options.is_empty() {
+            return None;
+        }
+        let answer = answer.trim().to_string();
+        options.push(answer.clone());
+        options.shuffle(rng);
+        let correct = options.iter().position(|option| *option == answer)?;
+        Some(Self { options, correct })
+    }
+
+    /// The option chosen by a 1-based number, e.g. "2"
+    pub fn pick(&self, input: &str) -> Option<&str> {
+        let number = input.trim().parse::<usize>().ok()?;
+        self.options.get(number.checked_sub(1)?).map(String::as_str)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::models::Question;
+use crate::shell;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Editor opened for manifest answers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorSettings {
+    /// Command line to run instead of `$EDITOR`, split on whitespace
+    pub command: Option<String>,
+    /// Keep the question's clock running while the editor is open
+    pub timed: bool,
+}
+
+impl EditorSettings {
+    /// The configured command, else `$EDITOR`, else `vi`, followed by `path`
+    pub fn command_line(&self, path: &Path) -> Vec<String> {
+        let line = self
+            .command
+            .clone()
+            .or_else(|| std::env::var("EDITOR").ok())
+            .filter(|line| !line.trim().is_empty())
+            .unwrap_or_else(|| "vi".to_string());
+        let mut argv: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        argv.push(path.to_string_lossy().into_owned());
+        argv
+    }
+}
+
+/// Why an edited answer was not submitted
+#[derive(Debug)]
+pub enum EditorError {
+    /// The answer file could not be written or read back
+    File(io::Error),
+    /// The editor command could not be started
+    Launch(io::Error),
+    /// The editor exited unsuccessfully (e.g. `:cq` in vi)
+    Failed(ExitStatus),
+    /// Nothing but the comment header was left in the file
+    Empty,
+}
+
+impl fmt::Display for EditorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditorError::File(err) => write!(f, "could not use the answer file: {}", err),
+            EditorError::Launch(err) => write!(f, "could not start editor: {}", err),
+            EditorError::Failed(status) => {
+                write!(f, "editor exited with {}; nothing submitted", status)
+            }
+            EditorError::Empty => write!(f, "the answer file was empty; nothing submitted"),
+        }
+    }
+}
+
+impl std::error::Error for EditorError {}
+
+/// Temporary file holding one answer; removed when dropped, so every
+/// path out of the editor cleans up
+#[derive(Debug)]
+pub struct AnswerFile {
+    path: PathBuf,
+}
+
+impl AnswerFile {
+    /// Creates the file in `dir`, pre-filled with `template(question)`
+    pub fn create(dir: &Path, question: &Question) -> io::Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.subsec_nanos());
+        let path = dir.join(format!(
+            "ckad-answer-{}-{}-{}.yaml",
+            std::process::id(),
+            question.id,
+            nanos
+        ));
+        fs::write(&path, template(question))?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The file's contents without the comment header
+    pub fn read_answer(&self) -> Result<String, EditorError> {
+        let text = fs::read_to_string(&self.path).map_err(EditorError::File)?;
+        let answer = strip_header(&text);
+        if answer.is_empty() {
+            Err(EditorError::Empty)
+        } else {
+            Ok(answer)
+        }
+    }
+}
+
+impl Drop for AnswerFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Starting contents: the question as `#` comments, then the context to edit
+pub fn template(question: &Question) -> String {
+    let mut text = format!("# Question {}\n", question.id);
+    for line in question.question.lines() {
+        text.push_str(&format!("# {}\n", line));
+    }
+    text.push_str("#\n# Save and quit to submit. These leading comment lines are ignored;\n");
+    text.push_str("# leave nothing else in the file to cancel.\n");
+    if let Some(context) = &question.context {
+        text.push_str(context);
+        if !context.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Drops the leading comment and blank lines, and trailing whitespace
+pub fn strip_header(text: &str) -> String {
+    let body: Vec<&str> = text
+        .lines()
+        .skip_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        .collect();
+    body.join("\n").trim_end().to_string()
+}
+
+/// Opens the editor on a temporary answer file and returns what was saved
+/// The outer error is about the terminal, the inner one says why there is no answer.
+pub async fn edit(
+    settings: &EditorSettings,
+    question: &Question,
+) -> io::Result<Result<String, EditorError>> {
+    let file = match AnswerFile::create(&std::env::temp_dir(), question) {
+        Ok(file) => file,
+        Err(err) => return Ok(Err(EditorError::File(err))),
+    };
+    let argv = settings.command_line(file.path());
+    let status =
+        shell::hand_over(&argv, "Quiz paused. Save and quit the editor to submit.").await?;
+    Ok(match status {
+        Err(err) => Err(EditorError::Launch(err)),
+        Ok(status) if !status.success() => Err(EditorError::Failed(status)),
+        Ok(_) => file.read_answer(),
+    })
+}
@@ -0,0 +1,59 @@
+use crossterm::event::{Event, EventStream};
+use futures_util::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Anything the event loop can read terminal events from
+/// The loop ends when the source does.
+pub trait EventSource: Stream<Item = io::Result<Event>> + Unpin {}
+
+impl<S: Stream<Item = io::Result<Event>> + Unpin> EventSource for S {}
+
+/// What one read from an event source produced
+#[derive(Debug)]
+pub enum Input {
+    Event(Event),
+    /// The source ran out, as scripted events do
+    Ended,
+    /// Reading failed, e.g. stdin hit EOF or the SSH session dropped
+    Lost(io::Error),
+}
+
+/// Reads the next event, turning read errors into `Input::Lost` so the loop
+/// can wind down instead of failing
+pub async fn next_input<E: EventSource>(events: &mut E) -> Input {
+    match events.next().await {
+        Some(Ok(event)) => Input::Event(event),
+        Some(Err(err)) => Input::Lost(err),
+        None => Input::Ended,
+    }
+}
+
+/// Real keyboard input from the terminal
+pub fn terminal_events() -> EventStream {
+    EventStream::new()
+}
+
+/// A fixed list of events, delivered in order and then ended; for tests and demos
+#[derive(Debug, Default)]
+pub struct ScriptedEvents {
+    events: VecDeque<Event>,
+}
+
+impl ScriptedEvents {
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        Self {
+            events: events.into_iter().collect(),
+        }
+    }
+}
+
+impl Stream for ScriptedEvents {
+    type Item = io::Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.events.pop_front().map(Ok))
+    }
+}
@@ -0,0 +1,130 @@
+use crate::quiz_state::{QuestionStatus, QuizState};
+use crate::results::SessionResult;
+use chrono::{Days, NaiveDate, TimeZone};
+use std::collections::BTreeMap;
+
+/// Daily practice target from the `[goal]` config section
+/// When both parts are set, a day counts only if both are reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DailyGoal {
+    pub questions: Option<usize>,
+    pub minutes: Option<u64>,
+}
+
+/// Practice done on one local day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DayProgress {
+    /// Questions with an outcome other than skipped
+    pub questions: usize,
+    /// Time spent on those questions
+    pub secs: u64,
+}
+
+impl DayProgress {
+    pub fn add_session(&mut self, session: &SessionResult) {
+        for question in &session.questions {
+            if counts(question.outcome) {
+                self.questions += 1;
+                self.secs += question.time_spent_secs;
+            }
+        }
+    }
+
+    /// The running session, whose questions are not saved yet
+    pub fn add_quiz(&mut self, quiz_state: &QuizState) {
+        for record in quiz_state.records() {
+            if counts(record.status) {
+                self.questions += 1;
+                self.secs += record.time_spent.map_or(0, |spent| spent.as_secs());
+            }
+        }
+    }
+
+    /// Sum of two days' progress, e.g. saved sessions plus the running one
+    pub fn plus(self, other: DayProgress) -> DayProgress {
+        DayProgress {
+            questions: self.questions + other.questions,
+            secs: self.secs + other.secs,
+        }
+    }
+}
+
+/// Skipped questions don't count as practice
+fn counts(status: QuestionStatus) -> bool {
+    !matches!(status, QuestionStatus::Pending | QuestionStatus::Skipped)
+}
+
+impl DailyGoal {
+    pub fn is_set(&self) -> bool {
+        self.questions.is_some() || self.minutes.is_some()
+    }
+
+    pub fn is_met(&self, progress: &DayProgress) -> bool {
+        self.is_set()
+            && self
+                .questions
+                .is_none_or(|target| progress.questions >= target)
+            && self
+                .minutes
+                .is_none_or(|target| progress.secs >= target * 60)
+    }
+
+    /// e.g. "Goal: 4/10 questions, 12/20 min" or "Goal met: 10/10 questions"
+    pub fn progress_text(&self, progress: &DayProgress) -> String {
+        let mut parts = Vec::new();
+        if let Some(target) = self.questions {
+            parts.push(format!("{}/{} questions", progress.questions, target));
+        }
+        if let Some(target) = self.minutes {
+            parts.push(format!("{}/{} min", progress.secs / 60, target));
+        }
+        let label = if self.is_met(progress) {
+            "Goal met"
+        } else {
+            "Goal"
+        };
+        format!("{}: {}", label, parts.join(", "))
+    }
+}
+
+/// Progress per local date in `tz`
+/// A session belongs to the day it started on, even if it ran past midnight.
+pub fn daily_progress<Tz: TimeZone>(
+    history: &[SessionResult],
+    tz: &Tz,
+) -> BTreeMap<NaiveDate, DayProgress> {
+    let mut days: BTreeMap<NaiveDate, DayProgress> = BTreeMap::new();
+    for session in history {
+        let date = session.started_at.with_timezone(tz).date_naive();
+        days.entry(date).or_default().add_session(session);
+    }
+    days
+}
+
+/// How many of the `days` days ending with `today` met the goal
+pub fn days_met<Tz: TimeZone>(
+    history: &[SessionResult],
+    goal: &DailyGoal,
+    today: NaiveDate,
+    days: u64,
+    tz: &Tz,
+) -> usize {
+    let progress = daily_progress(history, tz);
+    (0..days)
+        .filter_map(|back| today.checked_sub_days(Days::new(back)))
+        .filter(|date| progress.get(date).is_some_and(|day| goal.is_met(day)))
+        .count()
+}
+
+/// Launch line such as "Goal met 4 of the last 7 days"
+pub fn week_summary<Tz: TimeZone>(
+    history: &[SessionResult],
+    goal: &DailyGoal,
+    today: NaiveDate,
+    tz: &Tz,
+) -> String {
+    format!(
+        "Goal met {} of the last 7 days",
+        days_met(history, goal, today, 7, tz)
+    )
+}
@@ -0,0 +1,1121 @@
+use crate::models::{Question, QuestionGrading, RubricItem};
+use regex::{Regex, RegexBuilder};
+use std::fmt;
+
+/// Long flags that never take a value, so a word after them is an argument
+const BOOLEAN_FLAGS: &[&str] = &[
+    "--all",
+    "--all-namespaces",
+    "--force",
+    "--now",
+    "--overwrite",
+    "--record",
+    "--recursive",
+    "--rm",
+    "--save-config",
+    "--show-labels",
+    "--stdin",
+    "--tty",
+    "--watch",
+];
+
+/// Single-letter flags that never take a value; clusters of them such as `-it`
+/// are split into separate flags
+const BOOLEAN_SHORT_FLAGS: &[char] = &['i', 't', 'A', 'w', 'R'];
+
+/// Names kubectl accepts for itself; the first word of a command is rewritten
+/// to the name on the left
+const COMMAND_ALIASES: &[(&str, &[&str])] = &[("kubectl", &["k"])];
+
+/// Resource types with the short, singular and other names kubectl accepts for
+/// them; resource arguments (see [`RESOURCE_VERBS`]) are rewritten to the name
+/// on the left, including in `deploy/web` and `po,svc` forms
+const RESOURCE_ALIASES: &[(&str, &[&str])] = &[
+    ("pods", &["po", "pod"]),
+    ("services", &["svc", "service"]),
+    ("deployments", &["deploy", "deployment"]),
+    ("replicasets", &["rs", "replicaset"]),
+    ("statefulsets", &["sts", "statefulset"]),
+    ("daemonsets", &["ds", "daemonset"]),
+    ("jobs", &["job"]),
+    ("cronjobs", &["cj", "cronjob"]),
+    ("configmaps", &["cm", "configmap"]),
+    ("secrets", &["secret"]),
+    ("serviceaccounts", &["sa", "serviceaccount"]),
+    ("namespaces", &["ns", "namespace"]),
+    ("nodes", &["no", "node"]),
+    ("persistentvolumes", &["pv", "persistentvolume"]),
+    ("persistentvolumeclaims", &["pvc", "persistentvolumeclaim"]),
+    ("ingresses", &["ing", "ingress"]),
+    ("networkpolicies", &["netpol", "networkpolicy"]),
+    (
+        "horizontalpodautoscalers",
+        &["hpa", "horizontalpodautoscaler"],
+    ),
+    ("poddisruptionbudgets", &["pdb", "poddisruptionbudget"]),
+    ("resourcequotas", &["quota", "resourcequota"]),
+    ("limitranges", &["limits", "limitrange"]),
+    ("endpoints", &["ep"]),
+    ("events", &["ev", "event"]),
+    ("roles", &["role"]),
+    ("rolebindings", &["rolebinding"]),
+    ("clusterroles", &["clusterrole"]),
+    ("clusterrolebindings", &["clusterrolebinding"]),
+    (
+        "customresourcedefinitions",
+        &["crd", "crds", "customresourcedefinition"],
+    ),
+];
+
+/// kubectl verbs whose first argument is a resource type, `type/name` or
+/// `type,type`, with the number of subcommand words in front of it, e.g. 1 for
+/// `rollout status deploy/web`. Later arguments are names, so only their
+/// `type/name` forms are resolved.
+const RESOURCE_VERBS: &[(&str, usize)] = &[
+    ("annotate", 0),
+    ("autoscale", 0),
+    ("delete", 0),
+    ("describe", 0),
+    ("edit", 0),
+    ("explain", 0),
+    ("expose", 0),
+    ("get", 0),
+    ("label", 0),
+    ("patch", 0),
+    ("rollout", 1),
+    ("scale", 0),
+    ("set", 1),
+    ("taint", 0),
+    ("top", 0),
+    ("wait", 0),
+];
+
+/// kubectl verbs whose first argument is a pod name or `type/name`; only the
+/// `type/name` form is resolved, so a pod called `pod` keeps its name
+const NAME_VERBS: &[&str] = &["attach", "exec", "logs", "port-forward"];
+
+/// `kubectl create` subcommands with the aliases kubectl accepts for them;
+/// unlike resource types they are singular, and `create deployments` is no
+/// command at all
+const CREATE_ALIASES: &[(&str, &[&str])] = &[
+    ("configmap", &["cm"]),
+    ("cronjob", &["cj"]),
+    ("deployment", &["deploy"]),
+    ("ingress", &["ing"]),
+    ("namespace", &["ns"]),
+    ("poddisruptionbudget", &["pdb"]),
+    ("priorityclass", &["pc"]),
+    ("quota", &["resourcequota"]),
+    ("service", &["svc"]),
+    ("serviceaccount", &["sa"]),
+];
+
+/// Short flags that stand for another long flag on some verbs, as (verb,
+/// short, long); looked up before [`FLAG_ALIASES`]
+const VERB_FLAG_ALIASES: &[(&str, &str, &str)] =
+    &[("expose", "-l", "--labels"), ("run", "-l", "--labels")];
+
+/// Short flags of kubectl commands with their long names
+const FLAG_ALIASES: &[(&str, &[&str])] = &[
+    ("--namespace", &["-n"]),
+    ("--output", &["-o"]),
+    ("--selector", &["-l"]),
+    ("--filename", &["-f"]),
+    ("--container", &["-c"]),
+    ("--all-namespaces", &["-A"]),
+    ("--watch", &["-w"]),
+    ("--stdin", &["-i"]),
+    ("--tty", &["-t"]),
+    ("--recursive", &["-R"]),
+    ("--label-columns", &["-L"]),
+];
+
+/// The name `word` stands for in `table`, if it is one of the aliases
+fn canonical(table: &'static [(&'static str, &[&str])], word: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&word))
+        .map(|(name, _)| *name)
+}
+
+/// Subcommand words before `verb`'s resource argument, if it takes one
+fn resource_position(verb: &str) -> Option<usize> {
+    RESOURCE_VERBS
+        .iter()
+        .find(|(name, _)| *name == verb)
+        .map(|(_, skip)| *skip)
+}
+
+/// The long name of the short flag `flag` on `verb`, if it is an alias
+fn canonical_flag(verb: &str, flag: &str) -> Option<&'static str> {
+    VERB_FLAG_ALIASES
+        .iter()
+        .find(|(on, short, _)| *on == verb && *short == flag)
+        .map(|(_, _, long)| *long)
+        .or_else(|| canonical(FLAG_ALIASES, flag))
+}
+
+/// How a submitted command compares with an expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The same command, apart from spacing (and case, when that is ignored)
+    Equal,
+    /// The same command written differently; each difference is listed
+    Equivalent(Vec<Difference>),
+    /// Some of a multi-command answer's commands were right, but not all
+    Partial(CommandBreakdown),
+    /// The API server refused the manifest in a server-side dry run; its message
+    Rejected(String),
+    /// A rubric-graded answer, correct when every rubric item was found
+    Rubric(RubricBreakdown),
+    Different,
+}
+
+impl Verdict {
+    pub fn is_correct(&self) -> bool {
+        match self {
+            Verdict::Equal | Verdict::Equivalent(_) => true,
+            Verdict::Rubric(rubric) => rubric.is_complete(),
+            _ => false,
+        }
+    }
+
+    /// Share of the question's credit earned: 1 when correct, the fraction of
+    /// commands matched when partial, the share of rubric points found for a
+    /// rubric, otherwise 0
+    pub fn credit(&self) -> f64 {
+        match self {
+            Verdict::Equal | Verdict::Equivalent(_) => 1.0,
+            Verdict::Partial(breakdown) => breakdown.fraction(),
+            Verdict::Rubric(rubric) => rubric.fraction(),
+            Verdict::Rejected(_) | Verdict::Different => 0.0,
+        }
+    }
+
+    pub fn breakdown(&self) -> Option<&CommandBreakdown> {
+        match self {
+            Verdict::Partial(breakdown) => Some(breakdown),
+            _ => None,
+        }
+    }
+
+    pub fn rubric(&self) -> Option<&RubricBreakdown> {
+        match self {
+            Verdict::Rubric(rubric) => Some(rubric),
+            _ => None,
+        }
+    }
+
+    pub fn differences(&self) -> &[Difference] {
+        match self {
+            Verdict::Equivalent(differences) => differences,
+            _ => &[],
+        }
+    }
+
+    /// The differences as one line, e.g. "flags in a different order; quotes
+    /// around app=web differ"; `None` unless the verdict is `Equivalent`
+    pub fn explanation(&self) -> Option<String> {
+        let differences = self.differences();
+        (!differences.is_empty()).then(|| {
+            differences
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+    }
+}
+
+/// One way an equivalent command is written differently from the expected one;
+/// a verdict lists `FlagOrder` first, then the rest as they appear in the submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// Flags given in another order, or on the other side of an argument
+    FlagOrder,
+    /// `--flag value` where `--flag=value` was expected, or the reverse
+    FlagSyntax { expected: String, submitted: String },
+    /// The same value with quotes on one side only
+    Quoting { value: String },
+    /// A short name or alias on one side, e.g. `po` for `pods` or `-n` for `--namespace`
+    Alias { expected: String, submitted: String },
+    /// kubectl renders both commands to the same manifest
+    SameManifest,
+    /// The submission matches the question's answer pattern
+    Pattern,
+    /// The commands of a multi-command answer came in another order
+    CommandOrder,
+    /// Commands beyond the expected ones, which cost nothing
+    ExtraCommands(usize),
+    /// A namespace flag on one side only, accepted by lenient grading
+    Namespace,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::FlagOrder => f.write_str("flags in a different order"),
+            Difference::FlagSyntax {
+                expected,
+                submitted,
+            } => write!(f, "`{}` written as `{}`", expected, submitted),
+            Difference::Quoting { value } => write!(f, "quotes around {} differ", value),
+            Difference::Alias {
+                expected,
+                submitted,
+            } => write!(f, "`{}` written as `{}`", expected, submitted),
+            Difference::SameManifest => f.write_str("kubectl renders the same manifest"),
+            Difference::Pattern => f.write_str("matches the accepted answer pattern"),
+            Difference::CommandOrder => f.write_str("commands in a different order"),
+            Difference::ExtraCommands(count) => write!(f, "{} extra command(s)", count),
+            Difference::Namespace => f.write_str("namespace given on one side only"),
+        }
+    }
+}
+
+/// Compiles a question's `answer_pattern`, anchored to the whole submission
+/// `^` and `$` also match at line breaks, so a pattern can describe each line
+/// of a multi-line answer.
+pub fn compile_pattern(pattern: &str, case_sensitive: bool) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(&format!(r"\A(?:{})\z", pattern))
+        .multi_line(true)
+        .case_insensitive(!case_sensitive)
+        .build()
+}
+
+/// Whether `submitted` matches a compiled answer pattern; each line is trimmed
+/// with runs of spaces collapsed, and blank lines are dropped first
+pub fn matches_pattern(pattern: &Regex, submitted: &str) -> bool {
+    let lines: Vec<String> = submitted
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect();
+    pattern.is_match(&lines.join("\n"))
+}
+
+/// Compares two commands the way a shell would read them
+/// Flags may come in any order and as `--flag value` or `--flag=value`, and
+/// quoting a plain value doesn't change it; arguments must match in order.
+/// Both sides are read with kubectl's aliases and short names resolved first
+/// (`k`, `po`, `deploy`, `-n` and so on); words not in the tables are left as they are.
+/// Commands that can't be tokenized (an unclosed quote, or a pipe or other
+/// shell operator joining several commands) only match as equal text.
+pub fn compare_commands(expected: &str, submitted: &str, case_sensitive: bool) -> Verdict {
+    compare(expected, submitted, case_sensitive, false)
+}
+
+/// Like [`compare_commands`], but a namespace flag (`-n`, `--namespace`) on
+/// one side only is left out of the comparison, so forgetting `-n` or adding
+/// `-n default` still matches; two different namespaces don't
+pub fn compare_commands_namespace_tolerant(
+    expected: &str,
+    submitted: &str,
+    case_sensitive: bool,
+) -> Verdict {
+    compare(expected, submitted, case_sensitive, true)
+}
+
+fn compare(
+    expected: &str,
+    submitted: &str,
+    case_sensitive: bool,
+    namespace_tolerant: bool,
+) -> Verdict {
+    let same = |a: &str, b: &str| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.eq_ignore_ascii_case(b)
+        }
+    };
+    let (Some(expected_tokens), Some(submitted_tokens)) = (tokenize(expected), tokenize(submitted))
+    else {
+        let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+        return if same(&normalize(expected), &normalize(submitted)) {
+            Verdict::Equal
+        } else {
+            Verdict::Different
+        };
+    };
+    let mut expected = ParsedCommand::parse(expected_tokens);
+    let mut submitted = ParsedCommand::parse(submitted_tokens);
+    expected.resolve_aliases();
+    submitted.resolve_aliases();
+
+    let mut differences = Vec::new();
+    let mut note = |difference: Difference| {
+        if !differences.contains(&difference) {
+            differences.push(difference);
+        }
+    };
+    if namespace_tolerant && expected.has_namespace() != submitted.has_namespace() {
+        expected.remove_namespace();
+        submitted.remove_namespace();
+        note(Difference::Namespace);
+    }
+
+    // Arguments, and anything after `--`, must match one for one in order
+    let positional = |command: &ParsedCommand| -> Vec<Token> {
+        command
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Argument(token) => Some(token.clone()),
+                Item::Flag(_) => None,
+            })
+            .chain(command.trailing.iter().cloned())
+            .collect()
+    };
+    let (expected_args, submitted_args) = (positional(&expected), positional(&submitted));
+    if expected_args.len() != submitted_args.len() {
+        return Verdict::Different;
+    }
+    for (want, got) in expected_args.iter().zip(&submitted_args) {
+        if !same(&want.text, &got.text) {
+            return Verdict::Different;
+        }
+        if !same(want.spelling(), got.spelling()) {
+            note(Difference::Alias {
+                expected: want.spelling().to_string(),
+                submitted: got.spelling().to_string(),
+            });
+        }
+        if want.quoted != got.quoted {
+            note(Difference::Quoting {
+                value: want.text.clone(),
+            });
+        }
+    }
+
+    // Each submitted item, as the position of its match among the expected items
+    let mut matched = vec![false; expected.items.len()];
+    let mut order = Vec::with_capacity(submitted.items.len());
+    let mut next_argument = expected
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| matches!(item, Item::Argument(_)))
+        .map(|(index, _)| index);
+    for item in &submitted.items {
+        let position = match item {
+            Item::Argument(_) => next_argument.next(),
+            Item::Flag(flag) => {
+                let found = expected.items.iter().enumerate().position(|(index, want)| {
+                    !matched[index] && matches!(want, Item::Flag(want) if want.matches(flag, &same))
+                });
+                let Some(index) = found else {
+                    return Verdict::Different;
+                };
+                if let Item::Flag(want) = &expected.items[index] {
+                    if !same(want.spelling(), flag.spelling()) {
+                        note(Difference::Alias {
+                            expected: want.spelling().to_string(),
+                            submitted: flag.spelling().to_string(),
+                        });
+                    }
+                    if want.joined != flag.joined && want.value.is_some() {
+                        note(Difference::FlagSyntax {
+                            expected: want.written(),
+                            submitted: flag.written(),
+                        });
+                    }
+                    if let (Some(want), Some(got)) = (&want.value, &flag.value) {
+                        if want.quoted != got.quoted {
+                            note(Difference::Quoting {
+                                value: want.text.clone(),
+                            });
+                        }
+                    }
+                }
+                Some(index)
+            }
+        };
+        let Some(position) = position else {
+            return Verdict::Different;
+        };
+        matched[position] = true;
+        order.push(position);
+    }
+    if matched.contains(&false) {
+        return Verdict::Different;
+    }
+    if order.windows(2).any(|pair| pair[0] > pair[1]) {
+        differences.insert(0, Difference::FlagOrder);
+    }
+
+    if differences.is_empty() {
+        Verdict::Equal
+    } else {
+        Verdict::Equivalent(differences)
+    }
+}
+
+/// A word of a command after quote removal
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    text: String,
+    /// Some part of the word was in quotes or escaped
+    quoted: bool,
+    /// The word as written, when `text` is the name an alias stands for
+    alias: Option<String>,
+}
+
+impl Token {
+    fn new(text: String, quoted: bool) -> Self {
+        Self {
+            text,
+            quoted,
+            alias: None,
+        }
+    }
+
+    fn spelling(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.text)
+    }
+
+    /// Resolves a resource argument: `po`, `deploy/web` or `po,svc`
+    fn resolve_resource(&mut self) {
+        let (types, name) = match self.text.split_once('/') {
+            Some((types, name)) => (types, Some(name)),
+            None => (self.text.as_str(), None),
+        };
+        let types: Vec<&str> = types
+            .split(',')
+            .map(|word| canonical(RESOURCE_ALIASES, word).unwrap_or(word))
+            .collect();
+        let mut resolved = types.join(",");
+        if let Some(name) = name {
+            resolved = format!("{}/{}", resolved, name);
+        }
+        if resolved != self.text {
+            self.alias = Some(std::mem::replace(&mut self.text, resolved));
+        }
+    }
+}
+
+/// Expected commands of a multi-command answer, each marked matched or not,
+/// plus the submitted commands that matched none of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandBreakdown {
+    pub commands: Vec<CommandMatch>,
+    pub extra: Vec<String>,
+    /// Extra commands count against the fraction
+    pub extra_penalized: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandMatch {
+    pub command: String,
+    pub matched: bool,
+}
+
+impl CommandBreakdown {
+    pub fn matched(&self) -> usize {
+        self.commands
+            .iter()
+            .filter(|command| command.matched)
+            .count()
+    }
+
+    /// Matched over expected commands; with extras penalized, extra commands
+    /// are added to the expected ones
+    pub fn fraction(&self) -> f64 {
+        let extra = if self.extra_penalized {
+            self.extra.len()
+        } else {
+            0
+        };
+        let total = self.commands.len() + extra;
+        if total == 0 {
+            0.0
+        } else {
+            self.matched() as f64 / total as f64
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.fraction() == 1.0
+    }
+}
+
+/// Each rubric item of a question, marked found in the submission or not
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RubricBreakdown {
+    pub items: Vec<RubricMatch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RubricMatch {
+    pub item: String,
+    pub points: u32,
+    pub matched: bool,
+}
+
+impl RubricBreakdown {
+    pub fn earned(&self) -> u32 {
+        self.items
+            .iter()
+            .filter(|item| item.matched)
+            .map(|item| item.points)
+            .sum()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.items.iter().map(|item| item.points).sum()
+    }
+
+    /// Earned over total points; 0 for a rubric worth nothing
+    pub fn fraction(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.earned() as f64 / total as f64,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.items.is_empty() && self.items.iter().all(|item| item.matched)
+    }
+}
+
+/// Scores a submission by the rubric items it contains
+/// Submission and items are split into words like a shell would, and a word
+/// also matches the parts of a `--flag=value` word, so the item
+/// `--from-literal` is found in `--from-literal=database.url=x`, as is
+/// `database.url`. An item of several words must appear as consecutive words
+/// of one command.
+pub fn grade_rubric(
+    rubric: &[RubricItem],
+    submitted: &str,
+    case_sensitive: bool,
+) -> RubricBreakdown {
+    let commands: Vec<Vec<String>> = split_commands(submitted)
+        .iter()
+        .map(|command| words(command))
+        .collect();
+    let same = |a: &str, b: &str| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase() == b.to_lowercase()
+        }
+    };
+    let contains = |word: &str, item: &str| {
+        same(word, item) || (word.contains('=') && word.split('=').any(|part| same(part, item)))
+    };
+    let items = rubric
+        .iter()
+        .map(|rubric_item| {
+            let item = words(&rubric_item.item);
+            let matched = !item.is_empty()
+                && commands.iter().any(|command| {
+                    command.windows(item.len()).any(|window| {
+                        window
+                            .iter()
+                            .zip(&item)
+                            .all(|(word, item)| contains(word, item))
+                    })
+                });
+            RubricMatch {
+                item: rubric_item.item.clone(),
+                points: rubric_item.points,
+                matched,
+            }
+        })
+        .collect();
+    RubricBreakdown { items }
+}
+
+/// A question's rubric problem, if any: selected but empty, an empty item, or
+/// worth no points at all
+pub fn check_rubric(question: &Question) -> Result<(), String> {
+    let items = question.rubric_items();
+    if question.grading() != QuestionGrading::Rubric {
+        return Ok(());
+    }
+    if items.is_empty() {
+        return Err("grading is rubric but the rubric has no items".to_string());
+    }
+    if let Some(index) = items.iter().position(|item| item.item.trim().is_empty()) {
+        return Err(format!("rubric item {} is empty", index + 1));
+    }
+    if items.iter().all(|item| item.points == 0) {
+        return Err("the rubric items are worth no points".to_string());
+    }
+    Ok(())
+}
+
+/// A tip naming the likely mistake in a wrong kubectl command, or `None`
+/// when no rule applies
+/// Both sides must be single kubectl commands. The rules are tried in order:
+/// `create pod` for `run`, another verb, another resource type, then a missing
+/// `--image`, `--replicas`, namespace, `--dry-run` or other flag.
+pub fn diagnose(submitted: &str, expected: &str) -> Option<String> {
+    let (Some(submitted), Some(expected)) = (kubectl_command(submitted), kubectl_command(expected))
+    else {
+        return None;
+    };
+    let (got, want) = (submitted.arguments(), expected.arguments());
+    let (got_verb, want_verb) = (got.get(1).copied()?, want.get(1).copied()?);
+    let create_pod = got
+        .get(2)
+        .is_some_and(|word| canonical(RESOURCE_ALIASES, word).unwrap_or(word) == "pods");
+    if got_verb == "create" && create_pod && want_verb == "run" {
+        return Some(
+            "You used `create pod` but need `run`: pods are started with `kubectl run`".to_string(),
+        );
+    }
+    if got_verb != want_verb {
+        return Some(format!("You used `{}` but need `{}`", got_verb, want_verb));
+    }
+    // `create` subcommands are singular, so they are named by their resource type
+    let resource_type = |argument: Option<&&str>| {
+        argument
+            .map(|argument| argument.split('/').next().unwrap_or(argument))
+            .map(|types| {
+                types
+                    .split(',')
+                    .map(|word| canonical(RESOURCE_ALIASES, word).unwrap_or(word))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .filter(|types| is_resource_type(types))
+    };
+    let takes_type = want_verb == "create" || resource_position(want_verb) == Some(0);
+    if let (true, Some(got_type), Some(want_type)) = (
+        takes_type,
+        resource_type(got.get(2)),
+        resource_type(want.get(2)),
+    ) {
+        if got_type != want_type {
+            return Some(format!(
+                "Wrong resource type: this asks for {}, not {}",
+                want_type, got_type
+            ));
+        }
+    }
+    if expected.flag("--image").is_some() && submitted.flag("--image").is_none() {
+        return Some("Missing `--image`: say which container image to run".to_string());
+    }
+    if let Some(replicas) = expected.flag("--replicas") {
+        match submitted.flag("--replicas") {
+            None => {
+                return Some(
+                    "Missing `--replicas`: without it you get a single replica".to_string(),
+                )
+            }
+            Some(given) if given.value_text() != replicas.value_text() => {
+                return Some(format!(
+                    "Wrong replica count: `--replicas` should be {}",
+                    replicas.value_text().unwrap_or("set")
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    if expected.has_namespace() && !submitted.has_namespace() {
+        return Some(
+            "Missing `-n`: without a namespace the command uses the current one".to_string(),
+        );
+    }
+    if expected.flag("--dry-run").is_some() && submitted.flag("--dry-run").is_none() {
+        return Some("Missing `--dry-run`: without it the command changes the cluster".to_string());
+    }
+    let missing = expected
+        .flags()
+        .find(|flag| submitted.flag(&flag.name).is_none())
+        .map(|flag| format!("Missing `{}`", flag.spelling()));
+    missing
+}
+
+/// A single kubectl command with its aliases resolved; `None` for anything else
+fn kubectl_command(text: &str) -> Option<ParsedCommand> {
+    if split_commands(text).len() != 1 {
+        return None;
+    }
+    let mut command = ParsedCommand::parse(tokenize(text.trim())?);
+    command.resolve_aliases();
+    (command.arguments().first() == Some(&"kubectl")).then_some(command)
+}
+
+/// Whether `types` names resource types, e.g. `pods` or `pods,services`; call
+/// on resolved arguments
+fn is_resource_type(types: &str) -> bool {
+    types
+        .split(',')
+        .all(|word| RESOURCE_ALIASES.iter().any(|(name, _)| *name == word))
+}
+
+/// The words of a command with quotes removed; commands the tokenizer refuses
+/// (pipes, redirects) are split at whitespace
+fn words(command: &str) -> Vec<String> {
+    match tokenize(command) {
+        Some(tokens) => tokens.into_iter().map(|token| token.text).collect(),
+        None => command.split_whitespace().map(str::to_string).collect(),
+    }
+}
+
+/// Splits an answer into commands at line breaks and `&&` outside quotes;
+/// blank lines and `#` comments are dropped
+pub fn split_commands(answer: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    for line in answer.lines() {
+        let mut current = String::new();
+        let mut quote = None;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (None, '\'' | '"') => quote = Some(c),
+                (Some(open), c) if c == open => quote = None,
+                (None, '&') if chars.peek() == Some(&'&') => {
+                    chars.next();
+                    commands.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+        commands.push(current);
+    }
+    commands
+        .into_iter()
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty() && !command.starts_with('#'))
+        .collect()
+}
+
+/// Matches submitted commands to expected ones
+/// Each expected command, in order, takes the first unused submitted command
+/// that `compare_commands` accepts, so commands may come in any order. The
+/// verdict is `Equal` or `Equivalent` when every expected command is matched
+/// (and extras are free), `Partial` when some are, otherwise `Different`.
+pub fn compare_command_lists(
+    expected: &str,
+    submitted: &str,
+    case_sensitive: bool,
+    penalize_extra: bool,
+) -> Verdict {
+    let submitted = split_commands(submitted);
+    let mut used = vec![false; submitted.len()];
+    let mut order = Vec::new();
+    let mut differences = Vec::new();
+    let commands = split_commands(expected)
+        .into_iter()
+        .map(|command| {
+            let found = submitted.iter().enumerate().find_map(|(index, candidate)| {
+                if used[index] {
+                    return None;
+                }
+                let verdict = compare_commands(&command, candidate, case_sensitive);
+                verdict.is_correct().then_some((index, verdict))
+            });
+            let matched = found.is_some();
+            if let Some((index, verdict)) = found {
+                used[index] = true;
+                order.push(index);
+                for difference in verdict.differences() {
+                    if !differences.contains(difference) {
+                        differences.push(difference.clone());
+                    }
+                }
+            }
+            CommandMatch { command, matched }
+        })
+        .collect::<Vec<_>>();
+    let extra: Vec<String> = submitted
+        .iter()
+        .zip(&used)
+        .filter(|(_, used)| !**used)
+        .map(|(command, _)| command.clone())
+        .collect();
+    let breakdown = CommandBreakdown {
+        commands,
+        extra,
+        extra_penalized: penalize_extra,
+    };
+
+    if breakdown.matched() == 0 {
+        return Verdict::Different;
+    }
+    if !breakdown.is_complete() {
+        return Verdict::Partial(breakdown);
+    }
+    if order.windows(2).any(|pair| pair[0] > pair[1]) {
+        differences.insert(0, Difference::CommandOrder);
+    }
+    if !breakdown.extra.is_empty() {
+        differences.push(Difference::ExtraCommands(breakdown.extra.len()));
+    }
+    if differences.is_empty() {
+        Verdict::Equal
+    } else {
+        Verdict::Equivalent(differences)
+    }
+}
+
+/// Splits a command into words with POSIX shell quoting: single quotes are
+/// literal, double quotes allow `\"`, `\\`, `\$` and `` \` ``, and a backslash
+/// outside quotes escapes the next character. `None` for an unclosed quote or
+/// an unquoted shell operator such as `|`, `;` or `>`.
+fn tokenize(command: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            tokens.extend(current.take());
+            continue;
+        }
+        let token = current.get_or_insert_with(|| Token::new(String::new(), false));
+        match c {
+            '\'' => {
+                token.quoted = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => token.text.push(c),
+                    }
+                }
+            }
+            '"' => {
+                token.quoted = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => token.text.push(c),
+                            c => {
+                                token.text.push('\\');
+                                token.text.push(c);
+                            }
+                        },
+                        c => token.text.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                token.quoted = true;
+                token.text.push(chars.next()?);
+            }
+            '|' | ';' | '&' | '<' | '>' | '(' | ')' | '`' => return None,
+            c => token.text.push(c),
+        }
+    }
+    tokens.extend(current);
+    Some(tokens)
+}
+
+#[derive(Debug, Clone)]
+struct Flag {
+    /// With its dashes, e.g. `--image` or `-n`
+    name: String,
+    value: Option<Token>,
+    /// Written as `--flag=value` rather than `--flag value`
+    joined: bool,
+    /// The name as written, when `name` is the long name of a short flag
+    alias: Option<String>,
+}
+
+impl Flag {
+    fn new(name: String, value: Option<Token>, joined: bool) -> Self {
+        Self {
+            name,
+            value,
+            joined,
+            alias: None,
+        }
+    }
+
+    fn spelling(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+
+    fn matches(&self, other: &Flag, same: &impl Fn(&str, &str) -> bool) -> bool {
+        same(&self.name, &other.name)
+            && match (&self.value, &other.value) {
+                (Some(a), Some(b)) => same(&a.text, &b.text),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn value_text(&self) -> Option<&str> {
+        self.value.as_ref().map(|value| value.text.as_str())
+    }
+
+    fn written(&self) -> String {
+        match &self.value {
+            Some(value) if self.joined => format!("{}={}", self.spelling(), value.text),
+            Some(value) => format!("{} {}", self.spelling(), value.text),
+            None => self.spelling().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    Argument(Token),
+    Flag(Flag),
+}
+
+/// Arguments and flags in the order written, plus everything after `--`
+#[derive(Debug)]
+struct ParsedCommand {
+    items: Vec<Item>,
+    trailing: Vec<Token>,
+}
+
+impl ParsedCommand {
+    fn parse(tokens: Vec<Token>) -> Self {
+        let mut items = Vec::new();
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            if token.text == "--" && !token.quoted {
+                return Self {
+                    items,
+                    trailing: tokens.collect(),
+                };
+            }
+            if !is_flag(&token.text) {
+                items.push(Item::Argument(token));
+                continue;
+            }
+            if let Some((name, value)) = token.text.split_once('=') {
+                let value = Token::new(value.to_string(), token.quoted);
+                items.push(Item::Flag(Flag::new(name.to_string(), Some(value), true)));
+                continue;
+            }
+
+            let short = token
+                .text
+                .strip_prefix('-')
+                .filter(|rest| !rest.starts_with('-'));
+            if let Some(letters) = short.filter(|letters| letters.chars().count() > 1) {
+                if letters.chars().all(|c| BOOLEAN_SHORT_FLAGS.contains(&c)) {
+                    items.extend(
+                        letters
+                            .chars()
+                            .map(|c| Item::Flag(Flag::new(format!("-{}", c), None, false))),
+                    );
+                    continue;
+                }
+            }
+            let boolean = match short {
+                Some(letters) => {
+                    letters.chars().count() > 1
+                        || letters.chars().all(|c| BOOLEAN_SHORT_FLAGS.contains(&c))
+                }
+                None => BOOLEAN_FLAGS.contains(&token.text.as_str()),
+            };
+            let value = if boolean {
+                None
+            } else {
+                tokens.next_if(|next| !is_flag(&next.text) && next.text != "--")
+            };
+            items.push(Item::Flag(Flag::new(token.text, value, false)));
+        }
+        Self {
+            items,
+            trailing: Vec::new(),
+        }
+    }
+}
+
+impl ParsedCommand {
+    /// Rewrites kubectl's aliases to the names they stand for; commands other
+    /// than kubectl are left alone. Words after `--` belong to another program.
+    /// Only resource arguments are resolved (see [`RESOURCE_VERBS`]), so object
+    /// names that look like an alias keep their spelling.
+    fn resolve_aliases(&mut self) {
+        let mut arguments = self.items.iter_mut().filter_map(|item| match item {
+            Item::Argument(token) => Some(token),
+            Item::Flag(_) => None,
+        });
+        let Some(command) = arguments.next() else {
+            return;
+        };
+        if let Some(name) = canonical(COMMAND_ALIASES, &command.text) {
+            command.alias = Some(std::mem::replace(&mut command.text, name.to_string()));
+        }
+        if command.text != "kubectl" {
+            return;
+        }
+        let verb = arguments
+            .next()
+            .map(|verb| verb.text.clone())
+            .unwrap_or_default();
+        if verb == "create" {
+            if let Some(subcommand) = arguments.next() {
+                if let Some(name) = canonical(CREATE_ALIASES, &subcommand.text) {
+                    subcommand.alias =
+                        Some(std::mem::replace(&mut subcommand.text, name.to_string()));
+                }
+            }
+        } else if let Some(skip) = resource_position(&verb) {
+            let mut arguments = arguments.skip(skip);
+            if let Some(types) = arguments.next() {
+                types.resolve_resource();
+            }
+            arguments
+                .filter(|name| name.text.contains('/'))
+                .for_each(Token::resolve_resource);
+        } else if NAME_VERBS.contains(&verb.as_str()) {
+            if let Some(target) = arguments.next().filter(|name| name.text.contains('/')) {
+                target.resolve_resource();
+            }
+        }
+        for item in &mut self.items {
+            if let Item::Flag(flag) = item {
+                if let Some(name) = canonical_flag(&verb, &flag.name) {
+                    flag.alias = Some(std::mem::replace(&mut flag.name, name.to_string()));
+                }
+            }
+        }
+    }
+}
+
+impl ParsedCommand {
+    /// Whether a `--namespace` flag is given; call after `resolve_aliases`
+    fn has_namespace(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| matches!(item, Item::Flag(flag) if flag.name == "--namespace"))
+    }
+
+    /// The positional arguments' text, in order
+    fn arguments(&self) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Argument(token) => Some(token.text.as_str()),
+                Item::Flag(_) => None,
+            })
+            .collect()
+    }
+
+    fn flags(&self) -> impl Iterator<Item = &Flag> {
+        self.items.iter().filter_map(|item| match item {
+            Item::Flag(flag) => Some(flag),
+            Item::Argument(_) => None,
+        })
+    }
+
+    /// The first flag called `name`; call after `resolve_aliases` for long names
+    fn flag(&self, name: &str) -> Option<&Flag> {
+        self.flags().find(|flag| flag.name == name)
+    }
+
+    fn remove_namespace(&mut self) {
+        self.items
+            .retain(|item| !matches!(item, Item::Flag(flag) if flag.name == "--namespace"));
+    }
+}
+
+/// `-n`, `--image=x` and the like; a lone `-` (stdin) and negative numbers are values
+fn is_flag(text: &str) -> bool {
+    text.len() > 1
+        && text.starts_with('-')
+        && text != "--"
+        && !text[1..].starts_with(|c: char| c.is_ascii_digit())
+}
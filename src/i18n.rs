@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// Locale used when nothing else is configured, and for keys a catalog lacks
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Catalogs compiled into the binary, by language code
+pub const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.toml")),
+    ("de", include_str!("../locales/de.toml")),
+];
+
+/// Parses a catalog file: flat `key = "text"` pairs
+pub fn parse_catalog(contents: &str) -> Result<BTreeMap<String, String>, String> {
+    toml::from_str(contents).map_err(|err| err.to_string())
+}
+
+/// Embedded catalog for a language code, e.g. "de"
+pub fn catalog_source(language: &str) -> Option<&'static str> {
+    LOCALES
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map(|(_, contents)| *contents)
+}
+
+/// Language code from a locale name such as "de_DE.UTF-8" or "pt-BR";
+/// `None` for "C", "POSIX" and empty values, which mean no preference
+pub fn language_code(locale: &str) -> Option<String> {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if language.is_empty() || language == "c" || language == "posix" {
+        None
+    } else {
+        Some(language)
+    }
+}
+
+/// Locale from `LC_ALL`, `LC_MESSAGES` or `LANG`, first set one wins
+pub fn locale_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// User-facing screen text keyed by identifier
+/// Lookups fall back to English, then to the key itself, so a missing
+/// translation shows up on screen instead of failing.
+#[derive(Debug, Clone)]
+pub struct Messages {
+    language: &'static str,
+    messages: BTreeMap<String, String>,
+    fallback: BTreeMap<String, String>,
+}
+
+impl Messages {
+    pub fn english() -> Self {
+        Self::for_locale(FALLBACK_LOCALE)
+    }
+
+    /// Catalog for a locale name; unknown languages get English
+    pub fn for_locale(locale: &str) -> Self {
+        let language = language_code(locale)
+            .and_then(|code| LOCALES.iter().find(|(shipped, _)| *shipped == code))
+            .map_or(FALLBACK_LOCALE, |(code, _)| *code);
+        let load = |code: &str| {
+            catalog_source(code)
+                .map(|contents| parse_catalog(contents).expect("embedded catalog parses"))
+                .unwrap_or_default()
+        };
+        Self {
+            language,
+            messages: load(language),
+            fallback: load(FALLBACK_LOCALE),
+        }
+    }
+
+    /// Language code of the catalog in use
+    pub fn language(&self) -> &str {
+        self.language
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Text for `key` with each `{name}` placeholder replaced by its value
+    /// Substituted values are not scanned again, so a question containing
+    /// braces is shown as written.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut rest = self.get(key);
+        let mut text = String::with_capacity(rest.len());
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after.find('}').and_then(|end| {
+                let name = &after[..end];
+                args.iter()
+                    .find(|(arg, _)| *arg == name)
+                    .map(|(_, value)| (value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    text.push_str(&value.to_string());
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = after;
+                }
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::english()
+    }
+}
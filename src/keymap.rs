@@ -0,0 +1,357 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Key binding presets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    /// Adds j/k to scroll and J/K to switch tabs
+    Vim,
+}
+
+/// Everything a remappable key can do in the quiz screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Hint,
+    Next,
+    Answer,
+    Skip,
+    ExtendTime,
+    RestartTimer,
+    Jump,
+    Shell,
+    Editor,
+    NextTab,
+    PreviousTab,
+    ScrollUp,
+    ScrollDown,
+    ContextUp,
+    ContextDown,
+    Related,
+    PrepCluster,
+    CollapseQuestion,
+}
+
+impl Action {
+    pub const ALL: [Action; 19] = [
+        Action::Quit,
+        Action::Hint,
+        Action::Next,
+        Action::Answer,
+        Action::Skip,
+        Action::ExtendTime,
+        Action::RestartTimer,
+        Action::Jump,
+        Action::Shell,
+        Action::Editor,
+        Action::NextTab,
+        Action::PreviousTab,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ContextUp,
+        Action::ContextDown,
+        Action::Related,
+        Action::PrepCluster,
+        Action::CollapseQuestion,
+    ];
+
+    /// Name used in the `[keys]` config table
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Hint => "hint",
+            Action::Next => "next",
+            Action::Answer => "answer",
+            Action::Skip => "skip",
+            Action::ExtendTime => "extend_time",
+            Action::RestartTimer => "restart_timer",
+            Action::Jump => "jump",
+            Action::Shell => "shell",
+            Action::Editor => "editor",
+            Action::NextTab => "next_tab",
+            Action::PreviousTab => "previous_tab",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::ContextUp => "context_up",
+            Action::ContextDown => "context_down",
+            Action::Related => "related",
+            Action::PrepCluster => "prep_cluster",
+            Action::CollapseQuestion => "collapse_question",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// A key plus modifiers, e.g. `ctrl+n`, `F5` or `?`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// Ctrl+C always quits so a broken config can never trap the user
+    pub const QUIT_FALLBACK: KeyBinding =
+        KeyBinding::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+    /// Parses a descriptor such as `j`, `Esc`, `F1`, `Up` or `ctrl+alt+n`
+    /// Named keys and modifiers are case-insensitive; single characters are not.
+    pub fn parse(descriptor: &str) -> Result<Self, KeymapError> {
+        let invalid = || KeymapError::InvalidKey(descriptor.to_string());
+        // The plus key itself is written "+" or, with modifiers, "ctrl++"
+        let (prefixes, key) = if descriptor == "+" {
+            ("", "+")
+        } else if let Some(prefixes) = descriptor.strip_suffix("++") {
+            (prefixes, "+")
+        } else {
+            descriptor.rsplit_once('+').unwrap_or(("", descriptor))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for prefix in prefixes.split('+').filter(|prefix| !prefix.is_empty()) {
+            let modifier = match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+            if modifiers.contains(modifier) {
+                return Err(invalid());
+            }
+            modifiers |= modifier;
+        }
+
+        let code = parse_key_code(key).ok_or_else(invalid)?;
+        Ok(Self::new(code, modifiers).normalized())
+    }
+
+    /// Folds shift into the key itself so `J`, `shift+j` and Shift+Tab compare equal
+    fn normalized(self) -> Self {
+        let shift = self.modifiers.contains(KeyModifiers::SHIFT);
+        let code = match self.code {
+            KeyCode::Char(c) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            KeyCode::Tab if shift => KeyCode::BackTab,
+            code => code,
+        };
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => self.modifiers - KeyModifiers::SHIFT,
+            _ => self.modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        *self == KeyBinding::new(event.code, event.modifiers).normalized()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, label) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(label)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+fn parse_key_code(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let lower = key.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        _ => {
+            let n: u8 = lower.strip_prefix('f')?.parse().ok()?;
+            return (1..=12).contains(&n).then_some(KeyCode::F(n));
+        }
+    };
+    Some(code)
+}
+
+/// Errors in the `[keys]` config table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapError {
+    UnknownAction(String),
+    InvalidKey(String),
+    /// The key is reserved (Ctrl+C always quits)
+    Reserved {
+        action: String,
+        key: String,
+    },
+    /// Two actions were given the same key
+    Duplicate {
+        key: String,
+        first: &'static str,
+        second: &'static str,
+    },
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::UnknownAction(name) => write!(f, "unknown key action `{}`", name),
+            KeymapError::InvalidKey(key) => write!(f, "invalid key `{}`", key),
+            KeymapError::Reserved { action, key } => {
+                write!(f, "`{}` cannot use `{}`: it always quits", action, key)
+            }
+            KeymapError::Duplicate { key, first, second } => {
+                write!(f, "`{}` is bound to both `{}` and `{}`", key, first, second)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+/// Effective key bindings for the quiz screen
+/// Starts from a preset; entries in `[keys]` replace the preset keys for that action.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl KeyBindings {
+    pub fn preset(preset: KeymapPreset) -> Self {
+        let plain = |c| KeyBinding::plain(KeyCode::Char(c));
+        let mut bindings = vec![
+            (plain('q'), Action::Quit),
+            (plain('h'), Action::Hint),
+            (plain('n'), Action::Next),
+            (plain('a'), Action::Answer),
+            (plain('s'), Action::Skip),
+            (plain('+'), Action::ExtendTime),
+            (plain('R'), Action::RestartTimer),
+            (plain('g'), Action::Jump),
+            (plain('!'), Action::Shell),
+            (plain('E'), Action::Editor),
+            (plain('r'), Action::Related),
+            (plain('K'), Action::PrepCluster),
+            (plain('z'), Action::CollapseQuestion),
+            (KeyBinding::plain(KeyCode::Tab), Action::NextTab),
+            (KeyBinding::plain(KeyCode::BackTab), Action::PreviousTab),
+            (KeyBinding::plain(KeyCode::Up), Action::ScrollUp),
+            (KeyBinding::plain(KeyCode::Down), Action::ScrollDown),
+            (KeyBinding::plain(KeyCode::PageUp), Action::ContextUp),
+            (KeyBinding::plain(KeyCode::PageDown), Action::ContextDown),
+        ];
+        if preset == KeymapPreset::Vim {
+            // K switches tabs in vim; prep_cluster can be bound under [keys]
+            bindings.retain(|(_, action)| *action != Action::PrepCluster);
+            bindings.extend([
+                (plain('k'), Action::ScrollUp),
+                (plain('j'), Action::ScrollDown),
+                (plain('J'), Action::NextTab),
+                (plain('K'), Action::PreviousTab),
+            ]);
+        }
+        Self { bindings }
+    }
+
+    /// Applies `[keys]` entries (action name to key descriptor, or "none" to unbind)
+    pub fn with_overrides(
+        mut self,
+        overrides: &BTreeMap<String, String>,
+    ) -> Result<Self, KeymapError> {
+        let mut assigned: Vec<(KeyBinding, Action)> = Vec::new();
+        for (name, descriptor) in overrides {
+            let action =
+                Action::from_name(name).ok_or_else(|| KeymapError::UnknownAction(name.clone()))?;
+            self.bindings.retain(|(_, bound)| *bound != action);
+            if descriptor.eq_ignore_ascii_case("none") {
+                continue;
+            }
+            let key = KeyBinding::parse(descriptor)?;
+            if key == KeyBinding::QUIT_FALLBACK && action != Action::Quit {
+                return Err(KeymapError::Reserved {
+                    action: name.clone(),
+                    key: descriptor.clone(),
+                });
+            }
+            if let Some((_, first)) = assigned.iter().find(|(bound, _)| *bound == key) {
+                return Err(KeymapError::Duplicate {
+                    key: descriptor.clone(),
+                    first: first.name(),
+                    second: action.name(),
+                });
+            }
+            assigned.push((key, action));
+        }
+
+        // Preset keys taken over by an explicit assignment move to the new action
+        self.bindings
+            .retain(|(key, _)| !assigned.iter().any(|(taken, _)| taken == key));
+        self.bindings.extend(assigned);
+        Ok(self)
+    }
+
+    pub fn action_for(&self, event: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(key, _)| key.matches(event))
+            .map(|(_, action)| *action)
+    }
+
+    /// Keys bound to an action, joined for on-screen help; `None` if unbound
+    pub fn label(&self, action: Action) -> Option<String> {
+        let keys: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(key, _)| key.to_string())
+            .collect();
+        (!keys.is_empty()).then(|| keys.join("/"))
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::preset(KeymapPreset::Default)
+    }
+}
@@ -0,0 +1,367 @@
+use serde_yaml::Value;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Subcommands that render a manifest with `--dry-run=client` and have no other
+/// side effects; anything else is graded by string comparison
+const DRY_RUN_SUBCOMMANDS: [&str; 3] = ["run", "create", "expose"];
+
+/// Why kubectl could not render a manifest
+#[derive(Debug)]
+pub enum KubectlError {
+    /// No `kubectl` on PATH
+    NotInstalled,
+    TimedOut(Duration),
+    /// kubectl ran but rejected the command
+    Failed(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for KubectlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KubectlError::NotInstalled => write!(f, "kubectl was not found on PATH"),
+            KubectlError::TimedOut(limit) => {
+                write!(f, "kubectl did not answer within {}s", limit.as_secs())
+            }
+            KubectlError::Failed(stderr) => write!(f, "kubectl failed: {}", stderr.trim()),
+            KubectlError::Io(err) => write!(f, "could not run kubectl: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for KubectlError {}
+
+/// Runs kubectl and returns its stdout
+/// Grading and cluster prep depend on this trait, so tests can hand back
+/// canned output.
+pub trait KubectlRunner: fmt::Debug + Send + Sync {
+    /// `args` excludes the leading `kubectl`
+    fn run(&self, args: &[String]) -> Result<String, KubectlError>;
+}
+
+/// The real `kubectl` binary, killed if it runs past the timeout
+#[derive(Debug, Clone)]
+pub struct SystemKubectl {
+    timeout: Duration,
+}
+
+impl SystemKubectl {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Checks that kubectl is installed before any answer depends on it
+    pub fn detect(timeout: Duration) -> Result<Self, KubectlError> {
+        let kubectl = Self { timeout };
+        kubectl.run(&["version".to_string(), "--client".to_string()])?;
+        Ok(kubectl)
+    }
+}
+
+impl KubectlRunner for SystemKubectl {
+    fn run(&self, args: &[String]) -> Result<String, KubectlError> {
+        let mut child = Command::new("kubectl")
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => KubectlError::NotInstalled,
+                _ => KubectlError::Io(err),
+            })?;
+
+        // Drain both pipes on threads so a chatty kubectl can't block on a full pipe
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait().map_err(KubectlError::Io)? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(KubectlError::TimedOut(self.timeout));
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        if status.success() {
+            Ok(stdout)
+        } else {
+            Err(KubectlError::Failed(stderr.join().unwrap_or_default()))
+        }
+    }
+}
+
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut text);
+        }
+        text
+    })
+}
+
+/// Compares two kubectl commands by the manifests they would create
+/// `None` means they can't be compared this way (not a supported kubectl
+/// command, or kubectl failed) and the caller should compare text instead.
+pub fn commands_match(runner: &dyn KubectlRunner, submitted: &str, expected: &str) -> Option<bool> {
+    let submitted = dry_run_args(submitted)?;
+    let expected = dry_run_args(expected)?;
+    let render = |args: &[String]| match runner.run(args) {
+        Ok(yaml) => serde_yaml::from_str::<Value>(&yaml)
+            .ok()
+            .map(strip_volatile),
+        Err(err) => {
+            tracing::warn!(error = %err, "kubectl grading unavailable");
+            None
+        }
+    };
+    Some(render(&submitted)? == render(&expected)?)
+}
+
+/// Arguments for a client-side dry run of `command`, or `None` if it isn't a
+/// single supported kubectl invocation
+fn dry_run_args(command: &str) -> Option<Vec<String>> {
+    let words = split_command(command)?;
+    let (program, args) = words.split_first()?;
+    if program != "kubectl" || !DRY_RUN_SUBCOMMANDS.contains(&args.first()?.as_str()) {
+        return None;
+    }
+    let mut args = args.to_vec();
+    args.extend(["--dry-run=client", "-o", "yaml"].map(str::to_string));
+    Some(args)
+}
+
+/// Splits a command line like a POSIX shell would for plain words and quotes.
+/// Returns `None` for anything a shell would treat specially (pipes, redirects,
+/// `;`, `&`, substitutions, several lines) or unbalanced quotes.
+pub fn split_command(command: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => word.push(chars.next()?),
+                        '$' | '`' => return None,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                word.push(chars.next()?);
+            }
+            '|' | ';' | '&' | '<' | '>' | '$' | '`' | '(' | ')' | '\n' => return None,
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+/// What the API server made of a submitted manifest
+#[derive(Debug)]
+pub enum ServerCheck {
+    /// Admission and validation passed
+    Accepted,
+    /// The server refused it; kubectl's message, on one line
+    Rejected(String),
+    /// No verdict: kubectl missing, no cluster reachable, or a timeout
+    Unavailable(KubectlError),
+}
+
+/// Validates `manifest` with `kubectl apply --dry-run=server`, which runs
+/// admission and validation without persisting anything
+/// The manifest goes through a temporary file that is removed afterwards.
+pub fn server_dry_run(runner: &dyn KubectlRunner, manifest: &str) -> ServerCheck {
+    let file = match ManifestFile::create(&std::env::temp_dir(), manifest) {
+        Ok(file) => file,
+        Err(err) => return ServerCheck::Unavailable(KubectlError::Io(err)),
+    };
+    let args = server_dry_run_args(file.path());
+    // Never let anything but a dry run reach the cluster
+    if !args.iter().any(|arg| arg == "--dry-run=server") {
+        return ServerCheck::Unavailable(KubectlError::Failed("refusing to apply".to_string()));
+    }
+    match runner.run(&args) {
+        Ok(_) => ServerCheck::Accepted,
+        Err(KubectlError::Failed(stderr)) if !is_unreachable(&stderr) => {
+            ServerCheck::Rejected(stderr.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+        Err(err) => ServerCheck::Unavailable(err),
+    }
+}
+
+/// `apply` of one file, always as a server-side dry run
+pub fn server_dry_run_args(path: &Path) -> Vec<String> {
+    vec![
+        "apply".to_string(),
+        "--dry-run=server".to_string(),
+        "-f".to_string(),
+        path.display().to_string(),
+    ]
+}
+
+/// kubectl failures that say nothing about the manifest itself
+fn is_unreachable(stderr: &str) -> bool {
+    const SIGNS: [&str; 6] = [
+        "Unable to connect to the server",
+        "The connection to the server",
+        "connection refused",
+        "no such host",
+        "i/o timeout",
+        "no configuration has been provided",
+    ];
+    SIGNS.iter().any(|sign| stderr.contains(sign))
+}
+
+/// The kube-context kubectl would use
+pub fn current_context(runner: &dyn KubectlRunner) -> Result<String, KubectlError> {
+    let output = runner.run(&["config".to_string(), "current-context".to_string()])?;
+    Ok(output.trim().to_string())
+}
+
+/// Temporary manifest file, removed when dropped
+#[derive(Debug)]
+pub(crate) struct ManifestFile {
+    path: PathBuf,
+}
+
+impl ManifestFile {
+    pub(crate) fn create(dir: &Path, manifest: &str) -> io::Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.subsec_nanos());
+        let path = dir.join(format!(
+            "ckad-dry-run-{}-{}.yaml",
+            std::process::id(),
+            nanos
+        ));
+        fs::write(&path, manifest)?;
+        Ok(Self { path })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ManifestFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Kube-contexts the user has agreed to send dry runs to, one per line
+#[derive(Debug, Clone)]
+pub struct ConfirmedContexts {
+    path: PathBuf,
+}
+
+impl ConfirmedContexts {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join("dry-run-contexts"),
+        }
+    }
+
+    pub fn contains(&self, context: &str) -> bool {
+        fs::read_to_string(&self.path)
+            .map(|text| text.lines().any(|line| line == context))
+            .unwrap_or(false)
+    }
+
+    pub fn add(&self, context: &str) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", context)
+    }
+
+    /// True once `context` is confirmed: asks on first use and remembers a yes
+    pub fn confirm<R: BufRead, W: Write>(
+        &self,
+        context: &str,
+        input: &mut R,
+        out: &mut W,
+    ) -> io::Result<bool> {
+        if self.contains(context) {
+            return Ok(true);
+        }
+        write!(
+            out,
+            "Validate manifests with `kubectl apply --dry-run=server` against context '{}'? \
+             Nothing is created. [y/N] ",
+            context
+        )?;
+        out.flush()?;
+        let mut reply = String::new();
+        input.read_line(&mut reply)?;
+        let confirmed = matches!(reply.trim(), "y" | "Y" | "yes");
+        if confirmed {
+            self.add(context)?;
+        }
+        Ok(confirmed)
+    }
+}
+
+/// Drops fields that differ between runs: `status` and every `creationTimestamp`
+pub(crate) fn strip_volatile(mut manifest: Value) -> Value {
+    if let Value::Mapping(map) = &mut manifest {
+        map.remove("status");
+    }
+    remove_key(&mut manifest, "creationTimestamp");
+    manifest
+}
+
+fn remove_key(value: &mut Value, key: &str) {
+    match value {
+        Value::Mapping(map) => {
+            map.remove(key);
+            for (_, child) in map.iter_mut() {
+                remove_key(child, key);
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(|item| remove_key(item, key)),
+        _ => {}
+    }
+}
@@ -0,0 +1,56 @@
+//! CKAD practice quiz for the terminal.
+//!
+//! Questions come from a [`question_repository::QuestionRepository`], are selected by
+//! [`scheduler::build_session`] and played through a headless [`session::Session`].
+//! The TUI ([`app`]), line-based mode ([`plain`]) and `--print` ([`mod@print`]) are
+//! front-ends over that shared core; `main.rs` only wires them to the terminal.
+
+pub mod accessible_ui;
+pub mod adaptive;
+pub mod anki;
+pub mod app;
+pub mod audio;
+pub mod authoring;
+pub mod autosave;
+pub mod bank_updates;
+pub mod cli;
+pub mod cluster;
+pub mod config;
+pub mod display_width;
+pub mod distractors;
+pub mod editor;
+pub mod events;
+pub mod goal;
+pub mod grading;
+pub mod i18n;
+pub mod keymap;
+pub mod kubectl;
+pub mod list;
+pub mod logging;
+pub mod markdown_import;
+pub mod mastery;
+pub mod models;
+pub mod paging;
+pub mod plain;
+pub mod playlist;
+pub mod print;
+pub mod profile;
+pub mod question_repository;
+pub mod quiz_state;
+pub mod report;
+pub mod results;
+pub mod scheduler;
+pub mod scorer;
+pub mod serve;
+pub mod session;
+pub mod settings;
+pub mod shell;
+pub mod stats;
+pub mod terminal;
+pub mod theme;
+pub mod timer;
+pub mod typing;
+pub mod typing_app;
+pub mod ui;
+pub mod validation;
+pub mod webhook;
@@ -0,0 +1,172 @@
+use crate::display_width;
+use crate::models::{Difficulty, Question};
+use crate::print::PrintFormat;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Longest question text shown in the table, in terminal columns
+const QUESTION_WIDTH: usize = 50;
+
+/// Row order for `list`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    #[default]
+    Id,
+    /// Shortest time limit first
+    Time,
+    /// Easy to hard, unrated questions last
+    Difficulty,
+}
+
+/// What `list` prints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    pub format: PrintFormat,
+    pub sort: ListSort,
+}
+
+/// A question as listed; the full text, unlike the table
+#[derive(Serialize)]
+struct ListedQuestion<'a> {
+    id: usize,
+    question: &'a str,
+    tags: &'a [String],
+    difficulty: Option<Difficulty>,
+    time_limit_secs: u64,
+}
+
+/// Orders questions for listing; ties keep id order
+pub fn sort_questions(questions: &mut [Question], sort: ListSort) {
+    match sort {
+        ListSort::Id => questions.sort_by_key(|question| question.id),
+        ListSort::Time => questions.sort_by_key(|question| (question.time_limit_secs, question.id)),
+        ListSort::Difficulty => questions.sort_by_key(|question| {
+            // `None` sorts first, so rate unrated questions past the hardest
+            (
+                question.difficulty.is_none(),
+                question.difficulty,
+                question.id,
+            )
+        }),
+    }
+}
+
+/// Writes a bank overview for `list`
+/// A table with a count summary, or a JSON array for scripts
+pub fn write_list<W: Write>(
+    out: &mut W,
+    questions: &[Question],
+    options: ListOptions,
+) -> io::Result<()> {
+    let mut questions = questions.to_vec();
+    sort_questions(&mut questions, options.sort);
+
+    match options.format {
+        PrintFormat::Json => {
+            let listed: Vec<ListedQuestion> = questions
+                .iter()
+                .map(|question| ListedQuestion {
+                    id: question.id,
+                    question: &question.question,
+                    tags: &question.tags,
+                    difficulty: question.difficulty,
+                    time_limit_secs: question.time_limit_secs,
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut *out, &listed)?;
+            writeln!(out)
+        }
+        PrintFormat::Text => write_table(out, &questions),
+    }
+}
+
+fn write_table<W: Write>(out: &mut W, questions: &[Question]) -> io::Result<()> {
+    let header = ["ID", "QUESTION", "TAGS", "DIFFICULTY", "TIME"];
+    let rows: Vec<[String; 5]> = questions
+        .iter()
+        .map(|question| {
+            [
+                question.id.to_string(),
+                truncate(&question.question, QUESTION_WIDTH),
+                question.tags.join(","),
+                question
+                    .difficulty
+                    .map_or("-".to_string(), |difficulty| difficulty.to_string()),
+                format_secs(question.time_limit_secs),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(display_width::width);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width::width(cell));
+        }
+    }
+
+    let header = header.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| display_width::pad(cell, width))
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end())?;
+    }
+    writeln!(out)?;
+    writeln!(out, "{}", summary(questions))
+}
+
+/// e.g. "3 questions (1 easy, 2 unrated), 4:30 total"
+pub fn summary(questions: &[Question]) -> String {
+    let count = |difficulty: Option<Difficulty>| {
+        questions
+            .iter()
+            .filter(|question| question.difficulty == difficulty)
+            .count()
+    };
+    let mut breakdown: Vec<String> = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+        .into_iter()
+        .map(|difficulty| (count(Some(difficulty)), difficulty.to_string()))
+        .chain(std::iter::once((count(None), "unrated".to_string())))
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+    // "3 questions (3 unrated)" says nothing the count doesn't
+    if breakdown.len() == 1 && count(None) == questions.len() {
+        breakdown.clear();
+    }
+
+    let total: u64 = questions
+        .iter()
+        .map(|question| question.time_limit_secs)
+        .sum();
+    let noun = if questions.len() == 1 {
+        "question"
+    } else {
+        "questions"
+    };
+    let breakdown = if breakdown.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", breakdown.join(", "))
+    };
+    format!(
+        "{} {}{}, {} total",
+        questions.len(),
+        noun,
+        breakdown,
+        format_secs(total)
+    )
+}
+
+/// Single-line text cut to `max` columns, ending in "..." when cut
+fn truncate(text: &str, max: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    display_width::truncate(&text, max, "...")
+}
+
+fn format_secs(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
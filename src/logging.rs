@@ -0,0 +1,74 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+/// Logging options resolved from the command line
+#[derive(Debug, Clone, Default)]
+pub struct LogSettings {
+    /// `--debug`: log at debug level to the default file
+    pub debug: bool,
+    /// `--log-file`: where to write records instead of `<data_dir>/debug.log`
+    pub file: Option<PathBuf>,
+    /// `--log-level`: takes precedence over `RUST_LOG`
+    pub level: Option<Level>,
+}
+
+/// Records from this crate only; dependencies such as the HTTP stack stay out
+/// unless `RUST_LOG` asks for them
+const CRATE: &str = env!("CARGO_CRATE_NAME");
+
+impl LogSettings {
+    /// The filter for the log file, or `None` when logging is off
+    /// `rust_log` is the `RUST_LOG` value, if set; `--log-level` takes precedence.
+    pub fn directives(&self, rust_log: Option<&str>) -> Option<String> {
+        match (self.level, rust_log) {
+            (Some(level), _) => Some(format!("{}={}", CRATE, level.as_str().to_lowercase())),
+            (None, Some(rust_log)) => Some(rust_log.to_string()),
+            (None, None) if self.debug || self.file.is_some() => Some(format!("{}=debug", CRATE)),
+            (None, None) => None,
+        }
+    }
+
+    /// Where records go: `--log-file`, or `debug.log` in the data directory
+    pub fn path(&self, data_dir: &Path) -> PathBuf {
+        self.file
+            .clone()
+            .unwrap_or_else(|| data_dir.join("debug.log"))
+    }
+}
+
+/// Installs a file logger when the command line or `RUST_LOG` asks for one
+/// Stdout belongs to the TUI, so records only ever go to a file.
+/// Without a subscriber every tracing macro is a cheap no-op and no file is created.
+pub fn init(settings: &LogSettings, data_dir: &Path) -> io::Result<Option<PathBuf>> {
+    let rust_log = std::env::var(EnvFilter::DEFAULT_ENV).ok();
+    init_with(settings, data_dir, rust_log.as_deref())
+}
+
+/// [`init`] with the `RUST_LOG` value passed in rather than read
+pub fn init_with(
+    settings: &LogSettings,
+    data_dir: &Path,
+    rust_log: Option<&str>,
+) -> io::Result<Option<PathBuf>> {
+    let Some(directives) = settings.directives(rust_log) else {
+        return Ok(None);
+    };
+
+    let path = settings.path(data_dir);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let file = File::options().create(true).append(true).open(&path)?;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(directives))
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .init();
+
+    Ok(Some(path))
+}
@@ -1,52 +1,769 @@
-mod app;
-mod models;
-mod question_repository;
-mod quiz_state;
-mod timer;
-mod ui;
-
-use app::App;
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+use chrono::Local;
+use ckad_practitioner::anki::{self, AnkiOnly, AnkiOptions};
+use ckad_practitioner::app::{self, App, ExitReason};
+use ckad_practitioner::audio::{self, SoundPlayer};
+use ckad_practitioner::cluster::{self, ClusterError, PrepOptions};
+use ckad_practitioner::i18n::Messages;
+use ckad_practitioner::kubectl::{self, ConfirmedContexts, SystemKubectl};
+use ckad_practitioner::markdown_import::{self, ImportOptions};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::results::{self, SessionResult};
+use ckad_practitioner::scorer::{AnswerScorer, Grading};
+use ckad_practitioner::serve::ServeOptions;
+use ckad_practitioner::session::Session;
+use ckad_practitioner::settings::{Settings, SettingsError};
+use ckad_practitioner::stats::{self, PersonalBests, TypingTrend};
+use ckad_practitioner::theme::Theme;
+use ckad_practitioner::typing_app::TypingApp;
+use ckad_practitioner::validation::ValidateOptions;
+use ckad_practitioner::webhook::{self, Posted};
+use ckad_practitioner::{
+    authoring, autosave, bank_updates, cli, list, logging, plain, playlist, print, profile, report,
+    scheduler, serve, terminal, typing, validation,
 };
-use question_repository::InMemoryQuestionRepository;
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::sync::Arc;
 
 /// Main entry point demonstrating Dependency Inversion Principle
 /// The App is created with a QuestionRepository abstraction, making it
 /// easy to swap implementations without changing the core application logic
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Parse arguments and load questions before touching the terminal,
+    // so usage and loading errors print normally
+    let (settings, warnings) = match cli::parse_args()
+        .and_then(|(settings, warnings)| Ok((with_schedule(settings)?, warnings)))
+    {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+    };
+    if settings.list_profiles {
+        return list_profiles(&settings);
+    }
+    if let Some(options) = &settings.add {
+        if let Err(err) = authoring::run(options, &mut io::stdin().lock(), &mut io::stdout()) {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    // Bank overview: the questions a session would use, then exit
+    if let Some(options) = settings.list {
+        let questions = build_session_or_exit(&settings);
+        return match list::write_list(&mut io::stdout().lock(), &questions, options) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.into()),
+            _ => Ok(()),
+        };
+    }
+    if let Some(options) = settings.export_anki {
+        return export_anki(&settings, options);
+    }
+    if let Some(options) = &settings.import_md {
+        return import_md(options);
+    }
+    if let Some(options) = &settings.validate {
+        return validate_bank(options);
+    }
+    if let Some(options) = &settings.serve {
+        return serve_results(&settings, options).await;
+    }
+    // A profile's directory is created the first time it is used
+    if let Some(profile) = &settings.profile {
+        if let Err(err) = fs::create_dir_all(&settings.data_dir) {
+            eprintln!("warning: could not create profile {}: {}", profile, err);
+        }
+    }
+    if let Err(err) = logging::init(&settings.log, &settings.data_dir) {
+        eprintln!("warning: logging disabled: {}", err);
+    }
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+        tracing::warn!(%warning, "config warning");
+    }
+    if settings.sounds.enabled && !audio::SUPPORTED {
+        eprintln!(
+            "warning: --sounds: this build has no sound support; rebuild with --features sounds"
+        );
+    }
+
+    if settings.typing {
+        return run_typing(&settings).await;
+    }
+    if settings.flush_outbox {
+        return flush_outbox(&settings).await;
+    }
+    if settings.stats {
+        let stats = stats::HintStats::from_history(&load_history(&settings));
+        return match stats::write_hint_report(&mut io::stdout().lock(), &stats) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.into()),
+            _ => Ok(()),
+        };
+    }
+    if let Some(options) = settings.prep_cluster {
+        return prep_cluster(&settings, options);
+    }
+
+    // Line-based quiz over stdin/stdout, saved like a TUI session
+    if settings.plain {
+        let questions = build_session_or_exit(&settings);
+        println!(
+            "Source: {}",
+            app::describe_source(settings.repository().as_ref(), questions.len())
+        );
+        let mut session =
+            Session::new(questions, settings.quiz.clone())?.with_scorer(answer_scorer(&settings));
+        let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+        let history = load_history(&settings);
+        let result = plain::run_session(
+            &mut session,
+            stdin,
+            &mut io::stdout(),
+            &settings.goal,
+            &history,
+            Local::now(),
+        )
+        .await?;
+        save_session(&settings, &result);
+        post_results(&settings, &result).await;
+        return Ok(());
+    }
+
+    // Plain output: no terminal setup, no saved session
+    if settings.print || !io::stdout().is_terminal() {
+        let questions = build_session_or_exit(&settings);
+        let written =
+            print::write_questions(&mut io::stdout().lock(), &questions, settings.print_options);
+        // A closed pipe (e.g. `| head`) is not an error
+        match written {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err.into()),
+            _ => return Ok(()),
+        }
+    }
+
+    let theme = theme(&settings);
+
+    // Dependency Injection: the repository is chosen from the command line or config
+    // (built-in questions, a JSON file or a directory of JSON files)
+    let history = load_history(&settings);
+    let autosave_path = autosave::autosave_path(&settings.data_dir);
+    let repository = settings.repository();
+    let personal_bests = PersonalBests::from_history(&history);
+    let app = match offer_recovery(&settings, &history, &autosave_path) {
+        Some(session) => {
+            let source =
+                app::describe_source(repository.as_ref(), session.quiz_state().total_questions());
+            Ok(App::from_session(
+                session,
+                source,
+                theme,
+                settings.keymap.clone(),
+                personal_bests,
+            ))
+        }
+        // A recovered session picks up where it was, without a start screen
+        None => App::new(
+            settings.repository(),
+            theme,
+            settings.keymap.clone(),
+            settings.quiz.clone(),
+            personal_bests,
+        )
+        .map(|app| match settings.start_countdown {
+            Some(secs) => app.with_start_screen(secs),
+            None => app,
+        }),
+    };
+    let app = match app {
+        Ok(app) => app,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+    };
+    warn_skipped(app.skipped_sources());
+    let mut app = app
+        .with_messages(Messages::for_locale(&settings.locale))
+        .with_profile(settings.profile.clone())
+        .with_goal(settings.goal, &history)
+        .with_history(&history)
+        .with_frame_rate(settings.frame_rate)
+        .with_layout(settings.layout)
+        .with_shell(settings.shell.clone())
+        .with_editor(settings.editor.clone())
+        .with_sounds(SoundPlayer::new(settings.sounds.clone()))
+        .with_cluster(settings.cluster.clone())
+        .with_autosave(autosave::Autosaver::new(
+            autosave_path.clone(),
+            repository.source_description(),
+        ))
+        .with_scorer(answer_scorer(&settings));
+    // A paged bank isn't loaded whole just for the check
+    if settings.bank_updates && !repository.supports_paging() {
+        app = app.with_bank_check(bank_updates::spawn_check(
+            bank_updates::checks_path(&settings.data_dir),
+            settings.repository(),
+        ));
+    }
+
     // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    terminal::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    // Dependency Injection: Create app with a concrete repository implementation
-    // This could easily be swapped with FileQuestionRepository or any other implementation
-    let repository = Box::new(InMemoryQuestionRepository);
-    let mut app = App::new(repository);
-
     // Run the application
     let res = app.run(&mut terminal).await;
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    // Restore terminal; when it has gone away this fails too, and the session
+    // below should still be saved
+    if let Err(err) = terminal::leave() {
+        tracing::warn!(error = %err, "could not restore the terminal");
+    }
+
+    let exit_code = match res {
+        Ok(ExitReason::Signal(signal)) => {
+            eprintln!("Interrupted by {}", signal);
+            1
+        }
+        Ok(ExitReason::InputLost) => {
+            eprintln!("Terminal input closed; the session so far has been saved");
+            0
+        }
+        // `run` handles the shell and editor itself, so anything else is a normal quit
+        Ok(_) => 0,
+        Err(err) => {
+            tracing::error!(error = %err, "quiz loop failed");
+            println!("{:?}", err);
+            1
+        }
+    };
+
+    // Results are saved even when the session was cut short, which makes the
+    // autosave redundant
+    let result = app.session_result();
+    save_session(&settings, &result);
+    autosave::discard(&autosave_path);
+    teardown_prepared(&settings, app.prepared_questions());
+    post_results(&settings, &result).await;
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// Accessibility: honour the NO_COLOR convention on top of the configured theme
+fn theme(settings: &Settings) -> Theme {
+    if std::env::var_os("NO_COLOR").is_some() {
+        Theme::new(false)
+    } else {
+        Theme::from_name(settings.theme)
+    }
+}
+
+/// Prints the selected questions (or the missed ones) as an Anki deck
+fn export_anki(
+    settings: &Settings,
+    options: AnkiOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let questions = if options.only == Some(AnkiOnly::Missed) {
+        let missed = stats::missed_questions(&load_history(settings));
+        let repository = settings.repository();
+        let (questions, gone) = anki::fetch_missed(repository.as_ref(), &missed)?;
+        warn_skipped(&repository.skipped_sources());
+        if !gone.is_empty() {
+            eprintln!(
+                "note: {} missed questions are no longer in the bank",
+                gone.len()
+            );
+        }
+        let questions = scheduler::select_questions(questions, &settings.quiz);
+        if questions.is_empty() {
+            eprintln!("note: no missed questions in the saved sessions");
+        }
+        questions
+    } else {
+        build_session_or_exit(settings)
+    };
+    match anki::write_tsv(&mut io::stdout().lock(), &questions) {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// `import-md`: writes the mapped bank to stdout or `--output` and reports the
+/// sections that needed guessing on stderr
+fn import_md(options: &ImportOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let import = markdown_import::import_path(&options.path)
+        .map_err(|err| format!("could not read {}: {}", options.path.display(), err))?;
+    let skipped = import
+        .issues
+        .iter()
+        .filter(|issue| issue.problem.is_skipped())
+        .count();
+    eprintln!(
+        "Imported {} questions ({} sections skipped)",
+        import.questions.len(),
+        skipped
+    );
+    for issue in &import.issues {
+        eprintln!("  {}", issue);
+    }
+    if import.questions.is_empty() {
+        eprintln!("error: no questions found in {}", options.path.display());
+        std::process::exit(1);
+    }
+    let yaml = markdown_import::to_yaml(&import.questions)?;
+    match &options.output {
+        Some(path) => {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .map_err(|err| format!("could not create {}: {}", path.display(), err))?;
+            file.write_all(yaml.as_bytes())?;
+            eprintln!("Wrote {}", path.display());
+            Ok(())
+        }
+        None => match io::stdout().lock().write_all(yaml.as_bytes()) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.into()),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// `validate`: writes the schema if asked, then lists every problem in the
+/// bank as `file: $[i].field: message`, exiting with 1 if there are any
+fn validate_bank(options: &ValidateOptions) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(path) = &options.schema_out {
+        fs::write(path, validation::SCHEMA)
+            .map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+        eprintln!("Wrote the question bank schema to {}", path.display());
+    }
+    let Some(bank) = &options.bank else {
+        return Ok(());
+    };
+    let errors = validation::validate_file(bank)?;
+    if errors.is_empty() {
+        println!("{}: no problems found", bank.display());
+        return Ok(());
+    }
+    for error in &errors {
+        println!("{}: {}", bank.display(), error);
+    }
+    eprintln!(
+        "{} problem{} in {}",
+        errors.len(),
+        if errors.len() == 1 { "" } else { "s" },
+        bank.display()
+    );
+    std::process::exit(1);
+}
+
+/// `serve`: loads the sessions, then serves them on a free localhost port
+/// until Ctrl+C
+async fn serve_results(
+    settings: &Settings,
+    options: &ServeOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut sessions = Vec::new();
+    if options.paths.is_empty() {
+        sessions = stats::load_history(&results::results_dir(&settings.data_dir));
+    }
+    for path in &options.paths {
+        if path.is_dir() {
+            sessions.extend(stats::load_history(path));
+            continue;
+        }
+        let session = fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<SessionResult>(&json).map_err(|err| err.to_string())
+            })
+            .map_err(|err| format!("could not load {}: {}", path.display(), err))?;
+        sessions.push(session);
+    }
+    sessions.sort_by_key(|session| session.started_at);
+
+    let listener = serve::bind().await?;
+    println!(
+        "Serving {} sessions at http://{}/ (Ctrl+C to stop)",
+        sessions.len(),
+        listener.local_addr()?
+    );
+    serve::run(listener, sessions, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await?;
+    Ok(())
+}
+
+/// Posts the results to the webhook, if one is set, once the terminal is back;
+/// each attempt has a timeout, so a dead endpoint delays the exit only briefly
+async fn post_results(settings: &Settings, result: &SessionResult) {
+    let Some(webhook) = &settings.webhook else {
+        return;
+    };
+    let outbox = webhook::outbox_dir(&settings.data_dir);
+    match webhook::post_session(webhook, result, &outbox).await {
+        Ok(Posted::Delivered) => tracing::info!(url = %webhook.url, "results posted"),
+        Ok(Posted::Queued { path, error }) => eprintln!(
+            "warning: could not post results to {}: {}; saved to {} for `flush-outbox`",
+            webhook.url,
+            error,
+            path.display()
+        ),
+        Err(err) => {
+            tracing::error!(error = %err, "failed to queue results");
+            eprintln!("warning: could not post or queue results: {}", err);
+        }
+    }
+}
+
+/// `prep-cluster`: applies or deletes one question's practice namespace
+fn prep_cluster(
+    settings: &Settings,
+    options: PrepOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let questions = settings.repository().get_questions()?;
+    let prepared = questions
+        .iter()
+        .find(|question| question.id == options.id)
+        .ok_or(ClusterError::UnknownQuestion(options.id))
+        .and_then(|question| {
+            let kubectl = SystemKubectl::detect(cluster::KUBECTL_TIMEOUT)?;
+            cluster::run(
+                &kubectl,
+                &settings.cluster,
+                question,
+                options.teardown,
+                &mut io::stdin().lock(),
+                &mut io::stdout(),
+            )
+        });
+    match prepared {
+        Ok(namespace) if options.teardown => println!("Namespace {} is being deleted.", namespace),
+        Ok(namespace) => println!(
+            "Setup applied. Work in it with: kubectl -n {} get all",
+            namespace
+        ),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Deletes the namespaces the prep key created once the quiz is over
+fn teardown_prepared(settings: &Settings, ids: &[usize]) {
+    if ids.is_empty() {
+        return;
+    }
+    println!("Removing the practice namespaces set up during the quiz:");
+    let removed = SystemKubectl::detect(cluster::KUBECTL_TIMEOUT)
+        .map_err(ClusterError::from)
+        .and_then(|kubectl| {
+            cluster::remove_all(&kubectl, &settings.cluster, ids, &mut io::stdout())
+        });
+    if let Err(err) = removed {
+        tracing::warn!(error = %err, "practice namespace teardown failed");
+        eprintln!(
+            "warning: {}; remove them with `prep-cluster <ID> --teardown`",
+            err
+        );
+    }
+}
+
+/// `flush-outbox`: retries every queued payload; fails if any is still undelivered
+async fn flush_outbox(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(webhook) = &settings.webhook else {
+        eprintln!("error: flush-outbox needs a webhook: pass --webhook <URL> or set [webhook] url");
+        std::process::exit(1);
+    };
+    let report = webhook::flush_outbox(webhook, &webhook::outbox_dir(&settings.data_dir)).await?;
+    println!(
+        "Delivered {} queued result{}",
+        report.delivered.len(),
+        if report.delivered.len() == 1 { "" } else { "s" }
+    );
+    for (path, err) in &report.failed {
+        eprintln!("  {}: {}", path.display(), err);
+    }
+    if !report.failed.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// `typing`: the benchmark TUI over the selected questions' commands, saved to
+/// the typing history
+async fn run_typing(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let questions = build_session_or_exit(settings);
+    let targets = typing::targets(&questions);
+    if targets.is_empty() {
+        eprintln!("error: the selected questions have no one-line commands to type");
+        std::process::exit(1);
+    }
+    if !io::stdout().is_terminal() {
+        eprintln!("error: typing needs a terminal");
+        std::process::exit(1);
+    }
+    let typing_dir = typing::typing_dir(&settings.data_dir);
+    let trend = TypingTrend::from_history(&stats::load_typing_history(&typing_dir));
+    let source = app::describe_source(settings.repository().as_ref(), questions.len());
+    let mut app = TypingApp::new(targets, source, theme(settings), trend)
+        .with_messages(Messages::for_locale(&settings.locale));
+
+    terminal::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let res = app.run(&mut terminal).await;
+    if let Err(err) = terminal::leave() {
+        tracing::warn!(error = %err, "could not restore the terminal");
+    }
+
+    let result = app.result();
+    if !result.attempts.is_empty() {
+        let path = typing_dir.join(result.file_name());
+        match typing::write(&path, &result) {
+            Ok(()) => println!("{}", result.summary()),
+            Err(err) => {
+                tracing::error!(path = %path.display(), error = %err, "failed to save typing result");
+                eprintln!("warning: failed to save {}: {}", path.display(), err);
+            }
+        }
+    }
+    match res {
+        Ok(ExitReason::Signal(signal)) => {
+            eprintln!("Interrupted by {}", signal);
+            std::process::exit(1);
+        }
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Asks whether to resume a session that ended without being saved, e.g. in a
+/// crash; autosaves that are declined or can't be resumed are removed
+fn offer_recovery(settings: &Settings, history: &[SessionResult], path: &Path) -> Option<Session> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+    let snapshot = autosave::load(path)?;
+    if !autosave::is_unfinished(&snapshot, history) {
+        autosave::discard(path);
+        return None;
+    }
+    let saved_at = snapshot
+        .saved_at
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M");
+    let session = match autosave::recover(
+        &snapshot,
+        settings.repository().as_ref(),
+        settings.quiz.clone(),
+    ) {
+        Ok(session) => session,
+        Err(err) => {
+            tracing::warn!(error = %err, "autosave not recoverable");
+            eprintln!(
+                "note: the unfinished session from {} can't be resumed: {}",
+                saved_at, err
+            );
+            autosave::discard(path);
+            return None;
+        }
+    };
 
-    if let Err(err) = res {
-        println!("{:?}", err);
+    print!(
+        "Resume the unfinished session from {} ({} of {} questions done)? [Y/n] ",
+        saved_at,
+        snapshot.answered(),
+        snapshot.questions.len()
+    );
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return None;
     }
+    if answer.trim().eq_ignore_ascii_case("n") {
+        autosave::discard(path);
+        return None;
+    }
+    Some(session)
+}
 
+/// `profiles list`: one profile per line, the active one marked with `*`
+fn list_profiles(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let names = profile::list(&settings.data_root)?;
+    if names.is_empty() {
+        println!("No profiles yet; pass --profile <name> to create one.");
+    }
+    for name in names {
+        let active =
+            settings.profile.as_ref().map(|profile| profile.as_str()) == Some(name.as_str());
+        println!("{} {}", if active { "*" } else { " " }, name);
+    }
     Ok(())
 }
+
+/// Text grading, plus kubectl dry-run grading when asked for and kubectl is installed
+fn answer_scorer(settings: &Settings) -> AnswerScorer {
+    let scorer = AnswerScorer::new(settings.quiz.case_sensitive)
+        .with_extra_command_penalty(settings.quiz.penalize_extra_commands)
+        .with_strictness(settings.strictness);
+    if settings.grading == Grading::Text {
+        return scorer;
+    }
+    let kubectl = match SystemKubectl::detect(SystemKubectl::DEFAULT_TIMEOUT) {
+        Ok(kubectl) => Arc::new(kubectl),
+        Err(err) => {
+            tracing::warn!(error = %err, "kubectl grading disabled");
+            eprintln!(
+                "warning: --grade {}: {}; answers are compared as text",
+                match settings.grading {
+                    Grading::Kubectl => "kubectl",
+                    _ => "server-dry-run",
+                },
+                err
+            );
+            return scorer;
+        }
+    };
+    if settings.grading == Grading::Kubectl {
+        return scorer.with_kubectl(kubectl);
+    }
+    match confirm_dry_run_context(settings, kubectl.as_ref()) {
+        Ok(()) => scorer.with_server_dry_run(kubectl),
+        Err(reason) => {
+            tracing::warn!(reason = %reason, "server dry run disabled");
+            eprintln!(
+                "warning: --grade server-dry-run: {}; manifests are compared as YAML only",
+                reason
+            );
+            scorer
+        }
+    }
+}
+
+/// Server dry runs only go to a kube-context the user has agreed to, asked
+/// once per context and remembered in the data dir
+fn confirm_dry_run_context(settings: &Settings, kubectl: &SystemKubectl) -> Result<(), String> {
+    let context = kubectl::current_context(kubectl)
+        .map_err(|err| format!("no current kube-context ({})", err))?;
+    let confirmed = ConfirmedContexts::new(&settings.data_dir);
+    if confirmed.contains(&context) {
+        return Ok(());
+    }
+    if !io::stdin().is_terminal() {
+        return Err(format!(
+            "context '{}' was never confirmed; run once in a terminal to confirm it",
+            context
+        ));
+    }
+    match confirmed.confirm(&context, &mut io::stdin().lock(), &mut io::stdout()) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("context '{}' not confirmed", context)),
+        Err(err) => Err(format!("could not confirm context '{}': {}", context, err)),
+    }
+}
+
+/// Reads what `Settings::resolve` leaves out to stay pure: the playlist file,
+/// the saved sessions `--fresh-days` looks back over, and the clock
+fn with_schedule(settings: Settings) -> Result<Settings, SettingsError> {
+    let settings = match &settings.playlist_file {
+        Some(path) => {
+            let playlist = playlist::load(path).map_err(SettingsError::Playlist)?;
+            settings.with_playlist(playlist)
+        }
+        None => settings,
+    };
+    let history = match settings.fresh_days {
+        Some(_) => stats::load_history(&results::results_dir(&settings.data_dir)),
+        None => Vec::new(),
+    };
+    Ok(settings.with_clock(Local::now(), &history))
+}
+
+/// Loads and selects the session questions for the non-TUI front-ends
+fn build_session_or_exit(settings: &Settings) -> Vec<Question> {
+    let repository = settings.repository();
+    match scheduler::build_session(repository.as_ref(), &settings.quiz) {
+        Ok(questions) => {
+            warn_skipped(&repository.skipped_sources());
+            questions
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Tells the user about bank files a lenient load left out
+fn warn_skipped(skipped: &[String]) {
+    for source in skipped {
+        eprintln!("warning: {} (skipped)", source);
+    }
+}
+
+/// The saved sessions, rekeyed first if any predate question identities
+fn load_history(settings: &Settings) -> Vec<SessionResult> {
+    stats::load_history_for(
+        &results::results_dir(&settings.data_dir),
+        settings.repository().as_ref(),
+    )
+}
+
+/// Writes the session results (and the optional exports) after the terminal is restored
+fn save_session(settings: &Settings, result: &SessionResult) {
+    let mut destinations = vec![results::results_dir(&settings.data_dir).join(result.file_name())];
+    destinations.extend(settings.results_out.clone());
+    for path in destinations {
+        if let Err(err) = results::write(&path, result) {
+            tracing::error!(path = %path.display(), error = %err, "failed to save results");
+            eprintln!(
+                "warning: failed to save results to {}: {}",
+                path.display(),
+                err
+            );
+        }
+    }
+
+    if let Some(path) = &settings.export_csv {
+        if let Err(err) = fs::write(path, report::to_timing_csv(result)) {
+            tracing::error!(path = %path.display(), error = %err, "failed to export CSV");
+            eprintln!(
+                "warning: failed to export CSV to {}: {}",
+                path.display(),
+                err
+            );
+        }
+    }
+
+    if let Some(format) = settings.report {
+        let path = settings.data_dir.join("reports").join(format!(
+            "{}.{}",
+            result.file_stem(),
+            format.extension()
+        ));
+        let written = fs::create_dir_all(path.parent().unwrap_or(&settings.data_dir))
+            .and_then(|_| fs::write(&path, format.render(result)));
+        match written {
+            Ok(()) => println!("Report written to {}", path.display()),
+            Err(err) => {
+                tracing::error!(path = %path.display(), error = %err, "failed to write report");
+                eprintln!(
+                    "warning: failed to write report {}: {}",
+                    path.display(),
+                    err
+                )
+            }
+        }
+    }
+}
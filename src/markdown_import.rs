@@ -0,0 +1,481 @@
+use crate::authoring::{self, DEFAULT_TIME_LIMIT_SECS};
+use crate::models::Question;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What `import-md` reads and where the bank goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// A Markdown file, or a directory whose `.md` files are read in name order
+    pub path: PathBuf,
+    /// Write the bank here instead of stdout; an existing file is left alone
+    pub output: Option<PathBuf>,
+}
+
+/// Questions mapped from Markdown, and the sections that needed guessing or
+/// were left out
+#[derive(Debug, Default)]
+pub struct Import {
+    pub questions: Vec<Question>,
+    pub issues: Vec<ImportIssue>,
+}
+
+/// A section the importer couldn't map with confidence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportIssue {
+    /// File name the section came from
+    pub source: String,
+    /// 1-based line of the section's heading
+    pub line: usize,
+    pub heading: String,
+    pub problem: ImportProblem,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportProblem {
+    /// Only a label like "Question 3" and no paragraph to take the text from
+    NoQuestionText,
+    /// No fenced code block to use as the answer
+    NoAnswer,
+    /// No "Solution" heading or `<details>` block; the last code block was used
+    NoSolutionMarker,
+    /// Shell and YAML blocks in the solution; the commands became the answer
+    /// and the YAML went to the notes
+    MixedAnswer,
+    /// Mapped, but the quiz couldn't run it, e.g. a YAML answer that doesn't parse
+    Invalid(String),
+}
+
+impl ImportProblem {
+    /// Whether the section was left out of the bank
+    pub fn is_skipped(&self) -> bool {
+        matches!(
+            self,
+            Self::NoQuestionText | Self::NoAnswer | Self::Invalid(_)
+        )
+    }
+}
+
+impl fmt::Display for ImportProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoQuestionText => write!(f, "no question text; skipped"),
+            Self::NoAnswer => write!(f, "no code block to use as the answer; skipped"),
+            Self::NoSolutionMarker => write!(
+                f,
+                "no Solution heading or <details> block; used the last code block as the answer"
+            ),
+            Self::MixedAnswer => write!(
+                f,
+                "solution mixes commands and YAML; kept the commands, moved the YAML to the notes"
+            ),
+            Self::Invalid(reason) => write!(f, "{}; skipped", reason),
+        }
+    }
+}
+
+impl fmt::Display for ImportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: \"{}\": {}",
+            self.source, self.line, self.heading, self.problem
+        )
+    }
+}
+
+/// Reads a Markdown file, or every `.md` file in a directory in name order,
+/// numbering the questions from 1 across files
+pub fn import_path(path: &Path) -> io::Result<Import> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+            })
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+    let mut import = Import::default();
+    for file in files {
+        let text = fs::read_to_string(&file)?;
+        let source = file.file_name().map_or_else(
+            || file.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let next = import_markdown(&text, &source, import.questions.len() + 1);
+        import.questions.extend(next.questions);
+        import.issues.extend(next.issues);
+    }
+    Ok(import)
+}
+
+/// Maps one Markdown document to questions
+/// Headings become questions, fenced code under a "Solution" heading or in a
+/// `<details>` block becomes the answer, and bullets before it become hints.
+/// A heading followed straight by deeper headings names the category of the
+/// questions under it.
+pub fn import_markdown(markdown: &str, source: &str, first_id: usize) -> Import {
+    let blocks = blocks(markdown);
+    let mut import = Import::default();
+    let mut categories: Vec<(u8, String)> = Vec::new();
+    let mut draft: Option<Draft> = None;
+    for (index, block) in blocks.iter().enumerate() {
+        let Block::Heading { level, text } = &block.kind else {
+            if let Some(draft) = &mut draft {
+                draft.add(&block.kind);
+            }
+            continue;
+        };
+        if let Some(draft) = &mut draft {
+            match label(text) {
+                Some(Label::Solution) => {
+                    draft.in_solution = true;
+                    draft.marked = true;
+                    continue;
+                }
+                Some(Label::Hints) => {
+                    draft.in_solution = false;
+                    continue;
+                }
+                None => {}
+            }
+        }
+        if let Some(done) = draft.take() {
+            done.finish(source, first_id, &mut import);
+        }
+        categories.retain(|(category_level, _)| category_level < level);
+        let next_heading = blocks[index + 1..]
+            .iter()
+            .find_map(|block| match &block.kind {
+                Block::Heading { level, text } if label(text).is_none() => Some(*level),
+                _ => None,
+            });
+        if next_heading.is_some_and(|next| next > *level) {
+            categories.push((*level, text.clone()));
+            continue;
+        }
+        draft = Some(Draft::new(
+            text.clone(),
+            block.line,
+            categories.last().map(|(_, category)| category.clone()),
+        ));
+    }
+    if let Some(done) = draft {
+        done.finish(source, first_id, &mut import);
+    }
+    for question in &mut import.questions {
+        question.source = Some(source.to_string());
+    }
+    import
+}
+
+/// The bank as YAML, the way `--file` loads it
+pub fn to_yaml(questions: &[Question]) -> Result<String, serde_yaml::Error> {
+    serde_yaml::to_string(questions)
+}
+
+#[derive(Debug)]
+enum Block {
+    Heading { level: u8, text: String },
+    Paragraph(String),
+    Item(String),
+    Code { lang: String, text: String },
+    Html(String),
+}
+
+struct Located {
+    kind: Block,
+    line: usize,
+}
+
+/// The document's top-level blocks in order, with inline markup flattened
+/// to text; nested list items stay part of the item they're in
+fn blocks(markdown: &str) -> Vec<Located> {
+    let line_of = |offset: usize| markdown[..offset].matches('\n').count() + 1;
+    let mut blocks = Vec::new();
+    let mut text = String::new();
+    let mut start = 0;
+    let mut list_depth = 0;
+    let mut html = false;
+    let mut code_lang: Option<String> = None;
+    for (event, range) in Parser::new(markdown).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { .. }) | Event::Start(Tag::Paragraph) if list_depth == 0 => {
+                text.clear();
+                start = range.start;
+            }
+            Event::Start(Tag::Item) if list_depth == 1 => {
+                text.clear();
+                start = range.start;
+            }
+            Event::Start(Tag::List(_)) => list_depth += 1,
+            Event::End(TagEnd::List(_)) => list_depth -= 1,
+            Event::Start(Tag::CodeBlock(kind)) => {
+                code_lang = Some(match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_lowercase()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                });
+                text.clear();
+                start = range.start;
+            }
+            Event::Start(Tag::HtmlBlock) => {
+                html = true;
+                text.clear();
+                start = range.start;
+            }
+            Event::End(TagEnd::Heading(level)) if list_depth == 0 => blocks.push(Located {
+                kind: Block::Heading {
+                    level: heading_level(level),
+                    text: text.trim().to_string(),
+                },
+                line: line_of(start),
+            }),
+            Event::End(TagEnd::Paragraph) if list_depth == 0 => blocks.push(Located {
+                kind: Block::Paragraph(text.trim().to_string()),
+                line: line_of(start),
+            }),
+            Event::End(TagEnd::Paragraph) => text.push(' '),
+            Event::End(TagEnd::Item) if list_depth == 1 => blocks.push(Located {
+                kind: Block::Item(text.split_whitespace().collect::<Vec<_>>().join(" ")),
+                line: line_of(start),
+            }),
+            Event::End(TagEnd::Item) => text.push(' '),
+            Event::End(TagEnd::CodeBlock) => {
+                blocks.push(Located {
+                    kind: Block::Code {
+                        lang: code_lang.take().unwrap_or_default(),
+                        text: text.trim_end().to_string(),
+                    },
+                    line: line_of(start),
+                });
+                // A code block inside an item ends it as a text run
+                text.clear();
+            }
+            Event::End(TagEnd::HtmlBlock) => {
+                html = false;
+                blocks.push(Located {
+                    kind: Block::Html(text.clone()),
+                    line: line_of(start),
+                });
+            }
+            Event::Text(chunk) | Event::Code(chunk) => text.push_str(&chunk),
+            Event::Html(chunk) if html => text.push_str(&chunk),
+            Event::SoftBreak | Event::HardBreak => {
+                text.push(if code_lang.is_some() { '\n' } else { ' ' })
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Label {
+    Solution,
+    Hints,
+}
+
+/// Headings and lead-in paragraphs like "Solution", "**Answer:**" or "Tips"
+fn label(text: &str) -> Option<Label> {
+    let text = text.trim();
+    let word_end = text
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(text.len());
+    let rest = &text[word_end..];
+    if !rest
+        .trim_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+        .is_empty()
+    {
+        return None;
+    }
+    match text[..word_end].to_lowercase().as_str() {
+        "solution" | "solutions" | "answer" | "answers" => Some(Label::Solution),
+        "hint" | "hints" | "tip" | "tips" => Some(Label::Hints),
+        _ => None,
+    }
+}
+
+/// The rest of a heading after a generic label like "Question 3:" or
+/// "Task 2 -", or `None` when the heading isn't one
+fn after_generic_label(heading: &str) -> Option<&str> {
+    let word_end = heading
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(heading.len());
+    let word = heading[..word_end].to_lowercase();
+    if !matches!(word.as_str(), "question" | "task" | "exercise" | "q") {
+        return None;
+    }
+    let rest = heading[word_end..].trim_start();
+    let number_end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    Some(
+        rest[number_end..]
+            .trim_start_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace()),
+    )
+}
+
+/// A question being collected from the blocks under its heading
+struct Draft {
+    heading: String,
+    line: usize,
+    category: Option<String>,
+    text: Vec<String>,
+    hints: Vec<String>,
+    notes: Vec<String>,
+    /// Code blocks before the solution: the material the question refers to
+    given: Vec<(String, String)>,
+    solution: Vec<(String, String)>,
+    in_solution: bool,
+    /// A "Solution" heading or `<details>` block was seen
+    marked: bool,
+}
+
+impl Draft {
+    fn new(heading: String, line: usize, category: Option<String>) -> Self {
+        Self {
+            heading,
+            line,
+            category,
+            text: Vec::new(),
+            hints: Vec::new(),
+            notes: Vec::new(),
+            given: Vec::new(),
+            solution: Vec::new(),
+            in_solution: false,
+            marked: false,
+        }
+    }
+
+    fn add(&mut self, block: &Block) {
+        match block {
+            Block::Heading { .. } => {}
+            Block::Paragraph(text) => match label(text) {
+                Some(Label::Solution) => {
+                    self.in_solution = true;
+                    self.marked = true;
+                }
+                Some(Label::Hints) => {}
+                None if self.in_solution => self.notes.push(text.clone()),
+                None => self.text.push(text.clone()),
+            },
+            Block::Item(text) if self.in_solution => self.notes.push(format!("- {}", text)),
+            Block::Item(text) => self.hints.push(text.clone()),
+            Block::Code { lang, text } if self.in_solution => {
+                self.solution.push((lang.clone(), text.clone()))
+            }
+            Block::Code { lang, text } => self.given.push((lang.clone(), text.clone())),
+            Block::Html(html) => {
+                let html = html.to_lowercase();
+                if html.contains("<details") {
+                    self.in_solution = true;
+                    self.marked = true;
+                } else if html.contains("</details") {
+                    self.in_solution = false;
+                }
+            }
+        }
+    }
+
+    fn finish(mut self, source: &str, first_id: usize, import: &mut Import) {
+        let mut problems = Vec::new();
+        let question = self.mapped(first_id + import.questions.len(), &mut problems);
+        let question = question.and_then(|question| match authoring::validate(&question) {
+            Ok(()) => Some(question),
+            Err(reason) => {
+                problems.push(ImportProblem::Invalid(reason));
+                None
+            }
+        });
+        import.questions.extend(question);
+        import
+            .issues
+            .extend(problems.into_iter().map(|problem| ImportIssue {
+                source: source.to_string(),
+                line: self.line,
+                heading: self.heading.clone(),
+                problem,
+            }));
+    }
+
+    fn mapped(&mut self, id: usize, problems: &mut Vec<ImportProblem>) -> Option<Question> {
+        let question = match after_generic_label(&self.heading) {
+            Some(_) if !self.text.is_empty() => self.text.join("\n"),
+            Some(rest) => rest.to_string(),
+            None => std::iter::once(self.heading.clone())
+                .chain(self.text.iter().cloned())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        if question.trim().is_empty() {
+            problems.push(ImportProblem::NoQuestionText);
+            return None;
+        }
+        if !self.marked {
+            if let Some(last) = self.given.pop() {
+                self.solution.push(last);
+                problems.push(ImportProblem::NoSolutionMarker);
+            }
+        }
+        if self.solution.is_empty() {
+            problems.push(ImportProblem::NoAnswer);
+            return None;
+        }
+        let (manifests, commands): (Vec<_>, Vec<_>) = std::mem::take(&mut self.solution)
+            .into_iter()
+            .partition(|(lang, _)| matches!(lang.as_str(), "yaml" | "yml"));
+        let yaml_answer = commands.is_empty();
+        let answer = if yaml_answer {
+            join_code(&manifests, "---\n")
+        } else {
+            if !manifests.is_empty() {
+                problems.push(ImportProblem::MixedAnswer);
+                self.notes.push(join_code(&manifests, "---\n"));
+            }
+            join_code(&commands, "")
+        };
+        Some(Question {
+            id,
+            question,
+            context: (!self.given.is_empty()).then(|| join_code(&self.given, "")),
+            hints: std::mem::take(&mut self.hints),
+            answer,
+            yaml_answer,
+            time_limit_secs: DEFAULT_TIME_LIMIT_SECS,
+            category: self.category.take(),
+            notes: (!self.notes.is_empty()).then(|| self.notes.join("\n")),
+            ..Default::default()
+        })
+    }
+}
+
+fn join_code(blocks: &[(String, String)], separator: &str) -> String {
+    blocks
+        .iter()
+        .map(|(_, code)| code.trim_end())
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}", separator))
+}
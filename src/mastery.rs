@@ -0,0 +1,95 @@
+/// Where a mastery drill stands between and after attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasteryState {
+    /// An attempt is under way
+    Attempting,
+    /// The last attempt was right, but the streak is short of the target
+    Passed,
+    /// The last attempt was wrong, skipped or ran out of time; the streak is back to 0
+    Failed,
+    /// The target streak was reached; the drill is over
+    Mastered,
+}
+
+/// Practice-until-mastery loop for a single question
+/// Each attempt is recorded once; a wrong one resets the streak of correct
+/// answers, and reaching the target streak ends the drill. The App starts the
+/// next attempt with `next_attempt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MasteryLoop {
+    target: usize,
+    streak: usize,
+    attempts: usize,
+    state: MasteryState,
+}
+
+impl MasteryLoop {
+    /// A drill ending after `target` correct answers in a row (at least 1)
+    pub fn new(target: usize) -> Self {
+        Self {
+            target: target.max(1),
+            streak: 0,
+            attempts: 1,
+            state: MasteryState::Attempting,
+        }
+    }
+
+    /// Records how the current attempt went; once an attempt has an outcome,
+    /// further calls leave the state alone until `next_attempt`
+    pub fn record(&mut self, correct: bool) -> MasteryState {
+        if self.state != MasteryState::Attempting {
+            return self.state;
+        }
+        if correct {
+            self.streak += 1;
+            self.state = if self.streak >= self.target {
+                MasteryState::Mastered
+            } else {
+                MasteryState::Passed
+            };
+        } else {
+            self.streak = 0;
+            self.state = MasteryState::Failed;
+        }
+        tracing::info!(
+            correct,
+            streak = self.streak,
+            target = self.target,
+            "mastery attempt recorded"
+        );
+        self.state
+    }
+
+    /// Starts another attempt after a pass or a failure; false while an
+    /// attempt is under way or once the question is mastered
+    pub fn next_attempt(&mut self) -> bool {
+        if !matches!(self.state, MasteryState::Passed | MasteryState::Failed) {
+            return false;
+        }
+        self.attempts += 1;
+        self.state = MasteryState::Attempting;
+        true
+    }
+
+    pub fn state(&self) -> MasteryState {
+        self.state
+    }
+
+    /// Correct answers in a row so far
+    pub fn streak(&self) -> usize {
+        self.streak
+    }
+
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Attempts so far, the current one included
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    pub fn is_mastered(&self) -> bool {
+        self.state == MasteryState::Mastered
+    }
+}
@@ -1,11 +1,195 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Represents a single quiz question with hints and answer
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Question {
+    /// Number shown and jumped to; may be renumbered when banks are merged
     pub id: usize,
+    /// Stable identity that history and stats are kept under; without one the
+    /// question's text identifies it, see [`Question::identity`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
     pub question: String,
+    /// Material the question refers to, e.g. a manifest to modify; shown verbatim
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Manifests the task starts from, applied into a practice namespace by
+    /// `prep-cluster` or the prep key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
     pub hints: Vec<String>,
     pub answer: String,
+    /// The answer is a manifest, written in an editor and compared as YAML
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub yaml_answer: bool,
+    /// Other submissions that also count as correct; `answer` is still the one revealed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_answers: Option<Vec<String>>,
+    /// Regex a correct submission must match as a whole, for open-ended answers
+    /// ("any pod name"); replaces the text comparison, while `answer` is still revealed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer_pattern: Option<String>,
+    /// Required tokens or phrases, each worth points, for answers best graded
+    /// by what they contain rather than by the whole text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rubric: Option<Vec<RubricItem>>,
+    /// Grades by `rubric` or by comparison with the answer; questions with a
+    /// rubric use it unless this says `answer`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grading: Option<QuestionGrading>,
+    /// Overrides the global case-sensitivity setting for this question's answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_sensitive: Option<bool>,
     pub time_limit_secs: u64,
+    /// What a correct answer is worth, like the exam's weighted tasks; 1 when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    /// Exam domain the question belongs to, used for per-domain breakdowns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Topics used to filter questions, e.g. "pods" or "configuration"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    /// Ids of questions that must be completed first when jumping in guided mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<usize>>,
+    /// How exam-critical the question is; higher runs first with `--by-priority`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// Commands that go with the answer, e.g. inspecting or deleting what it
+    /// creates; listed under the revealed answer on request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related: Option<Vec<String>>,
+    /// Documentation links, listed in the References tab once the answer is revealed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references: Option<Vec<String>>,
+    /// Optional background shown in the Notes tab
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Who wrote the question, credited under it and in the summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Where the question comes from, e.g. a bank name or URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl Question {
+    /// What persisted data keys this question by: its `uid`, or a hash of its
+    /// text, so the same question keeps its history whatever id it ends up with
+    pub fn identity(&self) -> String {
+        match &self.uid {
+            Some(uid) => uid.clone(),
+            None => text_identity(self.id, &self.question),
+        }
+    }
+
+    /// Points a correct answer earns; a rubric-graded question without its
+    /// own `points` is worth its rubric's total
+    pub fn points(&self) -> u32 {
+        self.points.unwrap_or_else(|| match self.grading() {
+            QuestionGrading::Rubric => self.rubric_items().iter().map(|item| item.points).sum(),
+            QuestionGrading::Answer => 1,
+        })
+    }
+
+    /// How submissions are graded, `rubric` by default when there is one
+    pub fn grading(&self) -> QuestionGrading {
+        self.grading.unwrap_or(if self.rubric_items().is_empty() {
+            QuestionGrading::Answer
+        } else {
+            QuestionGrading::Rubric
+        })
+    }
+
+    /// Rubric items, empty when the question has none
+    pub fn rubric_items(&self) -> &[RubricItem] {
+        self.rubric.as_deref().unwrap_or_default()
+    }
+
+    /// Related commands, empty when the question has none
+    pub fn related_commands(&self) -> &[String] {
+        self.related.as_deref().unwrap_or_default()
+    }
+
+    pub fn reference_links(&self) -> &[String] {
+        self.references.as_deref().unwrap_or_default()
+    }
+
+    /// Author and source as one line, e.g. "Jane Doe - k8s exercises #12"
+    pub fn attribution(&self) -> Option<String> {
+        let parts: Vec<&str> = [self.author.as_deref(), self.source.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" - "))
+    }
+}
+
+/// The identity of a question without a `uid`: `text:` and a hash of its
+/// text with whitespace collapsed, or `id:` and its id when it has no text
+pub fn text_identity(id: usize, text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        format!("id:{}", id)
+    } else {
+        format!("text:{:016x}", fnv1a(text.as_bytes()))
+    }
+}
+
+/// 64-bit FNV-1a; unlike `std`'s hasher it stays the same across builds,
+/// so it can be persisted
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A token or phrase a rubric-graded answer should contain, e.g.
+/// `--from-literal` or `create configmap`, and what finding it is worth
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RubricItem {
+    pub item: String,
+    #[serde(default = "RubricItem::default_points")]
+    pub points: u32,
+}
+
+impl RubricItem {
+    fn default_points() -> u32 {
+        1
+    }
+}
+
+/// Per-question choice between the session's answer comparison and the rubric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuestionGrading {
+    /// Compare with `answer` (and the accepted answers or pattern) as the session grades
+    Answer,
+    /// Score by the rubric items found in the submission
+    Rubric,
+}
+
+/// How hard a question is, as rated by the bank's author
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        })
+    }
 }
@@ -0,0 +1,142 @@
+use crate::config::{DuplicateIds, Mode, QuizConfig};
+use crate::models::Question;
+use crate::question_repository::{IdRenumbering, QuestionRepository, RepositoryError};
+use crate::scheduler;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+/// Questions fetched per page, the first one included
+pub const PAGE_SIZE: usize = 100;
+
+/// Pages loaded after the session started, in bank order; the channel closes
+/// after the last page or the first error
+pub type PageReceiver = UnboundedReceiver<Result<Vec<Question>, RepositoryError>>;
+
+/// Whether a session can start on the first page of `repository`
+/// Only for sources that page cheaply, and only when the session takes the
+/// questions in bank order: filters, shuffling, priority order, guided jumps,
+/// drill repeats, adaptive picks, the question of the day and leaving out recent
+/// questions all need the whole bank first.
+pub fn can_start_early(repository: &dyn QuestionRepository, config: &QuizConfig) -> bool {
+    repository.supports_paging()
+        && config.tags.is_empty()
+        && !config.shuffle_questions
+        && !config.sort_by_id
+        && !config.by_priority
+        && !config.priority_only
+        && !config.guided
+        && config.mode != Mode::Drill
+        && config.mode != Mode::Adaptive
+        && config.daily.is_none()
+        && config.fresh.is_none()
+        && config.mastery.is_none()
+}
+
+/// The first page of a session and the pages still to come
+#[derive(Debug)]
+pub struct PagedStart {
+    pub first: Vec<Question>,
+    /// Questions the session will hold, `--limit` applied
+    pub total: usize,
+    pub rest: PageReceiver,
+}
+
+/// Loads the first page of `repository` and keeps loading the rest on a
+/// background task; see [`can_start_early`] for when this applies
+/// Must be called within a tokio runtime.
+pub fn start(
+    repository: Arc<dyn QuestionRepository>,
+    config: &QuizConfig,
+) -> Result<PagedStart, RepositoryError> {
+    let count = repository.count()?;
+    let total = config.limit.map_or(count, |limit| count.min(limit));
+    let mut ids = IdCheck::new(config.duplicate_ids);
+    let first = repository.get_page(0, PAGE_SIZE.min(total))?;
+    if first.is_empty() {
+        return Err(RepositoryError::Empty(
+            "the question source has no questions".to_string(),
+        ));
+    }
+    let first = ids.admit(first, config, repository.as_ref())?;
+    tracing::info!(
+        resident = first.len(),
+        total,
+        "session started on the first page"
+    );
+
+    let (sender, rest) = mpsc::unbounded_channel();
+    let mut offset = first.len();
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || {
+        while offset < total {
+            let len = PAGE_SIZE.min(total - offset);
+            let page = repository
+                .get_page(offset, len)
+                .and_then(|page| ids.admit(page, &config, repository.as_ref()));
+            let loaded = page.as_ref().map_or(0, Vec::len);
+            let failed = page.is_err();
+            if sender.send(page).is_err() || failed || loaded < len {
+                break;
+            }
+            offset += loaded;
+        }
+        tracing::info!(loaded = offset, "background question loading ended");
+    });
+    Ok(PagedStart { first, total, rest })
+}
+
+/// Duplicate id and uid checks across pages, since no page sees the others
+/// A duplicate id is renumbered as a whole load would, above the bank's
+/// highest id, which is only looked up once a duplicate turns up.
+struct IdCheck {
+    policy: DuplicateIds,
+    ids: IdRenumbering,
+    uids: HashSet<String>,
+    max_id: Option<usize>,
+}
+
+impl IdCheck {
+    fn new(policy: DuplicateIds) -> Self {
+        Self {
+            policy,
+            ids: IdRenumbering::default(),
+            uids: HashSet::new(),
+            max_id: None,
+        }
+    }
+
+    /// The page with ids made unique and hints shuffled as configured
+    fn admit(
+        &mut self,
+        mut page: Vec<Question>,
+        config: &QuizConfig,
+        repository: &dyn QuestionRepository,
+    ) -> Result<Vec<Question>, RepositoryError> {
+        for question in &mut page {
+            if let Some(uid) = &question.uid {
+                if !self.uids.insert(uid.clone()) {
+                    return Err(RepositoryError::DuplicateUids(vec![uid.clone()]));
+                }
+            }
+            if self.ids.is_duplicate(question) {
+                if self.policy == DuplicateIds::Error {
+                    return Err(RepositoryError::DuplicateIds(vec![question.id]));
+                }
+                let max_id = match self.max_id {
+                    Some(max_id) => max_id,
+                    None => *self.max_id.insert(repository.max_id()?),
+                };
+                let old = self.ids.renumber(question, max_id);
+                tracing::warn!(old, new = question.id, "duplicate question id renumbered");
+            }
+        }
+        // The ordering options are off (see `can_start_early`), so this only
+        // shuffles hints; the limit was applied when counting
+        let config = QuizConfig {
+            limit: None,
+            ..config.clone()
+        };
+        Ok(scheduler::select_questions(page, &config))
+    }
+}
@@ -0,0 +1,345 @@
+use crate::config::Mode;
+use crate::distractors::ChoiceSet;
+use crate::goal::{self, DailyGoal};
+use crate::grading::Verdict;
+use crate::quiz_state::{Confidence, QuestionStatus, QuizState};
+use crate::results::{format_points, SessionResult};
+use crate::scheduler;
+use crate::session::{Session, SessionAction};
+use crate::stats::Calibration;
+use chrono::{DateTime, TimeZone};
+use std::io::{self, Write};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, Lines};
+use tokio::time::timeout;
+
+/// A whole plain-mode session: the week's goal standing, the questions, then
+/// today's progress toward the goal counting this session. `history` holds the
+/// saved sessions and `now` dates them; the result is left for the caller to save.
+pub async fn run_session<R, W, Tz>(
+    session: &mut Session,
+    input: R,
+    out: &mut W,
+    daily_goal: &DailyGoal,
+    history: &[SessionResult],
+    now: DateTime<Tz>,
+) -> io::Result<SessionResult>
+where
+    R: AsyncBufRead + Unpin,
+    W: Write,
+    Tz: TimeZone,
+{
+    let tz = now.timezone();
+    let today = now.date_naive();
+    if daily_goal.is_set() {
+        writeln!(
+            out,
+            "{}",
+            goal::week_summary(history, daily_goal, today, &tz)
+        )?;
+    }
+    run(session, input, out).await?;
+    if daily_goal.is_set() {
+        let mut progress = goal::daily_progress(history, &tz)
+            .get(&today)
+            .copied()
+            .unwrap_or_default();
+        progress.add_quiz(session.quiz_state());
+        writeln!(out, "{}", daily_goal.progress_text(&progress))?;
+    }
+    Ok(session.result())
+}
+
+/// Line-based quiz front-end for terminals without cursor control
+/// Drives the same Session as the TUI, reading one answer per line.
+/// Returns when the session finishes, the user quits or input ends.
+/// With `choices` configured, command questions list numbered options and a
+/// number picks one; typing a full answer still works.
+pub async fn run<R, W>(session: &mut Session, input: R, out: &mut W) -> io::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: Write,
+{
+    let mut lines = input.lines();
+    let mut rng = scheduler::seeded_rng(session.config().seed);
+    let prompt = if session.config().choices.is_some() {
+        "Type your answer or an option number and press Enter."
+    } else {
+        "Type your answer and press Enter."
+    };
+    writeln!(out, "{} Commands: :h hint, :s skip, :q quit", prompt)?;
+
+    while !session.is_finished() {
+        if session.quiz_state().is_armed() {
+            let quiz_state = session.quiz_state();
+            if let Some(title) = quiz_state.section_title() {
+                writeln!(out, "\n== {} ==", title)?;
+            }
+            write!(
+                out,
+                "\nQuestion {} of {}: press Enter to start the clock. ",
+                quiz_state.current_index() + 1,
+                quiz_state.total_questions()
+            )?;
+            out.flush()?;
+            match lines.next_line().await? {
+                Some(line) if line.trim() == ":q" => return Ok(()),
+                Some(_) => {
+                    let _ = session.apply(SessionAction::Start);
+                }
+                None => return Ok(()),
+            }
+        }
+        print_question(session.quiz_state(), out)?;
+        if session.config().mode == Mode::Study {
+            if !study_question(session, &mut lines, out).await? {
+                return Ok(());
+            }
+            continue;
+        }
+        let choices = session.config().choices.and_then(|size| {
+            ChoiceSet::build(
+                &session.quiz_state().current_question().answer,
+                size,
+                &mut rng,
+            )
+        });
+        if let Some(choices) = &choices {
+            for (index, option) in choices.options.iter().enumerate() {
+                writeln!(out, "  {}) {}", index + 1, option)?;
+            }
+        }
+
+        loop {
+            out.flush()?;
+            let remaining = session.quiz_state().timer().remaining();
+            let line = match timeout(remaining, lines.next_line()).await {
+                Ok(line) => line?,
+                Err(_) => {
+                    writeln!(out, "Time expired.")?;
+                    break;
+                }
+            };
+            let Some(line) = line else {
+                return Ok(());
+            };
+
+            match line.trim() {
+                ":q" => return Ok(()),
+                ":s" => break,
+                ":h" => show_next_hint(session, out)?,
+                "" => {}
+                _ if session.quiz_state().timer().is_expired() => {
+                    writeln!(out, "Time expired before the answer arrived.")?;
+                    break;
+                }
+                answer => {
+                    let answer = choices
+                        .as_ref()
+                        .and_then(|choices| choices.pick(answer))
+                        .unwrap_or(answer)
+                        .to_string();
+                    if session.config().asks_confidence() {
+                        rate_confidence(session, &mut lines, out).await?;
+                    }
+                    let _ = session.apply(SessionAction::Submit(answer));
+                    // --retry-wrong leaves a wrong answer's question open
+                    if !session.quiz_state().is_resolved() {
+                        writeln!(out, "Not quite, try again.")?;
+                        continue;
+                    }
+                    break;
+                }
+            }
+        }
+
+        // Skip settles an unanswered question; Next moves on from a resolved one
+        let index = session.quiz_state().current_index();
+        let explanation = session.verdict().and_then(Verdict::explanation);
+        let diagnosis = session.diagnosis();
+        let breakdown = session.verdict().and_then(Verdict::breakdown).cloned();
+        let rubric = session.verdict().and_then(Verdict::rubric).cloned();
+        let rejection = match session.verdict() {
+            Some(Verdict::Rejected(message)) => Some(message.clone()),
+            _ => None,
+        };
+        let action = if session.quiz_state().is_resolved() {
+            SessionAction::Next
+        } else {
+            SessionAction::Skip
+        };
+        let _ = session.apply(action);
+        print_outcome(session.quiz_state(), index, out)?;
+        if let Some(time_use) = session.quiz_state().time_use(index) {
+            writeln!(out, "{}", time_use)?;
+        }
+        if let Some(explanation) = explanation {
+            writeln!(out, "Accepted as equivalent: {}", explanation)?;
+        }
+        if let Some(tip) = diagnosis {
+            writeln!(out, "Tip: {}", tip)?;
+        }
+        if let Some(message) = rejection {
+            writeln!(out, "Rejected by the API server: {}", message)?;
+        }
+        if let Some(breakdown) = breakdown {
+            writeln!(
+                out,
+                "Partial credit: {} of {} commands",
+                breakdown.matched(),
+                breakdown.commands.len()
+            )?;
+            for command in &breakdown.commands {
+                let mark = if command.matched { "✓" } else { "✗" };
+                writeln!(out, "  {} {}", mark, command.command)?;
+            }
+            for command in &breakdown.extra {
+                writeln!(out, "  + {} (not needed)", command)?;
+            }
+        }
+        if let Some(rubric) = rubric {
+            writeln!(
+                out,
+                "Rubric: {} of {} points",
+                rubric.earned(),
+                rubric.total()
+            )?;
+            for item in &rubric.items {
+                if item.matched {
+                    writeln!(out, "  ✓ {} (+{})", item.item, item.points)?;
+                } else {
+                    writeln!(out, "  ✗ {} (0 of {})", item.item, item.points)?;
+                }
+            }
+        }
+    }
+
+    let quiz_state = session.quiz_state();
+    write!(
+        out,
+        "\nScore: {}/{}",
+        quiz_state.correct_count(),
+        quiz_state.total_questions()
+    )?;
+    if quiz_state.scores_by_points() {
+        write!(
+            out,
+            " ({}/{} points)",
+            format_points(quiz_state.earned_points()),
+            quiz_state.total_points()
+        )?;
+    }
+    writeln!(out)?;
+    let calibration = Calibration::from_quiz(quiz_state);
+    if calibration.rated > 0 {
+        writeln!(
+            out,
+            "Confidence: overconfident on {}, underconfident on {} of {} rated",
+            calibration.overconfident, calibration.underconfident, calibration.rated
+        )?;
+    }
+    Ok(())
+}
+
+/// Asks how sure the user is of the answer just typed, with the clock paused;
+/// anything but h, m or l (or 1-3) leaves the answer unrated
+async fn rate_confidence<R, W>(
+    session: &mut Session,
+    lines: &mut Lines<R>,
+    out: &mut W,
+) -> io::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: Write,
+{
+    write!(out, "How sure are you? [h]igh, [m]edium or [l]ow: ")?;
+    out.flush()?;
+    session.pause_clock();
+    let line = lines.next_line().await?;
+    session.resume_clock();
+    let rating = line.and_then(|line| line.trim().chars().next().and_then(Confidence::from_key));
+    if let Some(confidence) = rating {
+        let _ = session.apply(SessionAction::RateConfidence(confidence));
+    }
+    Ok(())
+}
+
+/// Shows hints and answer at once, then waits for Enter; false when the user quit
+async fn study_question<R, W>(
+    session: &mut Session,
+    lines: &mut Lines<R>,
+    out: &mut W,
+) -> io::Result<bool>
+where
+    R: AsyncBufRead + Unpin,
+    W: Write,
+{
+    let question = session.quiz_state().current_question();
+    for (index, hint) in question.hints.iter().enumerate() {
+        writeln!(out, "Hint {}: {}", index + 1, hint)?;
+    }
+    writeln!(out, "Answer:")?;
+    for line in question.answer.lines() {
+        writeln!(out, "  {}", line)?;
+    }
+    for link in question.reference_links() {
+        writeln!(out, "Reference: {}", link)?;
+    }
+    write!(out, "(Enter for the next question, :q to quit) ")?;
+    out.flush()?;
+    match lines.next_line().await? {
+        Some(line) if line.trim() == ":q" => Ok(false),
+        Some(_) => Ok(session.apply(SessionAction::Next).is_ok()),
+        None => Ok(false),
+    }
+}
+
+fn print_question<W: Write>(quiz_state: &QuizState, out: &mut W) -> io::Result<()> {
+    writeln!(
+        out,
+        "\nQuestion {} of {} ({}s): {}",
+        quiz_state.current_index() + 1,
+        quiz_state.total_questions(),
+        quiz_state.timer().limit().as_secs(),
+        quiz_state.current_question().question
+    )?;
+    if let Some(context) = &quiz_state.current_question().context {
+        for line in context.lines() {
+            writeln!(out, "  | {}", line)?;
+        }
+    }
+    Ok(())
+}
+
+fn show_next_hint<W: Write>(session: &mut Session, out: &mut W) -> io::Result<()> {
+    if session.out_of_hints() {
+        return writeln!(out, "No hints remaining.");
+    }
+    if session.apply(SessionAction::Hint) != Ok(true) {
+        return writeln!(out, "No hints available.");
+    }
+    let index = session.hint_state().hint_index();
+    match session.quiz_state().current_question().hints.get(index) {
+        Some(hint) => writeln!(out, "Hint {}: {}", index + 1, hint),
+        None => writeln!(out, "No hints available."),
+    }
+}
+
+/// Prints the settled outcome of the question at `index`
+fn print_outcome<W: Write>(quiz_state: &QuizState, index: usize, out: &mut W) -> io::Result<()> {
+    let label = match quiz_state.status(index) {
+        QuestionStatus::Pass => "PASS",
+        QuestionStatus::Fail => "FAIL",
+        QuestionStatus::Skipped => "SKIP",
+        QuestionStatus::Revealed => "REVEALED",
+        QuestionStatus::Pending => "PENDING",
+    };
+    let question = &quiz_state.questions()[index];
+    writeln!(out, "[{}] Answer:", label)?;
+    for line in question.answer.lines() {
+        writeln!(out, "  {}", line)?;
+    }
+    for link in question.reference_links() {
+        writeln!(out, "Reference: {}", link)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use crate::models::Question;
+use crate::question_repository::{QuestionRepository, RepositoryError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A study plan as data: question uids in a fixed order, in titled sections
+/// Uids are matched against [`Question::identity`], so questions from several
+/// packs can be mixed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Playlist {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub sections: Vec<PlaylistSection>,
+    /// The file it was loaded from
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// A group of questions, introduced by its title when the session reaches it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlaylistSection {
+    #[serde(default)]
+    pub title: Option<String>,
+    pub questions: Vec<PlaylistEntry>,
+}
+
+/// A question to run: its uid alone, or with its own time limit
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawEntry")]
+pub struct PlaylistEntry {
+    pub uid: String,
+    /// Replaces the question's own limit for this playlist
+    pub time_limit_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEntry {
+    Uid(String),
+    Entry {
+        uid: String,
+        #[serde(default)]
+        time_limit_secs: Option<u64>,
+    },
+}
+
+impl From<RawEntry> for PlaylistEntry {
+    fn from(raw: RawEntry) -> Self {
+        match raw {
+            RawEntry::Uid(uid) => Self {
+                uid,
+                time_limit_secs: None,
+            },
+            RawEntry::Entry {
+                uid,
+                time_limit_secs,
+            } => Self {
+                uid,
+                time_limit_secs,
+            },
+        }
+    }
+}
+
+/// Why a playlist could not be loaded or run
+#[derive(Debug)]
+pub enum PlaylistError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    /// No section lists a question
+    Empty(PathBuf),
+    /// Uids listed more than once, in playlist order
+    Repeated {
+        path: PathBuf,
+        uids: Vec<String>,
+    },
+    /// Uids no question in the bank has, in playlist order
+    UnknownUids {
+        path: PathBuf,
+        uids: Vec<String>,
+    },
+}
+
+impl fmt::Display for PlaylistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaylistError::Io { path, source } => {
+                write!(f, "failed to read playlist {}: {}", path.display(), source)
+            }
+            PlaylistError::Parse { path, message } => {
+                write!(f, "invalid playlist {}: {}", path.display(), message)
+            }
+            PlaylistError::Empty(path) => {
+                write!(f, "playlist {} lists no questions", path.display())
+            }
+            PlaylistError::Repeated { path, uids } => write!(
+                f,
+                "playlist {} lists {} more than once",
+                path.display(),
+                uids.join(", ")
+            ),
+            PlaylistError::UnknownUids { path, uids } => write!(
+                f,
+                "playlist {} refers to questions the bank doesn't have: {}",
+                path.display(),
+                uids.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlaylistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PlaylistError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a YAML playlist; every listed uid must be unique
+pub fn load(path: &Path) -> Result<Playlist, PlaylistError> {
+    let contents = fs::read_to_string(path).map_err(|source| PlaylistError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut playlist = parse(&contents).map_err(|message| PlaylistError::Parse {
+        path: path.to_path_buf(),
+        message,
+    })?;
+    playlist.path = path.to_path_buf();
+    if playlist.entries().next().is_none() {
+        return Err(PlaylistError::Empty(playlist.path));
+    }
+    let mut seen = Vec::new();
+    let mut repeated = Vec::new();
+    for entry in playlist.entries() {
+        if seen.contains(&&entry.uid) {
+            if !repeated.contains(&entry.uid) {
+                repeated.push(entry.uid.clone());
+            }
+        } else {
+            seen.push(&entry.uid);
+        }
+    }
+    if !repeated.is_empty() {
+        return Err(PlaylistError::Repeated {
+            path: playlist.path,
+            uids: repeated,
+        });
+    }
+    Ok(playlist)
+}
+
+/// The playlist in `contents`, without the checks `load` makes
+pub fn parse(contents: &str) -> Result<Playlist, String> {
+    serde_yaml::from_str(contents).map_err(|err| err.to_string())
+}
+
+impl Playlist {
+    /// Every entry, section after section
+    pub fn entries(&self) -> impl Iterator<Item = &PlaylistEntry> {
+        self.sections
+            .iter()
+            .flat_map(|section| section.questions.iter())
+    }
+
+    /// The playlist's questions from `bank`, in playlist order with its time
+    /// limits applied; unknown uids are an error listing all of them
+    pub fn resolve(&self, bank: &[Question]) -> Result<Vec<Question>, PlaylistError> {
+        let mut unknown = Vec::new();
+        let questions: Vec<Question> = self
+            .entries()
+            .filter_map(|entry| {
+                let Some(question) = bank
+                    .iter()
+                    .find(|question| question.identity() == entry.uid)
+                else {
+                    unknown.push(entry.uid.clone());
+                    return None;
+                };
+                let mut question = question.clone();
+                if let Some(secs) = entry.time_limit_secs {
+                    question.time_limit_secs = secs;
+                }
+                Some(question)
+            })
+            .collect();
+        if unknown.is_empty() {
+            Ok(questions)
+        } else {
+            Err(PlaylistError::UnknownUids {
+                path: self.path.clone(),
+                uids: unknown,
+            })
+        }
+    }
+
+    /// Section titles by the session index of the section's first question;
+    /// untitled and empty sections have none
+    pub fn section_starts(&self) -> BTreeMap<usize, String> {
+        let mut starts = BTreeMap::new();
+        let mut index = 0;
+        for section in &self.sections {
+            if let (Some(title), false) = (&section.title, section.questions.is_empty()) {
+                starts.insert(index, title.clone());
+            }
+            index += section.questions.len();
+        }
+        starts
+    }
+}
+
+/// A bank's questions as a playlist picks and orders them
+pub struct PlaylistRepository {
+    bank: Box<dyn QuestionRepository>,
+    playlist: Playlist,
+}
+
+impl PlaylistRepository {
+    pub fn new(bank: Box<dyn QuestionRepository>, playlist: Playlist) -> Self {
+        Self { bank, playlist }
+    }
+}
+
+impl QuestionRepository for PlaylistRepository {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        let bank = self.bank.get_questions()?;
+        self.playlist
+            .resolve(&bank)
+            .map_err(RepositoryError::Playlist)
+    }
+
+    fn source_description(&self) -> String {
+        let name = match &self.playlist.name {
+            Some(name) => name.clone(),
+            None => self.playlist.path.display().to_string(),
+        };
+        format!("{} from {}", name, self.bank.source_description())
+    }
+
+    fn skipped_sources(&self) -> Vec<String> {
+        self.bank.skipped_sources()
+    }
+}
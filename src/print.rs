@@ -0,0 +1,122 @@
+use crate::models::Question;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Output format for `--print`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PrintFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// What the non-interactive front-end prints
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrintOptions {
+    pub format: PrintFormat,
+    /// Include hints and answers, not just the questions
+    pub answers: bool,
+}
+
+/// A question as printed; hints and answers are left out unless asked for
+#[derive(Serialize)]
+struct PrintedQuestion<'a> {
+    id: usize,
+    question: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<&'a str>,
+    tags: &'a [String],
+    time_limit_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hints: Option<&'a [String]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    references: &'a [String],
+}
+
+/// Writes the selected questions without touching the terminal
+/// Used by `--print` and whenever stdout is not a TTY
+pub fn write_questions<W: Write>(
+    out: &mut W,
+    questions: &[Question],
+    options: PrintOptions,
+) -> io::Result<()> {
+    match options.format {
+        PrintFormat::Json => {
+            let printed: Vec<PrintedQuestion> = questions
+                .iter()
+                .map(|question| PrintedQuestion {
+                    id: question.id,
+                    question: &question.question,
+                    context: question.context.as_deref(),
+                    category: question.category.as_deref(),
+                    tags: &question.tags,
+                    time_limit_secs: question.time_limit_secs,
+                    hints: options.answers.then_some(question.hints.as_slice()),
+                    answer: options.answers.then_some(question.answer.as_str()),
+                    references: if options.answers {
+                        question.reference_links()
+                    } else {
+                        &[]
+                    },
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut *out, &printed)?;
+            writeln!(out)
+        }
+        PrintFormat::Text => {
+            for (position, question) in questions.iter().enumerate() {
+                if position > 0 {
+                    writeln!(out)?;
+                }
+                write_text(out, position + 1, question, options.answers)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_text<W: Write>(
+    out: &mut W,
+    number: usize,
+    question: &Question,
+    answers: bool,
+) -> io::Result<()> {
+    writeln!(out, "{}. {}", number, question.question)?;
+    let mut details = vec![format!("id {}", question.id)];
+    if let Some(category) = &question.category {
+        details.push(category.clone());
+    }
+    details.push(format!("{}s", question.time_limit_secs));
+    if !question.tags.is_empty() {
+        details.push(format!("tags: {}", question.tags.join(", ")));
+    }
+    writeln!(out, "   ({})", details.join(" | "))?;
+    if let Some(context) = &question.context {
+        writeln!(out, "   Context:")?;
+        for line in context.lines() {
+            writeln!(out, "     {}", line)?;
+        }
+    }
+
+    if answers {
+        if !question.hints.is_empty() {
+            writeln!(out, "   Hints:")?;
+            for hint in &question.hints {
+                writeln!(out, "     - {}", hint)?;
+            }
+        }
+        writeln!(out, "   Answer:")?;
+        for line in question.answer.lines() {
+            writeln!(out, "     {}", line)?;
+        }
+        for link in question.reference_links() {
+            writeln!(out, "   Reference: {}", link)?;
+        }
+    }
+    Ok(())
+}
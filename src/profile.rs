@@ -0,0 +1,105 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Longest accepted profile name
+const MAX_NAME_LEN: usize = 64;
+
+/// A validated profile name, safe to use as a single directory name
+/// Only ASCII letters, digits, `-` and `_` are allowed, so a name can never
+/// point outside `<data_dir>/profiles/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileName(String);
+
+/// Why a profile name was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    Empty,
+    TooLong(String),
+    InvalidCharacter { name: String, character: char },
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Empty => write!(f, "profile name is empty"),
+            ProfileError::TooLong(name) => write!(
+                f,
+                "profile name `{}` is longer than {} characters",
+                name, MAX_NAME_LEN
+            ),
+            ProfileError::InvalidCharacter { name, character } => write!(
+                f,
+                "profile name `{}` contains {:?}; use letters, digits, '-' and '_'",
+                name, character
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl ProfileName {
+    pub fn parse(name: &str) -> Result<Self, ProfileError> {
+        if name.is_empty() {
+            return Err(ProfileError::Empty);
+        }
+        if name.chars().count() > MAX_NAME_LEN {
+            return Err(ProfileError::TooLong(name.to_string()));
+        }
+        if let Some(character) = name
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+        {
+            return Err(ProfileError::InvalidCharacter {
+                name: name.to_string(),
+                character,
+            });
+        }
+        Ok(Self(name.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ProfileName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// `<data_dir>/profiles`
+pub fn profiles_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("profiles")
+}
+
+/// Where everything the profile saves lives: `<data_dir>/profiles/<name>`
+pub fn profile_dir(data_dir: &Path, profile: &ProfileName) -> PathBuf {
+    profiles_dir(data_dir).join(profile.as_str())
+}
+
+/// Names of the existing profiles, sorted; directories with invalid names are ignored
+pub fn list(data_dir: &Path) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(profiles_dir(data_dir)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            if ProfileName::parse(name).is_ok() {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
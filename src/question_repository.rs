@@ -1,27 +1,280 @@
+use crate::config::DuplicateIds;
+use crate::grading;
 use crate::models::Question;
+use crate::playlist::PlaylistError;
+use crate::scheduler;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 /// Abstraction for loading questions (Open/Closed Principle & Dependency Inversion)
 /// This trait allows extending with new implementations without modifying existing code
-pub trait QuestionRepository {
-    fn get_questions(&self) -> Vec<Question>;
+/// Sources are `Send + Sync` so very large ones can be paged in on a background task.
+pub trait QuestionRepository: Send + Sync {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError>;
+
+    /// Where the questions come from, e.g. "built-in" or a file path, for the header
+    fn source_description(&self) -> String;
+
+    /// True when `count` and `get_page` are much cheaper than `get_questions`,
+    /// e.g. for a database; a session in bank order then starts on the first
+    /// page while the rest loads (see [`crate::paging`])
+    fn supports_paging(&self) -> bool {
+        false
+    }
+
+    /// How many questions the source holds
+    fn count(&self) -> Result<usize, RepositoryError> {
+        Ok(self.get_questions()?.len())
+    }
+
+    /// Up to `len` questions starting at `offset`, in bank order; fewer at the end
+    fn get_page(&self, offset: usize, len: usize) -> Result<Vec<Question>, RepositoryError> {
+        Ok(self
+            .get_questions()?
+            .into_iter()
+            .skip(offset)
+            .take(len)
+            .collect())
+    }
+
+    /// The highest question id; a paged load only asks for it when it meets a
+    /// duplicate id, to renumber it the way a whole load would
+    fn max_id(&self) -> Result<usize, RepositoryError> {
+        Ok(self
+            .get_questions()?
+            .iter()
+            .map(|question| question.id)
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// Sources the last `get_questions` left out because they didn't parse,
+    /// as messages for the caller to show; only lenient composites skip any
+    fn skipped_sources(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The question with this [`Question::identity`], if the source has it
+    fn get_by_uid(&self, uid: &str) -> Result<Option<Question>, RepositoryError> {
+        Ok(self
+            .get_questions()?
+            .into_iter()
+            .find(|question| question.identity() == uid))
+    }
+
+    /// Exactly the questions with these [`Question::identity`]s, in this
+    /// order and repeated as often as listed, e.g. to resume a saved session;
+    /// their ids are the ones a session sees, with duplicates renumbered.
+    /// Identities the source doesn't have are an error naming all of them.
+    fn get_questions_by_uids(&self, uids: &[String]) -> Result<Vec<Question>, RepositoryError> {
+        let mut bank = self.get_questions()?;
+        scheduler::check_ids(&mut bank, DuplicateIds::Renumber)?;
+        let mut missing: Vec<String> = Vec::new();
+        let questions = uids
+            .iter()
+            .filter_map(|uid| {
+                let found = bank
+                    .iter()
+                    .find(|question| question.identity() == *uid)
+                    .cloned();
+                if found.is_none() && !missing.contains(uid) {
+                    missing.push(uid.clone());
+                }
+                found
+            })
+            .collect();
+        if missing.is_empty() {
+            Ok(questions)
+        } else {
+            Err(RepositoryError::MissingUids(missing))
+        }
+    }
+}
+
+/// Errors raised while loading questions from a source
+#[derive(Debug)]
+pub enum RepositoryError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    Empty(String),
+    /// The filters left nothing of the `loaded` questions; `filters` describes them
+    NoMatch {
+        loaded: usize,
+        filters: Vec<String>,
+    },
+    /// Ids used by more than one question, ascending
+    DuplicateIds(Vec<usize>),
+    /// Uids used by more than one question, sorted; unlike ids they are never renumbered
+    DuplicateUids(Vec<String>),
+    /// Requested identities the source doesn't have, in the order asked for
+    MissingUids(Vec<String>),
+    Playlist(PlaylistError),
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::Io { path, source } => {
+                write!(f, "failed to read {}: {}", path.display(), source)
+            }
+            RepositoryError::Parse { path, message } => {
+                write!(f, "failed to parse {}: {}", path.display(), message)
+            }
+            RepositoryError::Empty(reason) => write!(f, "no questions to practice: {}", reason),
+            RepositoryError::NoMatch { loaded, filters } => write!(
+                f,
+                "no questions match your filter ({}); {} question{} loaded, try a broader filter",
+                filters.join("; "),
+                loaded,
+                if *loaded == 1 { " was" } else { "s were" }
+            ),
+            RepositoryError::DuplicateIds(ids) => {
+                let ids: Vec<String> = ids.iter().map(usize::to_string).collect();
+                write!(
+                    f,
+                    "more than one question uses id {}; give each question its own id \
+                     or set duplicate_ids = \"renumber\" under [defaults]",
+                    ids.join(", ")
+                )
+            }
+            RepositoryError::DuplicateUids(uids) => write!(
+                f,
+                "more than one question uses uid {}; a uid must stay with one question, \
+                 since your history is kept under it",
+                uids.join(", ")
+            ),
+            RepositoryError::MissingUids(uids) => {
+                write!(f, "no question with uid {} in the source", uids.join(", "))
+            }
+            RepositoryError::Playlist(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RepositoryError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Ids shared by more than one question, ascending
+pub fn duplicate_ids(questions: &[Question]) -> Vec<usize> {
+    let mut ids: Vec<usize> = questions.iter().map(|question| question.id).collect();
+    ids.sort_unstable();
+    let mut duplicates: Vec<usize> = ids
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1])
+        .map(|pair| pair[0])
+        .collect();
+    duplicates.dedup();
+    duplicates
+}
+
+/// Uids shared by more than one question, sorted
+pub fn duplicate_uids(questions: &[Question]) -> Vec<String> {
+    let mut uids: Vec<&str> = questions
+        .iter()
+        .filter_map(|question| question.uid.as_deref())
+        .collect();
+    uids.sort_unstable();
+    let mut duplicates: Vec<String> = uids
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1])
+        .map(|pair| pair[0].to_string())
+        .collect();
+    duplicates.dedup();
+    duplicates
+}
+
+/// Gives every question after the first with a given id a new id above the
+/// highest one loaded; returns `(old, new)` for each change, in load order
+pub fn renumber_duplicates(questions: &mut [Question]) -> Vec<(usize, usize)> {
+    let max_id = questions
+        .iter()
+        .map(|question| question.id)
+        .max()
+        .unwrap_or(0);
+    let mut ids = IdRenumbering::default();
+    let mut renumbered = Vec::new();
+    for question in questions.iter_mut() {
+        if ids.is_duplicate(question) {
+            let old = ids.renumber(question, max_id);
+            renumbered.push((old, question.id));
+        }
+    }
+    renumbered
+}
+
+/// Duplicate id renumbering as questions arrive in load order, shared by
+/// [`renumber_duplicates`] and paged loads so both give the same ids
+#[derive(Debug, Default)]
+pub struct IdRenumbering {
+    seen: HashSet<usize>,
+    last: Option<usize>,
+}
+
+impl IdRenumbering {
+    /// Records `question`'s id; true if an earlier question already has it
+    pub fn is_duplicate(&mut self, question: &Question) -> bool {
+        !self.seen.insert(question.id)
+    }
+
+    /// Moves a duplicate to the next id above `max_id`, the bank's highest,
+    /// and returns its old id
+    pub fn renumber(&mut self, question: &mut Question, max_id: usize) -> usize {
+        let id = self.last.unwrap_or(max_id) + 1;
+        self.last = Some(id);
+        self.seen.insert(id);
+        std::mem::replace(&mut question.id, id)
+    }
 }
 
 /// In-memory implementation of QuestionRepository with hardcoded CKAD questions
 pub struct InMemoryQuestionRepository;
 
 impl QuestionRepository for InMemoryQuestionRepository {
-    fn get_questions(&self) -> Vec<Question> {
-        vec![
+    fn source_description(&self) -> String {
+        "built-in".to_string()
+    }
+
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        Ok(vec![
             Question {
                 id: 1,
                 question: "Create a Pod named 'nginx' using the nginx:1.14 image in the default namespace.".to_string(),
                 hints: vec![
                     "Use: kubectl run <pod-name> --image=<image>".to_string(),
                     "Full command: kubectl run nginx --image=nginx:1.14".to_string(),
-                    "Reference: https://kubernetes.io/docs/reference/kubectl/generated/kubectl-run/".to_string(),
                 ],
+                references: Some(vec!["https://kubernetes.io/docs/reference/kubectl/generated/kubectl-run/".to_string()]),
                 answer: "kubectl run nginx --image=nginx:1.14".to_string(),
+                accepted_answers: Some(vec![
+                    "kubectl run nginx --image=nginx:1.14 --restart=Never".to_string(),
+                ]),
+                related: Some(vec![
+                    "kubectl get pods".to_string(),
+                    "kubectl describe pod nginx".to_string(),
+                    "kubectl delete pod nginx".to_string(),
+                ]),
                 time_limit_secs: 60,
+                category: Some("Design and Build".to_string()),
+                tags: vec!["pods".to_string()],
+                author: Some("CKAD Practitioner".to_string()),
+                source: Some("https://kubernetes.io/docs/concepts/workloads/pods/".to_string()),
+                ..Default::default()
             },
             Question {
                 id: 2,
@@ -33,6 +286,9 @@ impl QuestionRepository for InMemoryQuestionRepository {
                 ],
                 answer: "kubectl create deployment web --image=httpd:2.4 --replicas=3\nkubectl expose deployment web --port=80 --type=ClusterIP".to_string(),
                 time_limit_secs: 120,
+                category: Some("Deployment".to_string()),
+                tags: vec!["deployments".to_string(), "services".to_string()],
+                ..Default::default()
             },
             Question {
                 id: 3,
@@ -40,10 +296,15 @@ impl QuestionRepository for InMemoryQuestionRepository {
                 hints: vec![
                     "Use resources.requests and resources.limits in the pod spec".to_string(),
                     "Memory is specified in Mi, CPU in m (millicores)".to_string(),
-                    "Reference: https://kubernetes.io/docs/concepts/configuration/manage-resources-containers/".to_string(),
                 ],
+                references: Some(vec!["https://kubernetes.io/docs/concepts/configuration/manage-resources-containers/".to_string()]),
                 answer: "resources:\n  requests:\n    memory: \"256Mi\"\n    cpu: \"100m\"\n  limits:\n    memory: \"512Mi\"\n    cpu: \"200m\"".to_string(),
+                yaml_answer: true,
                 time_limit_secs: 90,
+                category: Some("Configuration".to_string()),
+                tags: vec!["pods".to_string(), "resources".to_string()],
+                notes: Some("Requests are what the scheduler reserves on a node; limits are enforced at runtime. A container exceeding its memory limit is OOM-killed, while CPU above the limit is throttled.".to_string()),
+                ..Default::default()
             },
             Question {
                 id: 4,
@@ -51,10 +312,16 @@ impl QuestionRepository for InMemoryQuestionRepository {
                 hints: vec![
                     "Use: kubectl create configmap <name> --from-literal=<key>=<value>".to_string(),
                     "Full command: kubectl create configmap app-config --from-literal=database.url=postgres://db:5432".to_string(),
-                    "Reference: https://kubernetes.io/docs/concepts/configuration/configmap/".to_string(),
                 ],
+                references: Some(vec!["https://kubernetes.io/docs/concepts/configuration/configmap/".to_string()]),
                 answer: "kubectl create configmap app-config --from-literal=database.url=postgres://db:5432".to_string(),
+                accepted_answers: Some(vec![
+                    "kubectl create cm app-config --from-literal=database.url=postgres://db:5432".to_string(),
+                ]),
                 time_limit_secs: 60,
+                category: Some("Configuration".to_string()),
+                tags: vec!["configuration".to_string(), "configmaps".to_string()],
+                ..Default::default()
             },
             Question {
                 id: 5,
@@ -62,35 +329,514 @@ impl QuestionRepository for InMemoryQuestionRepository {
                 hints: vec![
                     "Use: kubectl create secret generic <name> --from-literal=<key>=<value>".to_string(),
                     "Full command: kubectl create secret generic db-secret --from-literal=username=admin --from-literal=password=secret123".to_string(),
-                    "Reference: https://kubernetes.io/docs/concepts/configuration/secret/".to_string(),
                 ],
+                references: Some(vec!["https://kubernetes.io/docs/concepts/configuration/secret/".to_string()]),
                 answer: "kubectl create secret generic db-secret --from-literal=username=admin --from-literal=password=secret123".to_string(),
                 time_limit_secs: 75,
+                category: Some("Configuration".to_string()),
+                tags: vec!["configuration".to_string(), "secrets".to_string()],
+                ..Default::default()
+            },
+            Question {
+                id: 6,
+                question: "Given this manifest in pod.yaml, add resource limits of 500m CPU and 256Mi memory to the container, then create the Pod.".to_string(),
+                context: Some(
+                    "apiVersion: v1\nkind: Pod\nmetadata:\n  name: limited\nspec:\n  containers:\n  - name: app\n    image: nginx:1.25\n".to_string(),
+                ),
+                hints: vec![
+                    "Limits go under spec.containers[].resources.limits".to_string(),
+                    "Add:\n    resources:\n      limits:\n        cpu: 500m\n        memory: 256Mi".to_string(),
+                ],
+                references: Some(vec!["https://kubernetes.io/docs/concepts/configuration/manage-resources-containers/".to_string()]),
+                answer: "Add under the container:\n    resources:\n      limits:\n        cpu: 500m\n        memory: 256Mi\nThen: kubectl apply -f pod.yaml".to_string(),
+                time_limit_secs: 120,
+                category: Some("Configuration".to_string()),
+                tags: vec!["pods".to_string(), "resources".to_string()],
+                ..Default::default()
+            },
+            Question {
+                id: 7,
+                question: "Write a Pod manifest for a Pod named web in the namespace shop, running nginx:1.25 and exposing container port 80.".to_string(),
+                hints: vec![
+                    "Start from: kubectl run web --image=nginx:1.25 --port=80 -n shop --dry-run=client -o yaml".to_string(),
+                    "The namespace goes in metadata; the port under spec.containers[].ports[].containerPort".to_string(),
+                ],
+                references: Some(vec!["https://kubernetes.io/docs/concepts/workloads/pods/".to_string()]),
+                answer: "apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\n  namespace: shop\nspec:\n  containers:\n  - name: web\n    image: nginx:1.25\n    ports:\n    - containerPort: 80".to_string(),
+                yaml_answer: true,
+                time_limit_secs: 180,
+                category: Some("Design and Build".to_string()),
+                tags: vec!["pods".to_string(), "manifests".to_string()],
+                ..Default::default()
             },
-        ]
+        ])
     }
 }
 
-/// Example: File-based implementation (extensible without modifying existing code)
+/// File-based implementation reading a JSON array of questions
 /// This demonstrates the Open/Closed Principle - we can add new implementations
 /// without modifying the QuestionRepository trait or InMemoryQuestionRepository
-#[allow(dead_code)]
+/// A `.jsonl` file, one question per line, is paged: counting it reads lines
+/// without parsing them, and a page parses only its own lines. A page that
+/// starts where the previous one ended reads on from there, so loading the
+/// pages in order reads the file once.
 pub struct FileQuestionRepository {
     file_path: String,
+    cursor: Mutex<Option<LineCursor>>,
 }
 
-#[allow(dead_code)]
 impl FileQuestionRepository {
     pub fn new(file_path: String) -> Self {
-        Self { file_path }
+        Self {
+            file_path,
+            cursor: Mutex::new(None),
+        }
+    }
+
+    fn path(&self) -> &Path {
+        Path::new(&self.file_path)
+    }
+
+    fn is_json_lines(&self) -> bool {
+        QuestionFormat::from_path(self.path()) == Some(QuestionFormat::JsonLines)
+    }
+
+    fn io_error(&self, source: io::Error) -> RepositoryError {
+        RepositoryError::Io {
+            path: self.path().to_path_buf(),
+            source,
+        }
+    }
+
+    /// The cursor after the previous page if this one starts there, otherwise
+    /// the file reopened and read up to `offset`
+    fn cursor_at(&self, offset: usize) -> Result<LineCursor, RepositoryError> {
+        match self.cursor.lock().unwrap().take() {
+            Some(cursor) if cursor.offset == offset => Ok(cursor),
+            _ => {
+                let mut cursor = LineCursor::open(self.path()).map_err(|err| self.io_error(err))?;
+                while cursor.offset < offset {
+                    if cursor
+                        .next_line()
+                        .map_err(|err| self.io_error(err))?
+                        .is_none()
+                    {
+                        break;
+                    }
+                }
+                Ok(cursor)
+            }
+        }
+    }
+}
+
+/// An open JSON Lines file and how many questions have been read from it
+struct LineCursor {
+    reader: io::BufReader<fs::File>,
+    offset: usize,
+    line_number: usize,
+}
+
+impl LineCursor {
+    fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            reader: io::BufReader::new(fs::File::open(path)?),
+            offset: 0,
+            line_number: 0,
+        })
+    }
+
+    /// The next non-blank line with its 1-based line number, like
+    /// [`question_lines`]; `None` at the end of the file
+    fn next_line(&mut self) -> io::Result<Option<(usize, String)>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+            if !line.trim().is_empty() {
+                self.offset += 1;
+                return Ok(Some((self.line_number, line)));
+            }
+        }
     }
 }
 
-#[allow(dead_code)]
 impl QuestionRepository for FileQuestionRepository {
-    fn get_questions(&self) -> Vec<Question> {
-        // Future implementation: load from JSON/YAML file
-        // This shows how the system is open for extension
-        vec![]
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        load_question_file(self.path())
+    }
+
+    fn source_description(&self) -> String {
+        self.file_path.clone()
+    }
+
+    fn supports_paging(&self) -> bool {
+        self.is_json_lines()
+    }
+
+    fn count(&self) -> Result<usize, RepositoryError> {
+        if !self.is_json_lines() {
+            return Ok(self.get_questions()?.len());
+        }
+        let mut cursor = LineCursor::open(self.path()).map_err(|err| self.io_error(err))?;
+        while cursor
+            .next_line()
+            .map_err(|err| self.io_error(err))?
+            .is_some()
+        {}
+        Ok(cursor.offset)
+    }
+
+    fn get_page(&self, offset: usize, len: usize) -> Result<Vec<Question>, RepositoryError> {
+        if !self.is_json_lines() {
+            return Ok(self
+                .get_questions()?
+                .into_iter()
+                .skip(offset)
+                .take(len)
+                .collect());
+        }
+        let mut cursor = self.cursor_at(offset)?;
+        let mut lines = Vec::new();
+        while lines.len() < len {
+            match cursor.next_line().map_err(|err| self.io_error(err))? {
+                Some(line) => lines.push(line),
+                None => break,
+            }
+        }
+        *self.cursor.lock().unwrap() = Some(cursor);
+        let lines = lines.iter().map(|(number, line)| (*number, line.as_str()));
+        let questions = parse_json_lines(lines).map_err(|message| RepositoryError::Parse {
+            path: self.path().to_path_buf(),
+            message,
+        })?;
+        check_questions(questions, self.path())
+    }
+
+    /// A JSON Lines file is read a line at a time, parsing only the ids
+    fn max_id(&self) -> Result<usize, RepositoryError> {
+        if !self.is_json_lines() {
+            return Ok(self
+                .get_questions()?
+                .iter()
+                .map(|question| question.id)
+                .max()
+                .unwrap_or(0));
+        }
+        #[derive(serde::Deserialize)]
+        struct Id {
+            id: usize,
+        }
+        let mut cursor = LineCursor::open(self.path()).map_err(|err| self.io_error(err))?;
+        let mut max_id = 0;
+        while let Some((number, line)) = cursor.next_line().map_err(|err| self.io_error(err))? {
+            let parsed: Id = serde_json::from_str(&line).map_err(|err| RepositoryError::Parse {
+                path: self.path().to_path_buf(),
+                message: format!("line {}: {}", number, err),
+            })?;
+            max_id = max_id.max(parsed.id);
+        }
+        Ok(max_id)
+    }
+
+    /// A JSON Lines file is read a line at a time up to the question
+    fn get_by_uid(&self, uid: &str) -> Result<Option<Question>, RepositoryError> {
+        if !self.is_json_lines() {
+            return Ok(self
+                .get_questions()?
+                .into_iter()
+                .find(|question| question.identity() == uid));
+        }
+        let mut cursor = LineCursor::open(self.path()).map_err(|err| self.io_error(err))?;
+        while let Some((number, line)) = cursor.next_line().map_err(|err| self.io_error(err))? {
+            let question =
+                parse_json_lines(std::iter::once((number, line.as_str()))).map_err(|message| {
+                    RepositoryError::Parse {
+                        path: self.path().to_path_buf(),
+                        message,
+                    }
+                })?;
+            if question[0].identity() == uid {
+                return Ok(check_questions(question, self.path())?.pop());
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Merges several repositories in order
+/// In lenient mode a source that fails to parse is skipped and reported
+/// through [`QuestionRepository::skipped_sources`] instead of failing the
+/// whole load; read errors always fail.
+pub struct CompositeQuestionRepository {
+    sources: Vec<Box<dyn QuestionRepository>>,
+    skip_invalid: bool,
+    skipped: Mutex<Vec<String>>,
+}
+
+impl CompositeQuestionRepository {
+    pub fn new(sources: Vec<Box<dyn QuestionRepository>>) -> Self {
+        Self {
+            sources,
+            skip_invalid: false,
+            skipped: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Skips sources that don't parse as question files
+    pub fn skipping_invalid(mut self) -> Self {
+        self.skip_invalid = true;
+        self
+    }
+}
+
+impl QuestionRepository for CompositeQuestionRepository {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        let mut questions = Vec::new();
+        let mut skipped = Vec::new();
+        for source in &self.sources {
+            match source.get_questions() {
+                Ok(loaded) => questions.extend(loaded),
+                Err(err @ RepositoryError::Parse { .. }) if self.skip_invalid => {
+                    tracing::warn!(error = %err, "skipping question source");
+                    skipped.push(err.to_string());
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        *self.skipped.lock().unwrap() = skipped;
+        Ok(questions)
+    }
+
+    fn skipped_sources(&self) -> Vec<String> {
+        self.skipped.lock().unwrap().clone()
+    }
+
+    /// The sources' descriptions joined with commas
+    fn source_description(&self) -> String {
+        self.sources
+            .iter()
+            .map(|source| source.source_description())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Loads every `.json`, `.jsonl`, `.yaml` and `.yml` file in a directory, in sorted filename order
+/// Files that aren't question lists are skipped and reported through
+/// [`QuestionRepository::skipped_sources`]
+pub struct DirectoryQuestionRepository {
+    dir_path: PathBuf,
+    skipped: Mutex<Vec<String>>,
+}
+
+impl DirectoryQuestionRepository {
+    pub fn new(dir_path: PathBuf) -> Self {
+        Self {
+            dir_path,
+            skipped: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl QuestionRepository for DirectoryQuestionRepository {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        let io_error = |source| RepositoryError::Io {
+            path: self.dir_path.clone(),
+            source,
+        };
+        let mut files = fs::read_dir(&self.dir_path)
+            .map_err(io_error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(io_error)?;
+        files.retain(|path| path.is_file() && QuestionFormat::from_path(path).is_some());
+        files.sort();
+
+        let sources = files
+            .into_iter()
+            .map(|file| {
+                Box::new(FileQuestionRepository::new(
+                    file.to_string_lossy().into_owned(),
+                )) as Box<dyn QuestionRepository>
+            })
+            .collect();
+        let composite = CompositeQuestionRepository::new(sources).skipping_invalid();
+        let questions = composite.get_questions()?;
+        *self.skipped.lock().unwrap() = composite.skipped_sources();
+        Ok(questions)
+    }
+
+    fn skipped_sources(&self) -> Vec<String> {
+        self.skipped.lock().unwrap().clone()
+    }
+
+    /// The directory with a trailing separator, e.g. "banks/"
+    fn source_description(&self) -> String {
+        let dir = self.dir_path.display().to_string();
+        if dir.ends_with(std::path::MAIN_SEPARATOR) {
+            dir
+        } else {
+            format!("{}{}", dir, std::path::MAIN_SEPARATOR)
+        }
+    }
+}
+
+/// The archive compiled in with the `bundled` feature
+#[cfg(feature = "bundled")]
+pub const BUNDLED_QUESTIONS: &str = include_str!("../questions/bundled.json");
+
+/// A JSON question list embedded in the binary at compile time. With the
+/// `bundled` feature, [`BundledQuestionRepository::new`] serves
+/// questions/bundled.json and replaces the built-in questions as the default
+/// source; `--file` and `--dir` still take precedence.
+pub struct BundledQuestionRepository {
+    contents: &'static str,
+}
+
+impl BundledQuestionRepository {
+    #[cfg(feature = "bundled")]
+    pub fn new() -> Self {
+        Self::from_json(BUNDLED_QUESTIONS)
+    }
+
+    /// Any embedded JSON, e.g. `include_str!` of another bank
+    pub fn from_json(contents: &'static str) -> Self {
+        Self { contents }
+    }
+}
+
+#[cfg(feature = "bundled")]
+impl Default for BundledQuestionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuestionRepository for BundledQuestionRepository {
+    fn source_description(&self) -> String {
+        "bundled".to_string()
+    }
+
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        parse_questions(
+            self.contents,
+            QuestionFormat::Json,
+            Path::new("questions/bundled.json"),
+        )
+    }
+}
+
+/// Question file formats, picked by extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionFormat {
+    Json,
+    /// JSON Lines: one question object per line, blank lines ignored
+    JsonLines,
+    Yaml,
+}
+
+impl QuestionFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(QuestionFormat::Json),
+            "jsonl" => Some(QuestionFormat::JsonLines),
+            "yaml" | "yml" => Some(QuestionFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+fn read_question_file(path: &Path) -> Result<String, RepositoryError> {
+    fs::read_to_string(path).map_err(|source| RepositoryError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads a question list; YAML or JSON Lines by extension, JSON otherwise
+fn load_question_file(path: &Path) -> Result<Vec<Question>, RepositoryError> {
+    let contents = read_question_file(path)?;
+    let format = QuestionFormat::from_path(path).unwrap_or(QuestionFormat::Json);
+    parse_questions(&contents, format, path)
+}
+
+/// Parses and checks a question list; `path` only names it in errors
+fn parse_questions(
+    contents: &str,
+    format: QuestionFormat,
+    path: &Path,
+) -> Result<Vec<Question>, RepositoryError> {
+    let parsed = match format {
+        QuestionFormat::Yaml => serde_yaml::from_str(contents).map_err(|err| err.to_string()),
+        QuestionFormat::Json => serde_json::from_str(contents).map_err(|err| err.to_string()),
+        QuestionFormat::JsonLines => parse_json_lines(question_lines(contents)),
+    };
+    let questions: Vec<Question> = parsed.map_err(|message| RepositoryError::Parse {
+        path: path.to_path_buf(),
+        message,
+    })?;
+    check_questions(questions, path)
+}
+
+/// The non-blank lines of a JSON Lines file with their 1-based line numbers
+pub fn question_lines(contents: &str) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, line))
+}
+
+fn parse_json_lines<'a>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Question>, String> {
+    lines
+        .map(|(number, line)| {
+            serde_json::from_str(line).map_err(|err| format!("line {}: {}", number, err))
+        })
+        .collect()
+}
+
+/// The answer pattern and rubric checks every loaded question goes through
+fn check_questions(
+    questions: Vec<Question>,
+    path: &Path,
+) -> Result<Vec<Question>, RepositoryError> {
+    check_answer_patterns(&questions).map_err(|message| RepositoryError::Parse {
+        path: path.to_path_buf(),
+        message,
+    })?;
+    check_rubrics(&questions).map_err(|message| RepositoryError::Parse {
+        path: path.to_path_buf(),
+        message,
+    })?;
+    Ok(questions)
+}
+
+/// Fails on the first question graded by a rubric that can't score anything
+pub fn check_rubrics(questions: &[Question]) -> Result<(), String> {
+    for question in questions {
+        grading::check_rubric(question)
+            .map_err(|err| format!("question {} has an invalid rubric: {}", question.id, err))?;
+    }
+    Ok(())
+}
+
+/// Fails on the first question whose `answer_pattern` is not a valid regex
+pub fn check_answer_patterns(questions: &[Question]) -> Result<(), String> {
+    for question in questions {
+        if let Some(pattern) = &question.answer_pattern {
+            grading::compile_pattern(pattern, true).map_err(|err| {
+                format!(
+                    "question {} has an invalid answer_pattern: {}",
+                    question.id, err
+                )
+            })?;
+        }
     }
+    Ok(())
 }
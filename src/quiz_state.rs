@@ -1,5 +1,125 @@
+use crate::config::{Mode, QuizConfig};
 use crate::models::Question;
-use crate::timer::Timer;
+use crate::scorer::normalize;
+use crate::timer::{Clock, TimeUse, Timer, TokioClock};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Outcome of a single question, tracked per question for the summary screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuestionStatus {
+    Pending,
+    Pass,
+    Fail,
+    Skipped,
+    Revealed,
+}
+
+/// How sure the user was of an answer, rated before seeing whether it was right
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    High,
+    Medium,
+    Low,
+}
+
+impl Confidence {
+    /// `h`, `m` or `l` (or `1` to `3`, high first), the keys of the rating prompt
+    pub fn from_key(key: char) -> Option<Self> {
+        match key.to_ascii_lowercase() {
+            'h' | '1' => Some(Confidence::High),
+            'm' | '2' => Some(Confidence::Medium),
+            'l' | '3' => Some(Confidence::Low),
+            _ => None,
+        }
+    }
+}
+
+/// What happened on one question of the session
+#[derive(Debug, Clone)]
+pub struct QuestionRecord {
+    pub status: QuestionStatus,
+    pub submission: Option<String>,
+    /// Every answer submitted, in order, the last one included; kept across
+    /// retries of the question
+    pub attempts: Vec<String>,
+    pub time_spent: Option<Duration>,
+    pub hints_used: usize,
+    /// The hint on screen when the question was left, shown again on coming
+    /// back to it; `None` while its hints are hidden
+    pub hint_index: Option<usize>,
+    /// Indices of the hints shown at some point, sorted, without duplicates
+    pub hints_viewed: Vec<usize>,
+    /// Share of a failed multi-command answer's commands, or of a failed
+    /// rubric answer's points, that were right
+    pub partial_credit: Option<f64>,
+    /// Rated before the verdict, when the confidence prompt is on
+    pub confidence: Option<Confidence>,
+}
+
+impl QuestionRecord {
+    fn new() -> Self {
+        Self {
+            status: QuestionStatus::Pending,
+            submission: None,
+            attempts: Vec::new(),
+            time_spent: None,
+            hints_used: 0,
+            hint_index: None,
+            hints_viewed: Vec::new(),
+            partial_credit: None,
+            confidence: None,
+        }
+    }
+
+    /// Share of the question's points earned: all of them for a pass, the
+    /// partial credit of a failed answer, otherwise none
+    pub fn credit(&self) -> f64 {
+        match self.status {
+            QuestionStatus::Pass => 1.0,
+            QuestionStatus::Fail => self.partial_credit.unwrap_or(0.0),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Why a jump to another question was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JumpError {
+    UnknownId(usize),
+    /// Guided mode: these prerequisite ids are not completed yet
+    Blocked {
+        id: usize,
+        pending: Vec<usize>,
+    },
+}
+
+impl fmt::Display for JumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JumpError::UnknownId(id) => write!(f, "no question with id {}", id),
+            JumpError::Blocked { id, pending } => {
+                let pending: Vec<String> = pending.iter().map(|id| format!("#{}", id)).collect();
+                write!(
+                    f,
+                    "question #{} needs {} completed first",
+                    id,
+                    pending.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for JumpError {}
 
 /// Manages the core quiz domain logic (Single Responsibility & Interface Segregation)
 /// This is separated from UI concerns
@@ -8,16 +128,89 @@ pub struct QuizState {
     questions: Vec<Question>,
     current_index: usize,
     timer: Timer,
+    records: Vec<QuestionRecord>,
+    guided: bool,
+    /// Study mode: every question is resolved from the start and the clock is ignored
+    study: bool,
+    /// New questions wait for `start_timer` before their clock runs
+    ready_gate: bool,
+    /// The current question is waiting for the start key, its clock held
+    armed: bool,
+    /// Playlist section titles by the index of their first question; the
+    /// session holds there like the ready gate does
+    sections: BTreeMap<usize, String>,
+    /// Questions the session will hold once every page has loaded; equal to
+    /// the resident ones unless the bank is paged in (see `crate::paging`)
+    expected_total: usize,
+    /// Break time the current question's clock ran through (see
+    /// `QuizConfig::strict_breaks`), left out of its time spent
+    break_time: Duration,
+    /// Runs from the session's start, paused with the question clock but not
+    /// held by the start key; see [`QuizState::session_elapsed`]
+    session_timer: Timer,
+    /// Break time the session clock ran through
+    session_break_time: Duration,
 }
 
 impl QuizState {
-    pub fn new(questions: Vec<Question>) -> Self {
+    pub fn new(questions: Vec<Question>, config: &QuizConfig) -> Self {
         let timer = Timer::new(questions[0].time_limit_secs);
-        Self {
+        let session_timer = Timer::with_clock(0, timer.clock());
+        let records = vec![QuestionRecord::new(); questions.len()];
+        let study = config.mode == Mode::Study;
+        let mut state = Self {
             questions,
             current_index: 0,
             timer,
+            records,
+            guided: config.guided,
+            study,
+            ready_gate: config.ready_gate && !study,
+            armed: false,
+            sections: if study {
+                BTreeMap::new()
+            } else {
+                config.sections.clone()
+            },
+            expected_total: 0,
+            break_time: Duration::ZERO,
+            session_timer,
+            session_break_time: Duration::ZERO,
+        };
+        state.expected_total = state.questions.len();
+        state.arm();
+        state
+    }
+
+    /// Picks a session up at `current_index` with the given outcomes so far,
+    /// the current question's clock already at `elapsed`; the session clock
+    /// starts at the time spent so far
+    pub fn restore(
+        questions: Vec<Question>,
+        records: Vec<QuestionRecord>,
+        current_index: usize,
+        elapsed: Duration,
+        config: &QuizConfig,
+    ) -> Self {
+        let mut state = Self::new(questions, config);
+        for (slot, record) in state.records.iter_mut().zip(records) {
+            *slot = record;
         }
+        state.current_index = current_index.min(state.questions.len() - 1);
+        state.timer = Timer::with_clock(
+            state.current_question().time_limit_secs,
+            state.timer.clock(),
+        )
+        .backdated(elapsed);
+        let spent: Duration = state
+            .records
+            .iter()
+            .filter_map(|record| record.time_spent)
+            .sum();
+        state.session_timer = Timer::with_clock(0, state.timer.clock()).backdated(spent + elapsed);
+        state.armed = false;
+        state.arm();
+        state
     }
 
     pub fn current_question(&self) -> &Question {
@@ -28,43 +221,473 @@ impl QuizState {
         self.current_index
     }
 
+    /// Questions in the session, counting any not loaded yet
     pub fn total_questions(&self) -> usize {
-        self.questions.len()
+        self.expected_total
+    }
+
+    /// Announces that the session will grow to `total` questions as pages load
+    pub fn expect_total(&mut self, total: usize) {
+        self.expected_total = total.max(self.questions.len());
+    }
+
+    /// Adds questions that finished loading after the session started
+    pub fn append_questions(&mut self, questions: Vec<Question>) {
+        self.records
+            .extend(std::iter::repeat_with(QuestionRecord::new).take(questions.len()));
+        self.questions.extend(questions);
+        self.expected_total = self.expected_total.max(self.questions.len());
+    }
+
+    /// Ends the session with the questions loaded so far, e.g. after a page failed
+    pub fn finish_loading(&mut self) {
+        self.expected_total = self.questions.len();
+    }
+
+    /// Some questions are still to be loaded
+    pub fn is_loading(&self) -> bool {
+        self.questions.len() < self.expected_total
+    }
+
+    /// The next question is due but its page hasn't arrived
+    pub fn is_waiting_for_next(&self) -> bool {
+        self.is_loading() && self.current_index + 1 >= self.questions.len()
+    }
+
+    pub fn questions(&self) -> &[Question] {
+        &self.questions
+    }
+
+    pub fn records(&self) -> &[QuestionRecord] {
+        &self.records
     }
 
     pub fn timer(&self) -> &Timer {
         &self.timer
     }
 
+    pub fn status(&self, index: usize) -> QuestionStatus {
+        self.records[index].status
+    }
+
+    pub fn current_status(&self) -> QuestionStatus {
+        self.records[self.current_index].status
+    }
+
+    pub fn current_submission(&self) -> Option<&str> {
+        self.records[self.current_index].submission.as_deref()
+    }
+
+    /// A question is resolved once it has been answered or its time has run out
+    /// (in study mode the answer is on screen from the start)
+    pub fn is_resolved(&self) -> bool {
+        self.study || self.current_status() != QuestionStatus::Pending || self.timer.is_expired()
+    }
+
+    pub fn correct_count(&self) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.status == QuestionStatus::Pass)
+            .count()
+    }
+
+    /// Points earned, including partial credit for multi-command answers
+    pub fn earned_points(&self) -> f64 {
+        self.questions
+            .iter()
+            .zip(&self.records)
+            .map(|(question, record)| question.points() as f64 * record.credit())
+            .sum()
+    }
+
+    /// Points the session's questions are worth together
+    pub fn total_points(&self) -> u32 {
+        self.questions.iter().map(Question::points).sum()
+    }
+
+    /// True when some question is worth other than 1 point, so points and
+    /// correct answers can differ
+    pub fn is_weighted(&self) -> bool {
+        self.questions.iter().any(|question| question.points() != 1)
+    }
+
+    /// True when points tell more than the correct count: weighted questions
+    /// or partial credit
+    pub fn scores_by_points(&self) -> bool {
+        self.is_weighted()
+            || self
+                .records
+                .iter()
+                .any(|record| record.credit() > 0.0 && record.credit() < 1.0)
+    }
+
     pub fn is_last_question(&self) -> bool {
-        self.current_index >= self.questions.len() - 1
+        self.current_index >= self.total_questions() - 1
+    }
+
+    /// Adds time to the current question while it is still open
+    pub fn extend_time(&mut self, secs: u64) {
+        if !self.is_resolved() {
+            self.timer.extend(secs);
+        }
+    }
+
+    /// Starts the current question's clock again from its full limit
+    pub fn restart_timer(&mut self) {
+        let limit = self.current_question().time_limit_secs;
+        tracing::info!(index = self.current_index, "timer restarted");
+        self.timer.reset(limit);
+        self.break_time = Duration::ZERO;
+    }
+
+    /// Leaves a break the current question's clock ran through out of its time spent
+    pub fn exclude_break(&mut self, duration: Duration) {
+        self.break_time += duration;
+        self.session_break_time += duration;
+    }
+
+    /// Time since the session started, less the time its clock was paused
+    /// and any break it ran through
+    pub fn session_elapsed(&self) -> Duration {
+        self.session_timer
+            .elapsed()
+            .saturating_sub(self.session_break_time)
+    }
+
+    /// Questions with an outcome, i.e. answered, skipped or revealed
+    pub fn completed_count(&self) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.status != QuestionStatus::Pending)
+            .count()
+    }
+
+    /// Time on the current question's clock, less any break it ran through
+    fn time_on_question(&self) -> Duration {
+        self.timer.elapsed().saturating_sub(self.break_time)
+    }
+
+    /// Times questions with `clock` from now on, keeping the current question's
+    /// elapsed time; e.g. a `MockClock` in tests
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        let timer =
+            Timer::with_clock(self.timer.limit().as_secs(), clock).backdated(self.timer.elapsed());
+        self.timer = timer;
+        let paused = self.session_timer.is_paused();
+        self.session_timer =
+            Timer::with_clock(0, self.timer.clock()).backdated(self.session_timer.elapsed());
+        if paused {
+            self.session_timer.pause();
+        }
+        if self.armed {
+            self.timer.pause();
+        }
+    }
+
+    pub fn pause_timer(&mut self) {
+        self.timer.pause();
+        self.session_timer.pause();
+    }
+
+    /// Restarts a paused clock; one waiting for the start key stays held, though
+    /// the session clock runs on
+    pub fn resume_timer(&mut self) {
+        self.session_timer.resume();
+        if !self.armed {
+            self.timer.resume();
+        }
+    }
+
+    /// Whether the current question is waiting for the start key
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    /// The title of the playlist section the current question opens, while
+    /// it waits for the start key
+    pub fn section_title(&self) -> Option<&str> {
+        if !self.armed {
+            return None;
+        }
+        self.sections.get(&self.current_index).map(String::as_str)
+    }
+
+    /// Starts the clock of a question waiting for the start key; false if
+    /// it wasn't waiting
+    pub fn start_timer(&mut self) -> bool {
+        if !self.armed {
+            return false;
+        }
+        self.armed = false;
+        self.timer.resume();
+        tracing::info!(index = self.current_index, "timer started");
+        true
+    }
+
+    /// With the ready gate on, or at the start of a playlist section, holds an
+    /// unresolved question's clock until `start_timer`
+    fn arm(&mut self) {
+        let gated = self.ready_gate || self.sections.contains_key(&self.current_index);
+        if gated && !self.is_resolved() {
+            self.armed = true;
+            self.timer.pause();
+        }
+    }
+
+    /// Records a graded submission for the current question
+    pub fn submit_answer(&mut self, answer: String, correct: bool) {
+        if self.is_resolved() {
+            return;
+        }
+        let elapsed = self.time_on_question();
+        let record = &mut self.records[self.current_index];
+        record.status = if correct {
+            QuestionStatus::Pass
+        } else {
+            QuestionStatus::Fail
+        };
+        record.attempts.push(answer.clone());
+        record.submission = Some(answer);
+        record.time_spent = Some(elapsed);
+        tracing::info!(
+            index = self.current_index,
+            correct,
+            elapsed_secs = elapsed.as_secs(),
+            "answer submitted"
+        );
+    }
+
+    /// Keeps a wrong answer among the current question's attempts without
+    /// resolving it, so it can be answered again
+    pub fn record_attempt(&mut self, answer: String) {
+        if self.is_resolved() {
+            return;
+        }
+        let record = &mut self.records[self.current_index];
+        record.attempts.push(answer);
+        tracing::info!(
+            index = self.current_index,
+            attempts = record.attempts.len(),
+            "wrong answer, question left open"
+        );
+    }
+
+    /// Distinct hints revealed over the whole quiz, counting each question's at most once
+    pub fn hints_spent(&self) -> usize {
+        self.records
+            .iter()
+            .zip(&self.questions)
+            .map(|(record, question)| record.hints_used.min(question.hints.len()))
+            .sum()
+    }
+
+    /// Hints left out of `budget`; `None` when hints are unlimited
+    pub fn hints_remaining(&self, budget: Option<usize>) -> Option<usize> {
+        budget.map(|budget| budget.saturating_sub(self.hints_spent()))
+    }
+
+    /// Records how sure the user is of the answer about to be graded
+    pub fn rate_confidence(&mut self, confidence: Confidence) {
+        self.records[self.current_index].confidence = Some(confidence);
+    }
+
+    /// Credits a failed answer with the share of its commands or rubric points
+    /// that were right
+    pub fn award_partial_credit(&mut self, fraction: f64) {
+        let record = &mut self.records[self.current_index];
+        if record.status == QuestionStatus::Fail && fraction > 0.0 {
+            record.partial_credit = Some(fraction);
+        }
+    }
+
+    /// Mirrors how many hints are on screen for the current question
+    pub fn record_hints_used(&mut self, count: usize) {
+        let record = &mut self.records[self.current_index];
+        record.hints_used = record.hints_used.max(count);
+    }
+
+    /// Remembers which hint of the current question is on screen, `None` for
+    /// hidden, and that it was viewed
+    pub fn record_hint_index(&mut self, index: Option<usize>) {
+        let record = &mut self.records[self.current_index];
+        record.hint_index = index;
+        if let Some(index) = index {
+            if let Err(position) = record.hints_viewed.binary_search(&index) {
+                record.hints_viewed.insert(position, index);
+            }
+        }
+    }
+
+    /// How the time spent on the question at `index` compares with its limit,
+    /// once it is resolved; `None` before that and in study mode
+    pub fn time_use(&self, index: usize) -> Option<TimeUse> {
+        if self.study {
+            return None;
+        }
+        let spent = match self.records[index].time_spent {
+            Some(spent) => spent,
+            None if index == self.current_index && self.is_resolved() => self.timer.elapsed(),
+            None => return None,
+        };
+        let limit = Duration::from_secs(self.questions[index].time_limit_secs);
+        Some(TimeUse::classify(spent, limit))
+    }
+
+    /// Settles the status of the current question when leaving it without a submission
+    pub fn finish_current(&mut self) {
+        let expired = self.study || self.timer.is_expired();
+        let time_spent = self.time_on_question().min(self.timer.limit());
+        let record = &mut self.records[self.current_index];
+        if record.status == QuestionStatus::Pending {
+            record.status = if expired {
+                QuestionStatus::Revealed
+            } else {
+                QuestionStatus::Skipped
+            };
+            record.time_spent = Some(time_spent);
+        }
+    }
+
+    /// Opens the current question again for another attempt: a fresh record,
+    /// its full time and, behind the ready gate, a held clock
+    pub fn retry_current(&mut self) {
+        let attempts = std::mem::take(&mut self.records[self.current_index].attempts);
+        self.records[self.current_index] = QuestionRecord {
+            attempts,
+            ..QuestionRecord::new()
+        };
+        self.restart_timer();
+        self.arm();
+    }
+
+    /// Moves the not yet reached question at `index` up to be asked next
+    pub fn queue_next(&mut self, index: usize) {
+        let next = self.current_index + 1;
+        if index <= next || index >= self.questions.len() {
+            return;
+        }
+        let question = self.questions.remove(index);
+        self.questions.insert(next, question);
+        let record = self.records.remove(index);
+        self.records.insert(next, record);
+    }
+
+    /// Appends another attempt at the current question to the end of the session
+    pub fn requeue_current(&mut self) {
+        let question = self.current_question().clone();
+        self.questions.push(question);
+        self.records.push(QuestionRecord::new());
+        self.expected_total += 1;
+    }
+
+    /// Prerequisites of the question at `index` that are in this session but not completed
+    /// A prerequisite counts as completed once an attempt at it passed or had its
+    /// answer revealed; skipping it, even by jumping away, doesn't count
+    pub fn pending_prerequisites(&self, index: usize) -> Vec<usize> {
+        let Some(depends_on) = &self.questions[index].depends_on else {
+            return Vec::new();
+        };
+        depends_on
+            .iter()
+            .copied()
+            .filter(|&id| {
+                let mut attempts = self
+                    .questions
+                    .iter()
+                    .zip(&self.records)
+                    .filter(|(question, _)| question.id == id)
+                    .peekable();
+                attempts.peek().is_some()
+                    && !attempts.any(|(_, record)| {
+                        matches!(
+                            record.status,
+                            QuestionStatus::Pass | QuestionStatus::Revealed
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Moves to the question with the given id, settling the current one first
+    /// In guided mode the jump is refused while the target has pending prerequisites
+    pub fn jump_to_id(&mut self, id: usize) -> Result<(), JumpError> {
+        let index = self
+            .questions
+            .iter()
+            .position(|question| question.id == id)
+            .ok_or(JumpError::UnknownId(id))?;
+        if self.guided {
+            let pending = self.pending_prerequisites(index);
+            if !pending.is_empty() {
+                tracing::info!(id, ?pending, "jump blocked by prerequisites");
+                return Err(JumpError::Blocked { id, pending });
+            }
+        }
+        if index != self.current_index {
+            self.finish_current();
+            tracing::info!(from = self.current_index, to = index, "jumped to question");
+            self.current_index = index;
+            let new_limit = self.questions[index].time_limit_secs;
+            self.timer.reset(new_limit);
+            self.break_time = Duration::ZERO;
+            self.arm();
+        }
+        Ok(())
     }
 
     pub fn next_question(&mut self) {
-        if !self.is_last_question() {
+        if !self.is_last_question() && !self.is_waiting_for_next() {
+            self.finish_current();
+            tracing::info!(
+                from = self.current_index,
+                to = self.current_index + 1,
+                status = ?self.current_status(),
+                "question advanced"
+            );
             self.current_index += 1;
             let new_limit = self.questions[self.current_index].time_limit_secs;
             self.timer.reset(new_limit);
+            self.break_time = Duration::ZERO;
+            self.arm();
         }
     }
 }
 
+/// Shuffles a question's hints in place, keeping any hint that spells out the answer last
+/// An empty answer gives nothing away, so every hint is shuffled.
+pub fn shuffle_hints<R: Rng>(question: &mut Question, rng: &mut R) {
+    let answer = normalize(&question.answer);
+    let (mut giveaways, mut hints): (Vec<String>, Vec<String>) = question
+        .hints
+        .drain(..)
+        .partition(|hint| !answer.is_empty() && normalize(hint).contains(&answer));
+    hints.shuffle(rng);
+    hints.append(&mut giveaways);
+    question.hints = hints;
+}
+
 /// Manages UI-specific state (Interface Segregation Principle)
 /// Separated from domain logic to follow ISP
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HintState {
+    enabled: bool,
     show_hints: bool,
     hint_index: usize,
 }
 
 impl HintState {
-    pub fn new() -> Self {
+    pub fn new(enabled: bool) -> Self {
         Self {
+            enabled,
             show_hints: false,
             hint_index: 0,
         }
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
     pub fn show_hints(&self) -> bool {
         self.show_hints
     }
@@ -73,18 +696,350 @@ impl HintState {
         self.hint_index
     }
 
+    /// Number of hints currently revealed
+    pub fn hints_viewed(&self) -> usize {
+        if self.show_hints {
+            self.hint_index + 1
+        } else {
+            0
+        }
+    }
+
     pub fn enable_hints(&mut self) {
         self.show_hints = true;
     }
 
+    /// Shows hint `index` directly, e.g. the one left on screen earlier
+    pub fn show_hint(&mut self, index: usize) {
+        self.show_hints = true;
+        self.hint_index = index;
+    }
+
     pub fn next_hint(&mut self, max_hints: usize) {
         if self.hint_index < max_hints.saturating_sub(1) {
             self.hint_index += 1;
         }
     }
 
+    /// Like `next_hint`, but wraps back to the first hint after the last one
+    pub fn cycle_hint(&mut self, max_hints: usize) {
+        if max_hints > 0 {
+            self.hint_index = (self.hint_index + 1) % max_hints;
+        }
+    }
+
     pub fn reset(&mut self) {
         self.show_hints = false;
         self.hint_index = 0;
     }
 }
+
+/// Typewriter reveal of the answer after the timer expires
+/// The event loop advances `progress`; rendering only shows that many characters.
+/// Reads the time from a `Clock`, like the question timer.
+#[derive(Debug)]
+pub struct RevealAnimation {
+    clock: Arc<dyn Clock>,
+    started: Option<Instant>,
+    progress: usize,
+    total: usize,
+}
+
+impl RevealAnimation {
+    /// How long a full reveal takes, whatever the answer length
+    pub const DURATION: Duration = Duration::from_secs(1);
+
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(TokioClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            started: None,
+            progress: 0,
+            total: 0,
+        }
+    }
+
+    pub fn start(&mut self, total_chars: usize) {
+        self.started = Some(self.clock.now());
+        self.progress = 0;
+        self.total = total_chars;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some() && self.progress < self.total
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn advance(&mut self) {
+        if let Some(started) = self.started {
+            let elapsed = self.clock.now().saturating_duration_since(started);
+            self.progress = revealed_chars(self.total, elapsed, Self::DURATION);
+        }
+    }
+
+    /// Shows the whole answer at once
+    pub fn skip(&mut self) {
+        self.progress = self.total;
+    }
+
+    /// Characters to show; `None` when no animation is active
+    pub fn visible_chars(&self) -> Option<usize> {
+        self.is_running().then_some(self.progress)
+    }
+
+    pub fn reset(&mut self) {
+        self.started = None;
+        self.progress = 0;
+        self.total = 0;
+    }
+}
+
+impl Default for RevealAnimation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Number of characters revealed `elapsed` into an animation of length `duration`
+pub fn revealed_chars(total: usize, elapsed: Duration, duration: Duration) -> usize {
+    if duration.is_zero() || elapsed >= duration {
+        return total;
+    }
+    (total as u128 * elapsed.as_millis() / duration.as_millis()) as usize
+}
+
+/// Manages the typed answer buffer
+/// Kept apart from QuizState, which only sees the final submission.
+/// Typing and Backspace work at the cursor, which Left and Right move.
+#[derive(Debug)]
+pub struct AnswerInput {
+    active: bool,
+    buffer: String,
+    /// Position between characters, 0 to the buffer's length in chars
+    cursor: usize,
+}
+
+impl AnswerInput {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            buffer: String::new(),
+            cursor: 0,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Characters before the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The buffer up to the cursor, e.g. for placing the terminal cursor
+    pub fn before_cursor(&self) -> &str {
+        &self.buffer[..self.byte_index(self.cursor)]
+    }
+
+    pub fn open(&mut self) {
+        self.active = true;
+    }
+
+    /// Inserts `c` at the cursor and moves past it
+    pub fn push(&mut self, c: char) {
+        let index = self.byte_index(self.cursor);
+        self.buffer.insert(index, c);
+        self.cursor += 1;
+    }
+
+    /// Deletes the character before the cursor, if any
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let index = self.byte_index(self.cursor);
+            self.buffer.remove(index);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.buffer.chars().count());
+    }
+
+    /// Closes the input and hands back whatever was typed
+    pub fn take(&mut self) -> String {
+        self.active = false;
+        self.cursor = 0;
+        std::mem::take(&mut self.buffer)
+    }
+
+    pub fn reset(&mut self) {
+        self.active = false;
+        self.buffer.clear();
+        self.cursor = 0;
+    }
+
+    fn byte_index(&self, chars: usize) -> usize {
+        self.buffer
+            .char_indices()
+            .nth(chars)
+            .map_or(self.buffer.len(), |(index, _)| index)
+    }
+}
+
+impl Default for AnswerInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tabs available in the content pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentTab {
+    Hints,
+    Answer,
+    References,
+    Notes,
+}
+
+impl ContentTab {
+    pub const ALL: [ContentTab; 4] = [
+        ContentTab::Hints,
+        ContentTab::Answer,
+        ContentTab::References,
+        ContentTab::Notes,
+    ];
+
+    pub fn index(self) -> usize {
+        match self {
+            ContentTab::Hints => 0,
+            ContentTab::Answer => 1,
+            ContentTab::References => 2,
+            ContentTab::Notes => 3,
+        }
+    }
+
+    /// Tabs kept locked until the answer is revealed; references can give it away
+    pub fn locks_with_answer(self) -> bool {
+        matches!(self, ContentTab::Answer | ContentTab::References)
+    }
+
+    /// Message catalog key for the tab's title
+    pub fn title_key(self) -> &'static str {
+        match self {
+            ContentTab::Hints => "tab_hints",
+            ContentTab::Answer => "tab_answer",
+            ContentTab::References => "tab_references",
+            ContentTab::Notes => "tab_notes",
+        }
+    }
+}
+
+/// Manages the tabbed content pane
+/// Tracks the active tab, a scroll offset per tab and whether the answer is locked
+#[derive(Debug)]
+pub struct ContentTabState {
+    active: ContentTab,
+    scroll: [u16; ContentTab::ALL.len()],
+    answer_locked: bool,
+    /// Related commands are listed under the answer; kept from question to question
+    show_related: bool,
+}
+
+impl ContentTabState {
+    pub fn new() -> Self {
+        Self {
+            active: ContentTab::Hints,
+            scroll: [0; ContentTab::ALL.len()],
+            answer_locked: true,
+            show_related: false,
+        }
+    }
+
+    pub fn active(&self) -> ContentTab {
+        self.active
+    }
+
+    pub fn scroll(&self, tab: ContentTab) -> u16 {
+        self.scroll[tab.index()]
+    }
+
+    pub fn answer_locked(&self) -> bool {
+        self.answer_locked
+    }
+
+    pub fn show_related(&self) -> bool {
+        self.show_related
+    }
+
+    pub fn toggle_related(&mut self) {
+        self.show_related = !self.show_related;
+    }
+
+    pub fn select(&mut self, tab: ContentTab) {
+        self.active = tab;
+    }
+
+    pub fn select_index(&mut self, index: usize) {
+        if let Some(tab) = ContentTab::ALL.get(index) {
+            self.active = *tab;
+        }
+    }
+
+    pub fn next_tab(&mut self) {
+        let next = (self.active.index() + 1) % ContentTab::ALL.len();
+        self.active = ContentTab::ALL[next];
+    }
+
+    pub fn previous_tab(&mut self) {
+        let count = ContentTab::ALL.len();
+        let previous = (self.active.index() + count - 1) % count;
+        self.active = ContentTab::ALL[previous];
+    }
+
+    pub fn scroll_down(&mut self) {
+        let offset = &mut self.scroll[self.active.index()];
+        *offset = offset.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        let offset = &mut self.scroll[self.active.index()];
+        *offset = offset.saturating_sub(1);
+    }
+
+    /// Keeps the lock in step with the question; unlocking brings the answer to the front
+    pub fn sync_answer_lock(&mut self, revealed: bool) {
+        if self.answer_locked && revealed {
+            self.answer_locked = false;
+            self.active = ContentTab::Answer;
+        } else if !revealed {
+            self.answer_locked = true;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.active = ContentTab::Hints;
+        self.scroll = [0; ContentTab::ALL.len()];
+        self.answer_locked = true;
+    }
+}
+
+impl Default for ContentTabState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
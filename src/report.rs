@@ -0,0 +1,199 @@
+use crate::quiz_state::QuestionStatus;
+use crate::results::{format_points, QuestionResult, SessionResult};
+use clap::ValueEnum;
+use std::fmt::Write;
+
+/// Human-readable report formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown with a summary table, domain breakdown and missed questions
+    Md,
+    /// One row per question for spreadsheets
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Md => "md",
+            ReportFormat::Csv => "csv",
+        }
+    }
+
+    pub fn render(self, result: &SessionResult) -> String {
+        match self {
+            ReportFormat::Md => to_markdown(result),
+            ReportFormat::Csv => to_csv(result),
+        }
+    }
+}
+
+/// Renders a session as Markdown for a study journal
+pub fn to_markdown(result: &SessionResult) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# CKAD practice session {}\n",
+        result.started_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    let _ = write!(
+        out,
+        "Mode: {} | Complete: {}",
+        mode_label(result),
+        if result.complete { "yes" } else { "no" }
+    );
+    // Results saved before the session clock existed have no elapsed time
+    if result.elapsed_secs > 0 {
+        let _ = write!(out, " | Elapsed: {}", format_secs(result.elapsed_secs));
+    }
+    out.push_str("\n\n");
+
+    out.push_str("## Summary\n\n");
+    out.push_str("| # | Question | Outcome | Time | Hints |\n");
+    out.push_str("|---|----------|---------|------|-------|\n");
+    for question in &result.questions {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            question.id,
+            table_cell(&question.question),
+            outcome_label(question.outcome),
+            format_secs(question.time_spent_secs),
+            question.hints_used
+        );
+    }
+    let _ = writeln!(
+        out,
+        "\n**Score:** {}/{} correct, {}/{} points ({:.0}%)\n",
+        result.score.correct,
+        result.score.total,
+        format_points(result.score.points),
+        result.score.possible_points,
+        result.score.percent
+    );
+
+    out.push_str("## Domains\n\n");
+    out.push_str("| Domain | Correct | Total |\n");
+    out.push_str("|--------|---------|-------|\n");
+    for domain in &result.domains {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} |",
+            table_cell(&domain.domain),
+            domain.correct,
+            domain.total
+        );
+    }
+
+    let missed: Vec<&QuestionResult> = result.questions.iter().filter(|q| is_missed(q)).collect();
+    if !missed.is_empty() {
+        out.push_str("\n## Missed questions\n");
+        for question in missed {
+            let _ = writeln!(
+                out,
+                "\n### Question {} ({})\n\n{}\n",
+                question.id,
+                outcome_label(question.outcome),
+                question.question
+            );
+            match &question.answer_submitted {
+                Some(answer) => {
+                    let _ = writeln!(out, "Your answer:\n\n```\n{}\n```\n", answer);
+                }
+                None => out.push_str("Your answer: _none_\n\n"),
+            }
+            let _ = writeln!(
+                out,
+                "Correct answer:\n\n```\n{}\n```\n\nHints used: {}",
+                question.expected_answer, question.hints_used
+            );
+        }
+    }
+
+    out
+}
+
+/// Renders a session as CSV, one row per question
+pub fn to_csv(result: &SessionResult) -> String {
+    let mut out = String::from(
+        "id,domain,outcome,time_spent_secs,hints_used,question,answer_submitted,expected_answer\n",
+    );
+    for question in &result.questions {
+        let fields = [
+            question.id.to_string(),
+            question.domain.clone().unwrap_or_default(),
+            outcome_label(question.outcome).to_string(),
+            question.time_spent_secs.to_string(),
+            question.hints_used.to_string(),
+            question.question.clone(),
+            question.answer_submitted.clone().unwrap_or_default(),
+            question.expected_answer.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders the answered questions as CSV for charting time and weak areas
+/// across sessions; unlike `to_csv` it leaves out the question and answer text
+pub fn to_timing_csv(result: &SessionResult) -> String {
+    let mut out = String::from("id,category,status,time_spent_secs,hints_used,points\n");
+    for question in result
+        .questions
+        .iter()
+        .filter(|question| question.outcome != QuestionStatus::Pending)
+    {
+        let fields = [
+            question.id.to_string(),
+            question.domain.clone().unwrap_or_default(),
+            outcome_label(question.outcome).to_string(),
+            question.time_spent_secs.to_string(),
+            question.hints_used.to_string(),
+            format_points(question.points),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quotes a CSV field when it contains separators, quotes or line breaks
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn is_missed(question: &QuestionResult) -> bool {
+    matches!(
+        question.outcome,
+        QuestionStatus::Fail | QuestionStatus::Skipped | QuestionStatus::Revealed
+    )
+}
+
+pub(crate) fn outcome_label(status: QuestionStatus) -> &'static str {
+    match status {
+        QuestionStatus::Pending => "pending",
+        QuestionStatus::Pass => "pass",
+        QuestionStatus::Fail => "fail",
+        QuestionStatus::Skipped => "skipped",
+        QuestionStatus::Revealed => "revealed",
+    }
+}
+
+pub(crate) fn mode_label(result: &SessionResult) -> String {
+    format!("{:?}", result.mode).to_lowercase()
+}
+
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+pub(crate) fn format_secs(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
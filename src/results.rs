@@ -0,0 +1,210 @@
+use crate::config::Mode;
+use crate::models;
+use crate::quiz_state::{Confidence, QuestionStatus, QuizState};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Machine-readable record of a finished (or abandoned) session
+/// This is the shared schema for exports, reports and statistics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionResult {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub mode: Mode,
+    /// False when the user quit before reaching the summary
+    pub complete: bool,
+    pub questions: Vec<QuestionResult>,
+    pub score: Score,
+    pub domains: Vec<DomainResult>,
+    /// Time spent on breaks between questions, not part of any question's time
+    #[serde(default)]
+    pub break_secs: u64,
+    /// Time the session ran, less pauses and breaks
+    #[serde(default)]
+    pub elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionResult {
+    pub id: usize,
+    /// The question's [`Question::identity`](crate::models::Question::identity) when the session ran; empty in
+    /// results saved before identities existed
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub uid: String,
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub expected_answer: String,
+    pub domain: Option<String>,
+    pub outcome: QuestionStatus,
+    pub time_spent_secs: u64,
+    pub hints_used: usize,
+    /// Which of the question's hints were shown, by position, sorted and
+    /// without duplicates; empty in results saved before this was recorded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints_viewed: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub answer_submitted: Option<String>,
+    /// Every answer submitted, in order; empty in results saved before this
+    /// was recorded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<String>,
+    /// Share of the commands (or rubric points) that were right, for a failed
+    /// multi-command or rubric answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_credit: Option<f64>,
+    /// Points earned: the question's weight times its credit
+    #[serde(default)]
+    pub points: f64,
+    /// How sure the user said they were before the verdict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub correct: usize,
+    pub total: usize,
+    /// Points earned out of `possible_points`; `percent` is based on these
+    #[serde(default)]
+    pub points: f64,
+    #[serde(default)]
+    pub possible_points: u32,
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainResult {
+    pub domain: String,
+    pub correct: usize,
+    pub total: usize,
+}
+
+impl QuestionResult {
+    /// The identity stats are kept under; old results without a `uid` fall
+    /// back to their question text, as questions without a `uid` do
+    pub fn identity(&self) -> String {
+        if self.uid.is_empty() {
+            models::text_identity(self.id, &self.question)
+        } else {
+            self.uid.clone()
+        }
+    }
+}
+
+impl SessionResult {
+    pub fn from_quiz(
+        quiz_state: &QuizState,
+        mode: Mode,
+        started_at: DateTime<Utc>,
+        complete: bool,
+    ) -> Self {
+        let questions: Vec<QuestionResult> = quiz_state
+            .questions()
+            .iter()
+            .zip(quiz_state.records())
+            .map(|(question, record)| QuestionResult {
+                id: question.id,
+                uid: question.identity(),
+                question: question.question.clone(),
+                expected_answer: question.answer.clone(),
+                domain: question.category.clone(),
+                outcome: record.status,
+                time_spent_secs: record.time_spent.map_or(0, |spent| spent.as_secs()),
+                hints_used: record.hints_used,
+                hints_viewed: record.hints_viewed.clone(),
+                tags: question.tags.clone(),
+                answer_submitted: record.submission.clone(),
+                attempts: record.attempts.clone(),
+                partial_credit: record.partial_credit,
+                points: question.points() as f64 * record.credit(),
+                confidence: record.confidence,
+            })
+            .collect();
+
+        let mut domains: Vec<DomainResult> = Vec::new();
+        for result in &questions {
+            let name = result.domain.as_deref().unwrap_or("Uncategorized");
+            let index = match domains.iter().position(|domain| domain.domain == name) {
+                Some(index) => index,
+                None => {
+                    domains.push(DomainResult {
+                        domain: name.to_string(),
+                        correct: 0,
+                        total: 0,
+                    });
+                    domains.len() - 1
+                }
+            };
+            domains[index].total += 1;
+            if result.outcome == QuestionStatus::Pass {
+                domains[index].correct += 1;
+            }
+        }
+
+        let correct = quiz_state.correct_count();
+        let total = questions.len();
+        let (points, possible_points) = (quiz_state.earned_points(), quiz_state.total_points());
+        Self {
+            started_at,
+            finished_at: Utc::now(),
+            mode,
+            complete,
+            questions,
+            score: Score {
+                correct,
+                total,
+                points,
+                possible_points,
+                percent: percentage(points, possible_points as f64),
+            },
+            domains,
+            break_secs: 0,
+            elapsed_secs: quiz_state.session_elapsed().as_secs(),
+        }
+    }
+
+    /// File name used for automatic saves in the data dir
+    pub fn file_name(&self) -> String {
+        format!("{}.json", self.file_stem())
+    }
+
+    pub fn file_stem(&self) -> String {
+        format!("session-{}", self.started_at.format("%Y%m%dT%H%M%SZ"))
+    }
+}
+
+fn percentage(earned: f64, possible: f64) -> f64 {
+    if possible == 0.0 {
+        0.0
+    } else {
+        earned * 100.0 / possible
+    }
+}
+
+/// Points rounded to one decimal, without a trailing `.0`: "18", "17.5"
+pub fn format_points(points: f64) -> String {
+    let rounded = (points * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{:.0}", rounded)
+    } else {
+        format!("{:.1}", rounded)
+    }
+}
+
+/// Directory holding one JSON file per session
+pub fn results_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("results")
+}
+
+pub fn write(path: &Path, result: &SessionResult) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(result)?;
+    fs::write(path, json)
+}
@@ -0,0 +1,219 @@
+use crate::config::{DuplicateIds, Freshness, QuizConfig};
+use crate::models::{self, Question};
+use crate::question_repository::{self, QuestionRepository, RepositoryError};
+use crate::quiz_state::shuffle_hints;
+use chrono::{DateTime, NaiveDate, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Loads questions and applies the session options; shared by the TUI and `--print`
+/// An empty selection is an error so neither front-end has to handle it
+pub fn build_session(
+    repository: &dyn QuestionRepository,
+    config: &QuizConfig,
+) -> Result<Vec<Question>, RepositoryError> {
+    let mut loaded = repository.get_questions().inspect_err(|err| {
+        tracing::error!(error = %err, "failed to load questions");
+    })?;
+    tracing::info!(count = loaded.len(), "questions loaded from repository");
+    check_ids(&mut loaded, config.duplicate_ids)?;
+    let count = loaded.len();
+    let questions = select_questions(loaded, config);
+    tracing::info!(count = questions.len(), mode = ?config.mode, "session selected");
+    if questions.is_empty() {
+        return Err(match active_filters(config) {
+            filters if count > 0 && !filters.is_empty() => RepositoryError::NoMatch {
+                loaded: count,
+                filters,
+            },
+            _ => RepositoryError::Empty("the question source has no questions".to_string()),
+        });
+    }
+    Ok(questions)
+}
+
+/// Makes question ids unique, or fails, per `policy`; `jump_to_id` assumes
+/// one question per id. Duplicate uids always fail, as history is kept under them
+pub fn check_ids(questions: &mut [Question], policy: DuplicateIds) -> Result<(), RepositoryError> {
+    let uids = question_repository::duplicate_uids(questions);
+    if !uids.is_empty() {
+        tracing::error!(uids = ?uids, "duplicate question uids");
+        return Err(RepositoryError::DuplicateUids(uids));
+    }
+    let duplicates = question_repository::duplicate_ids(questions);
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+    if policy == DuplicateIds::Error {
+        tracing::error!(ids = ?duplicates, "duplicate question ids");
+        return Err(RepositoryError::DuplicateIds(duplicates));
+    }
+    for (old, new) in question_repository::renumber_duplicates(questions) {
+        tracing::warn!(old, new, "duplicate question id renumbered");
+        eprintln!(
+            "warning: more than one question uses id {}; a later one is now question {}",
+            old, new
+        );
+    }
+    Ok(())
+}
+
+/// Decides which questions make up a session and in what order
+/// Applies tag and priority filtering, then recent-question exclusion, then the
+/// mastery drill's question, then the question of the day, then
+/// ordering (by id or shuffled, then by priority), then the question limit,
+/// then hint shuffling
+pub fn select_questions(questions: Vec<Question>, config: &QuizConfig) -> Vec<Question> {
+    let mut selected: Vec<Question> = questions
+        .into_iter()
+        .filter(|question| matches_tags(question, &config.tags))
+        .collect();
+    if config.priority_only {
+        selected = only_prioritized(selected);
+    }
+
+    if let Some(freshness) = &config.fresh {
+        // A session needs at least one question, however recently it was seen
+        let wanted = config.limit.unwrap_or(1);
+        selected = exclude_recent(selected, freshness, wanted);
+    }
+
+    if let Some(mastery) = config.mastery {
+        selected.retain(|question| question.id == mastery.id);
+    }
+
+    if let Some(date) = config.daily {
+        // By id, so the pick doesn't depend on the order files were loaded in
+        selected.sort_by_key(|question| question.id);
+        if !selected.is_empty() {
+            let index = daily_index(date, selected.len());
+            selected = vec![selected.swap_remove(index)];
+        }
+    }
+
+    if config.sort_by_id {
+        // Stable sort keeps duplicate ids in load order
+        selected.sort_by_key(|question| question.id);
+    }
+
+    if config.shuffle_questions {
+        selected.shuffle(&mut seeded_rng(config.seed));
+    }
+
+    if config.by_priority || config.priority_only {
+        // After shuffling, so a shuffle still mixes questions of equal priority
+        order_by_priority(&mut selected);
+    }
+
+    if let Some(limit) = config.limit {
+        selected.truncate(limit);
+    }
+
+    if config.shuffle_hints {
+        let mut rng = seeded_rng(config.seed);
+        for question in &mut selected {
+            shuffle_hints(question, &mut rng);
+        }
+    }
+
+    selected
+}
+
+/// `questions` without those attempted in the last `freshness.days` days
+/// When fewer than `wanted` are left, the least recently attempted of the
+/// excluded ones come back to make up the number. Order is kept either way.
+pub fn exclude_recent(
+    questions: Vec<Question>,
+    freshness: &Freshness,
+    wanted: usize,
+) -> Vec<Question> {
+    let window = chrono::Duration::days(i64::from(freshness.days));
+    let seen = |question: &Question| freshness.last_seen.get(&question.identity()).copied();
+    let is_recent =
+        |question: &Question| seen(question).is_some_and(|at| freshness.now - at < window);
+
+    let fresh = questions
+        .iter()
+        .filter(|question| !is_recent(question))
+        .count();
+    let mut recent: Vec<(DateTime<Utc>, String)> = questions
+        .iter()
+        .filter(|question| is_recent(question))
+        .filter_map(|question| Some((seen(question)?, question.identity())))
+        .collect();
+    recent.sort();
+    let readmitted: HashSet<String> = recent
+        .into_iter()
+        .take(wanted.saturating_sub(fresh))
+        .map(|(_, identity)| identity)
+        .collect();
+    tracing::info!(
+        fresh,
+        readmitted = readmitted.len(),
+        days = freshness.days,
+        "recently attempted questions left out"
+    );
+    questions
+        .into_iter()
+        .filter(|question| !is_recent(question) || readmitted.contains(&question.identity()))
+        .collect()
+}
+
+/// Which of `len` questions is the question of the day on `date`
+/// A hash of the date rather than the day number, so consecutive days land far
+/// apart in the bank; everyone with the same bank gets the same question.
+pub fn daily_index(date: NaiveDate, len: usize) -> usize {
+    let hash = models::fnv1a(date.format("%Y-%m-%d").to_string().as_bytes());
+    (hash % len.max(1) as u64) as usize
+}
+
+/// The options that drop questions, as the user gave them, for messages about
+/// an empty selection
+pub fn active_filters(config: &QuizConfig) -> Vec<String> {
+    let mut filters = Vec::new();
+    if !config.tags.is_empty() {
+        filters.push(format!("--tags {}", config.tags.join(",")));
+    }
+    if config.priority_only {
+        filters.push("--priority-only".to_string());
+    }
+    if let Some(mastery) = config.mastery {
+        filters.push(format!("--master {}", mastery.id));
+    }
+    filters
+}
+
+/// Higher priority first; a stable sort, so ties keep their current order and
+/// questions without a priority count as 0
+pub fn order_by_priority(questions: &mut [Question]) {
+    questions.sort_by_key(|question| Reverse(question.priority.unwrap_or(0)));
+}
+
+/// The questions marked exam-critical, i.e. with a priority above 0
+pub fn only_prioritized(questions: Vec<Question>) -> Vec<Question> {
+    questions
+        .into_iter()
+        .filter(|question| question.priority.unwrap_or(0) > 0)
+        .collect()
+}
+
+/// Seeded when a seed is configured, so runs can be reproduced
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+fn matches_tags(question: &Question, tags: &[String]) -> bool {
+    tags.is_empty()
+        || tags.iter().any(|tag| {
+            question
+                .tags
+                .iter()
+                .any(|own| own.eq_ignore_ascii_case(tag))
+        })
+}
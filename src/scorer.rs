@@ -0,0 +1,325 @@
+use crate::grading::{self, Difference, Verdict};
+use crate::kubectl::{self, KubectlRunner, ServerCheck};
+use crate::models::{Question, QuestionGrading};
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::sync::Arc;
+
+/// How typed answers are graded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Grading {
+    /// Compare the normalized text
+    #[default]
+    Text,
+    /// Also accept kubectl commands that render the same manifest with
+    /// `--dry-run=client -o yaml`
+    Kubectl,
+    /// Validate manifest answers with `kubectl apply --dry-run=server` against
+    /// the current context before comparing them as YAML
+    #[serde(rename = "server-dry-run")]
+    ServerDryRun,
+}
+
+/// How forgiving answer matching is, as one preset for the options below
+/// Questions with a rubric or an answer pattern decide on their own either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strictness {
+    /// The answer exactly as written, apart from leading and trailing whitespace
+    Strict,
+    /// Whitespace collapsed, flag order, flag syntax, quoting and aliases forgiven
+    #[default]
+    Normal,
+    /// Also case-insensitive, and a namespace flag may be left out or added
+    Lenient,
+}
+
+/// Decides whether a submitted answer is correct
+/// Comparison happens on normalized text so spacing differences don't matter.
+/// Case matters unless turned off globally or by the question itself.
+/// Commands that differ only in flag order, `--flag=value` style or quoting
+/// also match (see `grading::compare_commands`).
+/// Answers of several commands (lines or `&&`) are matched command by command
+/// in any order, earning partial credit when only some are right.
+/// With a kubectl runner, commands that render the same manifest also match.
+/// Manifest answers match when they parse to the same YAML, whatever the layout.
+/// Questions graded by a rubric skip all of that and score the rubric items found.
+/// A [`Strictness`] preset narrows all of that to exact text, or widens it.
+#[derive(Debug, Clone)]
+pub struct AnswerScorer {
+    case_sensitive: bool,
+    kubectl: Option<Arc<dyn KubectlRunner>>,
+    /// Commands beyond a multi-command answer's lower its partial credit
+    penalize_extra_commands: bool,
+    /// Validates manifest answers on the API server first
+    server: Option<Arc<dyn KubectlRunner>>,
+    strictness: Strictness,
+}
+
+impl AnswerScorer {
+    /// `case_sensitive` applies to questions that don't set their own
+    pub fn new(case_sensitive: bool) -> Self {
+        Self {
+            case_sensitive,
+            kubectl: None,
+            penalize_extra_commands: false,
+            server: None,
+            strictness: Strictness::Normal,
+        }
+    }
+
+    /// Applies a matching preset; `Lenient` turns case sensitivity off for
+    /// questions that don't set their own
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Sends manifest answers through `kubectl apply --dry-run=server` first;
+    /// a rejection fails the answer with the server's message
+    pub fn with_server_dry_run(mut self, runner: Arc<dyn KubectlRunner>) -> Self {
+        self.server = Some(runner);
+        self
+    }
+
+    /// Counts unneeded commands against multi-command answers (free by default)
+    pub fn with_extra_command_penalty(mut self, penalize: bool) -> Self {
+        self.penalize_extra_commands = penalize;
+        self
+    }
+
+    /// Also grades kubectl commands by their dry-run manifests
+    pub fn with_kubectl(mut self, runner: Arc<dyn KubectlRunner>) -> Self {
+        self.kubectl = Some(runner);
+        self
+    }
+
+    pub fn is_correct(&self, question: &Question, submitted: &str) -> bool {
+        self.grade(question, submitted).is_correct()
+    }
+
+    /// Like `is_correct`, but says how an accepted answer differs from the expected one
+    pub fn grade(&self, question: &Question, submitted: &str) -> Verdict {
+        let case_sensitive = question
+            .case_sensitive
+            .unwrap_or(self.case_sensitive && self.strictness != Strictness::Lenient);
+        if question.grading() == QuestionGrading::Rubric {
+            return Verdict::Rubric(grading::grade_rubric(
+                question.rubric_items(),
+                submitted,
+                case_sensitive,
+            ));
+        }
+        if let Some(pattern) = &question.answer_pattern {
+            return grade_by_pattern(question, pattern, submitted, case_sensitive);
+        }
+        if self.strictness == Strictness::Strict {
+            return grade_exactly(question, submitted, case_sensitive);
+        }
+        if question.yaml_answer {
+            if let Some(message) = self.server_rejection(submitted) {
+                return Verdict::Rejected(message);
+            }
+            if manifest_matches(question, submitted) {
+                return Verdict::Equal;
+            }
+        }
+        let lines = submitted;
+        let submitted = normalize(submitted);
+        if submitted.is_empty() {
+            return Verdict::Different;
+        }
+        let expected: Vec<&String> = std::iter::once(&question.answer)
+            .chain(question.accepted_answers.iter().flatten())
+            .collect();
+        let text_match = expected
+            .iter()
+            .map(|expected| normalize(expected))
+            .any(|expected| {
+                if case_sensitive {
+                    expected == submitted
+                } else {
+                    expected.to_lowercase() == submitted.to_lowercase()
+                }
+            });
+        if text_match {
+            return Verdict::Equal;
+        }
+        if !question.yaml_answer {
+            // A multi-line answer is several commands; flags can't move between them
+            let equivalent = expected
+                .iter()
+                .filter(|expected| !expected.trim().contains('\n'))
+                .map(|expected| {
+                    if self.strictness == Strictness::Lenient {
+                        grading::compare_commands_namespace_tolerant(
+                            expected,
+                            &submitted,
+                            case_sensitive,
+                        )
+                    } else {
+                        grading::compare_commands(expected, &submitted, case_sensitive)
+                    }
+                })
+                .find(Verdict::is_correct);
+            if let Some(verdict) = equivalent {
+                return verdict;
+            }
+        }
+        let partial = self.grade_by_command(question, &expected, lines, case_sensitive);
+        if let Some(verdict) = partial.as_ref().filter(|verdict| verdict.is_correct()) {
+            return verdict.clone();
+        }
+
+        // kubectl is only asked when the text differs; multi-line answers stay
+        // unnormalized so they are recognised as more than one command
+        let fallback = partial.unwrap_or(Verdict::Different);
+        let Some(runner) = &self.kubectl else {
+            return fallback;
+        };
+        let renders_same = expected.iter().any(|expected| {
+            kubectl::commands_match(runner.as_ref(), &submitted, expected) == Some(true)
+        });
+        if renders_same {
+            Verdict::Equivalent(vec![Difference::SameManifest])
+        } else {
+            fallback
+        }
+    }
+
+    /// The API server's message when it refuses a manifest; `None` when it
+    /// accepts it, there is no server to ask, or the submission isn't YAML
+    fn server_rejection(&self, submitted: &str) -> Option<String> {
+        let runner = self.server.as_ref()?;
+        parse_manifest(submitted)?;
+        match kubectl::server_dry_run(runner.as_ref(), submitted) {
+            ServerCheck::Accepted => None,
+            ServerCheck::Rejected(message) => Some(message),
+            ServerCheck::Unavailable(err) => {
+                tracing::warn!(error = %err, "server dry run unavailable");
+                None
+            }
+        }
+    }
+
+    /// The best command-by-command verdict over the multi-command expected answers
+    fn grade_by_command(
+        &self,
+        question: &Question,
+        expected: &[&String],
+        submitted: &str,
+        case_sensitive: bool,
+    ) -> Option<Verdict> {
+        if question.yaml_answer {
+            return None;
+        }
+        expected
+            .iter()
+            .filter(|expected| grading::split_commands(expected).len() > 1)
+            .map(|expected| {
+                grading::compare_command_lists(
+                    expected,
+                    submitted,
+                    case_sensitive,
+                    self.penalize_extra_commands,
+                )
+            })
+            .filter(|verdict| *verdict != Verdict::Different)
+            .max_by(|a, b| a.credit().total_cmp(&b.credit()))
+    }
+}
+
+impl Default for AnswerScorer {
+    /// Case-sensitive, like kubectl
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+/// `Strict` grading: the submission must be one of the expected answers as written
+fn grade_exactly(question: &Question, submitted: &str, case_sensitive: bool) -> Verdict {
+    let submitted = submitted.trim();
+    let exact = std::iter::once(&question.answer)
+        .chain(question.accepted_answers.iter().flatten())
+        .map(|expected| expected.trim())
+        .any(|expected| {
+            if case_sensitive {
+                expected == submitted
+            } else {
+                expected.to_lowercase() == submitted.to_lowercase()
+            }
+        });
+    if exact && !submitted.is_empty() {
+        Verdict::Equal
+    } else {
+        Verdict::Different
+    }
+}
+
+/// A pattern decides on its own; matching the revealed answer word for word is `Equal`
+fn grade_by_pattern(
+    question: &Question,
+    pattern: &str,
+    submitted: &str,
+    case_sensitive: bool,
+) -> Verdict {
+    // Bank loading rejects invalid patterns, so this only fails for hand-built questions
+    let Ok(pattern) = grading::compile_pattern(pattern, case_sensitive) else {
+        tracing::warn!(id = question.id, "invalid answer_pattern");
+        return Verdict::Different;
+    };
+    if !grading::matches_pattern(&pattern, submitted) {
+        Verdict::Different
+    } else if normalize(submitted) == normalize(&question.answer) {
+        Verdict::Equal
+    } else {
+        Verdict::Equivalent(vec![Difference::Pattern])
+    }
+}
+
+/// Whether `submitted` parses to the same manifest as any expected answer
+fn manifest_matches(question: &Question, submitted: &str) -> bool {
+    let Some(submitted) = parse_manifest(submitted) else {
+        return false;
+    };
+    std::iter::once(&question.answer)
+        .chain(question.accepted_answers.iter().flatten())
+        .any(|expected| parse_manifest(expected).as_ref() == Some(&submitted))
+}
+
+/// `text` as YAML without the fields that differ between runs and with
+/// scalars in their written form; `None` if it isn't YAML or is empty
+fn parse_manifest(text: &str) -> Option<Value> {
+    serde_yaml::from_str::<Value>(text)
+        .ok()
+        .filter(|value| !value.is_null())
+        .map(kubectl::strip_volatile)
+        .map(unquote_scalars)
+}
+
+/// Numbers and booleans become strings, so quoting a value never matters:
+/// `"256Mi"` and `256Mi`, `"1"` and `1`, `"true"` and `true` all compare equal,
+/// in keys too. Numbers are written out in their plain form first (`1.50` as
+/// `1.5`), so a quoted number only matches when written that way, and `1` and
+/// `1.0` stay different. `null` and `~` stay null rather than becoming text
+fn unquote_scalars(value: Value) -> Value {
+    match value {
+        Value::Number(number) => Value::String(number.to_string()),
+        Value::Bool(flag) => Value::String(flag.to_string()),
+        Value::Sequence(items) => Value::Sequence(items.into_iter().map(unquote_scalars).collect()),
+        Value::Mapping(map) => Value::Mapping(
+            map.into_iter()
+                .map(|(key, value)| (unquote_scalars(key), unquote_scalars(value)))
+                .collect(),
+        ),
+        Value::Tagged(tagged) => unquote_scalars(tagged.value),
+        other => other,
+    }
+}
+
+/// Collapses all whitespace (including line breaks) into single spaces
+pub fn normalize(answer: &str) -> String {
+    answer.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,299 @@
+use crate::quiz_state::{Confidence, QuestionStatus};
+use crate::report::{format_secs, mode_label, outcome_label};
+use crate::results::{format_points, QuestionResult, SessionResult};
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::future::Future;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Saved result files, or directories of them, for `serve`; the profile's
+/// results directory when empty
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServeOptions {
+    pub paths: Vec<PathBuf>,
+}
+
+/// A rendered response: status code and HTML
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Page {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: 404,
+            body: layout(
+                "Not found",
+                "<h1>Not found</h1><p><a href=\"/\">All sessions</a></p>",
+            ),
+        }
+    }
+}
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+a { color: #1f5fbf; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: .35rem .6rem; border-bottom: 1px solid #ddd; }
+.tiles { display: flex; gap: 1rem; margin: 1rem 0; }
+.tile { border: 1px solid #ddd; border-radius: .4rem; padding: .6rem 1rem; }
+.tile b { display: block; font-size: 1.5rem; }
+.bar { background: #eee; border-radius: .3rem; height: 1rem; width: 20rem; }
+.bar span { display: block; height: 100%; border-radius: .3rem; background: #3a9d5d; }
+details { border: 1px solid #ddd; border-radius: .4rem; margin: .5rem 0; padding: .4rem .8rem; }
+summary { cursor: pointer; }
+pre { background: #f6f6f6; padding: .5rem; overflow-x: auto; white-space: pre-wrap; }
+.pass { color: #2b7a46; } .fail { color: #b3261e; } .skipped, .revealed, .pending { color: #8a6d00; }
+";
+
+/// Answers a request from the saved sessions
+/// `/` lists the sessions, newest first; `/sessions/<n>` is the report of the
+/// n-th session in `sessions`. Pure, so it can be tested without a socket.
+pub fn route(method: &str, path: &str, sessions: &[SessionResult]) -> Page {
+    if method != "GET" {
+        return Page {
+            status: 405,
+            body: layout("Method not allowed", "<h1>Method not allowed</h1>"),
+        };
+    }
+    match path.trim_end_matches('/') {
+        "" => Page::ok(index_page(sessions)),
+        path => match path
+            .strip_prefix("/sessions/")
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| sessions.get(index))
+        {
+            Some(session) => Page::ok(session_page(session)),
+            None => Page::not_found(),
+        },
+    }
+}
+
+/// Every session with its date, mode and score, linking to its report
+pub fn index_page(sessions: &[SessionResult]) -> String {
+    let mut body = String::from("<h1>CKAD practice sessions</h1>");
+    if sessions.is_empty() {
+        body.push_str("<p>No saved sessions yet.</p>");
+        return layout("Sessions", &body);
+    }
+    body.push_str(
+        "<table><tr><th>Started</th><th>Mode</th><th>Score</th><th>Points</th><th>Complete</th></tr>",
+    );
+    let mut order: Vec<usize> = (0..sessions.len()).collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(sessions[index].started_at));
+    for index in order {
+        let session = &sessions[index];
+        let _ = write!(
+            body,
+            "<tr><td><a href=\"/sessions/{}\">{}</a></td><td>{}</td><td>{}/{} ({:.0}%)</td><td>{}/{}</td><td>{}</td></tr>",
+            index,
+            session.started_at.format("%Y-%m-%d %H:%M UTC"),
+            mode_label(session),
+            session.score.correct,
+            session.score.total,
+            session.score.percent,
+            format_points(session.score.points),
+            session.score.possible_points,
+            if session.complete { "yes" } else { "no" }
+        );
+    }
+    body.push_str("</table>");
+    layout("Sessions", &body)
+}
+
+/// One session: summary tiles, a bar per domain and each question folded
+/// into a `<details>` with the submitted and the model answer
+pub fn session_page(session: &SessionResult) -> String {
+    let started = session.started_at.format("%Y-%m-%d %H:%M UTC").to_string();
+    let mut body = format!(
+        "<p><a href=\"/\">All sessions</a></p><h1>Session {}</h1><p>Mode: {} | Complete: {}</p>",
+        started,
+        mode_label(session),
+        if session.complete { "yes" } else { "no" }
+    );
+    let score = &session.score;
+    let _ = write!(
+        body,
+        "<div class=\"tiles\"><div class=\"tile\"><b>{}/{}</b>correct</div>\
+         <div class=\"tile\"><b>{}/{}</b>points</div>\
+         <div class=\"tile\"><b>{:.0}%</b>score</div></div>",
+        score.correct,
+        score.total,
+        format_points(score.points),
+        score.possible_points,
+        score.percent
+    );
+
+    if !session.domains.is_empty() {
+        body.push_str("<h2>Domains</h2><table>");
+        for domain in &session.domains {
+            let percent = if domain.total == 0 {
+                0.0
+            } else {
+                domain.correct as f64 * 100.0 / domain.total as f64
+            };
+            let _ = write!(
+                body,
+                "<tr><td>{}</td><td><div class=\"bar\"><span style=\"width: {:.0}%\"></span></div></td><td>{}/{}</td></tr>",
+                escape(&domain.domain),
+                percent,
+                domain.correct,
+                domain.total
+            );
+        }
+        body.push_str("</table>");
+    }
+
+    body.push_str("<h2>Questions</h2>");
+    for question in &session.questions {
+        body.push_str(&question_details(question));
+    }
+    layout(&format!("Session {}", started), &body)
+}
+
+fn question_details(question: &QuestionResult) -> String {
+    let outcome = outcome_label(question.outcome);
+    let first_line = question.question.lines().next().unwrap_or_default();
+    let mut details = format!(
+        "<details><summary>#{} {} &middot; <span class=\"{}\">{}</span> &middot; {} &middot; {} hint{}</summary>",
+        question.id,
+        escape(first_line),
+        outcome,
+        outcome,
+        format_secs(question.time_spent_secs),
+        question.hints_used,
+        if question.hints_used == 1 { "" } else { "s" }
+    );
+    let _ = write!(
+        details,
+        "<p>{}</p>",
+        escape(&question.question).replace('\n', "<br>")
+    );
+    if let Some(domain) = &question.domain {
+        let _ = write!(details, "<p>Domain: {}</p>", escape(domain));
+    }
+    let _ = write!(details, "<p>Points: {}", format_points(question.points));
+    if let Some(credit) = question.partial_credit {
+        let _ = write!(details, " (partial credit {:.0}%)", credit * 100.0);
+    }
+    if let Some(confidence) = question.confidence {
+        let _ = write!(details, " | Confidence: {}", confidence_label(confidence));
+    }
+    details.push_str("</p>");
+    match &question.answer_submitted {
+        Some(answer) => {
+            let _ = write!(details, "<h3>Your answer</h3><pre>{}</pre>", escape(answer));
+        }
+        None if question.outcome == QuestionStatus::Pending => {
+            details.push_str("<h3>Your answer</h3><p><em>not reached</em></p>")
+        }
+        None => details.push_str("<h3>Your answer</h3><p><em>none</em></p>"),
+    }
+    if question.attempts.len() > 1 {
+        let _ = write!(
+            details,
+            "<h3>Attempts ({})</h3><ol>",
+            question.attempts.len()
+        );
+        for attempt in &question.attempts {
+            let _ = write!(details, "<li><pre>{}</pre></li>", escape(attempt));
+        }
+        details.push_str("</ol>");
+    }
+    let _ = write!(
+        details,
+        "<h3>Model answer</h3><pre>{}</pre></details>",
+        escape(&question.expected_answer)
+    );
+    details
+}
+
+fn confidence_label(confidence: Confidence) -> &'static str {
+    match confidence {
+        Confidence::High => "high",
+        Confidence::Medium => "medium",
+        Confidence::Low => "low",
+    }
+}
+
+fn layout(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\"><head><meta charset=\"utf-8\"><title>{} - ckad-practitioner</title><style>{}</style></head><body>{}</body></html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A listener on 127.0.0.1 only, on a free port picked by the OS
+pub async fn bind() -> io::Result<TcpListener> {
+    TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).await
+}
+
+/// Serves `route` over HTTP/1 until `shutdown` resolves
+pub async fn run(
+    listener: TcpListener,
+    sessions: Vec<SessionResult>,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let sessions = Arc::new(sessions);
+    tokio::pin!(shutdown);
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    tracing::warn!(error = %err, "could not accept a connection");
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+        let sessions = Arc::clone(&sessions);
+        tokio::spawn(async move {
+            let service = service_fn(move |request: Request<Incoming>| {
+                let page = route(request.method().as_str(), request.uri().path(), &sessions);
+                async move { Ok::<_, Infallible>(response(page)) }
+            });
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!(error = %err, "connection ended with an error");
+            }
+        });
+    }
+}
+
+fn response(page: Page) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(page.body)));
+    *response.status_mut() = StatusCode::from_u16(page.status).unwrap_or(StatusCode::OK);
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+    response
+}
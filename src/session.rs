@@ -0,0 +1,506 @@
+use crate::adaptive::{self, TierLadder};
+use crate::autosave::{self, SessionSnapshot, SnapshotQuestion};
+use crate::config::{Mode, QuizConfig};
+use crate::grading::{self, Verdict};
+use crate::models::{Difficulty, Question};
+use crate::question_repository::RepositoryError;
+use crate::quiz_state::{
+    Confidence, HintState, JumpError, QuestionRecord, QuestionStatus, QuizState,
+};
+use crate::results::SessionResult;
+use crate::scorer::AnswerScorer;
+use crate::timer::Clock;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Something a front-end can ask a session to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionAction {
+    /// Reveal the next hint (or the first one)
+    Hint,
+    /// Grade an answer for the current question
+    Submit(String),
+    /// Leave the current question unanswered
+    Skip,
+    /// Move on once the current question is resolved
+    Next,
+    /// Add time to the current question (accommodations mode only)
+    ExtendTime,
+    /// Start the current question over: full time, no hints (if the config allows it)
+    RestartTimer,
+    /// Go to the question with this id
+    JumpTo(usize),
+    /// Start the clock of a question held by the ready gate
+    Start,
+    /// Record how sure the user is of the answer about to be submitted
+    RateConfidence(Confidence),
+    /// Settle the current question as skipped but stay on it, its answer shown
+    GiveUp,
+    /// Open the resolved current question again: a fresh attempt with full time and no hints
+    Retry,
+}
+
+/// Headless quiz session: questions, options and progress, with no terminal attached
+/// Front-ends feed it actions and render from its state, so the TUI, plain mode
+/// and tests all share the same rules.
+#[derive(Debug)]
+pub struct Session {
+    quiz_state: QuizState,
+    hint_state: HintState,
+    scorer: AnswerScorer,
+    config: QuizConfig,
+    finished: bool,
+    started_at: DateTime<Utc>,
+    /// How the current question's submission was graded, until the quiz moves on
+    verdict: Option<Verdict>,
+    breaks_taken: usize,
+    /// Time spent on breaks, reported apart from the questions' time
+    break_time: Duration,
+    /// Adaptive mode: the tier the next question comes from
+    ladder: Option<TierLadder>,
+}
+
+impl Session {
+    /// Starts a session over already-selected questions
+    /// See `scheduler::build_session` for loading and filtering them.
+    /// In adaptive mode the first question is the first one at the starting tier.
+    pub fn new(mut questions: Vec<Question>, config: QuizConfig) -> Result<Self, RepositoryError> {
+        if config.mode == Mode::Adaptive {
+            if let Some(index) = adaptive::pick(&questions, TierLadder::new().tier()) {
+                let first = questions.remove(index);
+                questions.insert(0, first);
+            }
+        }
+        Self::in_order(questions, config)
+    }
+
+    fn in_order(questions: Vec<Question>, config: QuizConfig) -> Result<Self, RepositoryError> {
+        if questions.is_empty() {
+            return Err(RepositoryError::Empty(
+                "a session needs at least one question".to_string(),
+            ));
+        }
+        let ladder = (config.mode == Mode::Adaptive).then(TierLadder::new);
+        Ok(Self {
+            quiz_state: QuizState::new(questions, &config),
+            hint_state: HintState::new(config.mode != Mode::Exam),
+            scorer: AnswerScorer::new(config.case_sensitive)
+                .with_extra_command_penalty(config.penalize_extra_commands),
+            config,
+            finished: false,
+            started_at: Utc::now(),
+            verdict: None,
+            breaks_taken: 0,
+            break_time: Duration::ZERO,
+            ladder,
+        })
+    }
+
+    /// Picks up an autosaved session; `questions` are the snapshot's, in its order
+    /// The current question keeps its clock and the hints that were on screen.
+    pub fn restore(
+        questions: Vec<Question>,
+        config: QuizConfig,
+        snapshot: &SessionSnapshot,
+    ) -> Result<Self, RepositoryError> {
+        let mut session = Self::in_order(questions, config)?;
+        let records = snapshot
+            .questions
+            .iter()
+            .map(|saved| QuestionRecord {
+                status: saved.status,
+                submission: saved.submission.clone(),
+                attempts: saved.attempts.clone(),
+                time_spent: saved.time_spent_secs.map(Duration::from_secs),
+                hints_used: saved.hints_used,
+                hint_index: saved.hints_used.checked_sub(1),
+                hints_viewed: saved.hints_viewed.clone(),
+                partial_credit: saved.partial_credit,
+                confidence: saved.confidence,
+            })
+            .collect();
+        session.quiz_state = QuizState::restore(
+            session.quiz_state.questions().to_vec(),
+            records,
+            snapshot.current_index,
+            Duration::from_secs(snapshot.current_elapsed_secs),
+            &session.config,
+        );
+        session.started_at = snapshot.started_at;
+        if session.ladder.is_some() {
+            let current = session.quiz_state.current_index();
+            session.ladder = Some(TierLadder::replay(
+                session.quiz_state.records()[..current]
+                    .iter()
+                    .filter(|record| record.status != QuestionStatus::Pending)
+                    .map(|record| record.status == QuestionStatus::Pass),
+            ));
+        }
+        // Breaks already due were taken before the interruption
+        if let Some(every) = session.config.break_interval() {
+            session.breaks_taken = session.quiz_state.completed_count() / every;
+        }
+
+        session.restore_hints();
+        let index = session.quiz_state.current_index();
+        tracing::info!(
+            index,
+            total = session.quiz_state.total_questions(),
+            "session restored from autosave"
+        );
+        Ok(session)
+    }
+
+    /// Announces that pages loading in the background will grow the session to `total`
+    pub fn expect_total(&mut self, total: usize) {
+        self.quiz_state.expect_total(total);
+    }
+
+    /// Adds a page of questions that loaded after the session started
+    pub fn append_questions(&mut self, questions: Vec<Question>) {
+        self.quiz_state.append_questions(questions);
+    }
+
+    /// Ends the session with the questions loaded so far
+    pub fn finish_loading(&mut self) {
+        self.quiz_state.finish_loading();
+    }
+
+    /// Grades answers with `scorer` instead of the text comparison from the config
+    pub fn with_scorer(mut self, scorer: AnswerScorer) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    /// Times questions with `clock`, e.g. a `MockClock` for tests that move time by hand
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.quiz_state.set_clock(clock);
+        self
+    }
+
+    pub fn quiz_state(&self) -> &QuizState {
+        &self.quiz_state
+    }
+
+    pub fn hint_state(&self) -> &HintState {
+        &self.hint_state
+    }
+
+    pub fn config(&self) -> &QuizConfig {
+        &self.config
+    }
+
+    /// Grading of the current question's submission, e.g. to explain why a
+    /// differently written command was accepted
+    pub fn verdict(&self) -> Option<&Verdict> {
+        self.verdict.as_ref()
+    }
+
+    /// A tip naming the likely mistake in a wrong command, from
+    /// [`grading::diagnose`]; `None` once right, partly right or not yet submitted
+    pub fn diagnosis(&self) -> Option<String> {
+        match (&self.verdict, self.quiz_state.current_submission()) {
+            (Some(Verdict::Different), Some(submission)) => {
+                grading::diagnose(submission, &self.quiz_state.current_question().answer)
+            }
+            _ => None,
+        }
+    }
+
+    /// Hints left in the quiz's hint budget; `None` when hints are unlimited
+    pub fn hints_remaining(&self) -> Option<usize> {
+        self.quiz_state.hints_remaining(self.config.hint_budget)
+    }
+
+    /// True when the next hint request would reveal a new hint but the budget is spent
+    /// Hints already revealed for this question, e.g. before a restart, stay free.
+    pub fn out_of_hints(&self) -> bool {
+        if self.finished
+            || self.hints_remaining() != Some(0)
+            || !self.hint_state.is_enabled()
+            || self.quiz_state.is_resolved()
+        {
+            return false;
+        }
+        let question = self.quiz_state.current_question();
+        let revealed = self.quiz_state.records()[self.quiz_state.current_index()].hints_used;
+        let next = self
+            .next_hint_state()
+            .hints_viewed()
+            .min(question.hints.len());
+        next > revealed
+    }
+
+    /// True once the last question has been left and the summary is due
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Applies an action; `Ok(true)` means it changed the session, `Ok(false)` that
+    /// it did not apply right now (e.g. `Next` before the question is resolved).
+    /// Only `JumpTo` can fail.
+    pub fn apply(&mut self, action: SessionAction) -> Result<bool, JumpError> {
+        if self.finished {
+            return Ok(false);
+        }
+        // A question behind the ready gate waits for its start key
+        if self.quiz_state.is_armed() && action != SessionAction::Start {
+            return Ok(false);
+        }
+        let applied = match action {
+            SessionAction::Hint => self.request_hint(),
+            SessionAction::Submit(answer) => self.submit(answer),
+            SessionAction::Skip => !self.quiz_state.is_resolved() && self.advance(),
+            SessionAction::Next => self.quiz_state.is_resolved() && self.advance(),
+            SessionAction::ExtendTime => self.extend_time(),
+            SessionAction::RestartTimer => self.restart_timer(),
+            SessionAction::JumpTo(id) => {
+                self.quiz_state.jump_to_id(id)?;
+                self.restore_hints();
+                self.verdict = None;
+                true
+            }
+            SessionAction::Start => self.quiz_state.start_timer(),
+            SessionAction::RateConfidence(confidence) => {
+                let open = !self.quiz_state.is_resolved();
+                if open {
+                    self.quiz_state.rate_confidence(confidence);
+                }
+                open
+            }
+            SessionAction::GiveUp => {
+                let open = !self.quiz_state.is_resolved();
+                if open {
+                    self.quiz_state.finish_current();
+                }
+                open
+            }
+            SessionAction::Retry => self.retry(),
+        };
+        Ok(applied)
+    }
+
+    /// Stops the current question's clock, e.g. while the user works in a shell
+    pub fn pause_clock(&mut self) {
+        self.quiz_state.pause_timer();
+    }
+
+    pub fn resume_clock(&mut self) {
+        self.quiz_state.resume_timer();
+    }
+
+    /// Whether another `break_every` questions were completed since the last
+    /// break, with a question still to come
+    pub fn break_due(&self) -> bool {
+        let Some(every) = self.config.break_interval() else {
+            return false;
+        };
+        !self.finished
+            && !self.quiz_state.is_resolved()
+            && self.quiz_state.completed_count() / every > self.breaks_taken
+    }
+
+    /// Counts a break of `duration`; see [`Session::break_due`]
+    pub fn record_break(&mut self, duration: Duration) {
+        self.breaks_taken += 1;
+        self.break_time += duration;
+        if !self.config.breaks_pause_clock() {
+            self.quiz_state.exclude_break(duration);
+        }
+        tracing::info!(
+            breaks = self.breaks_taken,
+            secs = duration.as_secs(),
+            "break ended"
+        );
+    }
+
+    /// Time spent on breaks so far
+    pub fn break_time(&self) -> Duration {
+        self.break_time
+    }
+
+    /// Snapshot for export; incomplete unless the session finished
+    pub fn result(&self) -> SessionResult {
+        SessionResult {
+            break_secs: self.break_time.as_secs(),
+            ..SessionResult::from_quiz(
+                &self.quiz_state,
+                self.config.mode,
+                self.started_at,
+                self.finished,
+            )
+        }
+    }
+
+    /// Progress so far, for the autosave; `source` names the question source
+    pub fn snapshot(&self, source: &str) -> SessionSnapshot {
+        let quiz_state = &self.quiz_state;
+        SessionSnapshot {
+            version: autosave::SNAPSHOT_VERSION,
+            saved_at: Utc::now(),
+            started_at: self.started_at,
+            mode: self.config.mode,
+            source: source.to_string(),
+            fingerprint: autosave::fingerprint(quiz_state.questions()),
+            questions: quiz_state
+                .questions()
+                .iter()
+                .zip(quiz_state.records())
+                .map(|(question, record)| SnapshotQuestion {
+                    uid: question.identity(),
+                    id: question.id,
+                    status: record.status,
+                    submission: record.submission.clone(),
+                    attempts: record.attempts.clone(),
+                    time_spent_secs: record.time_spent.map(|spent| spent.as_secs()),
+                    hints_used: record.hints_used,
+                    hints_viewed: record.hints_viewed.clone(),
+                    partial_credit: record.partial_credit,
+                    confidence: record.confidence,
+                })
+                .collect(),
+            current_index: quiz_state.current_index(),
+            current_elapsed_secs: quiz_state.timer().elapsed().as_secs(),
+        }
+    }
+
+    fn request_hint(&mut self) -> bool {
+        if !self.hint_state.is_enabled() || self.quiz_state.is_resolved() || self.out_of_hints() {
+            return false;
+        }
+        self.hint_state = self.next_hint_state();
+        self.quiz_state
+            .record_hints_used(self.hint_state.hints_viewed());
+        self.quiz_state
+            .record_hint_index(Some(self.hint_state.hint_index()));
+        true
+    }
+
+    /// Shows the hints the current question had on screen when it was left,
+    /// so moving back and forth doesn't lose the place
+    fn restore_hints(&mut self) {
+        self.hint_state.reset();
+        let index = self.quiz_state.current_index();
+        let hints = self.quiz_state.current_question().hints.len();
+        if let Some(hint) = self.quiz_state.records()[index].hint_index {
+            if self.hint_state.is_enabled() && hints > 0 {
+                self.hint_state.show_hint(hint.min(hints - 1));
+            }
+        }
+    }
+
+    /// The hint state after one more hint request
+    fn next_hint_state(&self) -> HintState {
+        let mut next = self.hint_state.clone();
+        if next.show_hints() {
+            let max_hints = self.quiz_state.current_question().hints.len();
+            if self.config.wrap_hints {
+                next.cycle_hint(max_hints);
+            } else {
+                next.next_hint(max_hints);
+            }
+        } else {
+            next.enable_hints();
+        }
+        next
+    }
+
+    fn submit(&mut self, answer: String) -> bool {
+        if self.quiz_state.is_resolved() {
+            return false;
+        }
+        let verdict = self
+            .scorer
+            .grade(self.quiz_state.current_question(), &answer);
+        // A mastery drill counts every graded answer, so it never leaves one open
+        if self.config.retry_wrong
+            && self.config.mastery.is_none()
+            && !verdict.is_correct()
+            && !self.quiz_state.timer().is_expired()
+        {
+            self.quiz_state.record_attempt(answer);
+            return true;
+        }
+        self.quiz_state.submit_answer(answer, verdict.is_correct());
+        self.quiz_state.award_partial_credit(verdict.credit());
+        self.verdict = Some(verdict);
+        true
+    }
+
+    fn extend_time(&mut self) -> bool {
+        if !self.config.allows_time_extension() || self.quiz_state.is_resolved() {
+            return false;
+        }
+        self.quiz_state.extend_time(QuizConfig::EXTENSION_SECS);
+        true
+    }
+
+    fn restart_timer(&mut self) -> bool {
+        if !self.config.allows_timer_restart() || self.quiz_state.is_resolved() {
+            return false;
+        }
+        self.quiz_state.restart_timer();
+        self.quiz_state.record_hint_index(None);
+        self.hint_state.reset();
+        true
+    }
+
+    fn retry(&mut self) -> bool {
+        if !self.quiz_state.is_resolved() {
+            return false;
+        }
+        self.quiz_state.retry_current();
+        self.hint_state.reset();
+        self.verdict = None;
+        true
+    }
+
+    /// Adaptive mode: grades the question being left on the ladder and brings
+    /// the best match for the new tier forward
+    fn adapt_next(&mut self) {
+        let Some(ladder) = &mut self.ladder else {
+            return;
+        };
+        let tier = ladder.record(self.quiz_state.current_status() == QuestionStatus::Pass);
+        let next = self.quiz_state.current_index() + 1;
+        let pool = self.quiz_state.questions().get(next..).unwrap_or_default();
+        if let Some(offset) = adaptive::pick(pool, tier) {
+            self.quiz_state.queue_next(next + offset);
+        }
+    }
+
+    /// The tier each graded question was asked at, in adaptive mode; empty otherwise
+    pub fn tier_history(&self) -> &[Difficulty] {
+        self.ladder.as_ref().map_or(&[], TierLadder::history)
+    }
+
+    fn advance(&mut self) -> bool {
+        // Stay on the question until the next one has loaded
+        if self.quiz_state.is_waiting_for_next() {
+            return false;
+        }
+        self.quiz_state.finish_current();
+        if self.config.mode == Mode::Drill
+            && self.quiz_state.current_status() != QuestionStatus::Pass
+        {
+            self.quiz_state.requeue_current();
+        }
+        self.adapt_next();
+
+        if self.quiz_state.is_last_question() {
+            tracing::info!(
+                correct = self.quiz_state.correct_count(),
+                total = self.quiz_state.total_questions(),
+                "quiz finished"
+            );
+            self.finished = true;
+            self.hint_state.reset();
+        } else {
+            self.quiz_state.next_question();
+            self.restore_hints();
+        }
+        self.verdict = None;
+        true
+    }
+}
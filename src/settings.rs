@@ -0,0 +1,694 @@
+use crate::anki::AnkiOptions;
+use crate::audio::SoundSettings;
+use crate::authoring::AddOptions;
+use crate::cli::{Cli, Command, ProfilesCommand};
+use crate::cluster::{ClusterSettings, PrepOptions};
+use crate::config::{DuplicateIds, Freshness, Mastery, Mode, QuizConfig};
+use crate::editor::EditorSettings;
+use crate::goal::DailyGoal;
+use crate::i18n;
+use crate::keymap::{KeyBindings, KeymapError, KeymapPreset};
+use crate::list::ListOptions;
+use crate::logging::LogSettings;
+use crate::markdown_import::ImportOptions;
+use crate::playlist::{Playlist, PlaylistError, PlaylistRepository};
+use crate::print::PrintOptions;
+use crate::profile::{self, ProfileError, ProfileName};
+#[cfg(feature = "bundled")]
+use crate::question_repository::BundledQuestionRepository;
+#[cfg(not(feature = "bundled"))]
+use crate::question_repository::InMemoryQuestionRepository;
+use crate::question_repository::{
+    DirectoryQuestionRepository, FileQuestionRepository, QuestionRepository,
+};
+use crate::report::ReportFormat;
+use crate::results::SessionResult;
+use crate::scorer::{Grading, Strictness};
+use crate::serve::ServeOptions;
+use crate::shell::{ExamShell, ShellSettings};
+use crate::stats;
+use crate::theme::ThemeName;
+use crate::timer::FrameRate;
+use crate::ui::ScreenLayout;
+use crate::validation::ValidateOptions;
+use crate::webhook::WebhookSettings;
+use chrono::{DateTime, Local, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "ckad-practitioner";
+/// The start screen's 3-2-1
+const DEFAULT_COUNTDOWN_SECS: u64 = 3;
+
+/// Where questions are loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestionSource {
+    BuiltIn,
+    File(PathBuf),
+    Directory(PathBuf),
+}
+
+/// Contents of `config.toml`; every value is optional
+#[derive(Debug, Default, Deserialize)]
+pub struct FileConfig {
+    #[serde(default)]
+    pub defaults: DefaultsSection,
+    #[serde(default)]
+    pub timer: TimerSection,
+    #[serde(default)]
+    pub ui: UiSection,
+    #[serde(default)]
+    pub stats: StatsSection,
+    #[serde(default)]
+    pub shell: ShellSection,
+    #[serde(default)]
+    pub editor: EditorSection,
+    #[serde(default)]
+    pub goal: GoalSection,
+    #[serde(default)]
+    pub webhook: WebhookSection,
+    #[serde(default)]
+    pub sounds: SoundsSection,
+    #[serde(default)]
+    pub cluster: ClusterSection,
+    /// Action name to key, e.g. `next = "j"` or `quit = "ctrl+q"`
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DefaultsSection {
+    pub file: Option<PathBuf>,
+    pub dir: Option<PathBuf>,
+    pub mode: Option<Mode>,
+    pub shuffle: Option<bool>,
+    pub sort_by_id: Option<bool>,
+    pub by_priority: Option<bool>,
+    pub priority_only: Option<bool>,
+    pub shuffle_hints: Option<bool>,
+    pub wrap_hints: Option<bool>,
+    pub hint_budget: Option<usize>,
+    pub penalize_extra_commands: Option<bool>,
+    pub confidence: Option<bool>,
+    pub tags: Option<Vec<String>>,
+    pub limit: Option<usize>,
+    pub duplicate_ids: Option<DuplicateIds>,
+    pub guided: Option<bool>,
+    pub retry_wrong: Option<bool>,
+    pub case_sensitive: Option<bool>,
+    pub grade: Option<Grading>,
+    pub strictness: Option<Strictness>,
+    pub fresh_days: Option<u32>,
+    pub mastery_streak: Option<usize>,
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TimerSection {
+    pub warning_secs: Option<u64>,
+    pub bell: Option<bool>,
+    pub auto_advance_secs: Option<u64>,
+    pub reveal_delay_secs: Option<u64>,
+    pub ready_gate: Option<bool>,
+    pub accommodations: Option<bool>,
+    pub allow_restart: Option<bool>,
+    pub idle_pause_secs: Option<u64>,
+    pub break_every: Option<usize>,
+    pub break_secs: Option<u64>,
+    pub strict_breaks: Option<bool>,
+    /// Open on a screen with the session's parameters before the first clock
+    pub start_screen: Option<bool>,
+    /// Countdown after Enter on the start screen; 0 starts at once
+    pub countdown_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UiSection {
+    pub theme: Option<ThemeName>,
+    pub keymap: Option<KeymapPreset>,
+    pub reveal_animation: Option<bool>,
+    pub flash: Option<bool>,
+    /// Language code or locale name, e.g. "de" or "de_DE.UTF-8"
+    pub language: Option<String>,
+    pub fps: Option<u32>,
+    pub layout: Option<ScreenLayout>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsSection {
+    pub data_dir: Option<PathBuf>,
+    pub bank_updates: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ShellSection {
+    pub command: Option<String>,
+    pub exam: Option<ExamShell>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GoalSection {
+    pub questions: Option<usize>,
+    pub minutes: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WebhookSection {
+    pub url: Option<String>,
+    pub token: Option<String>,
+    pub retries: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SoundsSection {
+    pub enabled: Option<bool>,
+    pub correct: Option<PathBuf>,
+    pub incorrect: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ClusterSection {
+    pub deny_contexts: Option<Vec<String>>,
+    pub log_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct EditorSection {
+    pub command: Option<String>,
+    pub timed: Option<bool>,
+}
+
+/// Errors raised while reading the configuration file
+#[derive(Debug)]
+pub enum SettingsError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    Keys(KeymapError),
+    Profile(ProfileError),
+    Playlist(PlaylistError),
+    /// A command that writes to a bank was given no `--file`
+    MissingFile(&'static str),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io { path, source } => {
+                write!(f, "failed to read config {}: {}", path.display(), source)
+            }
+            SettingsError::Parse { path, message } => {
+                write!(f, "invalid config {}: {}", path.display(), message)
+            }
+            SettingsError::Keys(err) => write!(f, "invalid [keys] config: {}", err),
+            SettingsError::Profile(err) => write!(f, "invalid profile: {}", err),
+            SettingsError::Playlist(err) => write!(f, "{}", err),
+            SettingsError::MissingFile(command) => {
+                write!(
+                    f,
+                    "{} needs --file <PATH> to know which bank to write",
+                    command
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// Fully resolved settings for a run
+/// Precedence: command-line flags, then the config file, then built-in defaults
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub source: QuestionSource,
+    pub quiz: QuizConfig,
+    pub theme: ThemeName,
+    pub layout: ScreenLayout,
+    pub keymap: KeyBindings,
+    /// Redraw rate for animations, `--fps` clamped to a sane range
+    pub frame_rate: FrameRate,
+    pub grading: Grading,
+    pub strictness: Strictness,
+    /// `--playlist FILE`, read by the caller and passed to [`Settings::with_playlist`]
+    pub playlist_file: Option<PathBuf>,
+    /// The loaded playlist: picks and orders the source's questions
+    pub playlist: Option<Playlist>,
+    /// `--daily`, dated by [`Settings::with_clock`]
+    pub daily: bool,
+    /// `--fresh-days`, 0 meaning off; a filter once [`Settings::with_clock`] has the history
+    pub fresh_days: Option<u32>,
+    pub shell: ShellSettings,
+    pub editor: EditorSettings,
+    pub sounds: SoundSettings,
+    pub cluster: ClusterSettings,
+    pub goal: DailyGoal,
+    /// Countdown seconds on the start screen; `None` skips the screen
+    pub start_countdown: Option<u64>,
+    /// Locale for screen text: `[ui] language`, else the environment's
+    pub locale: String,
+    /// Where this run reads and writes its data: the profile's directory when a
+    /// profile is active, else `data_root`
+    pub data_dir: PathBuf,
+    /// The configured data directory, holding `profiles/`
+    pub data_root: PathBuf,
+    /// Note once a day at launch how the bank changed since the last check
+    pub bank_updates: bool,
+    pub profile: Option<ProfileName>,
+    /// `profiles list`: print the profiles instead of running the quiz
+    pub list_profiles: bool,
+    /// `list`: print the selected questions as a table or JSON and exit
+    pub list: Option<ListOptions>,
+    /// `add`: prompt for a question and append it to a bank
+    pub add: Option<AddOptions>,
+    /// `typing`: run the typing benchmark instead of the quiz
+    pub typing: bool,
+    /// `export-anki`: print the selected questions as an Anki deck and exit
+    pub export_anki: Option<AnkiOptions>,
+    /// `import-md`: convert Markdown exercises to a YAML bank and exit
+    pub import_md: Option<ImportOptions>,
+    /// `validate`: check a bank and/or write its schema, then exit
+    pub validate: Option<ValidateOptions>,
+    /// `serve`: show saved sessions as web pages on localhost
+    pub serve: Option<ServeOptions>,
+    /// `prep-cluster`: set up or tear down a question's practice namespace
+    pub prep_cluster: Option<PrepOptions>,
+    /// File logging; disabled unless asked for
+    pub log: LogSettings,
+    /// Extra copy of the session results
+    pub results_out: Option<PathBuf>,
+    /// Per-question timing CSV for spreadsheets
+    pub export_csv: Option<PathBuf>,
+    /// Human-readable report written next to the saved results
+    pub report: Option<ReportFormat>,
+    /// Where session results are posted; off without a URL
+    pub webhook: Option<WebhookSettings>,
+    /// `flush-outbox`: retry the webhook payloads that failed earlier
+    pub flush_outbox: bool,
+    /// `stats`: print hint usage from the saved sessions
+    pub stats: bool,
+    /// Line-based quiz instead of the TUI
+    pub plain: bool,
+    /// Print questions instead of running the TUI (`--print`/`--print-answers`)
+    pub print: bool,
+    /// Also used when printing because stdout is not a terminal
+    pub print_options: PrintOptions,
+}
+
+impl Settings {
+    pub fn resolve(cli: &Cli, file: &FileConfig) -> Result<Self, SettingsError> {
+        let defaults = &file.defaults;
+        let builtin = QuizConfig::default();
+
+        let source = match (&cli.file, &cli.dir, &defaults.file, &defaults.dir) {
+            (Some(path), _, _, _) => QuestionSource::File(path.clone()),
+            (None, Some(path), _, _) => QuestionSource::Directory(path.clone()),
+            (None, None, Some(path), _) => QuestionSource::File(path.clone()),
+            (None, None, None, Some(path)) => QuestionSource::Directory(path.clone()),
+            (None, None, None, None) => QuestionSource::BuiltIn,
+        };
+
+        let mut quiz = QuizConfig {
+            mode: if cli.study {
+                Mode::Study
+            } else {
+                cli.mode.or(defaults.mode).unwrap_or(builtin.mode)
+            },
+            shuffle_questions: flag(cli.shuffle, cli.no_shuffle)
+                .or(defaults.shuffle)
+                .unwrap_or(builtin.shuffle_questions),
+            sort_by_id: flag(cli.sort_by_id, cli.no_sort_by_id)
+                .or(defaults.sort_by_id)
+                .unwrap_or(builtin.sort_by_id),
+            by_priority: flag(cli.by_priority, cli.no_by_priority)
+                .or(defaults.by_priority)
+                .unwrap_or(builtin.by_priority),
+            priority_only: flag(cli.priority_only, cli.no_priority_only)
+                .or(defaults.priority_only)
+                .unwrap_or(builtin.priority_only),
+            shuffle_hints: flag(cli.shuffle_hints, cli.no_shuffle_hints)
+                .or(defaults.shuffle_hints)
+                .unwrap_or(builtin.shuffle_hints),
+            wrap_hints: flag(cli.wrap_hints, cli.no_wrap_hints)
+                .or(defaults.wrap_hints)
+                .unwrap_or(builtin.wrap_hints),
+            hint_budget: cli.hint_budget.or(defaults.hint_budget),
+            penalize_extra_commands: defaults
+                .penalize_extra_commands
+                .unwrap_or(builtin.penalize_extra_commands),
+            seed: cli.seed,
+            tags: if cli.tags.is_empty() {
+                defaults.tags.clone().unwrap_or(builtin.tags)
+            } else {
+                cli.tags.clone()
+            },
+            limit: cli.limit.or(defaults.limit),
+            duplicate_ids: defaults.duplicate_ids.unwrap_or(builtin.duplicate_ids),
+            choices: cli.choices.map(usize::from),
+            case_sensitive: flag(cli.no_ignore_case, cli.ignore_case)
+                .or(defaults.case_sensitive)
+                .unwrap_or(builtin.case_sensitive),
+            guided: flag(cli.guided, cli.no_guided)
+                .or(defaults.guided)
+                .unwrap_or(builtin.guided),
+            retry_wrong: flag(cli.retry_wrong, cli.no_retry_wrong)
+                .or(defaults.retry_wrong)
+                .unwrap_or(builtin.retry_wrong),
+            reveal_animation: file.ui.reveal_animation.unwrap_or(builtin.reveal_animation),
+            flash: file.ui.flash.unwrap_or(builtin.flash),
+            warning_secs: file.timer.warning_secs.unwrap_or(builtin.warning_secs),
+            bell: flag(cli.bell, cli.no_bell)
+                .or(file.timer.bell)
+                .unwrap_or(builtin.bell),
+            auto_advance_secs: cli.auto_advance.or(file.timer.auto_advance_secs),
+            idle_pause_secs: cli.idle_pause.or(file.timer.idle_pause_secs),
+            break_every: cli.break_every.or(file.timer.break_every),
+            break_secs: cli
+                .break_secs
+                .or(file.timer.break_secs)
+                .unwrap_or(builtin.break_secs),
+            strict_breaks: file.timer.strict_breaks.unwrap_or(builtin.strict_breaks),
+            reveal_delay_secs: cli
+                .reveal_delay
+                .or(file.timer.reveal_delay_secs)
+                .unwrap_or(builtin.reveal_delay_secs),
+            ready_gate: flag(cli.ready_gate, cli.no_ready_gate)
+                .or(file.timer.ready_gate)
+                .unwrap_or(builtin.ready_gate),
+            accommodations: flag(cli.accommodations, cli.no_accommodations)
+                .or(file.timer.accommodations)
+                .unwrap_or(builtin.accommodations),
+            allow_restart: file.timer.allow_restart.unwrap_or(builtin.allow_restart),
+            confidence_prompt: flag(cli.confidence, cli.no_confidence)
+                .or(defaults.confidence)
+                .unwrap_or(builtin.confidence_prompt),
+            // Dated, read and looked up by `with_playlist` and `with_clock`
+            daily: None,
+            sections: BTreeMap::new(),
+            fresh: None,
+            mastery: cli.master.map(|id| Mastery {
+                id,
+                streak: cli
+                    .streak
+                    .or(defaults.mastery_streak)
+                    .unwrap_or(Mastery::DEFAULT_STREAK),
+            }),
+        };
+        // The accessible layout spells out what color and animation would show
+        let layout = cli.layout.or(file.ui.layout).unwrap_or_default();
+        if layout == ScreenLayout::Accessible {
+            quiz.reveal_animation = false;
+            quiz.flash = false;
+        }
+        let theme = if cli.no_color || layout == ScreenLayout::Accessible {
+            ThemeName::Mono
+        } else {
+            file.ui.theme.unwrap_or_default()
+        };
+
+        let data_root = file.stats.data_dir.clone().unwrap_or_else(default_data_dir);
+        let profile = cli
+            .profile
+            .as_deref()
+            .or(defaults.profile.as_deref())
+            .map(ProfileName::parse)
+            .transpose()
+            .map_err(SettingsError::Profile)?;
+        let data_dir = match &profile {
+            Some(profile) => profile::profile_dir(&data_root, profile),
+            None => data_root.clone(),
+        };
+        let fresh_days = cli
+            .fresh_days
+            .or(defaults.fresh_days)
+            .filter(|&days| days > 0);
+
+        let add = match cli.command {
+            Some(Command::Add { from_template }) => Some(AddOptions {
+                file: cli.file.clone().ok_or(SettingsError::MissingFile("add"))?,
+                template: from_template,
+            }),
+            _ => None,
+        };
+
+        let keymap = KeyBindings::preset(file.ui.keymap.unwrap_or_default())
+            .with_overrides(&file.keys)
+            .map_err(SettingsError::Keys)?;
+
+        let frame_rate = match cli.fps.or(file.ui.fps) {
+            Some(fps) => {
+                let frame_rate = FrameRate::new(fps);
+                if frame_rate.fps() != fps {
+                    tracing::warn!(fps, clamped = frame_rate.fps(), "frame rate out of range");
+                }
+                frame_rate
+            }
+            None => FrameRate::default(),
+        };
+
+        Ok(Self {
+            source,
+            quiz,
+            theme,
+            layout,
+            keymap,
+            frame_rate,
+            grading: cli.grade.or(defaults.grade).unwrap_or_default(),
+            strictness: cli.strictness.or(defaults.strictness).unwrap_or_default(),
+            playlist_file: cli.playlist.clone(),
+            playlist: None,
+            daily: cli.daily,
+            fresh_days,
+            shell: ShellSettings {
+                command: file.shell.command.clone(),
+                exam: file.shell.exam.unwrap_or_default(),
+            },
+            editor: EditorSettings {
+                command: file.editor.command.clone(),
+                timed: file.editor.timed.unwrap_or_default(),
+            },
+            sounds: SoundSettings {
+                enabled: flag(cli.sounds, cli.no_sounds)
+                    .or(file.sounds.enabled)
+                    .unwrap_or_default(),
+                correct: file.sounds.correct.clone(),
+                incorrect: file.sounds.incorrect.clone(),
+            },
+            cluster: ClusterSettings {
+                deny_contexts: file
+                    .cluster
+                    .deny_contexts
+                    .clone()
+                    .unwrap_or_else(|| ClusterSettings::default().deny_contexts),
+                log_file: Some(
+                    file.cluster
+                        .log_file
+                        .clone()
+                        .unwrap_or_else(|| data_dir.join("cluster.log")),
+                ),
+            },
+            goal: DailyGoal {
+                questions: file.goal.questions,
+                minutes: file.goal.minutes,
+            },
+            start_countdown: flag(cli.countdown, cli.no_countdown)
+                .or(file.timer.start_screen)
+                .unwrap_or(true)
+                .then(|| file.timer.countdown_secs.unwrap_or(DEFAULT_COUNTDOWN_SECS)),
+            locale: file
+                .ui
+                .language
+                .clone()
+                .or_else(i18n::locale_from_env)
+                .unwrap_or_else(|| i18n::FALLBACK_LOCALE.to_string()),
+            data_dir,
+            data_root,
+            bank_updates: file.stats.bank_updates.unwrap_or(true),
+            profile,
+            list_profiles: cli.command
+                == Some(Command::Profiles {
+                    action: ProfilesCommand::List,
+                }),
+            list: match cli.command {
+                Some(Command::List { format, sort }) => Some(ListOptions { format, sort }),
+                _ => None,
+            },
+            add,
+            typing: cli.command == Some(Command::Typing),
+            export_anki: match cli.command {
+                Some(Command::ExportAnki { only }) => Some(AnkiOptions { only }),
+                _ => None,
+            },
+            import_md: match &cli.command {
+                Some(Command::ImportMd { path, output }) => Some(ImportOptions {
+                    path: path.clone(),
+                    output: output.clone(),
+                }),
+                _ => None,
+            },
+            serve: match &cli.command {
+                Some(Command::Serve { paths }) => Some(ServeOptions {
+                    paths: paths.clone(),
+                }),
+                _ => None,
+            },
+            prep_cluster: match cli.command {
+                Some(Command::PrepCluster { id, teardown }) => Some(PrepOptions { id, teardown }),
+                _ => None,
+            },
+            validate: match &cli.command {
+                Some(Command::Validate { bank, schema_out }) => Some(ValidateOptions {
+                    bank: bank.clone(),
+                    schema_out: schema_out.clone(),
+                }),
+                _ => None,
+            },
+            log: LogSettings {
+                debug: cli.debug,
+                file: cli.log_file.clone(),
+                level: cli.log_level,
+            },
+            results_out: cli.results_out.clone(),
+            export_csv: cli.export_csv.clone(),
+            report: cli.report,
+            webhook: cli
+                .webhook
+                .clone()
+                .or_else(|| file.webhook.url.clone())
+                .map(|url| {
+                    WebhookSettings::new(url)
+                        .with_token(file.webhook.token.clone())
+                        .with_retries(
+                            file.webhook
+                                .retries
+                                .unwrap_or(WebhookSettings::DEFAULT_RETRIES),
+                        )
+                }),
+            flush_outbox: cli.command == Some(Command::FlushOutbox),
+            stats: cli.command == Some(Command::Stats),
+            plain: cli.plain,
+            print: cli.print || cli.print_answers,
+            print_options: PrintOptions {
+                format: cli.print_format,
+                answers: cli.print_answers,
+            },
+        })
+    }
+
+    /// Lets a loaded `--playlist` decide which questions run and in what
+    /// order, over any ordering flags
+    pub fn with_playlist(mut self, playlist: Playlist) -> Self {
+        self.quiz.sections = playlist.section_starts();
+        self.quiz.shuffle_questions = false;
+        self.quiz.sort_by_id = false;
+        self.quiz.by_priority = false;
+        self.quiz.priority_only = false;
+        self.quiz.tags.clear();
+        self.playlist = Some(playlist);
+        self
+    }
+
+    /// Dates `--daily` and the `--fresh-days` window at `now`; `history` is the
+    /// saved sessions the window looks back over. Call it after
+    /// [`Settings::with_playlist`].
+    pub fn with_clock(mut self, now: DateTime<Local>, history: &[SessionResult]) -> Self {
+        if self.daily {
+            self.quiz.daily = Some(now.date_naive());
+        }
+        // The question of the day and playlists pick their own questions
+        if let (Some(days), false, None) = (self.fresh_days, self.daily, &self.playlist) {
+            self.quiz.fresh = Some(Freshness {
+                days,
+                now: now.with_timezone(&Utc),
+                last_seen: stats::last_seen(history),
+            });
+        }
+        self
+    }
+
+    pub fn repository(&self) -> Box<dyn QuestionRepository> {
+        let bank = self.bank();
+        match &self.playlist {
+            Some(playlist) => Box::new(PlaylistRepository::new(bank, playlist.clone())),
+            None => bank,
+        }
+    }
+
+    /// The question source without the playlist
+    fn bank(&self) -> Box<dyn QuestionRepository> {
+        match &self.source {
+            #[cfg(feature = "bundled")]
+            QuestionSource::BuiltIn => Box::new(BundledQuestionRepository::new()),
+            #[cfg(not(feature = "bundled"))]
+            QuestionSource::BuiltIn => Box::new(InMemoryQuestionRepository),
+            QuestionSource::File(path) => Box::new(FileQuestionRepository::new(
+                path.to_string_lossy().into_owned(),
+            )),
+            QuestionSource::Directory(path) => {
+                Box::new(DirectoryQuestionRepository::new(path.clone()))
+            }
+        }
+    }
+}
+
+/// A `--x`/`--no-x` pair as an override of the config file: `None` when
+/// neither was passed (clap keeps only the last of the two)
+fn flag(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// `$XDG_CONFIG_HOME/ckad-practitioner/config.toml` (or the platform equivalent)
+pub fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR).join("config.toml"))
+}
+
+/// `$XDG_DATA_HOME/ckad-practitioner` (or the platform equivalent)
+pub fn default_data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+/// Reads the config file; a missing default file is not an error, an explicit one is
+pub fn load_file_config(path: Option<&Path>) -> Result<(FileConfig, Vec<String>), SettingsError> {
+    let (path, explicit) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok((FileConfig::default(), Vec::new())),
+        },
+    };
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound && !explicit => {
+            return Ok((FileConfig::default(), Vec::new()))
+        }
+        Err(source) => return Err(SettingsError::Io { path, source }),
+    };
+
+    parse_file_config(&contents).map_err(|message| SettingsError::Parse { path, message })
+}
+
+/// Parses config text, collecting unknown keys as warnings so newer configs still load
+pub fn parse_file_config(contents: &str) -> Result<(FileConfig, Vec<String>), String> {
+    let mut warnings = Vec::new();
+    let deserializer = toml::Deserializer::new(contents);
+    let config = serde_ignored::deserialize(deserializer, |path| {
+        warnings.push(format!("unknown config key `{}` ignored", path));
+    })
+    .map_err(|err| err.to_string())?;
+    Ok((config, warnings))
+}
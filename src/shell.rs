@@ -0,0 +1,96 @@
+use crate::terminal::{self, TtyModes};
+use serde::Deserialize;
+use std::io;
+use std::process::ExitStatus;
+use tokio::process::Command;
+
+/// What the shell key does in exam mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExamShell {
+    /// Not available, like the real exam's single terminal
+    #[default]
+    Disabled,
+    /// Available, but the clock keeps running while the shell is open
+    Timed,
+}
+
+/// Shell opened from the quiz, e.g. to try a command against a cluster
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShellSettings {
+    /// Command line to run instead of `$SHELL`, split on whitespace
+    pub command: Option<String>,
+    pub exam: ExamShell,
+}
+
+impl ShellSettings {
+    /// The configured command, else `$SHELL`, else `/bin/sh` (`cmd` on Windows)
+    pub fn command_line(&self) -> Vec<String> {
+        let line = self
+            .command
+            .clone()
+            .or_else(|| std::env::var("SHELL").ok())
+            .filter(|line| !line.trim().is_empty())
+            .unwrap_or_else(|| default_shell().to_string());
+        line.split_whitespace().map(str::to_string).collect()
+    }
+}
+
+#[cfg(unix)]
+fn default_shell() -> &'static str {
+    "/bin/sh"
+}
+
+#[cfg(not(unix))]
+fn default_shell() -> &'static str {
+    "cmd"
+}
+
+/// Hands the terminal to the shell and takes it back once the shell exits
+/// The tty settings from before the shell are put back whatever the shell did
+/// to them, and the TUI is re-entered even when the shell could not start.
+/// The outer error is about the terminal, the inner one about starting the shell.
+pub async fn run(settings: &ShellSettings) -> io::Result<io::Result<ExitStatus>> {
+    hand_over(
+        &settings.command_line(),
+        "Quiz paused. Exit the shell to return.",
+    )
+    .await
+}
+
+/// Leaves the TUI, runs `argv` in the terminal after printing `banner`, and
+/// comes back; shared by the shell and the answer editor
+pub async fn hand_over(argv: &[String], banner: &str) -> io::Result<io::Result<ExitStatus>> {
+    terminal::leave()?;
+    let modes = TtyModes::save();
+    println!("{}", banner);
+
+    let status = spawn_and_wait(argv).await;
+
+    if let Some(modes) = &modes {
+        modes.restore()?;
+    }
+    terminal::enter()?;
+    Ok(status)
+}
+
+async fn spawn_and_wait(argv: &[String]) -> io::Result<ExitStatus> {
+    // Ctrl+C in the child reaches this process too now that raw mode is off;
+    // listening for the signals keeps it from killing the quiz
+    #[cfg(unix)]
+    let _guards = {
+        use tokio::signal::unix::{signal, SignalKind};
+        (
+            signal(SignalKind::interrupt())?,
+            signal(SignalKind::quit())?,
+        )
+    };
+
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty command"))?;
+    tracing::info!(program = %program, "handing over the terminal");
+    let status = Command::new(program).args(args).status().await;
+    tracing::info!(status = ?status.as_ref().map(|status| status.code()), "terminal handed back");
+    status
+}
@@ -0,0 +1,526 @@
+use crate::models::{self, Question};
+use crate::question_repository::QuestionRepository;
+use crate::quiz_state::{Confidence, QuestionStatus, QuizState};
+use crate::results::{self, SessionResult};
+use crate::typing::TypingResult;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Reads every saved session in the results directory
+/// A missing directory is an empty history; unreadable files are logged and skipped.
+pub fn load_history(results_dir: &Path) -> Vec<SessionResult> {
+    load_json_dir(results_dir)
+}
+
+/// Reads the saved sessions after rekeying them by the identities of
+/// `repository`'s questions (see [`migrate_history`])
+/// A paged bank isn't loaded whole for this, so its older sessions keep the
+/// keys they were saved with.
+pub fn load_history_for(
+    results_dir: &Path,
+    repository: &dyn QuestionRepository,
+) -> Vec<SessionResult> {
+    if !repository.supports_paging() {
+        if let Ok(questions) = repository.get_questions() {
+            match migrate_history(results_dir, &questions) {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "rekeyed saved sessions by question identity"),
+                Err(err) => tracing::warn!(error = %err, "could not rekey saved sessions"),
+            }
+        }
+    }
+    load_history(results_dir)
+}
+
+/// Every saved typing benchmark, oldest first
+pub fn load_typing_history(typing_dir: &Path) -> Vec<TypingResult> {
+    load_json_dir(typing_dir)
+}
+
+/// Parses each `.json` file in `dir` in file name order, skipping unreadable ones
+fn load_json_dir<T: DeserializeOwned>(dir: &Path) -> Vec<T> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let parsed = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|json| serde_json::from_str::<T>(&json).map_err(|err| err.to_string()));
+            match parsed {
+                Ok(result) => Some(result),
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), error = %err, "skipping history file");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Identities of the questions still missed: their latest answered outcome
+/// across past sessions is a fail, skip or reveal. Passing one again drops it.
+pub fn missed_questions(history: &[SessionResult]) -> HashSet<String> {
+    let mut latest: HashMap<String, QuestionStatus> = HashMap::new();
+    let mut sessions: Vec<&SessionResult> = history.iter().collect();
+    sessions.sort_by_key(|session| session.started_at);
+    for question in sessions.iter().flat_map(|session| &session.questions) {
+        if question.outcome != QuestionStatus::Pending {
+            latest.insert(question.identity(), question.outcome);
+        }
+    }
+    latest
+        .into_iter()
+        .filter(|(_, outcome)| *outcome != QuestionStatus::Pass)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// How confidence ratings compared with the verdicts
+/// High confidence on a wrong answer is overconfidence, low confidence on a
+/// right one underconfidence; medium is never either.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Calibration {
+    pub rated: usize,
+    pub overconfident: usize,
+    pub underconfident: usize,
+    /// Right and rated answers per level, high first
+    levels: [(usize, usize); 3],
+}
+
+impl Calibration {
+    /// From (rating, answer was right) pairs
+    pub fn from_ratings(ratings: impl IntoIterator<Item = (Confidence, bool)>) -> Self {
+        let mut calibration = Self::default();
+        for (confidence, correct) in ratings {
+            calibration.rated += 1;
+            let level = &mut calibration.levels[Self::level_index(confidence)];
+            level.1 += 1;
+            if correct {
+                level.0 += 1;
+            }
+            match (confidence, correct) {
+                (Confidence::High, false) => calibration.overconfident += 1,
+                (Confidence::Low, true) => calibration.underconfident += 1,
+                _ => {}
+            }
+        }
+        calibration
+    }
+
+    /// The rated answers of a session; a pass is right, anything else wrong
+    pub fn from_quiz(quiz_state: &QuizState) -> Self {
+        Self::from_ratings(quiz_state.records().iter().filter_map(|record| {
+            record
+                .confidence
+                .map(|confidence| (confidence, record.status == QuestionStatus::Pass))
+        }))
+    }
+
+    /// Share of the answers rated `confidence` that were right; `None` if none were
+    pub fn accuracy(&self, confidence: Confidence) -> Option<f64> {
+        let (correct, rated) = self.levels[Self::level_index(confidence)];
+        (rated > 0).then(|| correct as f64 / rated as f64)
+    }
+
+    pub fn is_calibrated(&self) -> bool {
+        self.overconfident == 0 && self.underconfident == 0
+    }
+
+    fn level_index(confidence: Confidence) -> usize {
+        match confidence {
+            Confidence::High => 0,
+            Confidence::Medium => 1,
+            Confidence::Low => 2,
+        }
+    }
+}
+
+/// Fastest passing time per question identity across past sessions
+#[derive(Debug, Clone, Default)]
+pub struct PersonalBests {
+    best_secs: HashMap<String, u64>,
+}
+
+impl PersonalBests {
+    pub fn from_history(history: &[SessionResult]) -> Self {
+        let mut best_secs: HashMap<String, u64> = HashMap::new();
+        for question in history.iter().flat_map(|session| &session.questions) {
+            if question.outcome != QuestionStatus::Pass {
+                continue;
+            }
+            best_secs
+                .entry(question.identity())
+                .and_modify(|best| *best = (*best).min(question.time_spent_secs))
+                .or_insert(question.time_spent_secs);
+        }
+        Self { best_secs }
+    }
+
+    /// Personal best for a question, `None` if it has never been passed
+    pub fn best(&self, question: &Question) -> Option<Duration> {
+        self.best_secs
+            .get(&question.identity())
+            .copied()
+            .map(Duration::from_secs)
+    }
+}
+
+/// Rekeys saved sessions from before question identities existed
+/// A result without a `uid`, or keyed by its text only, gets the identity of
+/// the loaded question with the same text, e.g. one that has a `uid` now.
+/// Results whose question isn't loaded are left for a later run. Returns how
+/// many files were rewritten; unreadable files are skipped.
+pub fn migrate_history(results_dir: &Path, questions: &[Question]) -> io::Result<usize> {
+    let by_text: HashMap<String, String> = questions
+        .iter()
+        .map(|question| {
+            (
+                models::text_identity(question.id, &question.question),
+                question.identity(),
+            )
+        })
+        .collect();
+    let Ok(entries) = fs::read_dir(results_dir) else {
+        return Ok(0);
+    };
+    let mut rewritten = 0;
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(mut session) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<SessionResult>(&json).ok())
+        else {
+            continue;
+        };
+        let mut changed = false;
+        for result in &mut session.questions {
+            if !result.uid.is_empty() && !result.uid.starts_with("text:") {
+                continue;
+            }
+            let text = models::text_identity(result.id, &result.question);
+            if let Some(identity) = by_text
+                .get(&text)
+                .filter(|identity| **identity != result.uid)
+            {
+                result.uid = identity.clone();
+                changed = true;
+            }
+        }
+        if changed {
+            results::write(&path, &session)?;
+            rewritten += 1;
+        }
+    }
+    Ok(rewritten)
+}
+
+/// Typing speed across saved benchmarks, to see it improve
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TypingTrend {
+    pub sessions: usize,
+    pub best_wpm: f64,
+    /// Average of the last `TypingTrend::RECENT` benchmarks
+    pub recent_wpm: f64,
+}
+
+impl TypingTrend {
+    pub const RECENT: usize = 5;
+
+    /// Benchmarks without any attempts don't count
+    pub fn from_history(history: &[TypingResult]) -> Self {
+        let speeds: Vec<f64> = history
+            .iter()
+            .filter(|result| !result.attempts.is_empty())
+            .map(TypingResult::wpm)
+            .collect();
+        if speeds.is_empty() {
+            return Self::default();
+        }
+        let recent = &speeds[speeds.len().saturating_sub(Self::RECENT)..];
+        Self {
+            sessions: speeds.len(),
+            best_wpm: speeds.iter().copied().fold(0.0, f64::max),
+            recent_wpm: recent.iter().sum::<f64>() / recent.len() as f64,
+        }
+    }
+}
+
+/// How a finished session compares with the one before it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunComparison {
+    /// Change in the score percentage, in percentage points
+    pub score_delta: f64,
+    /// Change in the time spent on questions, in seconds; negative is faster
+    pub time_delta_secs: i64,
+    /// Ids of this run's questions that the previous run missed and this one passed
+    pub newly_correct: Vec<usize>,
+}
+
+impl RunComparison {
+    /// Questions are matched by identity, so ids that moved between runs still
+    /// line up; `newly_correct` follows `current`'s order
+    pub fn between(previous: &SessionResult, current: &SessionResult) -> Self {
+        let missed: HashSet<String> = previous
+            .questions
+            .iter()
+            .filter(|question| {
+                !matches!(
+                    question.outcome,
+                    QuestionStatus::Pass | QuestionStatus::Pending
+                )
+            })
+            .map(|question| question.identity())
+            .collect();
+        let newly_correct = current
+            .questions
+            .iter()
+            .filter(|question| {
+                question.outcome == QuestionStatus::Pass && missed.contains(&question.identity())
+            })
+            .map(|question| question.id)
+            .collect();
+        Self {
+            score_delta: current.score.percent - previous.score.percent,
+            time_delta_secs: time_spent_secs(current) as i64 - time_spent_secs(previous) as i64,
+            newly_correct,
+        }
+    }
+}
+
+/// The latest complete session in `history`, the one a new run is compared with
+pub fn previous_run(history: &[SessionResult]) -> Option<&SessionResult> {
+    history
+        .iter()
+        .filter(|session| session.complete)
+        .max_by_key(|session| session.started_at)
+}
+
+fn time_spent_secs(session: &SessionResult) -> u64 {
+    session
+        .questions
+        .iter()
+        .map(|question| question.time_spent_secs)
+        .sum()
+}
+
+/// When each question identity was last attempted (answered, skipped or
+/// revealed), by the end of the session it was in
+pub fn last_seen(history: &[SessionResult]) -> HashMap<String, DateTime<Utc>> {
+    let mut seen: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for session in history {
+        for question in &session.questions {
+            if question.outcome == QuestionStatus::Pending {
+                continue;
+            }
+            seen.entry(question.identity())
+                .and_modify(|at| *at = (*at).max(session.finished_at))
+                .or_insert(session.finished_at);
+        }
+    }
+    seen
+}
+
+/// Where a session is headed at its current pace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinishProjection {
+    /// Time the whole session would take: the time spent so far plus the
+    /// average per completed question for each question left
+    pub estimated: Duration,
+    /// The questions' time limits added up
+    pub budget: Duration,
+}
+
+impl FinishProjection {
+    /// `None` before any question is completed, when there is no pace to go by
+    pub fn project(completed: &[Duration], remaining: usize, budget: Duration) -> Option<Self> {
+        let count = u32::try_from(completed.len())
+            .ok()
+            .filter(|&count| count > 0)?;
+        let spent: Duration = completed.iter().sum();
+        let average = spent / count;
+        let remaining = u32::try_from(remaining).unwrap_or(u32::MAX);
+        Some(Self {
+            estimated: spent.saturating_add(average.saturating_mul(remaining)),
+            budget,
+        })
+    }
+
+    /// The projection for a session so far; `None` while pages are still
+    /// loading, since the budget isn't known yet
+    pub fn for_quiz(quiz_state: &QuizState) -> Option<Self> {
+        if quiz_state.questions().len() < quiz_state.total_questions() {
+            return None;
+        }
+        let completed: Vec<Duration> = quiz_state
+            .records()
+            .iter()
+            .filter(|record| record.status != QuestionStatus::Pending)
+            .map(|record| record.time_spent.unwrap_or_default())
+            .collect();
+        let budget = quiz_state
+            .questions()
+            .iter()
+            .map(|question| Duration::from_secs(question.time_limit_secs))
+            .sum();
+        Self::project(
+            &completed,
+            quiz_state.total_questions() - completed.len(),
+            budget,
+        )
+    }
+
+    pub fn is_over_budget(&self) -> bool {
+        self.estimated > self.budget
+    }
+
+    /// Time to spare, or the time over budget when `is_over_budget`
+    pub fn margin(&self) -> Duration {
+        self.budget.abs_diff(self.estimated)
+    }
+}
+
+/// How often hints were shown across attempts of a question or a tag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HintUsage {
+    pub attempts: usize,
+    /// Attempts with at least one hint shown
+    pub with_hints: usize,
+}
+
+impl HintUsage {
+    pub fn record(&mut self, used_hints: bool) {
+        self.attempts += 1;
+        if used_hints {
+            self.with_hints += 1;
+        }
+    }
+
+    /// Share of the attempts that used hints, in percent
+    pub fn rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.with_hints as f64 * 100.0 / self.attempts as f64
+        }
+    }
+}
+
+/// Hint usage of one question across saved sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuestionHints {
+    /// The question text as last saved
+    pub question: String,
+    pub usage: HintUsage,
+    /// Times each hint was shown, by its position in the question's hints
+    pub by_hint: BTreeMap<usize, usize>,
+}
+
+/// Hint usage from saved sessions, per question and per tag
+/// An attempt is any answer, skip or reveal. Results saved before hint
+/// positions were recorded count their first `hints_used` hints as shown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HintStats {
+    /// By question identity
+    pub questions: BTreeMap<String, QuestionHints>,
+    pub tags: BTreeMap<String, HintUsage>,
+}
+
+impl HintStats {
+    pub fn from_history(history: &[SessionResult]) -> Self {
+        let mut stats = Self::default();
+        for question in history.iter().flat_map(|session| &session.questions) {
+            if question.outcome == QuestionStatus::Pending {
+                continue;
+            }
+            let viewed: Vec<usize> = if question.hints_viewed.is_empty() {
+                (0..question.hints_used).collect()
+            } else {
+                question.hints_viewed.clone()
+            };
+            let entry = stats.questions.entry(question.identity()).or_default();
+            entry.question = question.question.clone();
+            entry.usage.record(!viewed.is_empty());
+            for index in viewed.iter().copied() {
+                *entry.by_hint.entry(index).or_default() += 1;
+            }
+            for tag in &question.tags {
+                let tag = stats.tags.entry(tag.clone()).or_default();
+                tag.record(!viewed.is_empty());
+            }
+        }
+        stats
+    }
+
+    /// Usage of the question with `identity`; none for a question never attempted
+    pub fn question(&self, identity: &str) -> HintUsage {
+        self.questions
+            .get(identity)
+            .map(|question| question.usage)
+            .unwrap_or_default()
+    }
+}
+
+/// Writes the hint usage report for `stats`, tags first and then the
+/// questions that needed hints, each with the highest rate first
+pub fn write_hint_report<W: Write>(out: &mut W, stats: &HintStats) -> io::Result<()> {
+    if stats.questions.is_empty() {
+        return writeln!(out, "No saved attempts yet");
+    }
+    let by_rate = |a: &HintUsage, b: &HintUsage| b.rate().total_cmp(&a.rate());
+    let mut tags: Vec<_> = stats.tags.iter().collect();
+    tags.sort_by(|a, b| by_rate(a.1, b.1));
+    if !tags.is_empty() {
+        writeln!(out, "Hint usage by tag")?;
+        for (tag, usage) in tags {
+            writeln!(
+                out,
+                "  You used hints on {:.0}% of {} attempts ({} of {})",
+                usage.rate(),
+                tag,
+                usage.with_hints,
+                usage.attempts
+            )?;
+        }
+    }
+    let mut questions: Vec<_> = stats
+        .questions
+        .values()
+        .filter(|question| question.usage.with_hints > 0)
+        .collect();
+    questions.sort_by(|a, b| by_rate(&a.usage, &b.usage));
+    if questions.is_empty() {
+        return writeln!(out, "No hints used on any question");
+    }
+    writeln!(out, "Hint usage by question")?;
+    for question in questions {
+        let hints: Vec<String> = question
+            .by_hint
+            .iter()
+            .map(|(index, count)| format!("#{} ×{}", index + 1, count))
+            .collect();
+        writeln!(
+            out,
+            "  {:>4.0}% of {}  {}  (hints {})",
+            question.usage.rate(),
+            question.usage.attempts,
+            question.question.lines().next().unwrap_or_default(),
+            hints.join(", ")
+        )?;
+    }
+    Ok(())
+}
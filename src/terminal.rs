@@ -0,0 +1,63 @@
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io;
+
+/// Raw mode, alternate screen and mouse capture for the TUI
+pub fn enter() -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+}
+
+/// Undoes `enter`, leaving the terminal as the shell expects it
+pub fn leave() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    )
+}
+
+/// Snapshot of the tty line settings, so a child process that leaves the
+/// terminal in a strange state (raw, no echo, ...) can be undone
+#[cfg(unix)]
+pub struct TtyModes(libc::termios);
+
+#[cfg(unix)]
+impl TtyModes {
+    /// `None` when stdin is not a terminal
+    pub fn save() -> Option<Self> {
+        let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: tcgetattr fully initializes `termios` when it returns 0
+        let ok = unsafe { libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) } == 0;
+        // SAFETY: only read after a successful tcgetattr
+        ok.then(|| Self(unsafe { termios.assume_init() }))
+    }
+
+    pub fn restore(&self) -> io::Result<()> {
+        // SAFETY: `self.0` came from tcgetattr on the same descriptor
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub struct TtyModes;
+
+#[cfg(not(unix))]
+impl TtyModes {
+    pub fn save() -> Option<Self> {
+        None
+    }
+
+    pub fn restore(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
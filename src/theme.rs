@@ -0,0 +1,91 @@
+use ratatui::style::{Color, Style};
+use serde::Deserialize;
+
+/// Named themes selectable from the config file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    /// No colors at all, same as `--no-color`
+    Mono,
+}
+
+/// Colour settings for the UI
+/// In no-color mode every style collapses to the terminal default
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    color: bool,
+}
+
+impl Theme {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    pub fn from_name(name: ThemeName) -> Self {
+        Self::new(name != ThemeName::Mono)
+    }
+
+    pub fn color_enabled(&self) -> bool {
+        self.color
+    }
+
+    pub fn fg(&self, color: Color) -> Style {
+        if self.color {
+            Style::default().fg(color)
+        } else {
+            Style::default()
+        }
+    }
+}
+
+/// The tag and style that mark a question's category in the header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryStyle {
+    pub tag: String,
+    pub style: Style,
+}
+
+/// Exam domains by a word their names contain (older curricula included), with
+/// their tag and color; checked in order, so the first match wins
+const CATEGORY_STYLES: &[(&[&str], &str, Color)] = &[
+    (
+        &["design", "build", "multi-container"],
+        "BUILD",
+        Color::Magenta,
+    ),
+    (&["deploy"], "DEPLOY", Color::Cyan),
+    (&["observability", "maintenance"], "OBS", Color::Green),
+    (&["config", "environment", "security"], "CONF", Color::Blue),
+    (&["network", "service"], "NET", Color::Yellow),
+    (&["persistence", "storage"], "STORE", Color::LightRed),
+    (&["core"], "CORE", Color::LightBlue),
+];
+
+impl Theme {
+    /// Tag and color for a category, so domains tell apart at a glance in
+    /// mixed runs; other categories are tagged with their own name, uncolored
+    pub fn category_style(&self, category: &str) -> CategoryStyle {
+        let lower = category.to_lowercase();
+        CATEGORY_STYLES
+            .iter()
+            .find(|(words, _, _)| words.iter().any(|word| lower.contains(word)))
+            .map_or_else(
+                || CategoryStyle {
+                    tag: category.trim().to_string(),
+                    style: Style::default(),
+                },
+                |(_, tag, color)| CategoryStyle {
+                    tag: tag.to_string(),
+                    style: self.fg(*color),
+                },
+            )
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
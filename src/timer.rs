@@ -1,34 +1,350 @@
-use std::time::{Duration, Instant};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The header starts flashing when fewer seconds than this remain
+pub const FLASH_SECS: u64 = 5;
+
+/// Which of the two alternating header styles to show, flipping on each whole
+/// second of `remaining`; `None` outside the last `FLASH_SECS` or once time is up
+pub fn flash_phase(remaining: Duration) -> Option<bool> {
+    if remaining.is_zero() || remaining >= Duration::from_secs(FLASH_SECS) {
+        return None;
+    }
+    Some(remaining.as_secs().is_multiple_of(2))
+}
+
+/// How a finished question's time compares with its limit, to build a feel
+/// for pacing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUse {
+    /// At least `TIGHT_PERCENT` of the limit left: the whole percentage left
+    Spare(u64),
+    /// Under the limit, but with less than `TIGHT_PERCENT` of it left
+    JustUnder(Duration),
+    /// The whole limit or more, e.g. when time ran out
+    UsedAll,
+}
+
+impl TimeUse {
+    /// Finishing with less than this share of the limit left is cutting it close
+    pub const TIGHT_PERCENT: u64 = 10;
+
+    pub fn classify(spent: Duration, limit: Duration) -> Self {
+        if spent >= limit {
+            return Self::UsedAll;
+        }
+        let left = limit - spent;
+        let percent = (left.as_millis() * 100 / limit.as_millis()) as u64;
+        if percent >= Self::TIGHT_PERCENT {
+            Self::Spare(percent)
+        } else {
+            Self::JustUnder(left)
+        }
+    }
+}
+
+impl fmt::Display for TimeUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spare(percent) => write!(f, "Finished with {}% of the time to spare", percent),
+            Self::JustUnder(left) => {
+                let secs = left.as_secs();
+                write!(f, "Just made it, with {}:{:02} left", secs / 60, secs % 60)
+            }
+            Self::UsedAll => f.write_str("Used all the time"),
+        }
+    }
+}
+
+/// Where timers read the current time
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// tokio's clock: real time, unless a test runtime pauses it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that stands still until `advance` moves it; clones share one time,
+/// so a test can keep a handle to the clock it gave a timer
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap_or_else(|err| err.into_inner()) += by;
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
 
 /// Timer manages time-related logic for questions (Single Responsibility Principle)
+/// Reads the time from a `Clock`: tokio's by default, so tests can pause and
+/// fast-forward time, or a `MockClock` moved by hand
 #[derive(Debug)]
 pub struct Timer {
+    clock: Arc<dyn Clock>,
     started: Instant,
     limit: Duration,
+    /// Set while the clock is stopped, e.g. during a shell break
+    paused_at: Option<Instant>,
 }
 
 impl Timer {
     pub fn new(limit_secs: u64) -> Self {
+        Self::with_clock(limit_secs, Arc::new(TokioClock))
+    }
+
+    pub fn with_clock(limit_secs: u64, clock: Arc<dyn Clock>) -> Self {
         Self {
-            started: Instant::now(),
+            started: clock.now(),
+            clock,
             limit: Duration::from_secs(limit_secs),
+            paused_at: None,
         }
     }
 
+    /// The same timer already showing `elapsed`, e.g. for a recovered session
+    pub fn backdated(mut self, elapsed: Duration) -> Self {
+        let now = self.clock.now();
+        self.started = now.checked_sub(elapsed).unwrap_or(now);
+        self
+    }
+
+    /// The clock this timer reads, for timers that should share it
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock)
+    }
+
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
     pub fn elapsed(&self) -> Duration {
-        self.started.elapsed()
+        self.paused_at
+            .unwrap_or_else(|| self.clock.now())
+            .saturating_duration_since(self.started)
     }
 
     pub fn remaining(&self) -> Duration {
         self.limit.saturating_sub(self.elapsed())
     }
 
+    /// How long ago the limit was reached (zero while time remains)
+    pub fn overtime(&self) -> Duration {
+        self.elapsed().saturating_sub(self.limit)
+    }
+
     pub fn is_expired(&self) -> bool {
         self.elapsed() >= self.limit
     }
 
+    /// Whether an expired question's answer is still held back by a reveal
+    /// delay; false before expiry and from `delay_secs` past the limit on
+    pub fn reveal_pending(&self, delay_secs: u64) -> bool {
+        self.is_expired() && self.overtime() < Duration::from_secs(delay_secs)
+    }
+
+    /// Grants extra time without restarting the clock
+    pub fn extend(&mut self, secs: u64) {
+        self.limit += Duration::from_secs(secs);
+        tracing::debug!(secs, "timer extended");
+    }
+
+    /// Stops the clock until `resume`
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(self.clock.now());
+            tracing::debug!("timer paused");
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Restarts the clock, leaving out the time spent paused
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            let paused = self.clock.now().saturating_duration_since(paused_at);
+            self.started += paused;
+            tracing::debug!(paused_secs = paused.as_secs(), "timer resumed");
+        }
+    }
+
+    /// Flash phase for the header while under `FLASH_SECS` remain; see [`flash_phase`]
+    pub fn flash_phase(&self) -> Option<bool> {
+        flash_phase(self.remaining())
+    }
+
     pub fn reset(&mut self, limit_secs: u64) {
-        self.started = Instant::now();
+        tracing::debug!(limit_secs, "timer reset");
+        self.started = self.clock.now();
         self.limit = Duration::from_secs(limit_secs);
+        self.paused_at = None;
+    }
+}
+
+/// Notices when no key has been pressed for a while, so the clock can be
+/// paused for someone who stepped away
+#[derive(Debug)]
+pub struct IdleWatch {
+    clock: Arc<dyn Clock>,
+    threshold: Duration,
+    last_input: Instant,
+    idle: bool,
+}
+
+impl IdleWatch {
+    pub fn new(threshold: Duration) -> Self {
+        Self::with_clock(threshold, Arc::new(TokioClock))
+    }
+
+    pub fn with_clock(threshold: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            last_input: clock.now(),
+            clock,
+            threshold,
+            idle: false,
+        }
+    }
+
+    /// Records a key press; true if it ends an idle stretch
+    pub fn input(&mut self) -> bool {
+        self.last_input = self.clock.now();
+        std::mem::take(&mut self.idle)
+    }
+
+    /// True once per idle stretch: on the first check after `threshold`
+    /// passed without input
+    pub fn check(&mut self) -> bool {
+        let quiet = self.clock.now().saturating_duration_since(self.last_input);
+        if self.idle || quiet < self.threshold {
+            return false;
+        }
+        self.idle = true;
+        true
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+}
+
+/// A screen's countdown, e.g. before the first question or on a break: waits
+/// for `begin`, then counts down `secs`
+#[derive(Debug)]
+pub struct Countdown {
+    clock: Arc<dyn Clock>,
+    secs: u64,
+    /// When Enter was pressed; `None` while waiting for it
+    begun: Option<Instant>,
+}
+
+impl Countdown {
+    pub fn new(secs: u64) -> Self {
+        Self::with_clock(secs, Arc::new(TokioClock))
+    }
+
+    pub fn with_clock(secs: u64, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            secs,
+            begun: None,
+        }
+    }
+
+    /// Starts the count; pressing Enter again changes nothing
+    pub fn begin(&mut self) {
+        if self.begun.is_none() {
+            self.begun = Some(self.clock.now());
+        }
+    }
+
+    /// Whole seconds still to count, rounded up (3, 2, 1); `None` before Enter
+    pub fn remaining_secs(&self) -> Option<u64> {
+        let begun = self.begun?;
+        let elapsed = self.clock.now().saturating_duration_since(begun);
+        let remaining = Duration::from_secs(self.secs).saturating_sub(elapsed);
+        Some(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0))
+    }
+
+    /// Length of the count
+    pub fn secs(&self) -> u64 {
+        self.secs
+    }
+
+    /// Time since `begin`, zero before it
+    pub fn elapsed(&self) -> Duration {
+        self.begun.map_or(Duration::ZERO, |begun| {
+            self.clock.now().saturating_duration_since(begun)
+        })
+    }
+
+    /// True once the count has reached zero
+    pub fn is_done(&self) -> bool {
+        self.remaining_secs() == Some(0)
+    }
+}
+
+/// How often the quiz screen redraws while something animates
+/// The reveal animation and the final-seconds flash tick once per frame; lower
+/// rates save CPU at the cost of smoothness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRate {
+    fps: u32,
+}
+
+impl FrameRate {
+    pub const MIN_FPS: u32 = 5;
+    pub const MAX_FPS: u32 = 60;
+    pub const DEFAULT_FPS: u32 = 20;
+
+    /// `fps` clamped to `MIN_FPS..=MAX_FPS`
+    pub fn new(fps: u32) -> Self {
+        Self {
+            fps: fps.clamp(Self::MIN_FPS, Self::MAX_FPS),
+        }
+    }
+
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    /// Time between frames, e.g. 50ms at 20 fps
+    pub fn frame_period(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_FPS)
     }
 }
@@ -0,0 +1,306 @@
+use crate::models::Question;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Characters per word in words-per-minute figures, the usual typing-test convention
+pub const CHARS_PER_WORD: f64 = 5.0;
+
+/// How one character of a typed command compares with the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharState {
+    Correct,
+    Wrong,
+    /// Not typed yet
+    Pending,
+    /// Typed past the end of the target
+    Extra,
+}
+
+/// Per-character comparison of `typed` against `target`; one entry per
+/// position of the longer of the two
+pub fn compare(target: &str, typed: &str) -> Vec<CharState> {
+    let mut target = target.chars();
+    let mut typed = typed.chars();
+    let mut states = Vec::new();
+    loop {
+        let state = match (target.next(), typed.next()) {
+            (Some(expected), Some(actual)) if expected == actual => CharState::Correct,
+            (Some(_), Some(_)) => CharState::Wrong,
+            (Some(_), None) => CharState::Pending,
+            (None, Some(_)) => CharState::Extra,
+            (None, None) => return states,
+        };
+        states.push(state);
+    }
+}
+
+/// Mistakes left in `typed`: wrong, extra and missing characters
+pub fn uncorrected_errors(target: &str, typed: &str) -> usize {
+    compare(target, typed)
+        .into_iter()
+        .filter(|state| *state != CharState::Correct)
+        .count()
+}
+
+/// Gross words per minute for `chars` typed over `elapsed`; zero for no time
+pub fn wpm(chars: usize, elapsed: Duration) -> f64 {
+    let minutes = elapsed.as_secs_f64() / 60.0;
+    if minutes <= 0.0 {
+        return 0.0;
+    }
+    chars as f64 / CHARS_PER_WORD / minutes
+}
+
+/// Share of keystrokes that were right when typed, as a percentage
+pub fn accuracy(keystrokes: usize, typos: usize) -> f64 {
+    if keystrokes == 0 {
+        return 100.0;
+    }
+    keystrokes.saturating_sub(typos) as f64 * 100.0 / keystrokes as f64
+}
+
+/// A command to retype, taken from a question's answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypingTarget {
+    pub question_id: usize,
+    pub command: String,
+}
+
+/// The one-line commands in the questions' answers, in question order
+/// Manifest answers are skipped, and so are blank and comment lines.
+pub fn targets(questions: &[Question]) -> Vec<TypingTarget> {
+    questions
+        .iter()
+        .filter(|question| !question.yaml_answer)
+        .flat_map(|question| {
+            question
+                .answer
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| TypingTarget {
+                    question_id: question.id,
+                    command: line.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// One retyped command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypingAttempt {
+    pub question_id: usize,
+    pub command: String,
+    pub typed: String,
+    /// From the first keystroke to the last
+    pub elapsed_ms: u64,
+    /// Characters that didn't match when typed, corrected or not
+    pub typos: usize,
+    /// Characters typed, including ones later deleted
+    pub keystrokes: usize,
+    /// Mistakes still there when the command was finished
+    pub errors: usize,
+}
+
+impl TypingAttempt {
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    pub fn wpm(&self) -> f64 {
+        wpm(self.typed.chars().count(), self.elapsed())
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        accuracy(self.keystrokes, self.typos)
+    }
+}
+
+/// A finished (or abandoned) benchmark, saved for the typing history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypingResult {
+    pub started_at: DateTime<Utc>,
+    pub attempts: Vec<TypingAttempt>,
+}
+
+impl TypingResult {
+    /// Words per minute over every attempt, weighted by length
+    pub fn wpm(&self) -> f64 {
+        let chars = self
+            .attempts
+            .iter()
+            .map(|attempt| attempt.typed.chars().count())
+            .sum();
+        let elapsed = self.attempts.iter().map(TypingAttempt::elapsed).sum();
+        wpm(chars, elapsed)
+    }
+
+    pub fn errors(&self) -> usize {
+        self.attempts.iter().map(|attempt| attempt.errors).sum()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        let keystrokes = self.attempts.iter().map(|attempt| attempt.keystrokes).sum();
+        let typos = self.attempts.iter().map(|attempt| attempt.typos).sum();
+        accuracy(keystrokes, typos)
+    }
+
+    /// e.g. "52 WPM, 96% accuracy, 2 errors over 5 commands"
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.0} WPM, {:.0}% accuracy, {} error{} over {} command{}",
+            self.wpm(),
+            self.accuracy(),
+            self.errors(),
+            if self.errors() == 1 { "" } else { "s" },
+            self.attempts.len(),
+            if self.attempts.len() == 1 { "" } else { "s" }
+        )
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("typing-{}.json", self.started_at.format("%Y%m%dT%H%M%SZ"))
+    }
+}
+
+/// Progress through a benchmark
+/// Key handling feeds it characters; the clock for a command starts on its
+/// first keystroke, so reading the command first doesn't count against you.
+#[derive(Debug)]
+pub struct TypingRun {
+    targets: Vec<TypingTarget>,
+    current: usize,
+    typed: String,
+    started: Option<Instant>,
+    typos: usize,
+    keystrokes: usize,
+    attempts: Vec<TypingAttempt>,
+    started_at: DateTime<Utc>,
+}
+
+impl TypingRun {
+    pub fn new(targets: Vec<TypingTarget>) -> Self {
+        Self {
+            targets,
+            current: 0,
+            typed: String::new(),
+            started: None,
+            typos: 0,
+            keystrokes: 0,
+            attempts: Vec::new(),
+            started_at: Utc::now(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.targets.len()
+    }
+
+    /// The command being typed; `None` once every command is done
+    pub fn target(&self) -> Option<&TypingTarget> {
+        self.targets.get(self.current)
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn total(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    pub fn typos(&self) -> usize {
+        self.typos
+    }
+
+    pub fn attempts(&self) -> &[TypingAttempt] {
+        &self.attempts
+    }
+
+    /// Time on the current command so far
+    pub fn elapsed(&self) -> Duration {
+        self.started
+            .map_or(Duration::ZERO, |started| started.elapsed())
+    }
+
+    /// Live words per minute for the current command
+    pub fn current_wpm(&self) -> f64 {
+        wpm(self.typed.chars().count(), self.elapsed())
+    }
+
+    /// Adds a character; typing the whole command exactly finishes it
+    pub fn push(&mut self, c: char) {
+        let Some(target) = self.targets.get(self.current) else {
+            return;
+        };
+        self.started.get_or_insert_with(Instant::now);
+        let position = self.typed.chars().count();
+        if target.command.chars().nth(position) != Some(c) {
+            self.typos += 1;
+        }
+        self.keystrokes += 1;
+        self.typed.push(c);
+        if self.typed == target.command {
+            self.finish_current();
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.typed.pop();
+    }
+
+    /// Records the current command as typed so far and moves to the next;
+    /// nothing happens before the first keystroke
+    pub fn finish_current(&mut self) {
+        let Some(target) = self.targets.get(self.current) else {
+            return;
+        };
+        let Some(started) = self.started else {
+            return;
+        };
+        let typed = std::mem::take(&mut self.typed);
+        self.attempts.push(TypingAttempt {
+            question_id: target.question_id,
+            command: target.command.clone(),
+            errors: uncorrected_errors(&target.command, &typed),
+            typed,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            typos: self.typos,
+            keystrokes: self.keystrokes,
+        });
+        self.current += 1;
+        self.started = None;
+        self.typos = 0;
+        self.keystrokes = 0;
+    }
+
+    /// The attempts so far, for saving
+    pub fn result(&self) -> TypingResult {
+        TypingResult {
+            started_at: self.started_at,
+            attempts: self.attempts.clone(),
+        }
+    }
+}
+
+/// Directory holding one JSON file per typing benchmark
+pub fn typing_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("typing")
+}
+
+pub fn write(path: &Path, result: &TypingResult) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(result)?;
+    fs::write(path, json)
+}
@@ -0,0 +1,137 @@
+use crate::app::{shutdown_signal, ExitReason, Outcome};
+use crate::events::{self, terminal_events, EventSource, Input};
+use crate::i18n::Messages;
+use crate::keymap::KeyBinding;
+use crate::stats::TypingTrend;
+use crate::theme::Theme;
+use crate::typing::{TypingResult, TypingRun, TypingTarget};
+use crate::ui::{QuizUI, TypingView};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::Backend, Terminal};
+use std::io;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Terminal front-end for the typing benchmark
+/// Every key is text here, so only Enter, Backspace and Esc (plus Ctrl+C)
+/// do anything else; the keymap doesn't apply.
+pub struct TypingApp {
+    run: TypingRun,
+    theme: Theme,
+    messages: Messages,
+    /// Question source and count, shown in the header's corner
+    source: String,
+    /// Speed from earlier benchmarks
+    trend: TypingTrend,
+}
+
+impl TypingApp {
+    /// Redraw period while a command is being typed, for the live WPM figure
+    const TICK: Duration = Duration::from_millis(250);
+
+    pub fn new(
+        targets: Vec<TypingTarget>,
+        source: String,
+        theme: Theme,
+        trend: TypingTrend,
+    ) -> Self {
+        Self {
+            run: TypingRun::new(targets),
+            theme,
+            messages: Messages::english(),
+            source,
+            trend,
+        }
+    }
+
+    pub fn with_messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<ExitReason> {
+        self.run_with(terminal, terminal_events()).await
+    }
+
+    /// Event loop over any event source; ends as `Quit` when the source runs dry
+    pub async fn run_with<B: Backend, E: EventSource>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        mut events: E,
+    ) -> io::Result<ExitReason> {
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
+
+        let mut redraw = true;
+        loop {
+            if redraw {
+                self.draw(terminal)?;
+            }
+
+            tokio::select! {
+                _ = sleep(Self::TICK) => redraw = !self.run.is_finished(),
+                input = events::next_input(&mut events) => match input {
+                    Input::Event(Event::Key(key)) => match self.handle_key(key) {
+                        Outcome::Exit => return Ok(ExitReason::Quit),
+                        outcome => redraw = outcome == Outcome::Redraw,
+                    },
+                    Input::Event(Event::Resize(..)) => redraw = true,
+                    Input::Event(_) => redraw = false,
+                    Input::Ended => return Ok(ExitReason::Quit),
+                    Input::Lost(err) => {
+                        tracing::warn!(error = %err, "terminal input lost");
+                        return Ok(ExitReason::InputLost);
+                    }
+                },
+                signal = &mut shutdown => {
+                    let signal = signal?;
+                    tracing::warn!(signal, "shutdown signal received");
+                    return Ok(ExitReason::Signal(signal));
+                }
+            }
+        }
+    }
+
+    /// Characters are typed, Enter finishes the command early, Esc stops
+    pub fn handle_key(&mut self, key: KeyEvent) -> Outcome {
+        if KeyBinding::QUIT_FALLBACK.matches(&key) || key.code == KeyCode::Esc {
+            return Outcome::Exit;
+        }
+        if self.run.is_finished() {
+            return match key.code {
+                KeyCode::Enter | KeyCode::Char('q') => Outcome::Exit,
+                _ => Outcome::Unchanged,
+            };
+        }
+        match key.code {
+            KeyCode::Char(c) => self.run.push(c),
+            KeyCode::Backspace => self.run.backspace(),
+            KeyCode::Enter => self.run.finish_current(),
+            _ => return Outcome::Unchanged,
+        }
+        Outcome::Redraw
+    }
+
+    pub fn typing_run(&self) -> &TypingRun {
+        &self.run
+    }
+
+    /// The commands finished so far, for saving
+    pub fn result(&self) -> TypingResult {
+        self.run.result()
+    }
+
+    fn draw<B: Backend>(&self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        terminal.draw(|f| {
+            let view = TypingView {
+                run: &self.run,
+                theme: &self.theme,
+                messages: &self.messages,
+                source: &self.source,
+                trend: &self.trend,
+            };
+            QuizUI::render_typing(f, &view);
+        })?;
+        Ok(())
+    }
+}
@@ -1,137 +1,1822 @@
-use crate::quiz_state::{HintState, QuizState};
+use crate::accessible_ui::AccessibleUI;
+use crate::config::{Mode, QuizConfig};
+use crate::display_width;
+use crate::grading::{CommandBreakdown, RubricBreakdown, Verdict};
+use crate::i18n::Messages;
+use crate::keymap::{Action, KeyBindings};
+use crate::mastery::MasteryLoop;
+use crate::models::{Difficulty, Question};
+use crate::profile::ProfileName;
+use crate::quiz_state::{
+    AnswerInput, ContentTab, ContentTabState, HintState, QuestionRecord, QuestionStatus, QuizState,
+    RevealAnimation,
+};
+use crate::results::format_points;
+use crate::scheduler;
+use crate::stats::{
+    Calibration, FinishProjection, HintStats, PersonalBests, RunComparison, TypingTrend,
+};
+use crate::theme::Theme;
+use crate::timer::TimeUse;
+use crate::typing::{self, CharState, TypingRun};
+use clap::ValueEnum;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    text::{Line, Span, Text},
+    widgets::{
+        block::{Position, Title},
+        Block, Borders, Cell, Clear, Paragraph, Row, Table, Tabs, Wrap,
+    },
     Frame,
 };
+use serde::Deserialize;
+
+/// Read-only borrow of the state a quiz frame is drawn from
+pub struct View<'a> {
+    pub quiz_state: &'a QuizState,
+    pub hint_state: &'a HintState,
+    pub tab_state: &'a ContentTabState,
+    pub answer_input: &'a AnswerInput,
+    pub jump_input: &'a AnswerInput,
+    pub reveal: &'a RevealAnimation,
+    /// How the submission was graded, to explain an accepted near-miss
+    pub verdict: Option<&'a Verdict>,
+    /// A tip on the likely mistake in a wrong command
+    pub diagnosis: Option<&'a str>,
+    /// Lines of the context block scrolled past
+    pub context_scroll: u16,
+    /// The question cut to its first line to make room for the content pane
+    pub question_collapsed: bool,
+    /// Feedback such as a blocked jump, shown under the controls
+    pub notice: Option<&'a str>,
+    /// Alternating header style in the last seconds (see `timer::flash_phase`)
+    pub flash: Option<bool>,
+    pub theme: &'a Theme,
+    pub config: &'a QuizConfig,
+    pub personal_bests: &'a PersonalBests,
+    /// Question source and count, in the header's top-right corner
+    pub source: &'a str,
+    /// Active profile, named in the header title
+    pub profile: Option<&'a ProfileName>,
+    /// Progress toward the daily goal, when one is configured
+    pub goal: Option<&'a str>,
+    /// The streak so far when drilling one question for mastery
+    pub mastery: Option<&'a MasteryLoop>,
+    /// Effective bindings, so the controls bar shows remapped keys
+    pub keymap: &'a KeyBindings,
+    /// False when the shell key is turned off (exam mode without a shell)
+    pub shell_enabled: bool,
+    /// A submitted answer waits for its confidence rating
+    pub confidence_pending: bool,
+    /// The clock was paused after no input for a while; drawn as an overlay
+    pub idle_paused: bool,
+    /// Screen text in the configured language
+    pub messages: &'a Messages,
+}
+
+/// Read-only borrow of the state a typing benchmark frame is drawn from
+pub struct TypingView<'a> {
+    pub run: &'a TypingRun,
+    pub theme: &'a Theme,
+    pub messages: &'a Messages,
+    /// Question source and count, in the header's top-right corner
+    pub source: &'a str,
+    pub trend: &'a TypingTrend,
+}
+
+/// Read-only borrow of the state the start screen is drawn from
+pub struct StartView<'a> {
+    pub quiz_state: &'a QuizState,
+    pub config: &'a QuizConfig,
+    /// Whole seconds left in the countdown; `None` until Enter is pressed
+    pub countdown: Option<u64>,
+    pub theme: &'a Theme,
+    pub messages: &'a Messages,
+    pub keymap: &'a KeyBindings,
+    /// Question source and count, in the top-right corner
+    pub source: &'a str,
+    pub profile: Option<&'a ProfileName>,
+}
+
+/// Read-only borrow of the state the summary is drawn from
+pub struct SummaryView<'a> {
+    pub quiz_state: &'a QuizState,
+    pub theme: &'a Theme,
+    pub keymap: &'a KeyBindings,
+    pub messages: &'a Messages,
+    /// Progress toward the daily goal, when one is configured
+    pub goal: Option<&'a str>,
+    /// How this run compares with the last complete one, if there was one
+    pub comparison: Option<&'a RunComparison>,
+    /// Hint usage in earlier sessions, combined with this one's for each question's rate
+    pub hint_stats: &'a HintStats,
+    /// Adaptive mode: the tier each question was asked at, charted under the table
+    pub tiers: &'a [Difficulty],
+    /// Time spent on breaks, reported apart from the questions
+    pub breaks: std::time::Duration,
+}
+
+/// Read-only borrow of the state a break screen is drawn from
+pub struct BreakView<'a> {
+    pub quiz_state: &'a QuizState,
+    pub remaining_secs: u64,
+    /// Strict breaks: the next question's clock isn't stopped
+    pub clock_running: bool,
+    pub theme: &'a Theme,
+    pub messages: &'a Messages,
+    pub keymap: &'a KeyBindings,
+    /// Question source and count, in the top-right corner
+    pub source: &'a str,
+    pub profile: Option<&'a ProfileName>,
+}
+
+/// How the TUI lays out its screens, picked at startup with `--layout`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenLayout {
+    /// Bordered panes with colored status cues
+    #[default]
+    Standard,
+    /// Labeled text blocks without borders or colors, for screen readers
+    /// and braille displays (see [`AccessibleUI`])
+    Accessible,
+}
+
+impl ScreenLayout {
+    pub fn render(self, f: &mut Frame, view: &View) {
+        match (self, view.config.mode) {
+            (ScreenLayout::Accessible, _) => AccessibleUI::render(f, view),
+            (ScreenLayout::Standard, Mode::Study) => QuizUI::render_study(f, view),
+            (ScreenLayout::Standard, _) => QuizUI::render(f, view),
+        }
+    }
+
+    pub fn render_start(self, f: &mut Frame, view: &StartView) {
+        match self {
+            ScreenLayout::Standard => QuizUI::render_start(f, view),
+            ScreenLayout::Accessible => AccessibleUI::render_start(f, view),
+        }
+    }
+
+    pub fn render_break(self, f: &mut Frame, view: &BreakView) {
+        match self {
+            ScreenLayout::Standard => QuizUI::render_break(f, view),
+            ScreenLayout::Accessible => AccessibleUI::render_break(f, view),
+        }
+    }
+
+    pub fn render_summary(self, f: &mut Frame, view: &SummaryView) {
+        match self {
+            ScreenLayout::Standard => QuizUI::render_summary(f, view),
+            ScreenLayout::Accessible => AccessibleUI::render_summary(f, view),
+        }
+    }
+}
 
 /// Handles all UI rendering logic (Single Responsibility Principle)
 /// This module is responsible only for presentation, not business logic
 pub struct QuizUI;
 
 impl QuizUI {
-    pub fn render(f: &mut Frame, quiz_state: &QuizState, hint_state: &HintState) {
+    pub fn render(f: &mut Frame, view: &View) {
+        // Behind the ready gate the context stays hidden along with the question
+        let context = if view.quiz_state.is_armed() {
+            None
+        } else {
+            view.quiz_state.current_question().context.as_deref()
+        };
+        let context_height = context.map_or(0, |context| {
+            (context.lines().count() as u16 + 2).min(Self::MAX_CONTEXT_HEIGHT)
+        });
+        let question_height = match (view.question_collapsed, context.is_some()) {
+            (true, _) => 3,
+            (false, true) => 5,
+            (false, false) => 8,
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(2)
             .constraints([
                 Constraint::Length(3),
-                Constraint::Length(8),
+                Constraint::Length(question_height),
+                Constraint::Length(context_height),
                 Constraint::Min(5),
                 Constraint::Length(4),
             ])
             .split(f.size());
 
-        Self::render_header(f, quiz_state, chunks[0]);
-        Self::render_question(f, quiz_state, chunks[1]);
-        Self::render_content(f, quiz_state, hint_state, chunks[2]);
-        Self::render_controls(f, quiz_state, chunks[3]);
+        Self::render_header(f, view, chunks[0]);
+        Self::render_question(f, view, chunks[1]);
+        if let Some(context) = context {
+            Self::render_context(f, view, context, chunks[2]);
+        }
+        Self::render_content(f, view, chunks[3]);
+        Self::render_controls(f, view, chunks[4]);
+        if view.idle_paused {
+            Self::render_idle_overlay(f, view);
+        }
+    }
+
+    /// Box over the middle of the screen while the clock is paused for idleness
+    fn render_idle_overlay(f: &mut Frame, view: &View) {
+        let messages = view.messages;
+        let secs = view.config.idle_pause_secs.unwrap_or_default();
+        let body = messages.format("idle_paused_body", &[("secs", &secs)]);
+        let screen = f.size();
+        let width = (display_width::width(&body) as u16 + 4).min(screen.width);
+        let area = ratatui::layout::Rect {
+            x: screen.x + (screen.width - width) / 2,
+            y: screen.y + screen.height.saturating_sub(5) / 2,
+            width,
+            height: 5.min(screen.height),
+        };
+        let overlay = Paragraph::new(body)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(messages.get("idle_paused_title"))
+                    .title_alignment(Alignment::Center)
+                    .border_style(view.theme.fg(Color::Yellow))
+                    .padding(ratatui::widgets::Padding::vertical(1)),
+            );
+        f.render_widget(Clear, area);
+        f.render_widget(overlay, area);
+    }
+
+    /// Tallest the context block gets before it scrolls, borders included
+    const MAX_CONTEXT_HEIGHT: u16 = 12;
+
+    /// The question's context verbatim: line breaks and indentation kept, long
+    /// lines wrapped, scrolled with the context keys when it doesn't fit
+    fn render_context(f: &mut Frame, view: &View, context: &str, area: ratatui::layout::Rect) {
+        let visible = area.height.saturating_sub(2) as usize;
+        let messages = view.messages;
+        let title = match (
+            context.lines().count() > visible,
+            view.keymap.label(Action::ContextUp),
+            view.keymap.label(Action::ContextDown),
+        ) {
+            (true, Some(up), Some(down)) => {
+                messages.format("block_context_scroll", &[("up", &up), ("down", &down)])
+            }
+            _ => messages.get("block_context").to_string(),
+        };
+        let lines: Vec<Line> = context.lines().map(Line::from).collect();
+        let widget = Paragraph::new(lines)
+            .style(view.theme.fg(Color::LightBlue))
+            .wrap(Wrap { trim: false })
+            .scroll((view.context_scroll, 0))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(widget, area);
+    }
+
+    /// Study mode: question and answer side by side with every hint below, no timer
+    pub fn render_study(f: &mut Frame, view: &View) {
+        let (quiz_state, theme, messages) = (view.quiz_state, view.theme, view.messages);
+        let question = quiz_state.current_question();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(8),
+                Constraint::Length(question.hints.len().max(1) as u16 + 2),
+                Constraint::Length(4),
+            ])
+            .split(f.size());
+
+        let header = Paragraph::new(messages.format(
+            "study_heading",
+            &[
+                ("number", &(quiz_state.current_index() + 1)),
+                ("total", &quiz_state.total_questions()),
+            ],
+        ))
+        .style(theme.fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(header_title(view))
+                .title(source_title(view, chunks[0].width)),
+        );
+        f.render_widget(header, chunks[0]);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        let mut question_lines = vec![Line::from(question.question.as_str())];
+        if let Some(context) = &question.context {
+            question_lines.push(Line::from(""));
+            question_lines.extend(
+                context
+                    .lines()
+                    .map(|line| Line::from(Span::styled(line, theme.fg(Color::LightBlue)))),
+            );
+        }
+        let question_widget = Paragraph::new(question_lines)
+            .wrap(Wrap { trim: false })
+            .block(question_block(view, false));
+        f.render_widget(question_widget, columns[0]);
+        let answer_lines: Vec<Line> = question.answer.lines().map(Line::from).collect();
+        let answer_widget = Paragraph::new(answer_lines)
+            .style(theme.fg(Color::Green))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(messages.get("block_answer")),
+            );
+        f.render_widget(answer_widget, columns[1]);
+
+        let hint_lines: Vec<Line> = if question.hints.is_empty() {
+            vec![Line::from(Span::styled(
+                messages.get("study_no_hints"),
+                theme.fg(Color::DarkGray),
+            ))]
+        } else {
+            question
+                .hints
+                .iter()
+                .enumerate()
+                .map(|(index, hint)| Line::from(format!("{}. {}", index + 1, hint)))
+                .collect()
+        };
+        let hints_widget = Paragraph::new(hint_lines)
+            .style(theme.fg(Color::Yellow))
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(messages.get("block_hints")),
+            );
+        f.render_widget(hints_widget, chunks[2]);
+
+        Self::render_controls(f, view, chunks[3]);
+    }
+
+    /// What the session holds before its first clock starts: question count,
+    /// mode, time budget and filters, then the countdown once Enter is pressed
+    pub fn render_start(f: &mut Frame, view: &StartView) {
+        let lines = Self::start_lines(view);
+        render_interlude(f, view.messages, view.profile, view.source, lines);
+    }
+
+    /// The start screen's text: question count, mode and time budget, then
+    /// the prompt or the countdown
+    pub(crate) fn start_lines<'a>(view: &StartView<'a>) -> Vec<Line<'a>> {
+        let (quiz_state, messages, theme) = (view.quiz_state, view.messages, view.theme);
+
+        // Pages still loading add to the budget; the "+" says it is a lower bound
+        let budget: u64 = quiz_state
+            .questions()
+            .iter()
+            .map(|question| question.time_limit_secs)
+            .sum();
+        let mut time = format_duration(std::time::Duration::from_secs(budget));
+        if quiz_state.questions().len() < quiz_state.total_questions() {
+            time.push('+');
+        }
+        let mode = view
+            .config
+            .mode
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                messages.get("start_title").to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(messages.format(
+                "start_summary",
+                &[
+                    ("count", &quiz_state.total_questions()),
+                    ("mode", &mode),
+                    ("time", &time),
+                ],
+            )),
+        ];
+        let filters = scheduler::active_filters(view.config);
+        if !filters.is_empty() {
+            lines.push(Line::from(Span::styled(
+                messages.format("start_filters", &[("filters", &filters.join(" "))]),
+                theme.fg(Color::DarkGray),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(match view.countdown {
+            Some(secs) => Line::from(Span::styled(
+                messages.format("start_countdown", &[("secs", &secs)]),
+                theme.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )),
+            None => Line::from(messages.format(
+                "start_prompt",
+                &[(
+                    "controls",
+                    &key_hints(view.keymap, &[(Action::Quit, messages.get("key_quit"))]),
+                )],
+            )),
+        });
+        lines
+    }
+
+    /// A breather between questions: no question content, just the time left
+    /// and how to end it early
+    pub fn render_break(f: &mut Frame, view: &BreakView) {
+        let lines = Self::break_lines(view);
+        render_interlude(f, view.messages, view.profile, view.source, lines);
+    }
+
+    /// The break screen's text: progress, time left and how to end it early
+    pub(crate) fn break_lines<'a>(view: &BreakView<'a>) -> Vec<Line<'a>> {
+        let (quiz_state, messages, theme) = (view.quiz_state, view.messages, view.theme);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                messages.get("break_title").to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(messages.format(
+                "break_progress",
+                &[
+                    ("done", &quiz_state.completed_count()),
+                    ("total", &quiz_state.total_questions()),
+                ],
+            )),
+            Line::from(Span::styled(
+                messages.format(
+                    "break_remaining",
+                    &[(
+                        "time",
+                        &format_duration(std::time::Duration::from_secs(view.remaining_secs)),
+                    )],
+                ),
+                theme.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )),
+        ];
+        if view.clock_running {
+            lines.push(Line::from(Span::styled(
+                messages.get("break_clock_running").to_string(),
+                theme.fg(Color::Red),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(messages.format(
+            "break_prompt",
+            &[(
+                "controls",
+                &key_hints(view.keymap, &[(Action::Quit, messages.get("key_quit"))]),
+            )],
+        )));
+        lines
+    }
+
+    /// Renders the end-of-quiz table with one result badge per question
+    pub fn render_summary(f: &mut Frame, view: &SummaryView) {
+        let (quiz_state, theme, messages) = (view.quiz_state, view.theme, view.messages);
+        let comparison = view.comparison;
+        let mut constraints = vec![Constraint::Min(5), Constraint::Length(3)];
+        if comparison.is_some() {
+            constraints.insert(1, Constraint::Length(4));
+        }
+        if !view.tiers.is_empty() {
+            constraints.insert(constraints.len() - 1, Constraint::Length(4));
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints(constraints)
+            .split(f.size());
+
+        // The hints column only appears when there were hints to use, and the
+        // credit column for banks that attribute questions
+        let hinted = quiz_state
+            .questions()
+            .iter()
+            .any(|question| !question.hints.is_empty());
+        let credited = quiz_state
+            .questions()
+            .iter()
+            .any(|question| question.attribution().is_some());
+        // How many tries it took, once any question took more than one
+        let retried = quiz_state
+            .records()
+            .iter()
+            .any(|record| record.attempts.len() > 1);
+        let rows = quiz_state
+            .questions()
+            .iter()
+            .enumerate()
+            .map(|(index, question)| {
+                let mut cells = vec![
+                    Cell::from(question.id.to_string()),
+                    Cell::from(question.question.clone()),
+                    Cell::from(match quiz_state.records()[index].partial_credit {
+                        Some(credit) => partial_badge(credit, theme, messages),
+                        None => status_badge(quiz_state.status(index), theme, messages),
+                    }),
+                ];
+                if hinted {
+                    cells.push(Cell::from(hint_rate(
+                        view.hint_stats,
+                        question,
+                        &quiz_state.records()[index],
+                        messages,
+                    )));
+                }
+                if retried {
+                    cells.push(Cell::from(
+                        quiz_state.records()[index].attempts.len().to_string(),
+                    ));
+                }
+                if credited {
+                    cells.push(
+                        Cell::from(question.attribution().unwrap_or_default())
+                            .style(theme.fg(Color::DarkGray)),
+                    );
+                }
+                Row::new(cells)
+            });
+
+        let mut widths = vec![
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Length(13),
+        ];
+        let mut header = vec![
+            messages.get("summary_id"),
+            messages.get("summary_question"),
+            messages.get("summary_result"),
+        ];
+        if hinted {
+            widths.push(Constraint::Length(12));
+            header.push(messages.get("summary_hints"));
+        }
+        if retried {
+            widths.push(Constraint::Length(6));
+            header.push(messages.get("summary_attempts"));
+        }
+        if credited {
+            widths.push(Constraint::Percentage(30));
+            header.push(messages.get("summary_credit"));
+        }
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(messages.get("block_summary")),
+            );
+
+        f.render_widget(table, chunks[0]);
+
+        let footer = Paragraph::new(Self::score_text(view))
+            .style(theme.fg(Color::Cyan))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+
+        if let Some(comparison) = comparison {
+            let lines = comparison_lines(comparison, theme, messages);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(messages.get("block_last_run"));
+            f.render_widget(
+                Paragraph::new(lines)
+                    .alignment(Alignment::Center)
+                    .block(block),
+                chunks[1],
+            );
+        }
+        if !view.tiers.is_empty() {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(messages.get("block_tiers"));
+            f.render_widget(
+                Paragraph::new(tier_lines(view.tiers, theme, messages))
+                    .alignment(Alignment::Center)
+                    .block(block),
+                chunks[chunks.len() - 2],
+            );
+        }
+        f.render_widget(footer, chunks[chunks.len() - 1]);
     }
 
-    fn render_header(f: &mut Frame, quiz_state: &QuizState, area: ratatui::layout::Rect) {
+    /// The summary's footer: score, points, calibration, goal and breaks, then
+    /// the quit key
+    pub(crate) fn score_text(view: &SummaryView) -> String {
+        let (quiz_state, keymap, messages) = (view.quiz_state, view.keymap, view.messages);
+        let (goal, breaks) = (view.goal, view.breaks);
+        let mut score = messages.format(
+            "summary_score",
+            &[
+                ("correct", &quiz_state.correct_count()),
+                ("total", &quiz_state.total_questions()),
+            ],
+        );
+        if quiz_state.scores_by_points() {
+            score.push_str(&format!(
+                " | {}",
+                messages.format(
+                    "summary_points",
+                    &[
+                        ("earned", &format_points(quiz_state.earned_points())),
+                        ("possible", &quiz_state.total_points()),
+                    ],
+                )
+            ));
+        }
+        if let Some(calibration) = calibration_text(quiz_state, messages) {
+            score.push_str(&format!(" | {}", calibration));
+        }
+        if let Some(goal) = goal {
+            score.push_str(&format!(" | {}", goal));
+        }
+        if !breaks.is_zero() {
+            score.push_str(&format!(
+                " | {}",
+                messages.format("summary_breaks", &[("time", &format_duration(breaks))])
+            ));
+        }
+        score.push_str(&format!(
+            " | {}",
+            key_hints(keymap, &[(Action::Quit, messages.get("key_quit"))])
+        ));
+        score
+    }
+
+    fn render_header(f: &mut Frame, view: &View, area: ratatui::layout::Rect) {
+        let (quiz_state, theme, messages) = (view.quiz_state, view.theme, view.messages);
         let timer = quiz_state.timer();
         let remaining_text = if timer.is_expired() {
-            "TIME EXPIRED".to_string()
+            messages.get("time_expired").to_string()
         } else {
-            let remaining = timer.remaining();
-            let secs = remaining.as_secs();
-            format!("Time remaining: {}:{:02}", secs / 60, secs % 60)
+            messages.format(
+                "time_remaining",
+                &[("time", &format_duration(timer.remaining()))],
+            )
         };
 
-        let color = if timer.remaining().as_secs() < 10 && !timer.is_expired() {
+        let color = if timer.remaining().as_secs() < view.config.warning_secs && !timer.is_expired()
+        {
             Color::Red
         } else {
             Color::Green
         };
 
-        let header = Paragraph::new(remaining_text)
-            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("CKAD Practitioner"));
+        let mut spans = vec![Span::styled(
+            remaining_text,
+            theme.fg(color).add_modifier(Modifier::BOLD),
+        )];
+        if let Some((text, color)) = best_time_text(quiz_state, view.personal_bests, messages) {
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled(text, theme.fg(color)));
+        }
+        if let Some(goal) = view.goal {
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled(goal, theme.fg(Color::Cyan)));
+        }
+        if let Some(mastery) = view.mastery {
+            let status = messages.format(
+                "mastery_status",
+                &[
+                    ("streak", &mastery.streak()),
+                    ("target", &mastery.target()),
+                    ("attempt", &mastery.attempts()),
+                ],
+            );
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled(status, theme.fg(Color::Magenta)));
+        }
 
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(header_title(view))
+            .title(source_title(view, area.width));
+        // Reversed video keeps the flash visible in mono themes too
+        block = match view.flash {
+            Some(true) => block.style(theme.fg(Color::Red).add_modifier(Modifier::REVERSED)),
+            Some(false) => block.border_style(theme.fg(Color::Yellow)),
+            None => block,
+        };
+        let inner = block.inner(area);
+        let line = Line::from(spans);
+        let centered = line.width();
+        let header = Paragraph::new(line)
+            .alignment(Alignment::Center)
+            .block(block);
         f.render_widget(header, area);
+
+        // Right-aligned opposite the question clock, and the first thing to go
+        // when the centered text needs the room
+        let session = format!(
+            "{} ",
+            messages.format(
+                "session_elapsed",
+                &[("time", &format_duration(quiz_state.session_elapsed()))],
+            )
+        );
+        let side = display_width::width(&session) + 2;
+        if centered + 2 * side <= inner.width as usize {
+            let session = Paragraph::new(Span::styled(session, theme.fg(Color::DarkGray)))
+                .alignment(Alignment::Right);
+            f.render_widget(session, inner);
+        }
     }
 
-    fn render_question(f: &mut Frame, quiz_state: &QuizState, area: ratatui::layout::Rect) {
+    fn render_question(f: &mut Frame, view: &View, area: ratatui::layout::Rect) {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
         let question = quiz_state.current_question();
-        let question_text = format!(
-            "Question {} of {}: {}",
-            quiz_state.current_index() + 1,
-            quiz_state.total_questions(),
-            question.question
-        );
+        let number = quiz_state.current_index() + 1;
+        let total = quiz_state.total_questions();
+        let question_text = if let Some(title) = quiz_state.section_title() {
+            messages.format(
+                "section_ready",
+                &[("title", &title), ("number", &number), ("total", &total)],
+            )
+        } else if quiz_state.is_armed() {
+            messages.format("question_ready", &[("number", &number), ("total", &total)])
+        } else {
+            messages.format(
+                "question_heading",
+                &[
+                    ("number", &number),
+                    ("total", &total),
+                    ("question", &question.question),
+                ],
+            )
+        };
+
+        let category = question
+            .category
+            .as_deref()
+            .map(|category| view.theme.category_style(category));
+        let tag = category
+            .as_ref()
+            .map(|category| format!("[{}] ", category.tag));
+        let priority = question
+            .priority
+            .filter(|&priority| priority > 0)
+            .map(|priority| {
+                format!(
+                    "  {}",
+                    messages.format("question_priority", &[("priority", &priority)])
+                )
+            });
+        let room = (area.width.saturating_sub(2) as usize)
+            .saturating_sub(tag.as_deref().map_or(0, display_width::width))
+            .saturating_sub(priority.as_deref().map_or(0, display_width::width));
+        let long = question_text.contains('\n') || display_width::width(&question_text) > room;
+        let question_text = if view.question_collapsed {
+            collapse_text(&question_text, room)
+        } else {
+            question_text
+        };
+
+        let mut question_text = Text::from(question_text);
+        if let (Some(category), Some(tag), Some(first)) =
+            (category, tag, question_text.lines.first_mut())
+        {
+            first.spans.insert(
+                0,
+                Span::styled(tag, category.style.add_modifier(Modifier::BOLD)),
+            );
+        }
+        if let (Some(priority), Some(first)) = (priority, question_text.lines.first_mut()) {
+            first.spans.push(Span::styled(
+                priority,
+                view.theme.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ));
+        }
 
         let question_widget = Paragraph::new(question_text)
             .wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL).title("Question"));
+            .block(question_block(view, long));
 
         f.render_widget(question_widget, area);
     }
 
-    fn render_content(
-        f: &mut Frame,
-        quiz_state: &QuizState,
-        hint_state: &HintState,
-        area: ratatui::layout::Rect,
-    ) {
-        let mut content_lines = vec![];
-        let question = quiz_state.current_question();
-        let timer = quiz_state.timer();
+    fn render_content(f: &mut Frame, view: &View, area: ratatui::layout::Rect) {
+        let (tab_state, answer_input, theme, messages) =
+            (view.tab_state, view.answer_input, view.theme, view.messages);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(messages.get("block_content"));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
 
-        if !timer.is_expired() {
-            let hint_text = if hint_state.show_hints() {
-                let hint_idx = hint_state.hint_index();
-                format!(
-                    "Hint {} (press 'h' for more): {}",
-                    hint_idx + 1,
-                    question.hints.get(hint_idx).unwrap_or(&"No more hints".to_string())
-                )
+        let input_height = if answer_input.is_active() { 2 } else { 0 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(input_height),
+            ])
+            .split(inner);
+
+        let titles: Vec<Line> = ContentTab::ALL
+            .iter()
+            .map(|tab| {
+                let title = messages.get(tab.title_key());
+                if tab.locks_with_answer() && tab_state.answer_locked() {
+                    Line::from(messages.format("tab_locked", &[("tab", &title)]))
+                } else {
+                    Line::from(title)
+                }
+            })
+            .collect();
+        let tabs = Tabs::new(titles)
+            .select(tab_state.active().index())
+            .highlight_style(theme.fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .block(Block::default().borders(Borders::BOTTOM));
+        f.render_widget(tabs, chunks[0]);
+
+        let active = tab_state.active();
+        let body = match active {
+            ContentTab::Hints => Self::hint_lines(view),
+            ContentTab::Answer => Self::answer_lines(view),
+            ContentTab::References => Self::reference_lines(view),
+            ContentTab::Notes => Self::note_lines(view),
+        };
+        let body = Paragraph::new(body)
+            .wrap(Wrap { trim: true })
+            .scroll((tab_state.scroll(active), 0));
+        f.render_widget(body, chunks[1]);
+
+        if answer_input.is_active() {
+            let prompt = messages.format("answer_prompt", &[("input", &answer_input.buffer())]);
+            f.render_widget(input_widget(Line::from(prompt)), chunks[2]);
+            // The terminal's own (blinking) cursor marks the edit position
+            let column = input_cursor_column(messages, "answer_prompt", answer_input);
+            let area = chunks[2];
+            f.set_cursor(
+                (area.x + column).min(area.right().saturating_sub(1)),
+                area.y + 1,
+            );
+        }
+    }
+
+    pub(crate) fn hint_lines<'a>(view: &View<'a>) -> Vec<Line<'a>> {
+        let (quiz_state, hint_state, theme, messages) =
+            (view.quiz_state, view.hint_state, view.theme, view.messages);
+        let question = quiz_state.current_question();
+        let hint_key = view.keymap.label(Action::Hint);
+        let remaining = quiz_state.hints_remaining(view.config.hint_budget);
+        let none_left = || {
+            Line::from(Span::styled(
+                messages.get("hints_none_left"),
+                theme.fg(Color::DarkGray),
+            ))
+        };
+        if !hint_state.is_enabled() {
+            return vec![Line::from(Span::styled(
+                messages.get("hints_disabled"),
+                theme.fg(Color::DarkGray),
+            ))];
+        }
+        if !hint_state.show_hints() {
+            if remaining == Some(0) && !quiz_state.is_resolved() {
+                return vec![none_left()];
+            }
+            let prompt = if quiz_state.is_resolved() {
+                messages.get("hints_unused").to_string()
             } else {
-                "Press 'h' for hints".to_string()
+                match &hint_key {
+                    Some(keys) => messages.format("hints_prompt", &[("key", keys)]),
+                    None => messages.get("hints_unbound").to_string(),
+                }
             };
-            content_lines.push(Line::from(Span::styled(
-                hint_text,
-                Style::default().fg(Color::Yellow),
+            return vec![Line::from(Span::styled(prompt, theme.fg(Color::Yellow)))];
+        }
+
+        let shown = (hint_state.hint_index() + 1).min(question.hints.len());
+        let mut lines: Vec<Line> = question.hints[..shown]
+            .iter()
+            .enumerate()
+            .map(|(index, hint)| {
+                Line::from(Span::styled(
+                    messages.format("hint_line", &[("number", &(index + 1)), ("hint", hint)]),
+                    theme.fg(Color::Yellow),
+                ))
+            })
+            .collect();
+        if shown == 0 {
+            lines.push(Line::from(messages.get("hints_exhausted")));
+        } else if shown < question.hints.len() && !quiz_state.is_resolved() {
+            if remaining == Some(0) {
+                lines.push(none_left());
+            } else if let Some(keys) = &hint_key {
+                lines.push(Line::from(messages.format("hints_more", &[("key", keys)])));
+            }
+        }
+        if let (Some(left), Some(total)) = (remaining, view.config.hint_budget) {
+            lines.push(Line::from(Span::styled(
+                messages.format("hints_budget", &[("left", &left), ("total", &total)]),
+                theme.fg(Color::DarkGray),
             )));
+        }
+        lines
+    }
+
+    pub(crate) fn answer_lines<'a>(view: &View<'a>) -> Vec<Line<'a>> {
+        let (quiz_state, theme, messages) = (view.quiz_state, view.theme, view.messages);
+        if view.tab_state.answer_locked() {
+            return vec![Line::from(Span::styled(
+                messages.get("answer_locked"),
+                theme.fg(Color::DarkGray),
+            ))];
+        }
+
+        let timer = quiz_state.timer();
+        if quiz_state.current_submission().is_none()
+            && timer.reveal_pending(view.config.reveal_delay_secs)
+        {
+            return vec![Line::from(Span::styled(
+                messages.get("answer_delayed"),
+                theme.fg(Color::Red).add_modifier(Modifier::BOLD),
+            ))];
+        }
+
+        let mut lines = vec![];
+        if let Some(submission) = quiz_state.current_submission() {
+            // Manifests from the editor span several lines; show them as written
+            let mut submitted = submission.lines();
+            lines.push(Line::from(vec![
+                status_badge(quiz_state.current_status(), theme, messages),
+                Span::raw(messages.format(
+                    "answer_submitted",
+                    &[("answer", &submitted.next().unwrap_or(""))],
+                )),
+            ]));
+            lines.extend(submitted.map(Line::from));
+            if let Some(explanation) = view.verdict.and_then(Verdict::explanation) {
+                lines.push(Line::from(Span::styled(
+                    messages.format("answer_equivalent", &[("differences", &explanation)]),
+                    theme.fg(Color::DarkGray),
+                )));
+            }
+            if let Some(Verdict::Rejected(message)) = view.verdict {
+                lines.push(Line::from(Span::styled(
+                    messages.format("answer_rejected", &[("message", message)]),
+                    theme.fg(Color::Red),
+                )));
+            }
+            if let Some(tip) = view.diagnosis {
+                lines.push(Line::from(Span::styled(
+                    messages.format("answer_tip", &[("tip", &tip)]),
+                    theme.fg(Color::Yellow),
+                )));
+            }
+            if let Some(breakdown) = view.verdict.and_then(Verdict::breakdown) {
+                lines.extend(breakdown_lines(breakdown, theme, messages));
+            }
+            if let Some(rubric) = view.verdict.and_then(Verdict::rubric) {
+                lines.extend(rubric_lines(rubric, theme, messages));
+            }
+            lines.push(Line::from(""));
+        }
+        if let Some(time_use) = quiz_state.time_use(quiz_state.current_index()) {
+            lines.push(time_use_line(time_use, theme, messages));
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            messages.get("answer_heading"),
+            theme.fg(Color::Green).add_modifier(Modifier::BOLD),
+        )));
+        let answer = quiz_state.current_question().answer.as_str();
+        let answer = match view.reveal.visible_chars() {
+            Some(count) => match answer.char_indices().nth(count) {
+                Some((end, _)) => &answer[..end],
+                None => answer,
+            },
+            None => answer,
+        };
+        for line in answer.lines() {
+            lines.push(Line::from(Span::raw(line)));
+        }
+        if view.reveal.visible_chars().is_none() {
+            lines.extend(Self::related_lines(view));
+        }
+        lines
+    }
+
+    /// The question's related commands when toggled on, otherwise a line
+    /// saying how to show them; nothing for questions without any
+    fn related_lines<'a>(view: &View<'a>) -> Vec<Line<'a>> {
+        let (theme, messages) = (view.theme, view.messages);
+        let related = view.quiz_state.current_question().related_commands();
+        if related.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![Line::from("")];
+        if !view.tab_state.show_related() {
+            if let Some(keys) = view.keymap.label(Action::Related) {
+                lines.push(Line::from(Span::styled(
+                    messages.format("related_prompt", &[("key", &keys)]),
+                    theme.fg(Color::DarkGray),
+                )));
+            }
+            return lines;
+        }
+        lines.push(Line::from(Span::styled(
+            messages.get("related_heading"),
+            theme.fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(
+            related.iter().map(|command| {
+                Line::from(messages.format("related_line", &[("command", command)]))
+            }),
+        );
+        lines
+    }
+
+    pub(crate) fn reference_lines<'a>(view: &View<'a>) -> Vec<Line<'a>> {
+        let (theme, messages) = (view.theme, view.messages);
+        if view.tab_state.answer_locked() {
+            return vec![Line::from(Span::styled(
+                messages.get("references_locked"),
+                theme.fg(Color::DarkGray),
+            ))];
+        }
+        let references = view.quiz_state.current_question().reference_links();
+        if references.is_empty() {
+            vec![Line::from(messages.get("no_references"))]
         } else {
-            content_lines.push(Line::from(Span::styled(
-                "Answer:",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
+            references
+                .iter()
+                .map(|link| Line::from(link.as_str()))
+                .collect()
+        }
+    }
+
+    pub(crate) fn note_lines<'a>(view: &View<'a>) -> Vec<Line<'a>> {
+        match &view.quiz_state.current_question().notes {
+            Some(notes) => notes.lines().map(Line::from).collect(),
+            None => vec![Line::from(view.messages.get("no_notes"))],
+        }
+    }
+
+    fn render_controls(f: &mut Frame, view: &View, area: ratatui::layout::Rect) {
+        let mut lines = vec![Line::from(Self::controls_text(view))];
+        if let Some(notice) = view.notice {
+            lines.push(Line::from(Span::styled(
+                notice.to_string(),
+                view.theme.fg(Color::Yellow),
             )));
-            for line in question.answer.lines() {
-                content_lines.push(Line::from(Span::raw(line)));
-            }
         }
 
-        let content = Paragraph::new(content_lines)
+        let mut block = Block::default().borders(Borders::ALL);
+        if let Some(pace) = pace_title(view) {
+            block = block.title(pace);
+        }
+        let controls_widget = Paragraph::new(lines)
+            .style(view.theme.fg(Color::Cyan))
+            .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL).title("Content"));
+            .block(block);
 
-        f.render_widget(content, area);
+        f.render_widget(controls_widget, area);
     }
 
-    fn render_controls(f: &mut Frame, quiz_state: &QuizState, area: ratatui::layout::Rect) {
-        let timer = quiz_state.timer();
-
-        let controls = if timer.is_expired() {
-            if quiz_state.is_last_question() {
-                "Quiz complete! Press 'q' to quit"
+    /// The controls bar: the prompt being typed into, or the keys that do
+    /// something right now
+    pub(crate) fn controls_text(view: &View) -> String {
+        let (quiz_state, messages) = (view.quiz_state, view.messages);
+        if view.confidence_pending {
+            messages.get("confidence_prompt").to_string()
+        } else if view.jump_input.is_active() {
+            messages.format("jump_prompt", &[("input", &view.jump_input.buffer())])
+        } else if view.answer_input.is_active() {
+            messages.get("answer_controls").to_string()
+        } else {
+            let entries: Vec<(Action, &str)> = control_entries(view)
+                .into_iter()
+                .map(|(action, label)| (action, messages.get(label)))
+                .collect();
+            let hints = key_hints(view.keymap, &entries);
+            if quiz_state.is_armed() {
+                messages.format("ready_controls", &[("controls", &hints)])
+            } else if quiz_state.is_resolved() && quiz_state.is_last_question() && !drilling(view) {
+                messages.format("quiz_complete", &[("controls", &hints)])
+            } else if !quiz_state.is_resolved() && view.config.mode != Mode::Study {
+                messages.format("unresolved_controls", &[("controls", &hints)])
             } else {
-                "Press 'n' for next question, 'q' to quit"
+                hints
             }
-        } else {
-            "h: hints | q: quit | (answer revealed after time expires)"
+        }
+    }
+}
+
+impl QuizUI {
+    /// Typing benchmark: the command to retype, the typed text checked character
+    /// by character, and live speed; a per-command table once every command is done
+    pub fn render_typing(f: &mut Frame, view: &TypingView) {
+        let (run, theme, messages) = (view.run, view.theme, view.messages);
+        let Some(target) = run.target() else {
+            Self::render_typing_summary(f, view);
+            return;
         };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(3),
+            ])
+            .split(f.size());
 
-        let controls_widget = Paragraph::new(controls)
-            .style(Style::default().fg(Color::Cyan))
+        let heading = messages.format(
+            "typing_heading",
+            &[
+                ("number", &(run.current_index() + 1)),
+                ("total", &run.total()),
+            ],
+        );
+        let header = Paragraph::new(heading)
+            .style(theme.fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(messages.get("app_title"))
+                    .title(
+                        Title::from(display_width::truncate(
+                            view.source,
+                            header_room(messages.get("app_title"), chunks[0].width),
+                            "…",
+                        ))
+                        .alignment(Alignment::Right),
+                    ),
+            );
+        f.render_widget(header, chunks[0]);
+
+        // The command itself, with each character marked as it's typed
+        let states = typing::compare(&target.command, run.typed());
+        let command_spans: Vec<Span> = target
+            .command
+            .chars()
+            .zip(&states)
+            .map(|(c, state)| Span::styled(c.to_string(), char_style(*state, theme)))
+            .collect();
+        let command =
+            Paragraph::new(Line::from(command_spans))
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(
+                    messages.format("block_typing_command", &[("id", &target.question_id)]),
+                ));
+        f.render_widget(command, chunks[1]);
+
+        let mut input_spans = vec![Span::raw(messages.get("typing_prompt").to_string())];
+        input_spans.extend(typed_spans(&target.command, run.typed(), theme));
+        input_spans.push(Span::raw("_"));
+        f.render_widget(input_widget(Line::from(input_spans)), chunks[2]);
+
+        let live = messages.format(
+            "typing_live",
+            &[
+                ("wpm", &format!("{:.0}", run.current_wpm())),
+                ("typos", &run.typos()),
+                ("time", &format_duration(run.elapsed())),
+            ],
+        );
+        let stats = Paragraph::new(vec![
+            Line::from(Span::styled(live, theme.fg(Color::Green))),
+            Line::from(Span::styled(trend_text(view), theme.fg(Color::DarkGray))),
+        ])
+        .alignment(Alignment::Center);
+        f.render_widget(stats, chunks[3]);
+
+        let controls = Paragraph::new(messages.get("typing_controls"))
+            .style(theme.fg(Color::Cyan))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
+        f.render_widget(controls, chunks[4]);
+    }
 
-        f.render_widget(controls_widget, area);
+    fn render_typing_summary(f: &mut Frame, view: &TypingView) {
+        let (run, theme, messages) = (view.run, view.theme, view.messages);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([Constraint::Min(5), Constraint::Length(4)])
+            .split(f.size());
+
+        let rows = run.attempts().iter().map(|attempt| {
+            let errors = Cell::from(attempt.errors.to_string());
+            Row::new(vec![
+                Cell::from(attempt.question_id.to_string()),
+                Cell::from(attempt.command.clone()),
+                Cell::from(format!("{:.0}", attempt.wpm())),
+                if attempt.errors > 0 {
+                    errors.style(theme.fg(Color::Red))
+                } else {
+                    errors
+                },
+                Cell::from(format_duration(attempt.elapsed())),
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(6),
+        ];
+        let header = [
+            "summary_id",
+            "summary_command",
+            "summary_wpm",
+            "summary_errors",
+            "summary_time",
+        ]
+        .map(|key| messages.get(key));
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(messages.get("block_summary")),
+            );
+        f.render_widget(table, chunks[0]);
+
+        let result = run.result();
+        let done = messages.format(
+            "typing_done",
+            &[
+                ("wpm", &format!("{:.0}", result.wpm())),
+                ("accuracy", &format!("{:.0}", result.accuracy())),
+                ("errors", &result.errors()),
+            ],
+        );
+        let footer = Paragraph::new(vec![
+            Line::from(Span::styled(
+                done,
+                theme.fg(Color::Green).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(format!(
+                "{} | {}",
+                trend_text(view),
+                messages.get("typing_done_controls")
+            )),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(footer, chunks[1]);
+    }
+}
+
+/// The text-entry line under the content tabs; the typing benchmark puts its
+/// checked input in the same place
+fn input_widget(line: Line) -> Paragraph {
+    Paragraph::new(line).block(Block::default().borders(Borders::TOP))
+}
+
+/// Screen column of the answer cursor within the prompt line with message `key`
+pub(crate) fn input_cursor_column(messages: &Messages, key: &str, input: &AnswerInput) -> u16 {
+    // A marker finds where the locale puts the buffer in its prompt
+    const MARKER: char = '\u{1}';
+    let prompt = messages.format(key, &[("input", &MARKER)]);
+    let label = prompt.split(MARKER).next().unwrap_or_default();
+    let width = Span::raw(label).width() + Span::raw(input.before_cursor()).width();
+    u16::try_from(width).unwrap_or(u16::MAX)
+}
+
+/// Typed characters styled by whether they match the command
+pub fn typed_spans(target: &str, typed: &str, theme: &Theme) -> Vec<Span<'static>> {
+    typed
+        .chars()
+        .zip(typing::compare(target, typed))
+        .map(|(c, state)| Span::styled(c.to_string(), char_style(state, theme)))
+        .collect()
+}
+
+/// Wrong characters are reversed as well as red so they stand out without color
+fn char_style(state: CharState, theme: &Theme) -> Style {
+    match state {
+        CharState::Correct => theme.fg(Color::Green),
+        CharState::Wrong | CharState::Extra => theme
+            .fg(Color::Red)
+            .add_modifier(Modifier::REVERSED | Modifier::UNDERLINED),
+        CharState::Pending => theme.fg(Color::DarkGray),
+    }
+}
+
+/// Best and recent speed from earlier benchmarks
+fn trend_text(view: &TypingView) -> String {
+    let trend = view.trend;
+    if trend.sessions == 0 {
+        return view.messages.get("typing_no_history").to_string();
+    }
+    view.messages.format(
+        "typing_trend",
+        &[
+            ("best", &format!("{:.0}", trend.best_wpm)),
+            ("count", &trend.sessions.min(TypingTrend::RECENT)),
+            ("recent", &format!("{:.0}", trend.recent_wpm)),
+        ],
+    )
+}
+
+/// "CKAD Practitioner", followed by the profile when one is active
+pub(crate) fn header_title(view: &View) -> String {
+    match view.profile {
+        Some(profile) => view
+            .messages
+            .format("app_title_profile", &[("profile", profile)]),
+        None => view.messages.get("app_title").to_string(),
+    }
+}
+
+/// The question block, crediting its author and source in the bottom corner
+/// The question's block; a `long` question's title names the collapse key
+fn question_block<'a>(view: &View<'a>, long: bool) -> Block<'a> {
+    let messages = view.messages;
+    let title = match view.keymap.label(Action::CollapseQuestion) {
+        Some(key) if view.question_collapsed => {
+            messages.format("block_question_collapsed", &[("key", &key)])
+        }
+        Some(key) if long => messages.format("block_question_long", &[("key", &key)]),
+        _ => messages.get("block_question").to_string(),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    match view.quiz_state.current_question().attribution() {
+        Some(credit) => block.title(
+            Title::from(Span::styled(
+                view.messages
+                    .format("question_credit", &[("credit", &credit)]),
+                view.theme.fg(Color::DarkGray),
+            ))
+            .position(Position::Bottom)
+            .alignment(Alignment::Right),
+        ),
+        None => block,
+    }
+}
+
+/// `text` cut to its first line and to `max` columns, ending in "…" when
+/// anything was left out; the full text is kept to expand it again
+pub fn collapse_text(text: &str, max: usize) -> String {
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or("");
+    if lines.all(|line| line.trim().is_empty()) {
+        return display_width::truncate(first, max, "…");
+    }
+    let cut = display_width::truncate(first, max.saturating_sub(1), "");
+    format!("{}…", cut)
+}
+
+/// In exam mode, where the session is headed at the pace so far, e.g. "At
+/// this pace you'll finish with 14:00 to spare", red when it runs over the
+/// budget; on the status bar's bottom edge
+fn pace_title<'a>(view: &View<'a>) -> Option<Title<'a>> {
+    let (text, color) = pace_text(view)?;
+    Some(
+        Title::from(Span::styled(format!(" {} ", text), view.theme.fg(color)))
+            .position(Position::Bottom)
+            .alignment(Alignment::Right),
+    )
+}
+
+/// The exam-mode projection's text, red when it runs over the budget
+pub(crate) fn pace_text(view: &View) -> Option<(String, Color)> {
+    if view.config.mode != Mode::Exam {
+        return None;
+    }
+    let projection = FinishProjection::for_quiz(view.quiz_state)?;
+    let time = format_duration(projection.margin());
+    Some(if projection.is_over_budget() {
+        (
+            view.messages.format("pace_over", &[("time", &time)]),
+            Color::Red,
+        )
+    } else {
+        (
+            view.messages.format("pace_spare", &[("time", &time)]),
+            Color::Green,
+        )
+    })
+}
+
+/// The source, followed by a quiet "loading…" while the next question's page
+/// hasn't arrived; cut with "…" where a header `width` columns wide has no
+/// room for it beside the app title
+fn source_title<'a>(view: &View<'a>, width: u16) -> Title<'a> {
+    let room = header_room(&header_title(view), width);
+    let source = display_width::truncate(view.source, room, "…");
+    if view.quiz_state.is_waiting_for_next() {
+        let loading = format!(" · {}", view.messages.get("questions_loading"));
+        let room = room.saturating_sub(display_width::width(&source));
+        let loading = Span::styled(
+            display_width::truncate(&loading, room, ""),
+            view.theme.fg(Color::DarkGray),
+        );
+        return Title::from(Line::from(vec![Span::raw(source), loading]))
+            .alignment(Alignment::Right);
+    }
+    Title::from(source).alignment(Alignment::Right)
+}
+
+/// A full-screen block titled like the quiz header, with `lines` centered in
+/// it; the start and break screens, which show no question
+fn render_interlude(
+    f: &mut Frame,
+    messages: &Messages,
+    profile: Option<&ProfileName>,
+    source: &str,
+    lines: Vec<Line>,
+) {
+    let area = f.size();
+    let title = match profile {
+        Some(profile) => messages.format("app_title_profile", &[("profile", profile)]),
+        None => messages.get("app_title").to_string(),
+    };
+    let room = header_room(&title, area.width);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title(Title::from(display_width::truncate(source, room, "…")).alignment(Alignment::Right));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let height = (lines.len() as u16).min(inner.height);
+    let top = inner.y + inner.height.saturating_sub(height) / 2;
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false }),
+        ratatui::layout::Rect::new(inner.x, top, inner.width, height),
+    );
+}
+
+/// Columns a header `width` wide leaves for a right-hand title beside `left`:
+/// two borders and a space between the titles
+fn header_room(left: &str, width: u16) -> usize {
+    (width as usize).saturating_sub(display_width::width(left) + 3)
+}
+
+/// A mastery drill is still going, so Next starts another attempt rather than
+/// ending the quiz
+fn drilling(view: &View) -> bool {
+    view.mastery.is_some_and(|mastery| !mastery.is_mastered())
+}
+
+/// The actions the controls bar offers right now, with the message key of
+/// each label: only keys that would do something in the current phase and
+/// mode, e.g. no hints in exam mode and no tabs in study mode
+fn control_entries(view: &View) -> Vec<(Action, &'static str)> {
+    let (quiz_state, config) = (view.quiz_state, view.config);
+    let question = quiz_state.current_question();
+    let candidates = if quiz_state.is_armed() {
+        vec![(Action::Quit, "key_quit")]
+    } else if quiz_state.is_resolved() && drilling(view) {
+        vec![
+            (Action::Next, "key_try_again"),
+            (Action::Related, "key_related"),
+            (Action::NextTab, "key_tabs"),
+            (Action::Quit, "key_quit"),
+        ]
+    } else if quiz_state.is_resolved() && quiz_state.is_last_question() {
+        vec![
+            (Action::Next, "key_summary"),
+            (Action::Related, "key_related"),
+            (Action::Quit, "key_quit"),
+        ]
+    } else if quiz_state.is_resolved() {
+        vec![
+            (Action::Next, "key_next_question"),
+            (Action::Related, "key_related"),
+            (Action::Jump, "key_go_to_id"),
+            (Action::NextTab, "key_tabs"),
+            (Action::Shell, "key_shell"),
+            (Action::Quit, "key_quit"),
+        ]
+    } else {
+        vec![
+            if question.yaml_answer {
+                (Action::Editor, "key_write_manifest")
+            } else {
+                (Action::Answer, "key_answer")
+            },
+            (Action::Hint, "key_hints"),
+            (Action::Skip, "key_skip"),
+            (Action::ExtendTime, "key_extend_time"),
+            (Action::RestartTimer, "key_restart_timer"),
+            (Action::Jump, "key_go_to_id"),
+            (Action::NextTab, "key_tabs"),
+            (Action::PrepCluster, "key_prep_cluster"),
+            (Action::Shell, "key_shell"),
+            (Action::Quit, "key_quit"),
+        ]
+    };
+    candidates
+        .into_iter()
+        .filter(|(action, _)| match action {
+            Action::Hint => view.hint_state.is_enabled() && !question.hints.is_empty(),
+            Action::ExtendTime => config.allows_time_extension(),
+            Action::RestartTimer => config.allows_timer_restart(),
+            Action::Related => !question.related_commands().is_empty(),
+            Action::PrepCluster => question.setup.is_some(),
+            Action::NextTab => config.mode != Mode::Study,
+            Action::Shell => view.shell_enabled && config.mode != Mode::Study,
+            _ => true,
+        })
+        .collect()
+}
+
+/// How the confidence ratings held up, e.g. "overconfident on 2, underconfident
+/// on 0 of 5 rated"; `None` when nothing was rated
+fn calibration_text(quiz_state: &QuizState, messages: &Messages) -> Option<String> {
+    let calibration = Calibration::from_quiz(quiz_state);
+    if calibration.rated == 0 {
+        None
+    } else if calibration.is_calibrated() {
+        Some(messages.format("summary_calibrated", &[("rated", &calibration.rated)]))
+    } else {
+        Some(messages.format(
+            "summary_calibration",
+            &[
+                ("over", &calibration.overconfident),
+                ("under", &calibration.underconfident),
+                ("rated", &calibration.rated),
+            ],
+        ))
+    }
+}
+
+/// "key: label" pairs from the effective bindings, leaving out unbound actions
+fn key_hints(keymap: &KeyBindings, entries: &[(Action, &str)]) -> String {
+    entries
+        .iter()
+        .filter_map(|(action, label)| {
+            keymap
+                .label(*action)
+                .map(|keys| format!("{}: {}", keys, label))
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Personal best for the current question and whether this attempt is beating it
+/// Nothing is shown for questions that have never been passed before
+pub(crate) fn best_time_text(
+    quiz_state: &QuizState,
+    bests: &PersonalBests,
+    messages: &Messages,
+) -> Option<(String, Color)> {
+    let best = bests.best(quiz_state.current_question())?;
+    let record = &quiz_state.records()[quiz_state.current_index()];
+    let (elapsed, finished) = match record.time_spent {
+        Some(spent) => (spent, true),
+        None => (quiz_state.timer().elapsed(), quiz_state.is_resolved()),
+    };
+    let best_text = messages.format("best_time", &[("time", &format_duration(best))]);
+    let with_pace = |key| messages.format(key, &[("best", &best_text)]);
+
+    Some(if record.status == QuestionStatus::Pass && elapsed < best {
+        (with_pace("best_new"), Color::Green)
+    } else if finished {
+        (best_text.clone(), Color::Gray)
+    } else if elapsed <= best {
+        (with_pace("best_on_pace"), Color::Green)
+    } else {
+        (with_pace("best_behind"), Color::Yellow)
+    })
+}
+
+/// The adaptive tier as a bar per question, then a legend with the highest
+/// tier reached and the one the session ended at
+pub(crate) fn tier_lines(
+    tiers: &[Difficulty],
+    theme: &Theme,
+    messages: &Messages,
+) -> Vec<Line<'static>> {
+    let bar = |tier: Difficulty| match tier {
+        Difficulty::Easy => ("▂", Color::Green, "tier_easy"),
+        Difficulty::Medium => ("▄", Color::Yellow, "tier_medium"),
+        Difficulty::Hard => ("█", Color::Red, "tier_hard"),
+    };
+    let chart = tiers
+        .iter()
+        .map(|&tier| {
+            let (glyph, color, _) = bar(tier);
+            Span::styled(glyph, theme.fg(color))
+        })
+        .collect::<Vec<_>>();
+    let name = |tier: Difficulty| messages.get(bar(tier).2).to_string();
+    let key = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+        .map(|tier| format!("{} {}", bar(tier).0, name(tier)))
+        .join("  ");
+    let (peak, last) = (tiers.iter().max(), tiers.last());
+    let legend = match peak.zip(last) {
+        Some((&peak, &last)) => format!(
+            "{} · {}",
+            key,
+            messages.format(
+                "tiers_legend",
+                &[("peak", &name(peak)), ("last", &name(last))]
+            )
+        ),
+        None => key,
+    };
+    vec![Line::from(chart), Line::from(legend)]
+}
+
+/// Score and time deltas with arrows, green where the run improved, then
+/// the questions missed last time and passed this time
+pub(crate) fn comparison_lines(
+    comparison: &RunComparison,
+    theme: &Theme,
+    messages: &Messages,
+) -> Vec<Line<'static>> {
+    // Rounded first, so float noise doesn't get an arrow
+    let score = (comparison.score_delta * 10.0).round() / 10.0;
+    let score_text = format!("{} {:.1}%", delta_arrow(score), score.abs());
+    let time = comparison.time_delta_secs;
+    let time_text = format!(
+        "{} {}",
+        delta_arrow(time as f64),
+        format_duration(std::time::Duration::from_secs(time.unsigned_abs()))
+    );
+    let deltas = Line::from(vec![
+        Span::raw(format!("{} ", messages.get("last_run_score"))),
+        Span::styled(score_text, theme.fg(delta_color(score))),
+        Span::raw(format!("  |  {} ", messages.get("last_run_time"))),
+        // Less time is the improvement
+        Span::styled(time_text, theme.fg(delta_color(-time as f64))),
+    ]);
+    let newly_correct = if comparison.newly_correct.is_empty() {
+        messages.get("last_run_none_fixed").to_string()
+    } else {
+        let ids: Vec<String> = comparison
+            .newly_correct
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        messages.format("last_run_fixed", &[("ids", &ids.join(", "))])
+    };
+    vec![deltas, Line::from(newly_correct)]
+}
+
+fn delta_arrow(delta: f64) -> &'static str {
+    if delta > 0.0 {
+        "▲"
+    } else if delta < 0.0 {
+        "▼"
+    } else {
+        "="
+    }
+}
+
+fn delta_color(improvement: f64) -> Color {
+    if improvement > 0.0 {
+        Color::Green
+    } else if improvement < 0.0 {
+        Color::Red
+    } else {
+        Color::DarkGray
+    }
+}
+
+pub(crate) fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Each expected command marked ✓ or ✗, then any commands that weren't needed
+fn breakdown_lines<'a>(
+    breakdown: &CommandBreakdown,
+    theme: &Theme,
+    messages: &Messages,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(Span::styled(
+        messages.format(
+            "answer_partial",
+            &[
+                ("matched", &breakdown.matched()),
+                ("total", &breakdown.commands.len()),
+            ],
+        ),
+        theme.fg(Color::Yellow),
+    ))];
+    for command in &breakdown.commands {
+        let (key, color) = if command.matched {
+            ("answer_command_matched", Color::Green)
+        } else {
+            ("answer_command_missed", Color::Red)
+        };
+        lines.push(Line::from(Span::styled(
+            messages.format(key, &[("command", &command.command)]),
+            theme.fg(color),
+        )));
+    }
+    for command in &breakdown.extra {
+        lines.push(Line::from(Span::styled(
+            messages.format("answer_command_extra", &[("command", command)]),
+            theme.fg(Color::DarkGray),
+        )));
+    }
+    lines
+}
+
+/// Rubric items with ✓ or ✗ and their points, under the earned total
+fn rubric_lines<'a>(rubric: &RubricBreakdown, theme: &Theme, messages: &Messages) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(Span::styled(
+        messages.format(
+            "answer_rubric",
+            &[("earned", &rubric.earned()), ("total", &rubric.total())],
+        ),
+        theme.fg(if rubric.is_complete() {
+            Color::Green
+        } else {
+            Color::Yellow
+        }),
+    ))];
+    for item in &rubric.items {
+        let (key, color) = if item.matched {
+            ("answer_rubric_matched", Color::Green)
+        } else {
+            ("answer_rubric_missed", Color::Red)
+        };
+        lines.push(Line::from(Span::styled(
+            messages.format(key, &[("item", &item.item), ("points", &item.points)]),
+            theme.fg(color),
+        )));
+    }
+    lines
+}
+
+/// Maps a question status to its summary badge, bracketed when colors are off
+pub fn status_badge(status: QuestionStatus, theme: &Theme, messages: &Messages) -> Span<'static> {
+    let (key, color) = match status {
+        QuestionStatus::Pass => ("status_pass", Color::Green),
+        QuestionStatus::Fail => ("status_fail", Color::Red),
+        QuestionStatus::Skipped => ("status_skipped", Color::Yellow),
+        QuestionStatus::Revealed => ("status_revealed", Color::Blue),
+        QuestionStatus::Pending => ("status_pending", Color::DarkGray),
+    };
+    badge(messages.get(key).to_string(), color, theme)
+}
+
+/// Pacing feedback for a resolved question: green with time to spare, yellow
+/// when it was close and red when the time ran out
+fn time_use_line(time_use: TimeUse, theme: &Theme, messages: &Messages) -> Line<'static> {
+    let (text, color) = match time_use {
+        TimeUse::Spare(percent) => (
+            messages.format("time_spare", &[("percent", &percent)]),
+            Color::Green,
+        ),
+        TimeUse::JustUnder(left) => (
+            messages.format("time_just_under", &[("time", &format_duration(left))]),
+            Color::Yellow,
+        ),
+        TimeUse::UsedAll => (messages.get("time_used_all").to_string(), Color::Red),
+    };
+    Line::from(Span::styled(text, theme.fg(color)))
+}
+
+/// Hints shown on this attempt and the share of all attempts, earlier ones
+/// included, that used hints, e.g. "2 · 67%"
+pub(crate) fn hint_rate(
+    stats: &HintStats,
+    question: &Question,
+    record: &QuestionRecord,
+    messages: &Messages,
+) -> String {
+    let mut usage = stats.question(&question.identity());
+    if record.status != QuestionStatus::Pending {
+        usage.record(!record.hints_viewed.is_empty());
+    }
+    messages.format(
+        "summary_hint_rate",
+        &[
+            ("used", &record.hints_viewed.len()),
+            ("rate", &format!("{:.0}", usage.rate())),
+        ],
+    )
+}
+
+/// Summary badge of a failed answer that earned partial credit
+pub(crate) fn partial_badge(credit: f64, theme: &Theme, messages: &Messages) -> Span<'static> {
+    let percent = (credit * 100.0).round() as u32;
+    badge(
+        messages.format("status_partial", &[("percent", &percent)]),
+        Color::Yellow,
+        theme,
+    )
+}
+
+fn badge(label: String, color: Color, theme: &Theme) -> Span<'static> {
+    if theme.color_enabled() {
+        Span::styled(label, theme.fg(color).add_modifier(Modifier::BOLD))
+    } else {
+        Span::raw(format!("[{}]", label))
     }
 }
@@ -0,0 +1,324 @@
+use crate::grading;
+use crate::models::Question;
+use crate::question_repository::{question_lines, QuestionFormat, RepositoryError};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// JSON Schema of a question bank, for editors and CI (also `validate --schema-out`)
+pub const SCHEMA: &str = include_str!("../schema/questions.schema.json");
+
+/// What `validate` checks and where the schema goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidateOptions {
+    pub bank: Option<PathBuf>,
+    pub schema_out: Option<PathBuf>,
+}
+
+/// One problem in a bank, located by a JSON path like `$[2].hints[0]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The embedded schema, parsed
+pub fn schema() -> Value {
+    serde_json::from_str(SCHEMA).expect("the embedded schema is valid JSON")
+}
+
+/// Checks a bank file, JSON, JSON Lines or YAML by extension; only a file that can't be
+/// read is an `Err`, everything wrong inside it is listed
+pub fn validate_file(path: &Path) -> Result<Vec<ValidationError>, RepositoryError> {
+    let contents = fs::read_to_string(path).map_err(|source| RepositoryError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let format = QuestionFormat::from_path(path).unwrap_or(QuestionFormat::Json);
+    Ok(validate_str(&contents, format))
+}
+
+pub fn validate_str(contents: &str, format: QuestionFormat) -> Vec<ValidationError> {
+    let parsed = match format {
+        QuestionFormat::Yaml => serde_yaml::from_str::<Value>(contents).map_err(|e| e.to_string()),
+        QuestionFormat::Json => serde_json::from_str::<Value>(contents).map_err(|e| e.to_string()),
+        // Checked as the array of its lines, so paths still index questions
+        QuestionFormat::JsonLines => question_lines(contents)
+            .map(|(number, line)| {
+                serde_json::from_str::<Value>(line).map_err(|e| format!("line {}: {}", number, e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+    };
+    match parsed {
+        Ok(bank) => validate_value(&bank),
+        Err(message) => vec![ValidationError::new("$", message)],
+    }
+}
+
+/// Schema validation, then the quiz's own rules on every entry that passed it:
+/// unique ids, known prerequisites, the per-question checks `add` applies,
+/// and hint lint
+pub fn validate_value(bank: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    check_schema(&schema(), bank, "$", &mut errors);
+    let Some(entries) = bank.as_array() else {
+        return errors;
+    };
+    let questions: Vec<(String, Question)> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (format!("$[{}]", index), entry))
+        .filter(|(path, _)| !errors.iter().any(|error| within(&error.path, path)))
+        .filter_map(|(path, entry)| {
+            serde_json::from_value(entry.clone())
+                .ok()
+                .map(|question| (path, question))
+        })
+        .collect();
+
+    let mut first_with_id: HashMap<usize, &str> = HashMap::new();
+    for (path, question) in &questions {
+        if let Some(first) = first_with_id.get(&question.id) {
+            errors.push(ValidationError::new(
+                format!("{}.id", path),
+                format!("id {} is already used by {}", question.id, first),
+            ));
+        } else {
+            first_with_id.insert(question.id, path);
+        }
+    }
+    let mut first_with_uid: HashMap<&str, &str> = HashMap::new();
+    for (path, question) in &questions {
+        let Some(uid) = question.uid.as_deref() else {
+            continue;
+        };
+        if let Some(first) = first_with_uid.get(uid) {
+            errors.push(ValidationError::new(
+                format!("{}.uid", path),
+                format!("uid {} is already used by {}", uid, first),
+            ));
+        } else {
+            first_with_uid.insert(uid, path);
+        }
+    }
+    for (path, question) in &questions {
+        for (field, message) in check_question(question)
+            .into_iter()
+            .chain(lint_hints(question))
+        {
+            errors.push(ValidationError::new(format!("{}.{}", path, field), message));
+        }
+        for (index, id) in question.depends_on.iter().flatten().enumerate() {
+            let path = format!("{}.depends_on[{}]", path, index);
+            if *id == question.id {
+                errors.push(ValidationError::new(
+                    path,
+                    "a question can't depend on itself",
+                ));
+            } else if !first_with_id.contains_key(id) {
+                errors.push(ValidationError::new(
+                    path,
+                    format!("no question with id {}", id),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// What the quiz needs from a question to run it, as `(field, message)` pairs
+pub fn check_question(question: &Question) -> Vec<(String, String)> {
+    let mut problems = Vec::new();
+    let mut problem = |field: &str, message: String| problems.push((field.to_string(), message));
+    if question.question.trim().is_empty() {
+        problem("question", "the question text is empty".to_string());
+    }
+    if question.answer.trim().is_empty() {
+        problem("answer", "the answer is empty".to_string());
+    }
+    if question.time_limit_secs == 0 {
+        problem(
+            "time_limit_secs",
+            "the time limit must be above 0".to_string(),
+        );
+    }
+    if let Some(pattern) = &question.answer_pattern {
+        if let Err(err) = grading::compile_pattern(pattern, true) {
+            problem(
+                "answer_pattern",
+                format!("the answer pattern is not a valid regex: {}", err),
+            );
+        }
+    }
+    if let Err(err) = grading::check_rubric(question) {
+        problem("rubric", format!("the rubric is invalid: {}", err));
+    }
+    if question.yaml_answer && !question.answer.trim().is_empty() {
+        if let Err(err) = serde_yaml::from_str::<serde_yaml::Value>(&question.answer) {
+            problem("answer", format!("the answer is not valid YAML: {}", err));
+        }
+    }
+    problems
+}
+
+/// Hints that can't help: blank ones, repeats, and a first hint that gives
+/// the whole answer away
+pub fn lint_hints(question: &Question) -> Vec<(String, String)> {
+    let mut problems = Vec::new();
+    for (index, hint) in question.hints.iter().enumerate() {
+        let field = format!("hints[{}]", index);
+        if hint.trim().is_empty() {
+            problems.push((field, "the hint is blank".to_string()));
+        } else if let Some(earlier) = question.hints[..index]
+            .iter()
+            .position(|other| other.trim() == hint.trim())
+        {
+            problems.push((field, format!("the hint repeats hints[{}]", earlier)));
+        } else if index == 0 && hint.trim() == question.answer.trim() {
+            problems.push((field, "the first hint is the whole answer".to_string()));
+        }
+    }
+    problems
+}
+
+/// Whether `path` is `parent` or inside it
+fn within(path: &str, parent: &str) -> bool {
+    path.strip_prefix(parent)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+/// The subset of JSON Schema the bank schema uses: `type`, `enum`, `required`,
+/// `properties`, `additionalProperties: false`, `items`, `minimum`, `maximum`
+/// and `minLength`
+fn check_schema(schema: &Value, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            errors.push(ValidationError::new(
+                path,
+                format!("expected one of {}, found {}", allowed.join(", "), value),
+            ));
+        }
+        return;
+    }
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        if !has_type(value, expected) {
+            errors.push(ValidationError::new(
+                path,
+                format!("expected {}, found {}", a_or_an(expected), describe(value)),
+            ));
+            return;
+        }
+    }
+    if let (Some(minimum), Some(number)) = (
+        schema.get("minimum").and_then(Value::as_f64),
+        value.as_f64(),
+    ) {
+        if number < minimum {
+            errors.push(ValidationError::new(
+                path,
+                format!("must be at least {}", minimum),
+            ));
+        }
+    }
+    if let (Some(maximum), Some(number)) = (
+        schema.get("maximum").and_then(Value::as_f64),
+        value.as_f64(),
+    ) {
+        if number > maximum {
+            errors.push(ValidationError::new(
+                path,
+                format!("must be at most {}", maximum),
+            ));
+        }
+    }
+    if let (Some(min_length), Some(text)) = (
+        schema.get("minLength").and_then(Value::as_u64),
+        value.as_str(),
+    ) {
+        if (text.chars().count() as u64) < min_length {
+            errors.push(ValidationError::new(path, "must not be empty"));
+        }
+    }
+    if let (Some(items), Some(values)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in values.iter().enumerate() {
+            check_schema(items, item, &format!("{}[{}]", path, index), errors);
+        }
+    }
+    if let Some(object) = value.as_object() {
+        for field in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(field) {
+                errors.push(ValidationError::new(
+                    path,
+                    format!("missing required field `{}`", field),
+                ));
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+        for (field, field_value) in object {
+            let field_path = format!("{}.{}", path, field);
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(field_schema) => check_schema(field_schema, field_value, &field_path, errors),
+                None if closed => errors.push(ValidationError::new(field_path, "unknown field")),
+                None => {}
+            }
+        }
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_u64() || value.is_i64(),
+        "number" => value.is_number(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(number) if number.is_f64() => "a number",
+        Value::Number(_) => "an integer",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn a_or_an(kind: &str) -> String {
+    match kind {
+        "array" | "object" | "integer" => format!("an {}", kind),
+        _ => format!("a {}", kind),
+    }
+}
@@ -0,0 +1,199 @@
+use crate::results::SessionResult;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::StatusCode;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where finished sessions are posted, e.g. a study group's dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSettings {
+    pub url: String,
+    /// Sent as `Authorization: Bearer <token>`
+    pub token: Option<String>,
+    /// Attempts after the first one fails
+    pub retries: u32,
+    /// Wait before the first retry; doubled before each later one
+    pub backoff: Duration,
+    /// How long one attempt may take, so a dead endpoint can't hold up the exit
+    pub timeout: Duration,
+}
+
+impl WebhookSettings {
+    pub const DEFAULT_RETRIES: u32 = 2;
+
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            token: None,
+            retries: Self::DEFAULT_RETRIES,
+            backoff: Duration::from_millis(500),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+/// Why a payload could not be delivered
+#[derive(Debug)]
+pub enum DeliveryError {
+    /// The endpoint answered with a non-success status
+    Status(u16),
+    /// No answer: connection refused, DNS failure, timeout
+    Transport(String),
+}
+
+impl DeliveryError {
+    /// Server errors, rate limiting and network trouble may pass; other
+    /// client errors will fail the same way again
+    fn is_retryable(&self) -> bool {
+        match self {
+            DeliveryError::Status(status) => {
+                *status >= 500 || *status == StatusCode::TOO_MANY_REQUESTS.as_u16()
+            }
+            DeliveryError::Transport(_) => true,
+        }
+    }
+}
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeliveryError::Status(status) => write!(f, "the webhook answered HTTP {}", status),
+            DeliveryError::Transport(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for DeliveryError {}
+
+/// Posts one JSON payload, retrying with exponential backoff
+pub async fn deliver(settings: &WebhookSettings, payload: &str) -> Result<(), DeliveryError> {
+    let client = reqwest::Client::builder()
+        .timeout(settings.timeout)
+        .build()
+        .map_err(|err| DeliveryError::Transport(err.to_string()))?;
+    let mut backoff = settings.backoff;
+    let mut attempt = 0;
+    loop {
+        let err = match post(&client, settings, payload).await {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        if attempt == settings.retries || !err.is_retryable() {
+            return Err(err);
+        }
+        attempt += 1;
+        tracing::info!(attempt, error = %err, "webhook delivery failed, retrying");
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+}
+
+async fn post(
+    client: &reqwest::Client,
+    settings: &WebhookSettings,
+    payload: &str,
+) -> Result<(), DeliveryError> {
+    let mut request = client
+        .post(&settings.url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(payload.to_string());
+    if let Some(token) = &settings.token {
+        request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| DeliveryError::Transport(err.to_string()))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(DeliveryError::Status(response.status().as_u16()))
+    }
+}
+
+/// What became of a session's results
+#[derive(Debug)]
+pub enum Posted {
+    Delivered,
+    /// Delivery failed and the payload waits in the outbox
+    Queued {
+        path: PathBuf,
+        error: DeliveryError,
+    },
+}
+
+/// Directory of payloads that could not be delivered yet
+pub fn outbox_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("outbox")
+}
+
+/// Posts the results document, the same JSON saved under `results/`; a
+/// failure keeps it in `outbox` for `flush-outbox`
+pub async fn post_session(
+    settings: &WebhookSettings,
+    result: &SessionResult,
+    outbox: &Path,
+) -> io::Result<Posted> {
+    let payload = serde_json::to_string_pretty(result)?;
+    match deliver(settings, &payload).await {
+        Ok(()) => Ok(Posted::Delivered),
+        Err(error) => {
+            tracing::warn!(url = %settings.url, error = %error, "webhook delivery failed");
+            fs::create_dir_all(outbox)?;
+            let path = outbox.join(result.file_name());
+            fs::write(&path, payload)?;
+            Ok(Posted::Queued { path, error })
+        }
+    }
+}
+
+/// Outcome of `flush-outbox`
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    pub delivered: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, DeliveryError)>,
+}
+
+/// Sends every queued payload in file name order, deleting the delivered ones
+pub async fn flush_outbox(settings: &WebhookSettings, outbox: &Path) -> io::Result<FlushReport> {
+    let mut report = FlushReport::default();
+    let mut paths: Vec<PathBuf> = match fs::read_dir(outbox) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(report),
+        Err(err) => return Err(err),
+    };
+    paths.sort();
+    for path in paths {
+        let payload = fs::read_to_string(&path)?;
+        match deliver(settings, &payload).await {
+            Ok(()) => {
+                fs::remove_file(&path)?;
+                report.delivered.push(path);
+            }
+            Err(err) => report.failed.push((path, err)),
+        }
+    }
+    Ok(report)
+}
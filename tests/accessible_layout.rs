@@ -0,0 +1,174 @@
+mod common;
+
+use ckad_practitioner::app::App;
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::keymap::Action;
+use ckad_practitioner::settings::{self, FileConfig, Settings};
+use ckad_practitioner::theme::ThemeName;
+use ckad_practitioner::ui::ScreenLayout;
+use clap::Parser;
+use common::{key_event, new_app, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, style::Color, Terminal};
+use std::time::Duration;
+
+const BORDER_GLYPHS: &[char] = &[
+    '─', '│', '┌', '┐', '└', '┘', '├', '┤', '┬', '┴', '┼', '╭', '╮', '╰', '╯',
+];
+
+fn accessible_app() -> App {
+    new_app().with_layout(ScreenLayout::Accessible)
+}
+
+/// Fails on any border glyph or colored cell in the last frame
+fn assert_accessible(terminal: &Terminal<TestBackend>) {
+    for cell in &terminal.backend().buffer().content {
+        assert!(
+            !cell.symbol().chars().any(|c| BORDER_GLYPHS.contains(&c)),
+            "border glyph {:?}",
+            cell.symbol()
+        );
+        assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_quiz_is_drawn_as_labeled_text() {
+    let mut app = accessible_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("SOURCE: mock (2 Qs)"), "{}", screen);
+    assert!(screen.contains("TIMER: 0:30"), "{}", screen);
+    assert!(
+        screen.contains("QUESTION 1/2: Create a pod named alpha"),
+        "{}",
+        screen
+    );
+    assert!(screen.contains("CONTROLS: "), "{}", screen);
+    assert_accessible(&terminal);
+}
+
+#[tokio::test(start_paused = true)]
+async fn hints_are_numbered_out_of_the_total() {
+    let mut app = accessible_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("HINTS: "), "{}", screen);
+
+    app.dispatch(Action::Hint);
+    app.dispatch(Action::Hint);
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("HINT 1/2: First hint"), "{}", screen);
+    assert!(screen.contains("HINT 2/2: Second hint"), "{}", screen);
+    assert_accessible(&terminal);
+}
+
+#[tokio::test(start_paused = true)]
+async fn typing_and_grading_are_spelled_out() {
+    let mut app = accessible_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let mut events = vec![Event::Key(key_event('a'))];
+    events.extend("kubectl".chars().map(|c| Event::Key(key_event(c))));
+    let screen = run_script(&mut app, &mut terminal, events).await;
+    assert!(screen.contains("YOUR ANSWER: kubectl"), "{}", screen);
+
+    let mut events: Vec<Event> = " run alpha --image=nginx"
+        .chars()
+        .map(|c| Event::Key(key_event(c)))
+        .collect();
+    events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+    let screen = run_script(&mut app, &mut terminal, events).await;
+    assert!(screen.contains("RESULT: PASS"), "{}", screen);
+    assert!(
+        screen.contains("kubectl run alpha --image=nginx"),
+        "{}",
+        screen
+    );
+    assert_accessible(&terminal);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_clock_running_low_and_out_is_said_in_words() {
+    let mut app = accessible_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    tokio::time::advance(Duration::from_secs(25)).await;
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("running out"), "{}", screen);
+
+    tokio::time::advance(Duration::from_secs(6)).await;
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("TIMER: expired"), "{}", screen);
+    assert_accessible(&terminal);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_summary_lists_each_result_on_its_own_line() {
+    let mut app = accessible_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let skips = vec![Event::Key(key_event('s')), Event::Key(key_event('s'))];
+    let screen = run_script(&mut app, &mut terminal, skips).await;
+    assert!(app.session().is_finished());
+    assert!(screen.contains("SUMMARY: "), "{}", screen);
+    assert!(
+        screen.contains("QUESTION 1: Create a pod named alpha: SKIP"),
+        "{}",
+        screen
+    );
+    assert_accessible(&terminal);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_start_screen_has_no_borders_either() {
+    let mut app = accessible_app().with_start_screen(3);
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("Ready?"), "{}", screen);
+    assert_accessible(&terminal);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_standard_layout_keeps_its_borders() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains('┌'), "{}", screen);
+    assert!(!screen.contains("TIMER: "));
+}
+
+#[test]
+fn the_accessible_layout_comes_from_the_flag_or_the_ui_section() {
+    let cli = Cli::parse_from(["ckad-practitioner", "--layout", "accessible"]);
+    let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+    assert_eq!(settings.layout, ScreenLayout::Accessible);
+    assert_eq!(settings.theme, ThemeName::Mono);
+    assert!(!settings.quiz.reveal_animation);
+    assert!(!settings.quiz.flash);
+
+    let (file, warnings) = settings::parse_file_config("[ui]\nlayout = \"accessible\"\n").unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let cli = Cli::parse_from(["ckad-practitioner"]);
+    assert_eq!(
+        Settings::resolve(&cli, &file).unwrap().layout,
+        ScreenLayout::Accessible
+    );
+
+    let cli = Cli::parse_from(["ckad-practitioner", "--layout", "standard"]);
+    let settings = Settings::resolve(&cli, &file).unwrap();
+    assert_eq!(settings.layout, ScreenLayout::Standard);
+    assert_eq!(settings.theme, ThemeName::Default);
+}
+
+#[test]
+fn the_accessible_layout_is_separate_from_line_based_plain_mode() {
+    let cli = Cli::parse_from(["ckad-practitioner", "--plain"]);
+    let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+    assert!(settings.plain);
+    assert_eq!(settings.layout, ScreenLayout::Standard);
+
+    let cli = Cli::parse_from(["ckad-practitioner", "--layout", "accessible"]);
+    let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+    assert!(!settings.plain);
+    assert_eq!(settings.layout, ScreenLayout::Accessible);
+
+    assert!(Cli::try_parse_from(["ckad-practitioner", "--render", "plain"]).is_err());
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use ckad_practitioner::adaptive::{self, TierLadder};
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::models::{Difficulty, Question};
+use ckad_practitioner::session::{Session, SessionAction};
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+
+/// Two questions per tier, hard first, so the order they come in shows the picks
+fn questions() -> Vec<Question> {
+    [Difficulty::Hard, Difficulty::Easy, Difficulty::Medium]
+        .into_iter()
+        .flat_map(|tier| [tier, tier])
+        .enumerate()
+        .map(|(index, tier)| Question {
+            id: index + 1,
+            question: format!("Question {}", index + 1),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 60,
+            difficulty: Some(tier),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn adaptive_session() -> Session {
+    let config = QuizConfig {
+        mode: Mode::Adaptive,
+        ..QuizConfig::default()
+    };
+    Session::new(questions(), config).unwrap()
+}
+
+fn current_tier(session: &Session) -> Option<Difficulty> {
+    session.quiz_state().current_question().difficulty
+}
+
+/// Answers the current question right or wrong and moves on
+fn answer(session: &mut Session, correct: bool) {
+    let text = if correct {
+        "kubectl get pods"
+    } else {
+        "kubectl get svc"
+    };
+    session.apply(SessionAction::Submit(text.into())).unwrap();
+    session.apply(SessionAction::Next).unwrap();
+}
+
+#[test]
+fn two_right_answers_climb_a_tier_and_a_miss_drops_one() {
+    let mut ladder = TierLadder::new();
+    assert_eq!(ladder.tier(), Difficulty::Medium);
+    assert_eq!(ladder.record(true), Difficulty::Medium);
+    assert_eq!(ladder.record(true), Difficulty::Hard);
+    assert_eq!(ladder.record(false), Difficulty::Medium);
+    assert_eq!(ladder.record(false), Difficulty::Easy);
+    assert_eq!(
+        ladder.history(),
+        [
+            Difficulty::Medium,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Medium
+        ]
+    );
+}
+
+#[test]
+fn the_ladder_stays_put_at_either_end() {
+    let mut ladder = TierLadder::replay([true, true, true, true]);
+    assert_eq!(ladder.tier(), Difficulty::Hard);
+    assert_eq!(ladder.record(true), Difficulty::Hard);
+
+    let mut ladder = TierLadder::replay([false]);
+    assert_eq!(ladder.record(false), Difficulty::Easy);
+}
+
+#[test]
+fn a_miss_breaks_the_streak_toward_the_next_tier() {
+    let ladder = TierLadder::replay([true, false, true]);
+    assert_eq!(ladder.tier(), Difficulty::Easy);
+    let ladder = TierLadder::replay([false, true, true]);
+    assert_eq!(ladder.tier(), Difficulty::Medium);
+}
+
+#[test]
+fn the_pick_falls_back_to_the_nearest_tier() {
+    let pool = questions();
+    assert_eq!(adaptive::pick(&pool, Difficulty::Medium), Some(4));
+    assert_eq!(adaptive::pick(&pool[..4], Difficulty::Easy), Some(2));
+    // No medium questions left: hard and easy are as near, the first one wins
+    assert_eq!(adaptive::pick(&pool[..4], Difficulty::Medium), Some(0));
+    assert_eq!(adaptive::pick(&pool[2..4], Difficulty::Hard), Some(0));
+    assert_eq!(adaptive::pick(&[], Difficulty::Hard), None);
+
+    let unrated = Question {
+        difficulty: None,
+        ..pool[0].clone()
+    };
+    assert_eq!(
+        adaptive::pick(&[pool[0].clone(), unrated], Difficulty::Medium),
+        Some(1)
+    );
+}
+
+#[test]
+fn the_session_follows_the_answers() {
+    let mut session = adaptive_session();
+    assert_eq!(current_tier(&session), Some(Difficulty::Medium));
+    answer(&mut session, true);
+    assert_eq!(current_tier(&session), Some(Difficulty::Medium));
+    answer(&mut session, true);
+    assert_eq!(current_tier(&session), Some(Difficulty::Hard));
+    answer(&mut session, false);
+    // The medium pool is used up, so the nearest tier stands in
+    assert_eq!(current_tier(&session), Some(Difficulty::Hard));
+    answer(&mut session, false);
+    assert_eq!(current_tier(&session), Some(Difficulty::Easy));
+    assert_eq!(session.tier_history().len(), 4);
+}
+
+#[test]
+fn other_modes_keep_the_bank_order() {
+    let session = Session::new(questions(), QuizConfig::default()).unwrap();
+    assert_eq!(current_tier(&session), Some(Difficulty::Hard));
+    assert!(session.tier_history().is_empty());
+}
+
+#[test]
+fn a_restored_session_keeps_its_order_and_tier() {
+    let mut session = adaptive_session();
+    answer(&mut session, true);
+    answer(&mut session, true);
+    let snapshot = session.snapshot("mock");
+    let order = session.quiz_state().questions().to_vec();
+    let config = QuizConfig {
+        mode: Mode::Adaptive,
+        ..QuizConfig::default()
+    };
+
+    let mut restored = Session::restore(order, config, &snapshot).unwrap();
+    let ids = |session: &Session| {
+        session
+            .quiz_state()
+            .questions()
+            .iter()
+            .map(|question| question.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&restored), ids(&session));
+    assert_eq!(restored.tier_history(), session.tier_history());
+    answer(&mut restored, true);
+    answer(&mut session, true);
+    assert_eq!(current_tier(&restored), current_tier(&session));
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_summary_charts_the_tier_over_the_session() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Adaptive,
+        ..QuizConfig::default()
+    });
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let skips = vec![Event::Key(key_event('s')), Event::Key(key_event('s'))];
+    let screen = run_script(&mut app, &mut terminal, skips).await;
+    assert!(app.session().is_finished());
+    assert!(screen.contains("Difficulty by question"), "{}", screen);
+    assert!(screen.contains("▄▂"), "{}", screen);
+    assert!(
+        screen.contains("peak: medium · ended at: easy"),
+        "{}",
+        screen
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn practice_summaries_have_no_chart() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let skips = vec![Event::Key(key_event('s')), Event::Key(key_event('s'))];
+    let screen = run_script(&mut app, &mut terminal, skips).await;
+    assert!(!screen.contains("Difficulty by question"));
+}
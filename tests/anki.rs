@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use ckad_practitioner::anki;
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::question_repository::{
+    InMemoryQuestionRepository, QuestionRepository, RepositoryError,
+};
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::results::{QuestionResult, Score, SessionResult};
+use ckad_practitioner::stats;
+
+/// The fields of a question an Anki note carries
+#[derive(Debug, PartialEq)]
+struct Card<'a> {
+    question: &'a str,
+    context: Option<&'a str>,
+    answer: &'a str,
+    notes: Option<&'a str>,
+    tags: &'a [String],
+    category: Option<&'a str>,
+}
+
+fn card(question: &Question) -> Card<'_> {
+    Card {
+        question: &question.question,
+        context: question.context.as_deref(),
+        answer: &question.answer,
+        notes: question.notes.as_deref(),
+        tags: &question.tags,
+        category: question.category.as_deref(),
+    }
+}
+
+fn tricky_question() -> Question {
+    Question {
+        id: 9,
+        question: "# Fix the <Pod> below & \"expose\" it".to_string(),
+        context: Some("apiVersion: v1\nkind: Pod\n\tmetadata: {}".to_string()),
+        answer: "kubectl expose pod web --port=80 && echo '<br>' &#9;".to_string(),
+        notes: Some("Line one\nLine two".to_string()),
+        tags: vec!["services".to_string(), "networking".to_string()],
+        category: Some("Services & Networking".to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn each_note_is_one_line_of_three_tab_separated_fields() {
+    let tsv = anki::to_tsv(&[tricky_question()]);
+    let lines: Vec<&str> = tsv.lines().collect();
+    assert_eq!(
+        &lines[..3],
+        ["#separator:tab", "#html:true", "#tags column:3"]
+    );
+    assert_eq!(lines.len(), 4, "{}", tsv);
+    let fields: Vec<&str> = lines[3].split('\t').collect();
+    assert_eq!(
+        fields,
+        [
+            "&#35; Fix the &lt;Pod&gt; below &amp; &quot;expose&quot; it<hr>apiVersion: v1<br>kind: Pod<br>&#9;metadata: {}",
+            "kubectl expose pod web --port=80 &amp;&amp; echo '&lt;br&gt;' &amp;#9;<hr>Line one<br>Line two",
+            "services networking domain::Services_&_Networking",
+        ]
+    );
+}
+
+#[test]
+fn exported_questions_import_back_unchanged() {
+    let mut questions = InMemoryQuestionRepository.get_questions().unwrap();
+    questions.push(tricky_question());
+    let imported = anki::from_tsv(&anki::to_tsv(&questions)).unwrap();
+    assert_eq!(imported.len(), questions.len());
+    for (original, imported) in questions.iter().zip(&imported) {
+        assert_eq!(card(imported), card(original), "question {}", original.id);
+    }
+}
+
+#[test]
+fn lines_without_a_back_field_fail_the_import() {
+    let err = anki::from_tsv("#html:true\nfront only\n").unwrap_err();
+    assert_eq!(err, "line 2 has no back field");
+}
+
+fn session(started_at: &str, outcomes: &[(usize, QuestionStatus)]) -> SessionResult {
+    let started_at: DateTime<Utc> = started_at.parse().unwrap();
+    SessionResult {
+        started_at,
+        finished_at: started_at,
+        mode: Mode::Practice,
+        complete: true,
+        questions: outcomes
+            .iter()
+            .map(|(id, outcome)| QuestionResult {
+                id: *id,
+                uid: String::new(),
+                question: String::new(),
+                expected_answer: String::new(),
+                domain: None,
+                outcome: *outcome,
+                time_spent_secs: 10,
+                hints_used: 0,
+                hints_viewed: Vec::new(),
+                tags: Vec::new(),
+                answer_submitted: None,
+                attempts: Vec::new(),
+                partial_credit: None,
+                points: 0.0,
+                confidence: None,
+            })
+            .collect(),
+        score: Score {
+            correct: 0,
+            total: outcomes.len(),
+            points: 0.0,
+            possible_points: outcomes.len() as u32,
+            percent: 0.0,
+        },
+        domains: Vec::new(),
+        break_secs: 0,
+        elapsed_secs: 0,
+    }
+}
+
+/// Questions 1, 2, 3 and 5, without text, so they're known by their ids
+struct Bank;
+
+impl QuestionRepository for Bank {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        Ok([5, 3, 2, 1]
+            .into_iter()
+            .map(|id| Question {
+                id,
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    fn source_description(&self) -> String {
+        "bank".to_string()
+    }
+}
+
+#[test]
+fn only_missed_keeps_questions_whose_latest_outcome_is_a_miss() {
+    use QuestionStatus::*;
+    // Listed out of order: the latest session decides, not the file order
+    let history = vec![
+        session(
+            "2024-05-03T10:00:00Z",
+            &[(1, Pass), (2, Fail), (4, Pending)],
+        ),
+        session(
+            "2024-05-01T10:00:00Z",
+            &[(1, Fail), (2, Pass), (3, Skipped), (4, Revealed)],
+        ),
+    ];
+    let missed = stats::missed_questions(&history);
+    // Results without question text are keyed by their id
+    let mut ids: Vec<&str> = missed.iter().map(String::as_str).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["id:2", "id:3", "id:4"]);
+
+    let (questions, gone) = anki::fetch_missed(&Bank, &missed).unwrap();
+    let kept: Vec<usize> = questions.iter().map(|question| question.id).collect();
+    assert_eq!(kept, vec![2, 3]);
+    assert_eq!(gone, vec!["id:4"]);
+}
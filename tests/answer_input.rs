@@ -0,0 +1,64 @@
+use ckad_practitioner::quiz_state::AnswerInput;
+
+fn typed(text: &str) -> AnswerInput {
+    let mut input = AnswerInput::new();
+    input.open();
+    text.chars().for_each(|c| input.push(c));
+    input
+}
+
+#[test]
+fn typing_inserts_at_the_cursor() {
+    let mut input = typed("kubectl get");
+    assert_eq!(input.cursor(), 11);
+    for _ in 0..3 {
+        input.move_left();
+    }
+    "-n dev ".chars().for_each(|c| input.push(c));
+    assert_eq!(input.buffer(), "kubectl -n dev get");
+    assert_eq!(input.before_cursor(), "kubectl -n dev ");
+}
+
+#[test]
+fn backspace_deletes_before_the_cursor() {
+    let mut input = typed("get pods");
+    input.move_left();
+    input.move_left();
+    input.backspace();
+    assert_eq!(input.buffer(), "get pds");
+    assert_eq!(input.cursor(), 5);
+
+    // Nothing before the cursor: nothing to delete
+    for _ in 0..10 {
+        input.move_left();
+    }
+    input.backspace();
+    assert_eq!(input.buffer(), "get pds");
+}
+
+#[test]
+fn the_cursor_stays_within_the_buffer() {
+    let mut input = typed("ab");
+    input.move_right();
+    assert_eq!(input.cursor(), 2);
+    input.move_left();
+    input.move_left();
+    input.move_left();
+    assert_eq!(input.cursor(), 0);
+    input.push('x');
+    assert_eq!(input.buffer(), "xab");
+
+    assert_eq!(input.take(), "xab");
+    assert_eq!(input.cursor(), 0);
+}
+
+#[test]
+fn the_cursor_moves_by_characters_not_bytes() {
+    let mut input = typed("é→");
+    input.move_left();
+    input.push('x');
+    assert_eq!(input.buffer(), "éx→");
+    input.move_right();
+    input.backspace();
+    assert_eq!(input.buffer(), "éx");
+}
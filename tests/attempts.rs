@@ -0,0 +1,179 @@
+mod common;
+
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::{Mastery, QuizConfig};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::{QuestionStatus, QuizState};
+use ckad_practitioner::serve;
+use ckad_practitioner::session::{Session, SessionAction};
+use ckad_practitioner::settings::{self, Settings};
+use clap::Parser;
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn questions() -> Vec<Question> {
+    ["kubectl get pods", "kubectl get svc"]
+        .into_iter()
+        .enumerate()
+        .map(|(index, answer)| Question {
+            id: index + 1,
+            question: format!("Question {}", index + 1),
+            answer: answer.to_string(),
+            time_limit_secs: 60,
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn retrying() -> QuizConfig {
+    QuizConfig {
+        retry_wrong: true,
+        ..QuizConfig::default()
+    }
+}
+
+fn submit(session: &mut Session, answer: &str) {
+    session
+        .apply(SessionAction::Submit(answer.to_string()))
+        .unwrap();
+}
+
+/// Presses `a`, types `text` and submits it
+fn answer(text: &str) -> Vec<Event> {
+    let mut events = vec![Event::Key(key_event('a'))];
+    events.extend(text.chars().map(|c| Event::Key(key_event(c))));
+    events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+    events
+}
+
+#[test]
+fn attempts_accumulate_in_order_on_their_own_question() {
+    let mut quiz = QuizState::new(questions(), &QuizConfig::default());
+    quiz.record_attempt("kubectl get nodes".to_string());
+    quiz.record_attempt("kubectl get pv".to_string());
+    quiz.submit_answer("kubectl get pods".to_string(), true);
+    // Resolved, so nothing more is kept
+    quiz.record_attempt("kubectl get nodes".to_string());
+    quiz.next_question();
+    quiz.record_attempt("kubectl get service".to_string());
+
+    assert_eq!(
+        quiz.records()[0].attempts,
+        ["kubectl get nodes", "kubectl get pv", "kubectl get pods"]
+    );
+    assert_eq!(
+        quiz.records()[0].submission.as_deref(),
+        Some("kubectl get pods")
+    );
+    assert_eq!(quiz.records()[1].attempts, ["kubectl get service"]);
+    assert_eq!(quiz.records()[1].status, QuestionStatus::Pending);
+}
+
+#[test]
+fn with_retry_wrong_a_miss_leaves_the_question_open() {
+    let mut session = Session::new(questions(), retrying()).unwrap();
+    submit(&mut session, "kubectl get nodes");
+    assert!(!session.quiz_state().is_resolved());
+    submit(&mut session, "kubectl get pods");
+    assert_eq!(session.quiz_state().current_status(), QuestionStatus::Pass);
+
+    let result = session.result();
+    assert_eq!(
+        result.questions[0].attempts,
+        ["kubectl get nodes", "kubectl get pods"]
+    );
+    assert!(result.questions[1].attempts.is_empty());
+}
+
+#[test]
+fn without_it_the_first_answer_is_final() {
+    let mut session = Session::new(questions(), QuizConfig::default()).unwrap();
+    submit(&mut session, "kubectl get nodes");
+    assert_eq!(session.quiz_state().current_status(), QuestionStatus::Fail);
+    assert_eq!(session.quiz_state().records()[0].attempts.len(), 1);
+}
+
+#[test]
+fn a_mastery_drill_grades_every_answer_but_keeps_the_attempts() {
+    let config = QuizConfig {
+        mastery: Some(Mastery { id: 1, streak: 2 }),
+        ..retrying()
+    };
+    let mut session = Session::new(questions(), config).unwrap();
+    submit(&mut session, "kubectl get nodes");
+    assert_eq!(session.quiz_state().current_status(), QuestionStatus::Fail);
+    session.apply(SessionAction::Retry).unwrap();
+    submit(&mut session, "kubectl get pods");
+    assert_eq!(
+        session.quiz_state().records()[0].attempts,
+        ["kubectl get nodes", "kubectl get pods"]
+    );
+}
+
+#[test]
+fn attempts_survive_a_restored_session() {
+    let mut session = Session::new(questions(), retrying()).unwrap();
+    submit(&mut session, "kubectl get nodes");
+    let snapshot = session.snapshot("mock");
+    let restored = Session::restore(questions(), retrying(), &snapshot).unwrap();
+    assert_eq!(
+        restored.quiz_state().records()[0].attempts,
+        ["kubectl get nodes"]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_summary_counts_the_tries_and_the_review_lists_them() {
+    let mut app = new_app_with(retrying());
+    // Wide enough for the notice to fit under the controls
+    let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, answer("kubectl run alpha")).await;
+    assert!(
+        screen.contains("Not quite, try again (attempt 2)"),
+        "{}",
+        screen
+    );
+
+    let mut events = answer("kubectl run alpha --image=nginx");
+    events.push(Event::Key(key_event('n')));
+    events.push(Event::Key(key_event('s')));
+    let screen = run_script(&mut app, &mut terminal, events).await;
+    assert!(app.session().is_finished());
+    assert!(screen.contains("Tries"), "{}", screen);
+
+    let page = serve::session_page(&app.session_result());
+    assert!(page.contains("<h3>Attempts (2)</h3>"), "{}", page);
+    assert!(
+        page.contains("<li><pre>kubectl run alpha</pre></li>"),
+        "{}",
+        page
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn single_tries_leave_the_column_out() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let skips = vec![Event::Key(key_event('s')), Event::Key(key_event('s'))];
+    let screen = run_script(&mut app, &mut terminal, skips).await;
+    assert!(!screen.contains("Tries"));
+}
+
+#[test]
+fn retry_wrong_comes_from_the_flag_or_the_defaults_section() {
+    let cli = Cli::parse_from(["ckad-practitioner", "--retry-wrong"]);
+    let file = settings::FileConfig::default();
+    assert!(Settings::resolve(&cli, &file).unwrap().quiz.retry_wrong);
+
+    let (file, warnings) = settings::parse_file_config("[defaults]\nretry_wrong = true\n").unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let cli = Cli::parse_from(["ckad-practitioner"]);
+    assert!(Settings::resolve(&cli, &file).unwrap().quiz.retry_wrong);
+    assert!(
+        !Settings::resolve(&cli, &settings::FileConfig::default())
+            .unwrap()
+            .quiz
+            .retry_wrong
+    );
+}
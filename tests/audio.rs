@@ -0,0 +1,74 @@
+use ckad_practitioner::audio::{Cue, SoundSettings};
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::settings::{self, Settings};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+const CONFIG: &str = "[sounds]\ncorrect = \"/sounds/ding.wav\"\nincorrect = \"/sounds/buzz.wav\"\n";
+
+fn sounds(config: &str, args: &[&str]) -> SoundSettings {
+    let (file, warnings) = settings::parse_file_config(config).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let cli = Cli::parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()));
+    Settings::resolve(&cli, &file).unwrap().sounds
+}
+
+#[test]
+fn sound_paths_come_from_the_config_and_play_only_with_the_flag() {
+    let configured = sounds(CONFIG, &[]);
+    assert_eq!(configured.correct, Some(PathBuf::from("/sounds/ding.wav")));
+    assert_eq!(
+        configured.incorrect,
+        Some(PathBuf::from("/sounds/buzz.wav"))
+    );
+    assert!(!configured.is_active());
+
+    let enabled = sounds(CONFIG, &["--sounds"]);
+    assert!(enabled.is_active());
+    assert_eq!(
+        enabled.path(Cue::Correct),
+        Some(Path::new("/sounds/ding.wav"))
+    );
+    assert_eq!(
+        enabled.path(Cue::Incorrect),
+        Some(Path::new("/sounds/buzz.wav"))
+    );
+
+    let from_file = sounds(&format!("{}enabled = true\n", CONFIG), &[]);
+    assert!(from_file.is_active());
+}
+
+#[test]
+fn the_flag_without_sound_files_stays_silent() {
+    let flag_only = sounds("", &["--sounds"]);
+    assert!(flag_only.enabled);
+    assert!(!flag_only.is_active());
+    assert_eq!(flag_only.path(Cue::Correct), None);
+}
+
+#[test]
+fn only_graded_answers_have_a_cue() {
+    assert_eq!(Cue::for_status(QuestionStatus::Pass), Some(Cue::Correct));
+    assert_eq!(Cue::for_status(QuestionStatus::Fail), Some(Cue::Incorrect));
+    assert_eq!(Cue::for_status(QuestionStatus::Skipped), None);
+    assert_eq!(Cue::for_status(QuestionStatus::Revealed), None);
+}
+
+/// Plays `CKAD_TEST_WAV` on the default device; run by hand with
+/// `cargo test --features sounds -- --ignored`
+#[cfg(feature = "sounds")]
+#[test]
+#[ignore = "needs an audio device and CKAD_TEST_WAV"]
+fn plays_a_wav_file_on_the_default_device() {
+    use ckad_practitioner::audio::SoundPlayer;
+
+    let wav = std::env::var_os("CKAD_TEST_WAV").expect("set CKAD_TEST_WAV to a .wav file");
+    let player = SoundPlayer::new(SoundSettings {
+        enabled: true,
+        correct: Some(PathBuf::from(wav)),
+        incorrect: None,
+    });
+    player.play(Cue::Correct);
+    std::thread::sleep(std::time::Duration::from_secs(2));
+}
@@ -0,0 +1,171 @@
+use ckad_practitioner::authoring::{self, AddError, AddOptions};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::question_repository::{FileQuestionRepository, QuestionRepository};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// A temp bank file holding `contents`, or no file at all for `None`
+fn temp_bank(name: &str, contents: Option<&str>) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ckad-add-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    if let Some(contents) = contents {
+        fs::write(&path, contents).unwrap();
+    }
+    path
+}
+
+/// Runs `add` with `input` typed at the prompts
+fn add(path: &Path, template: Option<usize>, input: &str) -> Result<Question, AddError> {
+    let options = AddOptions {
+        file: path.to_path_buf(),
+        template,
+    };
+    authoring::run(&options, &mut Cursor::new(input), &mut Vec::new())
+}
+
+fn reload(path: &Path) -> Vec<Question> {
+    FileQuestionRepository::new(path.display().to_string())
+        .get_questions()
+        .expect("bank still loads")
+}
+
+const BANK: &str = "\
+# Pods bank
+- id: 1
+  question: Create a pod named alpha
+  hints: [Use kubectl run]
+  answer: kubectl run alpha --image=nginx
+  time_limit_secs: 60
+  tags: [pods]
+  difficulty: easy
+
+- id: 7
+  question: Delete the pod alpha
+  hints: []
+  answer: kubectl delete pod alpha
+  time_limit_secs: 30
+";
+
+#[test]
+fn appended_question_gets_the_next_id_and_keeps_the_file_intact() {
+    let path = temp_bank("append.yaml", Some(BANK));
+    let input = "Create a deployment named api\n90\nUse kubectl create\nAdd replicas\n\n\
+                 kubectl create deployment api \\\n  --image=nginx\n.\n";
+    let added = add(&path, None, input).unwrap();
+    assert_eq!(added.id, 8);
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with(BANK), "existing entries are untouched");
+
+    let questions = reload(&path);
+    assert_eq!(questions.len(), 3);
+    let question = &questions[2];
+    assert_eq!(question.id, 8);
+    assert_eq!(question.question, "Create a deployment named api");
+    assert_eq!(question.time_limit_secs, 90);
+    assert_eq!(question.hints, ["Use kubectl create", "Add replicas"]);
+    assert_eq!(
+        question.answer,
+        "kubectl create deployment api \\\n  --image=nginx"
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn template_values_are_kept_when_enter_is_pressed() {
+    let path = temp_bank("template.yaml", Some(BANK));
+    // Keep the text, the time limit and the hints, add none, keep the answer
+    add(&path, Some(1), "\n\n\n\n.\n").unwrap();
+
+    let questions = reload(&path);
+    let (template, variant) = (&questions[0], &questions[2]);
+    assert_eq!(variant.id, 8);
+    assert_eq!(variant.question, template.question);
+    assert_eq!(variant.time_limit_secs, 60);
+    assert_eq!(variant.hints, template.hints);
+    assert_eq!(variant.answer, template.answer);
+    assert_eq!(variant.tags, ["pods"]);
+
+    assert!(matches!(
+        add(&path, Some(42), "\n"),
+        Err(AddError::TemplateNotFound(42))
+    ));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn answer_ends_at_end_of_input_and_a_new_file_is_created() {
+    let path = temp_bank("new.yaml", None);
+    add(
+        &path,
+        None,
+        "Create a namespace named beta\n30\n\nkubectl create ns beta",
+    )
+    .unwrap();
+
+    let questions = reload(&path);
+    assert_eq!(questions.len(), 1);
+    assert_eq!(questions[0].id, 1);
+    assert_eq!(questions[0].answer, "kubectl create ns beta");
+    assert!(questions[0].hints.is_empty());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn bad_input_is_asked_again_or_rejected() {
+    let path = temp_bank("invalid.yaml", Some(BANK));
+    // A blank question and a zero time limit are asked again
+    let added = add(
+        &path,
+        None,
+        "\nList pods\n0\nsoon\n45\n\nkubectl get pods\n.\n",
+    )
+    .unwrap();
+    assert_eq!(added.time_limit_secs, 45);
+
+    // No answer: nothing is written
+    let before = fs::read_to_string(&path).unwrap();
+    assert!(matches!(
+        add(&path, None, "List pods\n\n\n.\n"),
+        Err(AddError::Invalid(_))
+    ));
+    assert!(matches!(add(&path, None, ""), Err(AddError::Cancelled)));
+    assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn json_and_flow_style_banks_are_rewritten_whole() {
+    let json = temp_bank(
+        "bank.json",
+        Some(r#"[{"id": 3, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}]"#),
+    );
+    add(&json, None, "List pods\n\n\nkubectl get pods\n.\n").unwrap();
+    let ids: Vec<usize> = reload(&json).iter().map(|question| question.id).collect();
+    assert_eq!(ids, [3, 4]);
+
+    let flow = temp_bank(
+        "flow.yaml",
+        Some("[{id: 2, question: Q, hints: [], answer: A, time_limit_secs: 10}]\n"),
+    );
+    add(&flow, None, "List pods\n\n\nkubectl get pods\n.\n").unwrap();
+    let ids: Vec<usize> = reload(&flow).iter().map(|question| question.id).collect();
+    assert_eq!(ids, [2, 3]);
+
+    fs::remove_file(&json).unwrap();
+    fs::remove_file(&flow).unwrap();
+}
+
+#[test]
+fn json_lines_banks_get_one_more_line() {
+    let line = r#"{"id": 5, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}"#;
+    let bank = temp_bank("bank.jsonl", Some(line));
+    add(&bank, None, "List pods\n\n\nkubectl get pods\n.\n").unwrap();
+    let contents = fs::read_to_string(&bank).unwrap();
+    assert_eq!(contents.lines().count(), 2);
+    assert!(contents.starts_with(line));
+    let ids: Vec<usize> = reload(&bank).iter().map(|question| question.id).collect();
+    assert_eq!(ids, [5, 6]);
+    fs::remove_file(&bank).unwrap();
+}
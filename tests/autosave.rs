@@ -0,0 +1,257 @@
+use chrono::Duration as ChronoDuration;
+use ckad_practitioner::autosave::{self, Autosaver, RecoveryError, SessionSnapshot};
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::question_repository::{FileQuestionRepository, QuestionRepository};
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::results::SessionResult;
+use ckad_practitioner::session::{Session, SessionAction};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const BANK: &str = r#"
+- id: 1
+  question: Create a pod named nginx
+  answer: kubectl run nginx --image=nginx
+  time_limit_secs: 60
+  hints: [Use kubectl run, The image is nginx]
+- id: 2
+  question: Create a namespace named dev
+  answer: kubectl create namespace dev
+  time_limit_secs: 60
+  hints: [Use kubectl create]
+"#;
+
+/// A fresh temp directory for one test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ckad-autosave-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn repository(dir: &Path) -> FileQuestionRepository {
+    let bank = dir.join("bank.yaml");
+    if !bank.exists() {
+        fs::write(&bank, BANK).unwrap();
+    }
+    FileQuestionRepository::new(bank.display().to_string())
+}
+
+/// A session over the bank with question 1 answered and question 2 current,
+/// its first hint on screen
+fn session_in_progress(repository: &dyn QuestionRepository) -> Session {
+    let questions = repository.get_questions().unwrap();
+    let mut session = Session::new(questions, QuizConfig::default()).unwrap();
+    session
+        .apply(SessionAction::Submit(
+            "kubectl run nginx --image=nginx".into(),
+        ))
+        .unwrap();
+    session.apply(SessionAction::Next).unwrap();
+    session.apply(SessionAction::Hint).unwrap();
+    session
+}
+
+fn snapshot_of(repository: &dyn QuestionRepository) -> SessionSnapshot {
+    session_in_progress(repository).snapshot(&repository.source_description())
+}
+
+#[test]
+fn write_atomic_replaces_the_file_and_leaves_no_temp_file() {
+    let dir = temp_dir("atomic");
+    let path = dir.join("autosave.json");
+    autosave::write_atomic(&path, b"first").unwrap();
+    autosave::write_atomic(&path, b"second").unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+    assert!(!autosave::temp_path(&path).exists());
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[test]
+fn an_interrupted_write_leaves_the_previous_save_readable() {
+    let dir = temp_dir("truncated");
+    let repository = repository(&dir);
+    let path = autosave::autosave_path(&dir);
+    let snapshot = snapshot_of(&repository);
+    autosave::save(&path, &snapshot).unwrap();
+
+    // A crash mid-write: the temp file holds half a snapshot
+    let json = serde_json::to_string(&snapshot).unwrap();
+    fs::write(autosave::temp_path(&path), &json[..json.len() / 2]).unwrap();
+
+    assert_eq!(autosave::load(&path), Some(snapshot));
+    assert!(!autosave::temp_path(&path).exists());
+}
+
+#[test]
+fn an_unreadable_autosave_is_ignored() {
+    let dir = temp_dir("corrupt");
+    let path = autosave::autosave_path(&dir);
+    fs::write(&path, "{\"version\": 1, \"saved_at\"").unwrap();
+
+    assert_eq!(autosave::load(&path), None);
+    assert_eq!(autosave::load(&dir.join("missing.json")), None);
+}
+
+#[test]
+fn recovery_picks_up_answers_position_clock_and_hints() {
+    let dir = temp_dir("recover");
+    let repository = repository(&dir);
+    let snapshot = snapshot_of(&repository);
+
+    let session = autosave::recover(&snapshot, &repository, QuizConfig::default()).unwrap();
+    let quiz_state = session.quiz_state();
+    assert_eq!(quiz_state.current_index(), 1);
+    assert_eq!(quiz_state.status(0), QuestionStatus::Pass);
+    assert_eq!(
+        quiz_state.records()[0].submission.as_deref(),
+        Some("kubectl run nginx --image=nginx")
+    );
+    assert_eq!(quiz_state.status(1), QuestionStatus::Pending);
+    assert_eq!(session.hint_state().hints_viewed(), 1);
+    assert!(!session.is_finished());
+    assert_eq!(
+        session.snapshot(&snapshot.source).questions,
+        snapshot.questions
+    );
+}
+
+#[test]
+fn recovered_clock_continues_from_the_saved_time() {
+    let dir = temp_dir("clock");
+    let repository = repository(&dir);
+    let mut snapshot = snapshot_of(&repository);
+    snapshot.current_elapsed_secs = 45;
+
+    let session = autosave::recover(&snapshot, &repository, QuizConfig::default()).unwrap();
+    let timer = session.quiz_state().timer();
+    assert!(timer.elapsed() >= Duration::from_secs(45));
+    assert!(timer.remaining() <= Duration::from_secs(15));
+}
+
+#[test]
+fn an_edited_bank_is_not_recovered() {
+    let dir = temp_dir("stale");
+    let repository = repository(&dir);
+    let snapshot = snapshot_of(&repository);
+
+    fs::write(
+        dir.join("bank.yaml"),
+        BANK.replace("kubectl create namespace dev", "kubectl create ns dev"),
+    )
+    .unwrap();
+    let result = autosave::recover(&snapshot, &repository, QuizConfig::default());
+    assert!(matches!(result, Err(RecoveryError::BankChanged)));
+
+    let without_question_2 = BANK.split("- id: 2").next().unwrap();
+    fs::write(dir.join("bank.yaml"), without_question_2).unwrap();
+    let result = autosave::recover(&snapshot, &repository, QuizConfig::default());
+    assert!(matches!(result, Err(RecoveryError::MissingQuestions(ids)) if ids == [2]));
+}
+
+#[test]
+fn every_missing_question_is_reported() {
+    let dir = temp_dir("missing");
+    let repository = repository(&dir);
+    let snapshot = snapshot_of(&repository);
+
+    fs::write(
+        dir.join("bank.yaml"),
+        BANK.replace("a pod named nginx", "a pod named web")
+            .replace("a namespace named dev", "a namespace named prod"),
+    )
+    .unwrap();
+    let err = autosave::recover(&snapshot, &repository, QuizConfig::default()).unwrap_err();
+    assert!(matches!(&err, RecoveryError::MissingQuestions(ids) if ids == &[1, 2]));
+    assert_eq!(
+        err.to_string(),
+        "2 of its questions no longer exist (ids 1, 2)"
+    );
+}
+
+#[test]
+fn reordering_the_bank_does_not_count_as_a_change() {
+    let dir = temp_dir("reordered");
+    let repository = repository(&dir);
+    let snapshot = snapshot_of(&repository);
+
+    let (first, second) = BANK.split_at(BANK.find("- id: 2").unwrap());
+    fs::write(dir.join("bank.yaml"), format!("{}\n{}", second, first)).unwrap();
+    assert!(autosave::recover(&snapshot, &repository, QuizConfig::default()).is_ok());
+}
+
+#[test]
+fn questions_whose_ids_changed_are_recovered_by_identity() {
+    let dir = temp_dir("renumbered");
+    let repository = repository(&dir);
+    let snapshot = snapshot_of(&repository);
+
+    fs::write(
+        dir.join("bank.yaml"),
+        BANK.replace("- id: 1", "- id: 11")
+            .replace("- id: 2", "- id: 12"),
+    )
+    .unwrap();
+    let session = autosave::recover(&snapshot, &repository, QuizConfig::default()).unwrap();
+    let ids: Vec<usize> = session
+        .quiz_state()
+        .questions()
+        .iter()
+        .map(|question| question.id)
+        .collect();
+    assert_eq!(ids, vec![11, 12]);
+    assert_eq!(session.quiz_state().status(0), QuestionStatus::Pass);
+}
+
+#[test]
+fn an_autosave_for_another_source_is_not_recovered() {
+    let dir = temp_dir("source");
+    let mut snapshot = snapshot_of(&repository(&dir));
+    snapshot.source = "banks/other.yaml".into();
+
+    let result = autosave::recover(&snapshot, &repository(&dir), QuizConfig::default());
+    assert!(matches!(result, Err(RecoveryError::OtherSource { .. })));
+}
+
+#[test]
+fn only_autosaves_newer_than_every_saved_session_are_unfinished() {
+    let dir = temp_dir("history");
+    let repository = repository(&dir);
+    let snapshot = snapshot_of(&repository);
+    let mut result: SessionResult = session_in_progress(&repository).result();
+
+    result.finished_at = snapshot.saved_at - ChronoDuration::minutes(5);
+    assert!(autosave::is_unfinished(&snapshot, &[]));
+    assert!(autosave::is_unfinished(&snapshot, &[result.clone()]));
+
+    result.finished_at = snapshot.saved_at + ChronoDuration::seconds(1);
+    assert!(!autosave::is_unfinished(&snapshot, &[result]));
+}
+
+#[tokio::test(start_paused = true)]
+async fn autosaver_saves_on_progress_and_on_the_interval() {
+    let dir = temp_dir("autosaver");
+    let repository = repository(&dir);
+    let path = autosave::autosave_path(&dir);
+    let mut saver = Autosaver::new(path.clone(), repository.source_description());
+    let questions = repository.get_questions().unwrap();
+    let mut session = Session::new(questions, QuizConfig::default()).unwrap();
+
+    assert!(saver.save_if_due(&session), "first check saves");
+    assert!(!saver.save_if_due(&session), "nothing new to save");
+
+    session.apply(SessionAction::Skip).unwrap();
+    assert!(saver.save_if_due(&session), "a completed question saves");
+    assert_eq!(autosave::load(&path).unwrap().current_index, 1);
+
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert!(!saver.save_if_due(&session));
+    tokio::time::advance(autosave::INTERVAL).await;
+    assert!(saver.save_if_due(&session), "the interval saves the clock");
+    assert_eq!(autosave::load(&path).unwrap().current_elapsed_secs, 40);
+
+    autosave::discard(&path);
+    assert!(!path.exists());
+}
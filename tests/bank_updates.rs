@@ -0,0 +1,178 @@
+mod common;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use ckad_practitioner::bank_updates::{self, BankUpdate};
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::settings::{self, FileConfig, Settings};
+use clap::Parser;
+use common::{new_app, screen_text};
+use crossterm::event::Event;
+use futures_util::stream;
+use ratatui::{backend::TestBackend, Terminal};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A fresh path for the checks file of one test
+fn checks_path(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("ckad-bank-updates-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    bank_updates::checks_path(&dir)
+}
+
+fn question(id: usize, text: &str, answer: &str) -> Question {
+    Question {
+        id,
+        question: text.to_string(),
+        answer: answer.to_string(),
+        time_limit_secs: 60,
+        ..Default::default()
+    }
+}
+
+fn bank() -> Vec<Question> {
+    vec![
+        question(1, "Create a pod named web", "kubectl run web --image=nginx"),
+        question(2, "Create a namespace named dev", "kubectl create ns dev"),
+        question(
+            3,
+            "Scale web to 3 replicas",
+            "kubectl scale deploy web --replicas=3",
+        ),
+    ]
+}
+
+fn at(time: &str) -> DateTime<Utc> {
+    time.parse().unwrap()
+}
+
+#[test]
+fn a_day_after_the_last_check_the_changes_are_counted() {
+    let path = checks_path("counted");
+    let first = at("2024-05-01T10:00:00Z");
+    assert_eq!(
+        bank_updates::check(&path, "bank.yaml", &bank(), first),
+        None
+    );
+
+    let mut edited = bank();
+    edited[0].answer = "kubectl run web --image=nginx:1.27".to_string();
+    edited.remove(1);
+    edited.push(question(
+        4,
+        "Expose web on port 80",
+        "kubectl expose deploy web --port=80",
+    ));
+    edited.push(question(
+        5,
+        "Label web tier=frontend",
+        "kubectl label pod web tier=frontend",
+    ));
+    // Renumbering alone is not a change
+    edited[1].id = 30;
+
+    let later = first + ChronoDuration::hours(23);
+    assert_eq!(
+        bank_updates::check(&path, "bank.yaml", &edited, later),
+        None
+    );
+
+    let update = bank_updates::check(&path, "bank.yaml", &edited, first + ChronoDuration::days(1));
+    assert_eq!(
+        update,
+        Some(BankUpdate {
+            source: "bank.yaml".to_string(),
+            since: first,
+            added: 2,
+            removed: 1,
+            edited: 1,
+        })
+    );
+    assert_eq!(
+        update.unwrap().to_string(),
+        "bank.yaml changed since 2024-05-01 (questions new: 2, edited: 1, removed: 1)"
+    );
+
+    // Recorded, so the next check compares against the edited bank
+    let next_day = first + ChronoDuration::days(2);
+    assert_eq!(
+        bank_updates::check(&path, "bank.yaml", &edited, next_day),
+        None
+    );
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn each_source_is_checked_on_its_own() {
+    let path = checks_path("sources");
+    let first = at("2024-05-01T10:00:00Z");
+    bank_updates::check(&path, "pods.yaml", &bank(), first);
+    assert_eq!(
+        bank_updates::check(&path, "services.yaml", &bank()[..1], first),
+        None,
+        "the first check of a source only records it"
+    );
+    let update = bank_updates::check(
+        &path,
+        "pods.yaml",
+        &bank()[..1],
+        first + ChronoDuration::days(1),
+    );
+    assert_eq!(update.map(|update| update.removed), Some(2));
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn an_unreadable_record_starts_over_quietly() {
+    let path = checks_path("unreadable");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "{ not json").unwrap();
+    let now = at("2024-05-01T10:00:00Z");
+    assert_eq!(bank_updates::check(&path, "bank.yaml", &bank(), now), None);
+
+    let update = bank_updates::check(
+        &path,
+        "bank.yaml",
+        &bank()[..2],
+        now + ChronoDuration::days(1),
+    );
+    assert_eq!(update.map(|update| update.removed), Some(1));
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn the_check_shows_its_finding_as_a_notice() {
+    let update = BankUpdate {
+        source: "bank.yaml".to_string(),
+        since: at("2024-05-01T10:00:00Z"),
+        added: 3,
+        removed: 0,
+        edited: 0,
+    };
+    let mut app = new_app().with_bank_check(tokio::spawn(async move { Some(update) }));
+    // Wide enough for the controls to leave the notice a line
+    let mut terminal = Terminal::new(TestBackend::new(200, 30)).unwrap();
+    let idle = stream::once(async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok(Event::FocusGained)
+    });
+    app.run_with(&mut terminal, Box::pin(idle)).await.unwrap();
+    assert!(
+        screen_text(&terminal).contains("bank.yaml changed since 2024-05-01 (questions new: 3)"),
+        "{}",
+        screen_text(&terminal)
+    );
+}
+
+#[test]
+fn the_check_can_be_turned_off() {
+    let cli = Cli::parse_from(["ckad-practitioner"]);
+    let on = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+    assert!(on.bank_updates);
+
+    let (file, warnings) = settings::parse_file_config("[stats]\nbank_updates = false\n").unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert!(!Settings::resolve(&cli, &file).unwrap().bank_updates);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use ckad_practitioner::app::App;
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::settings::{self, Settings};
+use clap::Parser;
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+use std::time::Duration;
+
+fn new_terminal() -> Terminal<TestBackend> {
+    Terminal::new(TestBackend::new(120, 40)).expect("test terminal")
+}
+
+fn skip() -> Event {
+    Event::Key(key_event('s'))
+}
+
+fn enter() -> Event {
+    Event::Key(KeyEvent::from(KeyCode::Enter))
+}
+
+fn breaks_every_question(config: QuizConfig) -> App {
+    new_app_with(QuizConfig {
+        break_every: Some(1),
+        break_secs: 10,
+        ..config
+    })
+}
+
+fn remaining(app: &App) -> Duration {
+    app.session().quiz_state().timer().remaining()
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_break_hides_the_next_question_and_holds_its_clock() {
+    let mut app = breaks_every_question(QuizConfig::default());
+    let mut terminal = new_terminal();
+
+    let on_break = run_script(&mut app, &mut terminal, vec![skip()]).await;
+    assert!(on_break.contains("Break"), "{}", on_break);
+    assert!(on_break.contains("1 of 2 questions done"));
+    assert!(on_break.contains("Next question in 0:10"));
+    assert!(!on_break.contains("Create a namespace named beta"));
+
+    tokio::time::advance(Duration::from_secs(4)).await;
+    let counting = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(counting.contains("Next question in 0:06"), "{}", counting);
+    assert_eq!(remaining(&app), Duration::from_secs(30));
+
+    tokio::time::advance(Duration::from_secs(6)).await;
+    let back = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(back.contains("Create a namespace named beta"), "{}", back);
+    assert_eq!(remaining(&app), Duration::from_secs(30));
+
+    let summary = run_script(&mut app, &mut terminal, vec![skip()]).await;
+    assert!(summary.contains("0:10 on breaks"), "{}", summary);
+    assert_eq!(app.session_result().break_secs, 10);
+}
+
+#[tokio::test(start_paused = true)]
+async fn enter_ends_a_break_early() {
+    let mut app = new_app_with(QuizConfig {
+        break_every: Some(1),
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    run_script(&mut app, &mut terminal, vec![skip()]).await;
+    tokio::time::advance(Duration::from_secs(5)).await;
+    let back = run_script(&mut app, &mut terminal, vec![enter()]).await;
+    assert!(back.contains("Create a namespace named beta"), "{}", back);
+    assert_eq!(app.session().break_time(), Duration::from_secs(5));
+}
+
+#[tokio::test(start_paused = true)]
+async fn strict_exam_breaks_keep_the_clock_but_not_the_stats() {
+    let mut app = breaks_every_question(QuizConfig {
+        mode: Mode::Exam,
+        strict_breaks: true,
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    let on_break = run_script(&mut app, &mut terminal, vec![skip()]).await;
+    assert!(on_break.contains("The clock keeps running"), "{}", on_break);
+    tokio::time::advance(Duration::from_secs(10)).await;
+    run_script(&mut app, &mut terminal, vec![]).await;
+    assert_eq!(remaining(&app), Duration::from_secs(20));
+
+    tokio::time::advance(Duration::from_secs(5)).await;
+    run_script(&mut app, &mut terminal, vec![skip()]).await;
+    let result = app.session_result();
+    assert_eq!(result.questions[1].time_spent_secs, 5);
+    assert_eq!(result.break_secs, 10);
+}
+
+#[tokio::test(start_paused = true)]
+async fn no_breaks_unless_configured() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+    let next = run_script(&mut app, &mut terminal, vec![skip()]).await;
+    assert!(next.contains("Create a namespace named beta"));
+    assert!(!app.session().break_due());
+}
+
+#[test]
+fn break_settings_come_from_the_timer_section_and_flags() {
+    let (file, warnings) =
+        settings::parse_file_config("[timer]\nbreak_every = 8\nstrict_breaks = true\n").unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let cli = Cli::parse_from(["ckad-practitioner", "--break-secs", "90"]);
+    let quiz = Settings::resolve(&cli, &file).unwrap().quiz;
+    assert_eq!(quiz.break_every, Some(8));
+    assert_eq!(quiz.break_secs, 90);
+    assert!(quiz.strict_breaks);
+}
@@ -0,0 +1,70 @@
+use ckad_practitioner::question_repository::{
+    self, BundledQuestionRepository, QuestionFormat, QuestionRepository, RepositoryError,
+};
+use ckad_practitioner::validation;
+
+const ARCHIVE: &str = include_str!("../questions/bundled.json");
+
+#[test]
+fn the_shipped_archive_parses_into_questions() {
+    let questions = BundledQuestionRepository::from_json(ARCHIVE)
+        .get_questions()
+        .unwrap();
+    assert!(!questions.is_empty());
+    assert!(question_repository::duplicate_ids(&questions).is_empty());
+    assert!(questions
+        .iter()
+        .all(|question| question.difficulty.is_some()));
+}
+
+#[test]
+fn the_shipped_archive_passes_validation() {
+    let errors = validation::validate_str(ARCHIVE, QuestionFormat::Json);
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn a_broken_archive_is_a_parse_error() {
+    let repository = BundledQuestionRepository::from_json("[{\"id\": 1}]");
+    assert_eq!(repository.source_description(), "bundled");
+    match repository.get_questions() {
+        Err(RepositoryError::Parse { path, .. }) => {
+            assert_eq!(path.to_str(), Some("questions/bundled.json"))
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "bundled")]
+mod feature {
+    use ckad_practitioner::cli::Cli;
+    use ckad_practitioner::question_repository::{BundledQuestionRepository, QuestionRepository};
+    use ckad_practitioner::settings::{FileConfig, Settings};
+    use clap::Parser;
+
+    #[test]
+    fn the_bundled_bank_is_the_default_source() {
+        let cli = Cli::parse_from(["ckad-practitioner"]);
+        let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+        let repository = settings.repository();
+        assert_eq!(repository.source_description(), "bundled");
+        assert_eq!(
+            repository.count().unwrap(),
+            BundledQuestionRepository::new().count().unwrap()
+        );
+    }
+
+    #[test]
+    fn a_file_still_overrides_it() {
+        let cli = Cli::parse_from([
+            "ckad-practitioner",
+            "--file",
+            "tests/fixtures/list-bank.yaml",
+        ]);
+        let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+        assert_eq!(
+            settings.repository().source_description(),
+            "tests/fixtures/list-bank.yaml"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use ckad_practitioner::cli::{Cli, Command};
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::list::ListSort;
+use ckad_practitioner::print::PrintFormat;
+use clap::error::ErrorKind;
+use clap::Parser;
+use std::path::Path;
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()))
+}
+
+fn error_kind(args: &[&str]) -> ErrorKind {
+    match parse(args) {
+        Ok(cli) => panic!("{:?} parsed: {:?}", args, cli),
+        Err(err) => err.kind(),
+    }
+}
+
+#[test]
+fn no_arguments_leave_everything_to_the_config() {
+    let cli = parse(&[]).unwrap();
+    assert_eq!(cli.command, None);
+    assert_eq!(cli.file, None);
+    assert_eq!(cli.dir, None);
+    assert_eq!(cli.mode, None);
+    assert_eq!(cli.limit, None);
+    assert_eq!(cli.seed, None);
+    assert!(cli.tags.is_empty());
+    assert!(!cli.shuffle && !cli.no_shuffle);
+    assert!(!cli.study && !cli.plain && !cli.print && !cli.print_answers);
+    assert_eq!(cli.print_format, PrintFormat::Text);
+    assert_eq!(cli.log_level, None);
+}
+
+#[test]
+fn values_parse_into_their_types() {
+    let cli = parse(&[
+        "--file",
+        "bank.yaml",
+        "--mode",
+        "exam",
+        "--tags",
+        "pods,secrets",
+        "--limit",
+        "3",
+        "--shuffle",
+        "--seed",
+        "42",
+        "--print-format",
+        "json",
+        "--log-level",
+        "debug",
+    ])
+    .unwrap();
+    assert_eq!(cli.file.as_deref(), Some(Path::new("bank.yaml")));
+    assert_eq!(cli.mode, Some(Mode::Exam));
+    assert_eq!(cli.tags, ["pods", "secrets"]);
+    assert_eq!(cli.limit, Some(3));
+    assert_eq!(cli.seed, Some(42));
+    assert_eq!(cli.print_format, PrintFormat::Json);
+    assert_eq!(cli.log_level, Some(tracing::Level::DEBUG));
+}
+
+#[test]
+fn conflicting_flags_are_refused() {
+    for args in [
+        &["--file", "a.yaml", "--dir", "banks"][..],
+        &["--sort-by-id", "--shuffle"],
+        &["--study", "--mode", "exam"],
+        &["--playlist", "plan.yaml", "--tags", "pods"],
+        &["--playlist", "plan.yaml", "--shuffle"],
+        &["--fresh-days", "3", "--daily"],
+        &["--master", "2", "--plain"],
+        &["--plain", "--print"],
+        &["--plain", "--print-answers"],
+    ] {
+        assert_eq!(error_kind(args), ErrorKind::ArgumentConflict, "{:?}", args);
+    }
+}
+
+#[test]
+fn dependent_flags_need_their_partner() {
+    for args in [
+        &["--seed", "7"][..],
+        &["--streak", "5"],
+        &["--choices", "4"],
+    ] {
+        assert_eq!(
+            error_kind(args),
+            ErrorKind::MissingRequiredArgument,
+            "{:?}",
+            args
+        );
+    }
+    assert!(parse(&["--seed", "7", "--shuffle-hints"]).is_ok());
+    assert!(parse(&["--master", "2", "--streak", "5"]).is_ok());
+    assert!(parse(&["--plain", "--choices", "4"]).is_ok());
+}
+
+#[test]
+fn out_of_range_values_are_refused() {
+    assert_eq!(error_kind(&["--limit", "0"]), ErrorKind::ValueValidation);
+    assert_eq!(error_kind(&["--limit", "many"]), ErrorKind::ValueValidation);
+    assert_eq!(
+        error_kind(&["--plain", "--choices", "1"]),
+        ErrorKind::ValueValidation
+    );
+    assert_eq!(
+        error_kind(&["--plain", "--choices", "10"]),
+        ErrorKind::ValueValidation
+    );
+    assert_eq!(error_kind(&["--mode", "cram"]), ErrorKind::InvalidValue);
+}
+
+#[test]
+fn subcommands_take_their_defaults_and_the_global_flags() {
+    let cli = parse(&["list", "--tags", "pods", "--limit", "2"]).unwrap();
+    assert_eq!(
+        cli.command,
+        Some(Command::List {
+            format: PrintFormat::Text,
+            sort: ListSort::Id,
+        })
+    );
+    assert_eq!(cli.tags, ["pods"]);
+    assert_eq!(cli.limit, Some(2));
+
+    let cli = parse(&["list-questions", "--sort", "time"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::List {
+            sort: ListSort::Time,
+            ..
+        })
+    ));
+
+    assert_eq!(
+        error_kind(&["validate"]),
+        ErrorKind::MissingRequiredArgument
+    );
+    assert!(parse(&["validate", "--schema-out", "schema.json"]).is_ok());
+    assert_eq!(error_kind(&["teleport"]), ErrorKind::InvalidSubcommand);
+}
@@ -0,0 +1,227 @@
+use ckad_practitioner::cluster::{self, ClusterError, ClusterSettings};
+use ckad_practitioner::kubectl::{KubectlError, KubectlRunner};
+use ckad_practitioner::models::Question;
+use std::io::Cursor;
+use std::sync::Mutex;
+
+const SETUP: &str = "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: broken\n";
+
+/// Records every call; `apply -f` calls record the manifest instead of the
+/// temporary path, which is gone once the call returns
+#[derive(Debug)]
+struct FakeKubectl {
+    context: &'static str,
+    installed: bool,
+    namespace_exists: bool,
+    calls: Mutex<Vec<String>>,
+}
+
+impl FakeKubectl {
+    fn on(context: &'static str) -> Self {
+        Self {
+            context,
+            installed: true,
+            namespace_exists: false,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+impl KubectlRunner for FakeKubectl {
+    fn run(&self, args: &[String]) -> Result<String, KubectlError> {
+        if !self.installed {
+            return Err(KubectlError::NotInstalled);
+        }
+        let mut recorded = args.to_vec();
+        if let Some(file) = args.iter().position(|arg| arg == "-f") {
+            recorded[file + 1] = std::fs::read_to_string(&args[file + 1]).unwrap();
+        }
+        self.calls.lock().unwrap().push(recorded.join(" "));
+        match args[0].as_str() {
+            "config" => Ok(format!("{}\n", self.context)),
+            "create" if self.namespace_exists => Err(KubectlError::Failed(
+                "Error from server (AlreadyExists): namespaces \"ckad-practice-3\" already exists\n"
+                    .to_string(),
+            )),
+            "create" => Ok("namespace/ckad-practice-3 created\n".to_string()),
+            "apply" => Ok("deployment.apps/broken created\n".to_string()),
+            _ => Ok(String::new()),
+        }
+    }
+}
+
+fn question() -> Question {
+    Question {
+        id: 3,
+        question: "Fix the broken Deployment".to_string(),
+        setup: Some(SETUP.to_string()),
+        ..Default::default()
+    }
+}
+
+fn run(
+    kubectl: &FakeKubectl,
+    teardown: bool,
+    reply: &str,
+) -> (Result<String, ClusterError>, String) {
+    let mut out = Vec::new();
+    let result = cluster::run(
+        kubectl,
+        &ClusterSettings::default(),
+        &question(),
+        teardown,
+        &mut Cursor::new(reply.to_string()),
+        &mut out,
+    );
+    (result, String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn confirmed_setup_is_applied_into_the_question_namespace() {
+    let kubectl = FakeKubectl::on("kind-ckad");
+    let (result, out) = run(&kubectl, false, "y\n");
+
+    assert_eq!(result.unwrap(), "ckad-practice-3");
+    assert_eq!(
+        kubectl.calls(),
+        vec![
+            "config current-context".to_string(),
+            "create namespace ckad-practice-3".to_string(),
+            format!("apply -n ckad-practice-3 -f {}", SETUP),
+        ]
+    );
+    assert!(out.starts_with(
+        "Apply question 3's setup into namespace 'ckad-practice-3' on context 'kind-ckad'? [y/N] "
+    ));
+    assert!(out.contains(
+        "$ kubectl create namespace ckad-practice-3\nnamespace/ckad-practice-3 created\n"
+    ));
+    assert!(out.contains("deployment.apps/broken created\n"));
+}
+
+#[test]
+fn an_existing_namespace_is_reused() {
+    let kubectl = FakeKubectl {
+        namespace_exists: true,
+        ..FakeKubectl::on("k3d-practice")
+    };
+    let (result, out) = run(&kubectl, false, "yes\n");
+    assert_eq!(result.unwrap(), "ckad-practice-3");
+    assert_eq!(kubectl.calls().len(), 3);
+    assert!(out.contains("(AlreadyExists)"));
+}
+
+#[test]
+fn denied_contexts_are_refused_before_any_prompt_or_change() {
+    let kubectl = FakeKubectl::on("gke_acme_europe-west1_Prod-Main");
+    let (result, out) = run(&kubectl, false, "y\n");
+
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "refusing to touch context 'gke_acme_europe-west1_Prod-Main': it matches the deny-list pattern '*prod*'"
+    );
+    assert_eq!(kubectl.calls(), vec!["config current-context".to_string()]);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn anything_but_yes_changes_nothing() {
+    let kubectl = FakeKubectl::on("kind-ckad");
+    let (result, _) = run(&kubectl, false, "\n");
+    assert!(matches!(result, Err(ClusterError::Declined(context)) if context == "kind-ckad"));
+    assert_eq!(kubectl.calls().len(), 1);
+}
+
+#[test]
+fn teardown_deletes_the_namespace_without_waiting() {
+    let kubectl = FakeKubectl::on("kind-ckad");
+    let (result, out) = run(&kubectl, true, "y\n");
+    assert_eq!(result.unwrap(), "ckad-practice-3");
+    assert_eq!(
+        kubectl.calls()[1],
+        "delete namespace ckad-practice-3 --ignore-not-found --wait=false"
+    );
+    assert!(out.starts_with("Delete namespace 'ckad-practice-3'"));
+}
+
+#[test]
+fn missing_kubectl_or_setup_is_reported() {
+    let kubectl = FakeKubectl {
+        installed: false,
+        ..FakeKubectl::on("kind-ckad")
+    };
+    let (result, _) = run(&kubectl, false, "y\n");
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "kubectl was not found on PATH"
+    );
+
+    let bare = Question {
+        setup: None,
+        ..question()
+    };
+    let err = cluster::run(
+        &FakeKubectl::on("kind-ckad"),
+        &ClusterSettings::default(),
+        &bare,
+        false,
+        &mut Cursor::new(String::new()),
+        &mut Vec::new(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "question 3 has no setup manifests");
+}
+
+#[test]
+fn deny_patterns_match_whole_context_names_case_insensitively() {
+    let patterns: Vec<String> = ["*prod*", "arn:aws:eks:*:live", "staging"]
+        .map(str::to_string)
+        .to_vec();
+    let cases = [
+        ("PROD", Some("*prod*")),
+        ("team-production-1", Some("*prod*")),
+        ("arn:aws:eks:eu-west-1:live", Some("arn:aws:eks:*:live")),
+        ("arn:aws:eks:eu-west-1:live-2", None),
+        ("staging", Some("staging")),
+        ("staging-2", None),
+        ("kind-ckad", None),
+    ];
+    for (context, expected) in cases {
+        assert_eq!(
+            cluster::denied_by(context, &patterns),
+            expected,
+            "{}",
+            context
+        );
+    }
+}
+
+#[test]
+fn quitting_removes_prepared_namespaces_unless_the_context_is_denied() {
+    let kubectl = FakeKubectl::on("kind-ckad");
+    let mut out = Vec::new();
+    cluster::remove_all(&kubectl, &ClusterSettings::default(), &[3, 5], &mut out).unwrap();
+    assert_eq!(
+        kubectl.calls()[1..],
+        [
+            "delete namespace ckad-practice-3 --ignore-not-found --wait=false".to_string(),
+            "delete namespace ckad-practice-5 --ignore-not-found --wait=false".to_string(),
+        ]
+    );
+
+    let switched = FakeKubectl::on("live-eu");
+    let err = cluster::remove_all(
+        &switched,
+        &ClusterSettings::default(),
+        &[3],
+        &mut Vec::new(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ClusterError::Denied { .. }), "{:?}", err);
+    assert_eq!(switched.calls().len(), 1);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use ckad_practitioner::app::Outcome;
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::keymap::Action;
+use ckad_practitioner::ui::collapse_text;
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+
+#[test]
+fn collapsing_keeps_the_first_line_and_marks_the_cut() {
+    let prompt = "Create a deployment named web.\nUse the nginx image.\nExpose it on port 80.";
+    assert_eq!(collapse_text(prompt, 80), "Create a deployment named web.…");
+    assert_eq!(collapse_text(prompt, 12), "Create a de…");
+    assert_eq!(
+        collapse_text("Create a deployment named web with three replicas", 20),
+        "Create a deployment…"
+    );
+}
+
+#[test]
+fn a_short_question_collapses_to_itself() {
+    assert_eq!(collapse_text("List the pods", 40), "List the pods");
+    assert_eq!(collapse_text("List the pods\n\n", 40), "List the pods");
+}
+
+#[tokio::test(start_paused = true)]
+async fn z_collapses_a_long_question_and_expands_it_again() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(40, 30)).unwrap();
+
+    let full = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(full.contains("Question (z: collapse)"), "{}", full);
+    assert!(full.contains("alpha"), "{}", full);
+
+    let collapsed = run_script(&mut app, &mut terminal, vec![Event::Key(key_event('z'))]).await;
+    assert!(collapsed.contains("Question (z: expand)"), "{}", collapsed);
+    assert!(
+        collapsed.contains("Question 1 of 2: Create a pod nam…"),
+        "{}",
+        collapsed
+    );
+    assert!(!collapsed.contains("alpha"));
+
+    let expanded = run_script(&mut app, &mut terminal, vec![Event::Key(key_event('z'))]).await;
+    assert_eq!(expanded, full);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_next_question_starts_expanded() {
+    let mut app = new_app();
+    assert_eq!(app.dispatch(Action::CollapseQuestion), Outcome::Redraw);
+    app.dispatch(Action::Skip);
+    app.dispatch(Action::Next);
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(
+        screen.contains("Create a namespace named beta"),
+        "{}",
+        screen
+    );
+    assert!(!screen.contains("expand)"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn study_mode_has_nothing_to_collapse() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Study,
+        ..QuizConfig::default()
+    });
+    assert_eq!(app.dispatch(Action::CollapseQuestion), Outcome::Unchanged);
+}
@@ -0,0 +1,100 @@
+// Each test binary compiles its own copy, and not every one uses every helper
+#![allow(dead_code)]
+
+use ckad_practitioner::app::App;
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::events::ScriptedEvents;
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::question_repository::{QuestionRepository, RepositoryError};
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+
+/// Two known questions with a 30s limit; the first has two hints and related
+/// commands, the second a context and an author and source
+pub struct MockQuestionRepository;
+
+impl QuestionRepository for MockQuestionRepository {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        Ok(vec![
+            Question {
+                id: 1,
+                question: "Create a pod named alpha".to_string(),
+                hints: vec!["First hint".to_string(), "Second hint".to_string()],
+                answer: "kubectl run alpha --image=nginx".to_string(),
+                related: Some(vec![
+                    "kubectl get pod alpha".to_string(),
+                    "kubectl delete pod alpha".to_string(),
+                ]),
+                time_limit_secs: 30,
+                ..Default::default()
+            },
+            Question {
+                id: 2,
+                question: "Create a namespace named beta".to_string(),
+                context: Some(
+                    "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: beta\n".to_string(),
+                ),
+                hints: vec!["Only hint".to_string()],
+                answer: "kubectl create namespace beta".to_string(),
+                time_limit_secs: 30,
+                author: Some("Jane Doe".to_string()),
+                source: Some("namespaces bank".to_string()),
+                ..Default::default()
+            },
+        ])
+    }
+
+    fn source_description(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+pub fn new_app() -> App {
+    new_app_with(QuizConfig::default())
+}
+
+/// The reveal animation is always off so answers render in full at once
+pub fn new_app_with(config: QuizConfig) -> App {
+    let config = QuizConfig {
+        reveal_animation: false,
+        ..config
+    };
+    App::new(
+        Box::new(MockQuestionRepository),
+        Theme::new(false),
+        KeyBindings::default(),
+        config,
+        PersonalBests::default(),
+    )
+    .expect("mock questions load")
+}
+
+pub fn key_event(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+/// Runs the loop over `events` until they run out, then returns the screen text
+pub async fn run_script(
+    app: &mut App,
+    terminal: &mut Terminal<TestBackend>,
+    events: Vec<Event>,
+) -> String {
+    app.run_with(terminal, ScriptedEvents::new(events))
+        .await
+        .expect("event loop runs");
+    screen_text(terminal)
+}
+
+/// The last frame, one line per terminal row
+pub fn screen_text(terminal: &Terminal<TestBackend>) -> String {
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
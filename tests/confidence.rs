@@ -0,0 +1,101 @@
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::Confidence::{self, High, Low, Medium};
+use ckad_practitioner::session::{Session, SessionAction};
+use ckad_practitioner::stats::Calibration;
+
+/// Confidence ratings paired with whether the answer was right
+type Ratings = &'static [(Confidence, bool)];
+
+#[test]
+fn calibration_counts_over_and_underconfidence() {
+    // (ratings, overconfident, underconfident)
+    let cases: &[(Ratings, usize, usize)] = &[
+        (&[], 0, 0),
+        (&[(High, true), (Medium, false), (Low, false)], 0, 0),
+        (&[(High, false), (High, false), (High, true)], 2, 0),
+        (&[(Low, true), (Medium, true), (High, false)], 1, 1),
+    ];
+    for (ratings, over, under) in cases {
+        let calibration = Calibration::from_ratings(ratings.iter().copied());
+        assert_eq!(calibration.rated, ratings.len(), "{:?}", ratings);
+        assert_eq!(calibration.overconfident, *over, "{:?}", ratings);
+        assert_eq!(calibration.underconfident, *under, "{:?}", ratings);
+        assert_eq!(calibration.is_calibrated(), over + under == 0);
+    }
+}
+
+#[test]
+fn accuracy_is_tracked_per_confidence_level() {
+    let calibration = Calibration::from_ratings([
+        (High, true),
+        (High, true),
+        (High, false),
+        (High, true),
+        (Low, false),
+    ]);
+    assert_eq!(calibration.accuracy(High), Some(0.75));
+    assert_eq!(calibration.accuracy(Medium), None);
+    assert_eq!(calibration.accuracy(Low), Some(0.0));
+}
+
+#[test]
+fn prompt_keys_map_to_levels() {
+    let keys: Vec<Option<Confidence>> = "hMl123x".chars().map(Confidence::from_key).collect();
+    assert_eq!(
+        keys,
+        vec![
+            Some(High),
+            Some(Medium),
+            Some(Low),
+            Some(High),
+            Some(Medium),
+            Some(Low),
+            None
+        ]
+    );
+}
+
+#[test]
+fn a_session_calibrates_the_rated_answers_only() {
+    let question = |id: usize| Question {
+        id,
+        question: format!("Q{}", id),
+        answer: "kubectl get pods".to_string(),
+        time_limit_secs: 60,
+        ..Default::default()
+    };
+    let config = QuizConfig {
+        confidence_prompt: true,
+        ..QuizConfig::default()
+    };
+    let mut session = Session::new((1..=3).map(question).collect(), config).unwrap();
+    for action in [
+        SessionAction::RateConfidence(High),
+        SessionAction::Submit("kubectl get po".to_string()),
+        SessionAction::Next,
+        SessionAction::RateConfidence(High),
+        SessionAction::Submit("kubectl get nodes".to_string()),
+        SessionAction::Next,
+        SessionAction::Submit("kubectl get pods".to_string()),
+    ] {
+        assert_eq!(session.apply(action.clone()), Ok(true), "{:?}", action);
+    }
+    assert_eq!(
+        session.apply(SessionAction::RateConfidence(Low)),
+        Ok(false),
+        "a graded answer can't be rated afterwards"
+    );
+
+    let calibration = Calibration::from_quiz(session.quiz_state());
+    assert_eq!(calibration.rated, 2);
+    assert_eq!(calibration.overconfident, 1);
+    assert_eq!(calibration.accuracy(High), Some(0.5));
+    let result = session.result();
+    let ratings: Vec<Option<Confidence>> = result
+        .questions
+        .iter()
+        .map(|question| question.confidence)
+        .collect();
+    assert_eq!(ratings, vec![Some(High), Some(High), None]);
+}
@@ -0,0 +1,287 @@
+mod common;
+
+use ckad_practitioner::app::{App, Outcome, Suspension};
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::keymap::{Action, KeyBindings};
+use ckad_practitioner::question_repository::{QuestionRepository, RepositoryError};
+use ckad_practitioner::quiz_state::{ContentTab, QuestionStatus};
+use ckad_practitioner::session::{Session, SessionAction};
+use ckad_practitioner::shell::{ExamShell, ShellSettings};
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use common::{key_event, new_app, new_app_with, MockQuestionRepository};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::Duration;
+
+#[tokio::test(start_paused = true)]
+async fn hint_reveals_hints_and_selects_the_hints_tab() {
+    let mut app = new_app();
+    app.dispatch(Action::NextTab);
+
+    assert_eq!(app.dispatch(Action::Hint), Outcome::Redraw);
+    assert!(app.session().hint_state().show_hints());
+    assert_eq!(app.tab_state().active(), ContentTab::Hints);
+
+    assert_eq!(app.dispatch(Action::Hint), Outcome::Redraw);
+    assert_eq!(app.session().hint_state().hint_index(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn next_is_unchanged_until_the_question_is_resolved() {
+    let mut app = new_app();
+    assert_eq!(app.dispatch(Action::Next), Outcome::Unchanged);
+    assert_eq!(app.session().quiz_state().current_index(), 0);
+
+    tokio::time::advance(Duration::from_secs(31)).await;
+    assert_eq!(app.dispatch(Action::Next), Outcome::Redraw);
+    assert_eq!(app.session().quiz_state().current_index(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn skip_settles_the_question_and_moves_on() {
+    let mut app = new_app();
+    assert_eq!(app.dispatch(Action::Skip), Outcome::Redraw);
+    assert_eq!(
+        app.session().quiz_state().status(0),
+        QuestionStatus::Skipped
+    );
+    assert_eq!(app.session().quiz_state().current_index(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn answer_prompt_takes_keys_until_enter() {
+    let mut app = new_app();
+    assert_eq!(app.dispatch(Action::Answer), Outcome::Redraw);
+    assert!(app.answer_input().is_active());
+
+    // `q` is text while the prompt is open, not quit
+    assert_eq!(app.handle_key(key_event('q')), Outcome::Redraw);
+    assert_eq!(app.answer_input().buffer(), "q");
+
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(!app.answer_input().is_active());
+    assert_eq!(app.session().quiz_state().current_submission(), Some("q"));
+    assert_eq!(app.dispatch(Action::Answer), Outcome::Unchanged);
+}
+
+#[tokio::test(start_paused = true)]
+async fn quit_exits_from_either_binding() {
+    let mut app = new_app();
+    assert_eq!(app.dispatch(Action::Quit), Outcome::Exit);
+    assert_eq!(app.handle_key(key_event('q')), Outcome::Exit);
+    let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert_eq!(app.handle_key(ctrl_c), Outcome::Exit);
+}
+
+#[tokio::test(start_paused = true)]
+async fn unbound_keys_leave_the_screen_alone() {
+    let mut app = new_app();
+    assert_eq!(app.handle_key(key_event('x')), Outcome::Unchanged);
+    assert_eq!(app.handle_key(key_event('3')), Outcome::Redraw);
+    assert_eq!(app.tab_state().active(), ContentTab::ALL[2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn shell_key_suspends_outside_exam_mode() {
+    let mut app = new_app();
+    assert_eq!(
+        app.handle_key(key_event('!')),
+        Outcome::Suspend(Suspension::Shell)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn shell_is_refused_in_exam_mode_by_default() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Exam,
+        ..QuizConfig::default()
+    });
+    assert_eq!(app.dispatch(Action::Shell), Outcome::Redraw);
+
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Exam,
+        ..QuizConfig::default()
+    })
+    .with_shell(ShellSettings {
+        exam: ExamShell::Timed,
+        ..ShellSettings::default()
+    });
+    assert_eq!(
+        app.dispatch(Action::Shell),
+        Outcome::Suspend(Suspension::Shell)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn editor_key_explains_itself_on_typed_answer_questions() {
+    let mut app = new_app();
+    assert_eq!(app.handle_key(key_event('E')), Outcome::Redraw);
+    assert!(app.session().quiz_state().current_submission().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn restart_gives_back_the_full_time_and_clears_hints() {
+    let mut app = new_app_with(QuizConfig {
+        allow_restart: true,
+        ..QuizConfig::default()
+    });
+    tokio::time::advance(Duration::from_secs(20)).await;
+    app.dispatch(Action::Hint);
+    app.dispatch(Action::Hint);
+
+    assert_eq!(app.handle_key(key_event('R')), Outcome::Redraw);
+    let session = app.session();
+    assert_eq!(
+        session.quiz_state().timer().remaining(),
+        Duration::from_secs(30)
+    );
+    assert!(!session.hint_state().show_hints());
+    assert_eq!(session.hint_state().hint_index(), 0);
+    assert_eq!(session.quiz_state().current_index(), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn restart_needs_the_config_flag_and_is_never_allowed_in_exam_mode() {
+    let mut app = new_app();
+    tokio::time::advance(Duration::from_secs(20)).await;
+    assert_eq!(app.dispatch(Action::RestartTimer), Outcome::Unchanged);
+
+    let mut app = new_app_with(QuizConfig {
+        allow_restart: true,
+        mode: Mode::Exam,
+        ..QuizConfig::default()
+    });
+    assert_eq!(app.dispatch(Action::RestartTimer), Outcome::Unchanged);
+}
+
+#[tokio::test(start_paused = true)]
+async fn ready_gate_holds_the_clock_until_enter() {
+    let mut app = new_app_with(QuizConfig {
+        ready_gate: true,
+        ..QuizConfig::default()
+    });
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    assert!(app.session().quiz_state().is_armed());
+
+    tokio::time::advance(Duration::from_secs(45)).await;
+    let timer = app.session().quiz_state().timer();
+    assert_eq!(timer.remaining(), Duration::from_secs(30));
+    assert!(!timer.is_expired());
+    // Nothing but the start key applies while the question waits
+    assert_eq!(app.dispatch(Action::Skip), Outcome::Unchanged);
+    assert_eq!(app.handle_key(key_event('h')), Outcome::Unchanged);
+
+    assert_eq!(app.handle_key(enter), Outcome::Redraw);
+    assert!(!app.session().quiz_state().is_armed());
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert_eq!(
+        app.session().quiz_state().timer().remaining(),
+        Duration::from_secs(20)
+    );
+
+    // The next question waits again, with its full limit
+    app.dispatch(Action::Skip);
+    assert_eq!(app.session().quiz_state().current_index(), 1);
+    assert!(app.session().quiz_state().is_armed());
+    tokio::time::advance(Duration::from_secs(5)).await;
+    assert_eq!(
+        app.session().quiz_state().timer().remaining(),
+        Duration::from_secs(30)
+    );
+    assert_eq!(app.handle_key(key_event('q')), Outcome::Exit);
+}
+
+#[tokio::test(start_paused = true)]
+async fn ready_gate_is_ignored_in_study_mode() {
+    let app = new_app_with(QuizConfig {
+        ready_gate: true,
+        mode: Mode::Study,
+        ..QuizConfig::default()
+    });
+    assert!(!app.session().quiz_state().is_armed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn hint_budget_is_spent_per_new_hint_and_blocks_at_zero() {
+    let mut app = new_app_with(QuizConfig {
+        hint_budget: Some(2),
+        ..QuizConfig::default()
+    });
+    assert_eq!(app.session().hints_remaining(), Some(2));
+    app.dispatch(Action::Hint);
+    assert_eq!(app.session().hints_remaining(), Some(1));
+    app.dispatch(Action::Hint);
+    assert_eq!(app.session().hints_remaining(), Some(0));
+
+    // Pressing on the last hint reveals nothing new, so it costs nothing
+    assert_eq!(app.dispatch(Action::Hint), Outcome::Redraw);
+    assert!(!app.session().out_of_hints());
+    assert_eq!(app.session().hints_remaining(), Some(0));
+
+    app.dispatch(Action::Skip);
+    assert!(app.session().out_of_hints());
+    assert_eq!(app.dispatch(Action::Hint), Outcome::Redraw);
+    assert!(!app.session().hint_state().show_hints());
+    assert_eq!(app.session().quiz_state().records()[1].hints_used, 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn hints_are_unlimited_without_a_budget() {
+    let mut app = new_app();
+    app.dispatch(Action::Hint);
+    assert_eq!(app.session().hints_remaining(), None);
+    assert!(!app.session().out_of_hints());
+}
+
+#[tokio::test(start_paused = true)]
+async fn coming_back_to_a_question_shows_the_hints_left_on_screen() {
+    let questions = MockQuestionRepository.get_questions().unwrap();
+    let mut session = Session::new(questions, QuizConfig::default()).unwrap();
+    session.apply(SessionAction::Hint).unwrap();
+    session.apply(SessionAction::Hint).unwrap();
+    assert_eq!(session.hint_state().hint_index(), 1);
+
+    session.apply(SessionAction::JumpTo(2)).unwrap();
+    assert!(
+        !session.hint_state().show_hints(),
+        "question 2 has no hints open yet"
+    );
+    session.apply(SessionAction::Hint).unwrap();
+
+    session.apply(SessionAction::JumpTo(1)).unwrap();
+    assert!(session.hint_state().show_hints());
+    assert_eq!(session.hint_state().hint_index(), 1);
+    assert_eq!(session.hint_state().hints_viewed(), 2);
+
+    session.apply(SessionAction::JumpTo(2)).unwrap();
+    assert!(session.hint_state().show_hints());
+    assert_eq!(session.hint_state().hint_index(), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn an_impossible_filter_names_the_filter_instead_of_starting() {
+    let config = QuizConfig {
+        tags: vec!["no-such-tag".to_string()],
+        priority_only: true,
+        ..QuizConfig::default()
+    };
+    let err = match App::new(
+        Box::new(MockQuestionRepository),
+        Theme::new(false),
+        KeyBindings::default(),
+        config,
+        PersonalBests::default(),
+    ) {
+        Ok(_) => panic!("an empty selection must not start a quiz"),
+        Err(err) => err,
+    };
+    assert!(matches!(
+        &err,
+        RepositoryError::NoMatch { loaded: 2, filters } if filters.len() == 2
+    ));
+    assert_eq!(
+        err.to_string(),
+        "no questions match your filter (--tags no-such-tag; --priority-only); \
+         2 questions were loaded, try a broader filter"
+    );
+}
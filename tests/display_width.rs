@@ -0,0 +1,119 @@
+use ckad_practitioner::app::App;
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::display_width::{self, pad, truncate};
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::list::{self, ListOptions};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::session::Session;
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use crossterm::event::Event;
+use futures_util::stream;
+use ratatui::{backend::TestBackend, Terminal};
+
+#[test]
+fn text_that_fits_is_left_alone() {
+    assert_eq!(truncate("kubectl get pods", 16, "…"), "kubectl get pods");
+    assert_eq!(truncate("ポッド", 6, "…"), "ポッド");
+    assert_eq!(display_width::width("ポッド"), 6);
+}
+
+#[test]
+fn wide_characters_are_never_split_at_the_limit() {
+    // Six columns of CJK: the third character would straddle a limit of 5
+    let cut = truncate("ポッドを作成", 5, "…");
+    assert_eq!(cut, "ポッ…");
+    assert_eq!(display_width::width(&cut), 5);
+
+    let cut = truncate("デプロイメント", 8, "...");
+    assert_eq!(cut, "デプ...");
+    assert!(display_width::width(&cut) <= 8);
+}
+
+#[test]
+fn emoji_are_kept_or_dropped_whole() {
+    let text = "Deploy 🚀👍🏽 now";
+    for max in 0..display_width::width(text) {
+        let cut = truncate(text, max, "…");
+        assert!(display_width::width(&cut) <= max, "{:?} at {}", cut, max);
+        assert!(
+            !cut.contains('\u{1F3FD}') || cut.contains("👍🏽"),
+            "{:?}",
+            cut
+        );
+    }
+    assert_eq!(truncate(text, 9, "…"), "Deploy…");
+    assert_eq!(truncate(text, 10, "…"), "Deploy 🚀…");
+    assert_eq!(truncate(text, 12, "…"), "Deploy 🚀👍🏽…");
+    assert_eq!(truncate("🚀🚀", 1, "…"), "…");
+    assert_eq!(truncate("🚀🚀", 0, "…"), "");
+}
+
+#[test]
+fn padding_counts_columns_not_characters() {
+    assert_eq!(pad("ポッド", 8), "ポッド  ");
+    assert_eq!(pad("pods", 8), "pods    ");
+    assert_eq!(pad("too long", 3), "too long");
+}
+
+#[test]
+fn list_columns_line_up_after_wide_question_text() {
+    let questions: Vec<Question> = ["Create a pod", "ポッドを作成する", "Deploy 🚀 it"]
+        .iter()
+        .enumerate()
+        .map(|(index, text)| Question {
+            id: index + 1,
+            question: text.to_string(),
+            tags: vec!["pods".to_string()],
+            time_limit_secs: 60,
+            ..Default::default()
+        })
+        .collect();
+    let mut out = Vec::new();
+    list::write_list(&mut out, &questions, ListOptions::default()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    let columns: Vec<usize> = out
+        .lines()
+        .take(4)
+        .map(|line| {
+            let tags = line.find("pods").or_else(|| line.find("TAGS")).unwrap();
+            display_width::width(&line[..tags])
+        })
+        .collect();
+    assert!(columns.windows(2).all(|pair| pair[0] == pair[1]), "{}", out);
+}
+
+#[tokio::test]
+async fn a_wide_source_name_is_cut_before_the_app_title() {
+    let questions = vec![Question {
+        id: 1,
+        question: "Create a pod".to_string(),
+        answer: "kubectl run web --image=nginx".to_string(),
+        time_limit_secs: 60,
+        ..Default::default()
+    }];
+    let session = Session::new(questions, QuizConfig::default()).unwrap();
+    let mut app = App::from_session(
+        session,
+        "問題集/ネットワーク/サービス.yaml 🚀 (1 Qs)".to_string(),
+        Theme::new(false),
+        KeyBindings::default(),
+        PersonalBests::default(),
+    );
+    let mut terminal = Terminal::new(TestBackend::new(40, 30)).unwrap();
+    app.run_with(
+        &mut terminal,
+        Box::pin(stream::empty::<std::io::Result<Event>>()),
+    )
+    .await
+    .unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let header: String = (0..buffer.area.width)
+        .map(|x| buffer.get(x, 2).symbol().to_string())
+        .collect();
+    // The test backend fills the cell after a wide character with a space
+    assert!(header.contains("┌CKAD Practitioner─"), "{}", header);
+    assert!(header.contains("…┐"), "{}", header);
+}
@@ -0,0 +1,64 @@
+use ckad_practitioner::distractors::{generate_distractors, generate_distractors_with, ChoiceSet};
+use ckad_practitioner::scorer::normalize;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashSet;
+
+const ANSWER: &str = "kubectl create deployment web --image=nginx:1.25 --replicas=3";
+
+#[test]
+fn distractors_differ_from_the_answer_and_each_other() {
+    let distractors = generate_distractors(ANSWER, 5);
+    assert_eq!(distractors.len(), 5);
+    assert!(distractors.iter().all(|wrong| normalize(wrong) != ANSWER));
+    let unique: HashSet<_> = distractors.iter().collect();
+    assert_eq!(unique.len(), distractors.len());
+}
+
+#[test]
+fn each_distractor_changes_one_part_of_the_command() {
+    let all = generate_distractors(ANSWER, usize::MAX);
+    for expected in [
+        "kubectl create statefulset web --image=nginx:1.25 --replicas=3",
+        "kubectl create deployment web --image=nginx:latest --replicas=3",
+        "kubectl create deployment web --image=nginx:1.26 --replicas=3",
+        "kubectl create deployment web --image=nginx:1.25 --replicas=4",
+        "kubectl apply deployment web --image=nginx:1.25 --replicas=3",
+        "kubectl create deployment web --replicas=3",
+    ] {
+        assert!(all.iter().any(|wrong| wrong == expected), "{}", expected);
+    }
+}
+
+#[test]
+fn count_is_capped_by_what_the_command_allows() {
+    assert!(generate_distractors("kubectl version", 3).is_empty());
+    assert!(generate_distractors("line one\nline two", 3).is_empty());
+    assert_eq!(generate_distractors("kubectl get pods", 3).len(), 3);
+    assert_eq!(generate_distractors("kubectl get secrets", 9).len(), 1);
+}
+
+#[test]
+fn seeded_choice_sets_are_reproducible_and_hold_the_answer_once() {
+    let build = || ChoiceSet::build(ANSWER, 4, &mut StdRng::seed_from_u64(7)).unwrap();
+    let choices = build();
+    assert_eq!(choices, build());
+    assert_eq!(choices.options.len(), 4);
+    assert_eq!(choices.options[choices.correct], ANSWER);
+    assert_eq!(
+        choices
+            .options
+            .iter()
+            .filter(|option| **option == ANSWER)
+            .count(),
+        1
+    );
+
+    let number = (choices.correct + 1).to_string();
+    assert_eq!(choices.pick(&number), Some(ANSWER));
+    assert_eq!(choices.pick("0"), None);
+    assert_eq!(choices.pick("5"), None);
+
+    let seeded = generate_distractors_with(ANSWER, 3, &mut StdRng::seed_from_u64(7));
+    assert_eq!(seeded.len(), 3);
+}
@@ -0,0 +1,93 @@
+use ckad_practitioner::app::{App, Outcome, Suspension};
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::editor::{self, AnswerFile, EditorError};
+use ckad_practitioner::keymap::{Action, KeyBindings};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::question_repository::{QuestionRepository, RepositoryError};
+use ckad_practitioner::scorer::AnswerScorer;
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use std::fs;
+
+const MANIFEST: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\nspec:\n  containers:\n  - name: web\n    image: nginx";
+
+fn manifest_question() -> Question {
+    Question {
+        id: 7,
+        question: "Write a Pod manifest\nnamed web".to_string(),
+        context: Some("apiVersion: v1\nkind: Pod".to_string()),
+        answer: MANIFEST.to_string(),
+        yaml_answer: true,
+        time_limit_secs: 30,
+        ..Default::default()
+    }
+}
+
+struct ManifestRepository;
+
+impl QuestionRepository for ManifestRepository {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        Ok(vec![manifest_question()])
+    }
+
+    fn source_description(&self) -> String {
+        "manifest question".to_string()
+    }
+}
+
+#[test]
+fn template_comments_out_the_question_and_keeps_the_context() {
+    let text = editor::template(&manifest_question());
+    assert!(text.starts_with("# Question 7\n# Write a Pod manifest\n# named web\n"));
+    assert!(text.ends_with("apiVersion: v1\nkind: Pod\n"));
+    assert_eq!(editor::strip_header(&text), "apiVersion: v1\nkind: Pod");
+}
+
+#[test]
+fn only_leading_comments_are_stripped() {
+    let text = "# header\n\nkind: Pod\n# kept\nspec: {}\n\n";
+    assert_eq!(editor::strip_header(text), "kind: Pod\n# kept\nspec: {}");
+}
+
+#[test]
+fn answer_file_is_removed_when_dropped() {
+    let dir = std::env::temp_dir();
+    let file = AnswerFile::create(&dir, &manifest_question()).expect("temp file");
+    let path = file.path().to_path_buf();
+    assert!(path.exists());
+
+    fs::write(&path, "# only the header\n\n").unwrap();
+    assert!(matches!(file.read_answer(), Err(EditorError::Empty)));
+    drop(file);
+    assert!(!path.exists());
+}
+
+#[test]
+fn manifests_match_whatever_the_layout() {
+    let scorer = AnswerScorer::default();
+    let question = manifest_question();
+    let reordered = "kind: Pod\napiVersion: v1\nspec:\n  containers: [{image: nginx, name: web}]\nmetadata: {name: web}";
+    assert!(scorer.is_correct(&question, reordered));
+    assert!(!scorer.is_correct(&question, &MANIFEST.replace("nginx", "httpd")));
+    assert!(!scorer.is_correct(&question, "kind: [unclosed"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn editor_key_suspends_only_for_manifest_questions() {
+    let mut app = App::new(
+        Box::new(ManifestRepository),
+        Theme::new(false),
+        KeyBindings::default(),
+        QuizConfig::default(),
+        PersonalBests::default(),
+    )
+    .expect("question loads");
+    assert_eq!(
+        app.dispatch(Action::Editor),
+        Outcome::Suspend(Suspension::Editor)
+    );
+
+    // Resolved questions take no more answers
+    app.dispatch(Action::Skip);
+    assert_eq!(app.dispatch(Action::Editor), Outcome::Unchanged);
+}
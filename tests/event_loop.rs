@@ -0,0 +1,621 @@
+mod common;
+
+use ckad_practitioner::app::{App, ExitReason};
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::events::{self, Input};
+use ckad_practitioner::i18n::Messages;
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::models::{Question, RubricItem};
+use ckad_practitioner::question_repository::QuestionRepository;
+use ckad_practitioner::session::Session;
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use ckad_practitioner::timer::MockClock;
+use common::{key_event, new_app, new_app_with, run_script, screen_text, MockQuestionRepository};
+use crossterm::event::Event;
+use futures_util::{stream, StreamExt};
+use ratatui::{backend::TestBackend, Terminal};
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn new_terminal() -> Terminal<TestBackend> {
+    Terminal::new(TestBackend::new(120, 40)).expect("test terminal")
+}
+
+fn key(c: char) -> Event {
+    Event::Key(key_event(c))
+}
+
+#[tokio::test(start_paused = true)]
+async fn pressing_h_twice_shows_the_second_hint() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    let after_one = run_script(&mut app, &mut terminal, vec![key('h')]).await;
+    assert!(after_one.contains("First hint"));
+    assert!(!after_one.contains("Second hint"));
+
+    let after_two = run_script(&mut app, &mut terminal, vec![key('h')]).await;
+    assert!(after_two.contains("Second hint"));
+}
+
+/// The text of the controls bar, the screen's last block, on one line
+fn controls(screen: &str) -> String {
+    let lines: Vec<&str> = screen.lines().collect();
+    let top = lines.iter().rposition(|line| line.contains('┌')).unwrap();
+    lines[top + 1..]
+        .iter()
+        .take_while(|line| !line.contains('└'))
+        .map(|line| line.trim_matches(|c| c == '│' || c == ' '))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[tokio::test(start_paused = true)]
+async fn answer_input_controls_show_submit_and_cancel_but_not_hints() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    let question = run_script(&mut app, &mut terminal, vec![]).await;
+    assert_eq!(
+        controls(&question),
+        "a: answer | h: hints | s: skip | g: go to id | Tab: tabs (or 1-4) | !: shell | q: quit | (answer revealed after time expires)"
+    );
+
+    let typing = controls(&run_script(&mut app, &mut terminal, vec![key('a')]).await);
+    assert!(typing.contains("Enter: submit | Esc: cancel"), "{}", typing);
+    assert!(!typing.contains("h: hints"), "{}", typing);
+}
+
+#[tokio::test(start_paused = true)]
+async fn controls_offer_only_the_keys_the_mode_allows() {
+    let mut terminal = new_terminal();
+    let mut exam = new_app_with(QuizConfig {
+        mode: Mode::Exam,
+        accommodations: true,
+        allow_restart: true,
+        ..QuizConfig::default()
+    });
+    let exam = controls(&run_script(&mut exam, &mut terminal, vec![]).await);
+    assert_eq!(
+        exam,
+        "a: answer | s: skip | g: go to id | Tab: tabs (or 1-4) | q: quit | (answer revealed after time expires)"
+    );
+
+    let mut practice = new_app_with(QuizConfig {
+        accommodations: true,
+        allow_restart: true,
+        ..QuizConfig::default()
+    });
+    let practice = controls(&run_script(&mut practice, &mut terminal, vec![]).await);
+    assert!(
+        practice.contains("h: hints | s: skip | +: more time | R: restart clock"),
+        "{}",
+        practice
+    );
+    assert!(practice.contains("!: shell"), "{}", practice);
+}
+
+/// Types `text` into the answer prompt and presses Enter
+fn typed_answer(text: &str) -> Vec<Event> {
+    let mut events = vec![key('a')];
+    events.extend(text.chars().map(key));
+    events.push(Event::Key(crossterm::event::KeyEvent::from(
+        crossterm::event::KeyCode::Enter,
+    )));
+    events
+}
+
+#[tokio::test(start_paused = true)]
+async fn confidence_is_rated_before_the_verdict_and_summarized() {
+    let mut app = new_app_with(QuizConfig {
+        confidence_prompt: true,
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    let asked = run_script(
+        &mut app,
+        &mut terminal,
+        typed_answer("kubectl run beta --image=nginx"),
+    )
+    .await;
+    assert!(
+        controls(&asked).contains("How sure are you of this answer? h: high | m: medium | l: low"),
+        "{}",
+        controls(&asked)
+    );
+    assert!(!app.session().quiz_state().is_resolved());
+    tokio::time::advance(Duration::from_secs(60)).await;
+    let still_asked = run_script(&mut app, &mut terminal, vec![key('x')]).await;
+    assert!(!still_asked.contains("Time expired"), "the clock is paused");
+
+    let graded = run_script(&mut app, &mut terminal, vec![key('h')]).await;
+    assert!(graded.contains("[FAIL]"));
+
+    let mut events = vec![key('n')];
+    events.extend(typed_answer("kubectl create namespace beta"));
+    events.extend([key('l'), key('n')]);
+    let summary = run_script(&mut app, &mut terminal, events).await;
+    assert!(
+        summary.contains("overconfident on 1, underconfident on 1 of 2 rated"),
+        "{}",
+        summary
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn answer_appears_once_the_clock_passes_the_limit() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    let before = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!before.contains("kubectl run alpha"));
+
+    tokio::time::advance(Duration::from_secs(31)).await;
+    let after = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(after.contains("TIME EXPIRED"));
+    assert!(after.contains("kubectl run alpha --image=nginx"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_clock_pauses_while_idle_and_the_next_key_only_resumes_it() {
+    let mut app = new_app_with(QuizConfig {
+        idle_pause_secs: Some(10),
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    tokio::time::advance(Duration::from_secs(9)).await;
+    let active = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!active.contains("Auto-paused (idle)"));
+
+    tokio::time::advance(Duration::from_secs(1)).await;
+    let paused = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(paused.contains("Auto-paused (idle)"), "{}", paused);
+    assert!(paused.contains("stopped after 10s without a key press"));
+
+    // Away for longer than the question's 30 seconds
+    tokio::time::advance(Duration::from_secs(60)).await;
+    let resumed = run_script(&mut app, &mut terminal, vec![key('h')]).await;
+    assert!(!resumed.contains("Auto-paused (idle)"));
+    assert!(!resumed.contains("TIME EXPIRED"));
+    assert!(
+        !resumed.contains("First hint"),
+        "the resuming key is not a hint request"
+    );
+    assert_eq!(
+        app.session().quiz_state().timer().remaining(),
+        Duration::from_secs(20)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn exam_mode_never_pauses_for_idleness() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Exam,
+        idle_pause_secs: Some(10),
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+    tokio::time::advance(Duration::from_secs(15)).await;
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!screen.contains("Auto-paused (idle)"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn reveal_delay_holds_the_answer_back_until_its_last_millisecond() {
+    let mut app = new_app_with(QuizConfig {
+        reveal_delay_secs: 3,
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    tokio::time::advance(Duration::from_millis(32_999)).await;
+    let held = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(held.contains("Time expired"));
+    assert!(!held.contains("kubectl run alpha"));
+
+    tokio::time::advance(Duration::from_millis(1)).await;
+    let shown = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!shown.contains("Time expired"));
+    assert!(shown.contains("kubectl run alpha --image=nginx"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn pressing_n_after_expiry_loads_the_next_question() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    tokio::time::advance(Duration::from_secs(31)).await;
+    let screen = run_script(&mut app, &mut terminal, vec![key('n')]).await;
+    assert!(screen.contains("Question 2 of 2: Create a namespace named beta"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn pressing_q_exits_the_loop() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    // The source never ends on its own, so only `q` can stop the loop
+    let events = stream::iter(vec![Ok(key('q'))]).chain(stream::pending());
+    let exit = tokio::time::timeout(Duration::from_secs(5), app.run_with(&mut terminal, events))
+        .await
+        .expect("loop exits on q")
+        .expect("event loop runs");
+    assert_eq!(exit, ExitReason::Quit);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_read_error_ends_the_loop_cleanly() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    // What a closed stdin or a dropped SSH session looks like to the loop
+    let eof = io::Error::new(io::ErrorKind::UnexpectedEof, "stdin closed");
+    let events = stream::iter(vec![Ok(key('h')), Err(eof)]).chain(stream::pending());
+    let exit = tokio::time::timeout(Duration::from_secs(5), app.run_with(&mut terminal, events))
+        .await
+        .expect("loop exits on a read error")
+        .expect("a read error is not a failure");
+    assert_eq!(exit, ExitReason::InputLost);
+    // The key before the error was still handled
+    assert!(screen_text(&terminal).contains("First hint"));
+    assert!(!app.session_result().complete);
+}
+
+#[tokio::test]
+async fn next_input_reports_events_errors_and_the_end() {
+    let eof = io::Error::new(io::ErrorKind::UnexpectedEof, "stdin closed");
+    let mut events = stream::iter(vec![Ok(key('q')), Err(eof)]);
+    assert!(matches!(
+        events::next_input(&mut events).await,
+        Input::Event(Event::Key(_))
+    ));
+    assert!(matches!(
+        events::next_input(&mut events).await,
+        Input::Lost(err) if err.kind() == io::ErrorKind::UnexpectedEof
+    ));
+    assert!(matches!(
+        events::next_input(&mut events).await,
+        Input::Ended
+    ));
+}
+
+#[tokio::test(start_paused = true)]
+async fn countdown_redraws_without_any_input() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    let start = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(start.contains("Time remaining: 0:30"));
+
+    tokio::time::advance(Duration::from_secs(10)).await;
+    let later = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(later.contains("Time remaining: 0:20"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn study_mode_shows_question_and_answer_together() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Study,
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("Create a pod named alpha"));
+    assert!(screen.contains("kubectl run alpha --image=nginx"));
+    assert!(screen.contains("Second hint"));
+    assert!(!screen.contains("Time remaining"));
+
+    // Every question counts as resolved, so `n` moves on straight away
+    let later = run_script(&mut app, &mut terminal, vec![key('n')]).await;
+    assert!(later.contains("Create a namespace named beta"));
+    assert!(later.contains("kubectl create namespace beta"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn context_renders_verbatim_when_present() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    let first = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!first.contains("Context"));
+
+    let second = run_script(&mut app, &mut terminal, vec![key('s')]).await;
+    assert!(second.contains("Context"));
+    assert!(second.contains("kind: Namespace"));
+    // Indentation survives
+    assert!(second.contains("│  name: beta"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn screen_text_follows_the_configured_language() {
+    let mut app = new_app().with_messages(Messages::for_locale("de_DE.UTF-8"));
+    let mut terminal = new_terminal();
+
+    let screen = run_script(&mut app, &mut terminal, vec![key('h')]).await;
+    assert!(screen.contains("Verbleibende Zeit"));
+    assert!(screen.contains("Frage 1 von 2: Create a pod named alpha"));
+    assert!(screen.contains("Hinweis 1: First hint"));
+    assert!(!screen.contains("Time remaining"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn author_and_source_are_credited_under_the_question_and_in_the_summary() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    let first = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!first.contains("Credit:"));
+
+    let second = run_script(&mut app, &mut terminal, vec![key('s')]).await;
+    assert!(second.contains("Credit: Jane Doe - namespaces bank"));
+
+    let summary = run_script(&mut app, &mut terminal, vec![key('s')]).await;
+    assert!(summary.contains("Credit"));
+    assert!(summary.contains("Jane Doe - namespaces bank"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn ready_gate_shows_the_question_once_enter_is_pressed() {
+    let mut app = new_app_with(QuizConfig {
+        ready_gate: true,
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    let waiting = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(waiting.contains("Question 1 of 2: press Enter to start the clock"));
+    assert!(!waiting.contains("Create a pod named alpha"));
+    assert!(waiting.contains("Time remaining: 0:30"));
+
+    let enter = Event::Key(crossterm::event::KeyEvent::from(
+        crossterm::event::KeyCode::Enter,
+    ));
+    let started = run_script(&mut app, &mut terminal, vec![enter]).await;
+    assert!(started.contains("Question 1 of 2: Create a pod named alpha"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn related_commands_render_only_when_present_and_toggled_on() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+
+    // Nothing to toggle before the answer is revealed
+    let locked = run_script(&mut app, &mut terminal, vec![key('r')]).await;
+    assert!(!locked.contains("related commands"));
+    assert!(!locked.contains("kubectl get pod alpha"));
+
+    tokio::time::advance(Duration::from_secs(31)).await;
+    let revealed = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(revealed.contains("(press 'r' for related commands)"));
+    assert!(revealed.contains("r: related commands"));
+    assert!(!revealed.contains("kubectl get pod alpha"));
+
+    let shown = run_script(&mut app, &mut terminal, vec![key('r')]).await;
+    assert!(shown.contains("Related commands:"));
+    assert!(shown.contains("$ kubectl get pod alpha"));
+    assert!(shown.contains("$ kubectl delete pod alpha"));
+
+    let hidden = run_script(&mut app, &mut terminal, vec![key('r')]).await;
+    assert!(!hidden.contains("kubectl delete pod alpha"));
+
+    // Still toggled on, but the second question has none: no prompt, no key
+    run_script(&mut app, &mut terminal, vec![key('r'), key('n')]).await;
+    tokio::time::advance(Duration::from_secs(31)).await;
+    let second = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(second.contains("kubectl create namespace beta"));
+    assert!(!second.contains("related commands"));
+    assert!(!second.contains("Related commands"));
+}
+
+#[tokio::test]
+async fn auto_advance_follows_a_mock_clock() {
+    let clock = MockClock::new();
+    let config = QuizConfig {
+        auto_advance_secs: Some(3),
+        ..QuizConfig::default()
+    };
+    let questions = MockQuestionRepository.get_questions().unwrap();
+    let session = Session::new(questions, config)
+        .unwrap()
+        .with_clock(Arc::new(clock.clone()));
+    let mut app = App::from_session(
+        session,
+        "mock (2 Qs)".to_string(),
+        Theme::new(false),
+        KeyBindings::default(),
+        PersonalBests::default(),
+    );
+    let mut terminal = new_terminal();
+
+    clock.advance(Duration::from_secs(32));
+    let waiting = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(waiting.contains("Question 1 of 2"));
+    assert!(waiting.contains("TIME EXPIRED"));
+
+    clock.advance(Duration::from_secs(1));
+    let advanced = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(advanced.contains("Question 2 of 2: Create a namespace named beta"));
+    assert!(advanced.contains("Time remaining: 0:30"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn hints_tab_shows_the_budget_and_when_it_runs_out() {
+    let mut app = new_app_with(QuizConfig {
+        hint_budget: Some(1),
+        ..QuizConfig::default()
+    });
+    let mut terminal = new_terminal();
+
+    let first = run_script(&mut app, &mut terminal, vec![key('h')]).await;
+    assert!(first.contains("Hint 1: First hint"));
+    assert!(first.contains("Hint budget: 0 of 1 left"));
+    assert!(first.contains("No hints remaining"));
+    assert!(!first.contains("for more"));
+
+    let second = run_script(&mut app, &mut terminal, vec![key('s'), key('h')]).await;
+    assert!(second.contains("No hints remaining"));
+    assert!(!second.contains("Hint 1:"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn partial_answers_mark_each_expected_command() {
+    let question = Question {
+        id: 1,
+        question: "Create and expose a deployment".to_string(),
+        answer:
+            "kubectl create deployment web --image=httpd\nkubectl expose deployment web --port=80"
+                .to_string(),
+        time_limit_secs: 60,
+        ..Default::default()
+    };
+    let session = Session::new(vec![question], QuizConfig::default()).unwrap();
+    let mut app = App::from_session(
+        session,
+        "mock (1 Qs)".to_string(),
+        Theme::new(false),
+        KeyBindings::default(),
+        PersonalBests::default(),
+    );
+    let mut terminal = new_terminal();
+
+    let mut events = vec![key('a')];
+    events.extend(
+        "kubectl create deployment web --image httpd"
+            .chars()
+            .map(key),
+    );
+    events.push(Event::Key(crossterm::event::KeyEvent::from(
+        crossterm::event::KeyCode::Enter,
+    )));
+    let answered = run_script(&mut app, &mut terminal, events).await;
+    assert!(answered.contains("Partial credit: 1 of 2 commands"));
+    assert!(answered.contains("✓ kubectl create deployment web --image=httpd"));
+    assert!(answered.contains("✗ kubectl expose deployment web --port=80"));
+
+    let summary = run_script(&mut app, &mut terminal, vec![key('n')]).await;
+    assert!(summary.contains("[PARTIAL 50%]"));
+    assert!(summary.contains("0.5/1 points"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_question_header_is_tagged_with_its_category() {
+    let question = Question {
+        id: 1,
+        question: "Create the app-config ConfigMap".to_string(),
+        answer: "kubectl create configmap app-config".to_string(),
+        category: Some("Configuration".to_string()),
+        priority: Some(3),
+        time_limit_secs: 60,
+        ..Default::default()
+    };
+    let session = Session::new(vec![question], QuizConfig::default()).unwrap();
+    let mut app = App::from_session(
+        session,
+        "mock (1 Qs)".to_string(),
+        Theme::new(false),
+        KeyBindings::default(),
+        PersonalBests::default(),
+    );
+    let mut terminal = new_terminal();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(
+        screen.contains("[CONF] Question 1 of 1: Create the app-config ConfigMap  ★ priority 3"),
+        "{}",
+        screen
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn rubric_answers_list_each_item_with_its_points() {
+    let question = Question {
+        id: 1,
+        question: "Create the app-config ConfigMap".to_string(),
+        answer: "kubectl create configmap app-config --from-literal=database.url=db".to_string(),
+        rubric: Some(vec![
+            RubricItem {
+                item: "app-config".to_string(),
+                points: 1,
+            },
+            RubricItem {
+                item: "--from-literal".to_string(),
+                points: 2,
+            },
+            RubricItem {
+                item: "database.url".to_string(),
+                points: 1,
+            },
+        ]),
+        time_limit_secs: 60,
+        ..Default::default()
+    };
+    let session = Session::new(vec![question], QuizConfig::default()).unwrap();
+    let mut app = App::from_session(
+        session,
+        "mock (1 Qs)".to_string(),
+        Theme::new(false),
+        KeyBindings::default(),
+        PersonalBests::default(),
+    );
+    let mut terminal = new_terminal();
+
+    let mut events = vec![key('a')];
+    events.extend(
+        "kubectl create cm app-config --from-literal=url=db"
+            .chars()
+            .map(key),
+    );
+    events.push(Event::Key(crossterm::event::KeyEvent::from(
+        crossterm::event::KeyCode::Enter,
+    )));
+    let answered = run_script(&mut app, &mut terminal, events).await;
+    assert!(answered.contains("Rubric: 3 of 4 points"));
+    assert!(answered.contains("✓ app-config (+1)"));
+    assert!(answered.contains("✓ --from-literal (+2)"));
+    assert!(answered.contains("✗ database.url (0 of 1)"));
+
+    let summary = run_script(&mut app, &mut terminal, vec![key('n')]).await;
+    assert!(summary.contains("[PARTIAL 75%]"));
+    assert!(summary.contains("3/4 points"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_terminal_cursor_follows_the_answer_edit_position() {
+    let mut app = new_app();
+    let mut terminal = new_terminal();
+    let press = |code| Event::Key(crossterm::event::KeyEvent::from(code));
+    let mut events = vec![key('a')];
+    events.extend("get po".chars().map(key));
+    events.extend([
+        press(crossterm::event::KeyCode::Left),
+        press(crossterm::event::KeyCode::Left),
+    ]);
+    let shown = run_script(&mut app, &mut terminal, events).await;
+    assert!(shown.contains("Your answer: get po"));
+    assert!(!shown.contains("get po_"));
+    assert_eq!(app.answer_input().cursor(), 4);
+
+    let (column, row) = terminal.get_cursor().unwrap();
+    let buffer = terminal.backend().buffer();
+    let line: String = (0..buffer.area.width)
+        .map(|x| buffer.get(x, row).symbol().to_string())
+        .collect();
+    let prompt = line
+        .find("Your answer: get po")
+        .expect("prompt on the cursor row");
+    let prompt_column = line[..prompt].chars().count();
+    assert_eq!(column as usize, prompt_column + "Your answer: get ".len());
+
+    let edited = run_script(
+        &mut app,
+        &mut terminal,
+        vec![press(crossterm::event::KeyCode::Right), key('d')],
+    )
+    .await;
+    assert!(edited.contains("Your answer: get pdo"));
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::goal::{self, DailyGoal, DayProgress};
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::results::{QuestionResult, Score, SessionResult};
+
+/// A session starting at `started_at` (UTC) with one question per outcome
+fn session(started_at: &str, outcomes: &[QuestionStatus], secs_each: u64) -> SessionResult {
+    let started_at: DateTime<Utc> = started_at.parse().unwrap();
+    SessionResult {
+        started_at,
+        finished_at: started_at,
+        mode: Mode::Practice,
+        complete: true,
+        questions: outcomes
+            .iter()
+            .enumerate()
+            .map(|(index, outcome)| QuestionResult {
+                id: index + 1,
+                uid: String::new(),
+                question: String::new(),
+                expected_answer: String::new(),
+                domain: None,
+                outcome: *outcome,
+                time_spent_secs: secs_each,
+                hints_used: 0,
+                hints_viewed: Vec::new(),
+                tags: Vec::new(),
+                answer_submitted: None,
+                attempts: Vec::new(),
+                partial_credit: None,
+                points: 0.0,
+                confidence: None,
+            })
+            .collect(),
+        score: Score {
+            correct: 0,
+            total: outcomes.len(),
+            points: 0.0,
+            possible_points: outcomes.len() as u32,
+            percent: 0.0,
+        },
+        domains: Vec::new(),
+        break_secs: 0,
+        elapsed_secs: 0,
+    }
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+const PASS: QuestionStatus = QuestionStatus::Pass;
+const FAIL: QuestionStatus = QuestionStatus::Fail;
+const SKIP: QuestionStatus = QuestionStatus::Skipped;
+
+#[test]
+fn sessions_on_the_same_day_add_up_and_skips_do_not_count() {
+    let history = [
+        session("2024-05-01T08:00:00Z", &[PASS, FAIL, SKIP], 60),
+        session("2024-05-01T20:00:00Z", &[PASS, PASS], 90),
+    ];
+    let days = goal::daily_progress(&history, &Utc);
+    assert_eq!(
+        days[&date(2024, 5, 1)],
+        DayProgress {
+            questions: 4,
+            secs: 300
+        }
+    );
+    assert_eq!(days.len(), 1);
+}
+
+#[test]
+fn days_split_at_local_midnight() {
+    let history = [
+        session("2024-05-01T23:59:00Z", &[PASS], 30),
+        session("2024-05-02T00:01:00Z", &[PASS], 30),
+    ];
+    let days = goal::daily_progress(&history, &Utc);
+    assert_eq!(days[&date(2024, 5, 1)].questions, 1);
+    assert_eq!(days[&date(2024, 5, 2)].questions, 1);
+
+    // Two hours east of UTC both sessions fall on the 2nd
+    let east = FixedOffset::east_opt(2 * 3600).unwrap();
+    let days = goal::daily_progress(&history, &east);
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[&date(2024, 5, 2)].questions, 2);
+
+    // Five hours west they both still belong to the 1st
+    let west = FixedOffset::west_opt(5 * 3600).unwrap();
+    let days = goal::daily_progress(&history, &west);
+    assert_eq!(days[&date(2024, 5, 1)].questions, 2);
+}
+
+#[test]
+fn a_session_counts_on_the_day_it_started() {
+    let mut late = session("2024-05-01T23:50:00Z", &[PASS, PASS], 600);
+    late.finished_at = "2024-05-02T00:10:00Z".parse().unwrap();
+    let days = goal::daily_progress(&[late], &Utc);
+    assert_eq!(days.keys().copied().collect::<Vec<_>>(), [date(2024, 5, 1)]);
+}
+
+#[test]
+fn week_counts_only_days_that_met_every_target() {
+    let goal = DailyGoal {
+        questions: Some(2),
+        minutes: Some(1),
+    };
+    let history = [
+        // Met: two questions, two minutes
+        session("2024-05-07T10:00:00Z", &[PASS, FAIL], 60),
+        // Enough questions but not enough time
+        session("2024-05-06T10:00:00Z", &[PASS, PASS], 10),
+        // Met across two sessions
+        session("2024-05-04T09:00:00Z", &[PASS], 40),
+        session("2024-05-04T18:00:00Z", &[FAIL], 40),
+        // Met, but eight days back
+        session("2024-04-30T10:00:00Z", &[PASS, PASS], 60),
+    ];
+    let today = date(2024, 5, 7);
+    assert_eq!(goal::days_met(&history, &goal, today, 7, &Utc), 2);
+    assert_eq!(
+        goal::week_summary(&history, &goal, today, &Utc),
+        "Goal met 2 of the last 7 days"
+    );
+    assert_eq!(goal::days_met(&history, &goal, today, 8, &Utc), 3);
+
+    // Sessions after `today` are ignored
+    let tomorrow = [session("2024-05-08T10:00:00Z", &[PASS, PASS], 60)];
+    assert_eq!(goal::days_met(&tomorrow, &goal, today, 7, &Utc), 0);
+}
+
+#[test]
+fn progress_text_reports_each_target() {
+    let goal = DailyGoal {
+        questions: Some(10),
+        minutes: Some(20),
+    };
+    let progress = DayProgress {
+        questions: 4,
+        secs: 12 * 60 + 59,
+    };
+    assert_eq!(
+        goal.progress_text(&progress),
+        "Goal: 4/10 questions, 12/20 min"
+    );
+
+    let goal = DailyGoal {
+        questions: Some(3),
+        minutes: None,
+    };
+    assert_eq!(goal.progress_text(&progress), "Goal met: 4/3 questions");
+    assert!(!DailyGoal::default().is_met(&progress));
+}
+
+#[test]
+fn utc_timestamps_use_the_given_zone_for_today() {
+    let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+    let history = [session("2024-05-06T16:00:00Z", &[PASS], 60)];
+    let goal = DailyGoal {
+        questions: Some(1),
+        minutes: None,
+    };
+    let today = tz
+        .with_ymd_and_hms(2024, 5, 7, 8, 0, 0)
+        .unwrap()
+        .date_naive();
+    assert_eq!(goal::days_met(&history, &goal, today, 1, &tz), 1);
+}
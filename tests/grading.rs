@@ -0,0 +1,723 @@
+use ckad_practitioner::grading::{
+    compare_command_lists, compare_commands, diagnose, split_commands, CommandBreakdown,
+    CommandMatch, Difference, Verdict,
+};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::scorer::AnswerScorer;
+
+fn flag_syntax(expected: &str, submitted: &str) -> Difference {
+    Difference::FlagSyntax {
+        expected: expected.to_string(),
+        submitted: submitted.to_string(),
+    }
+}
+
+fn alias(expected: &str, submitted: &str) -> Difference {
+    Difference::Alias {
+        expected: expected.to_string(),
+        submitted: submitted.to_string(),
+    }
+}
+
+fn quoting(value: &str) -> Difference {
+    Difference::Quoting {
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn equal_commands() {
+    let cases = [
+        ("kubectl get pods", "kubectl get pods"),
+        ("kubectl get pods", "  kubectl   get\tpods "),
+        (
+            "kubectl run nginx --image=nginx",
+            "kubectl run nginx --image=nginx",
+        ),
+        ("kubectl get pods -o wide", "kubectl  get pods  -o  wide"),
+        ("kubectl exec -it web -- sh", "kubectl exec -it web -- sh"),
+        ("kubectl get pods -A", "kubectl get pods -A"),
+    ];
+    for (expected, submitted) in cases {
+        assert_eq!(
+            compare_commands(expected, submitted, true),
+            Verdict::Equal,
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+}
+
+#[test]
+fn equivalent_commands_list_their_differences() {
+    use Difference::FlagOrder;
+    let cases: Vec<(&str, &str, Vec<Difference>)> = vec![
+        (
+            "kubectl run nginx --image=nginx:1.14",
+            "kubectl run nginx --image nginx:1.14",
+            vec![flag_syntax("--image=nginx:1.14", "--image nginx:1.14")],
+        ),
+        (
+            "kubectl get pods -n shop -o yaml",
+            "kubectl get pods -o yaml -n shop",
+            vec![FlagOrder],
+        ),
+        (
+            "kubectl run nginx --image=nginx --restart=Never",
+            "kubectl run --restart=Never --image=nginx nginx",
+            vec![FlagOrder],
+        ),
+        (
+            "kubectl create deployment web --image=httpd:2.4 --replicas=3",
+            "kubectl create deployment web --replicas 3 --image httpd:2.4",
+            vec![
+                FlagOrder,
+                flag_syntax("--replicas=3", "--replicas 3"),
+                flag_syntax("--image=httpd:2.4", "--image httpd:2.4"),
+            ],
+        ),
+        (
+            "kubectl label pod web app=web",
+            "kubectl label pod web 'app=web'",
+            vec![quoting("app=web")],
+        ),
+        (
+            "kubectl get pods -l app=web",
+            "kubectl get pods -l \"app=web\"",
+            vec![quoting("app=web")],
+        ),
+        (
+            "kubectl get pods --selector=app=web",
+            "kubectl get pods --selector 'app=web'",
+            vec![
+                flag_syntax("--selector=app=web", "--selector app=web"),
+                quoting("app=web"),
+            ],
+        ),
+        (
+            "kubectl create configmap app --from-literal=a=1 --from-literal=b=2",
+            "kubectl create configmap app --from-literal=b=2 --from-literal=a=1",
+            vec![FlagOrder],
+        ),
+        (
+            "kubectl exec -it web -- sh",
+            "kubectl exec -i -t web -- sh",
+            vec![],
+        ),
+        (
+            "kubectl delete pod web --force --grace-period=0",
+            "kubectl delete pod --force web --grace-period 0",
+            vec![
+                FlagOrder,
+                flag_syntax("--grace-period=0", "--grace-period 0"),
+            ],
+        ),
+        (
+            "kubectl get pods --all-namespaces --show-labels",
+            "kubectl get --show-labels pods --all-namespaces",
+            vec![FlagOrder],
+        ),
+        (
+            "kubectl annotate pod web note=\"hello world\"",
+            "kubectl annotate pod web 'note=hello world'",
+            vec![],
+        ),
+        (
+            "kubectl apply -f pod.yaml",
+            "kubectl apply -f=pod.yaml",
+            vec![flag_syntax("-f pod.yaml", "-f=pod.yaml")],
+        ),
+        (
+            "kubectl scale deployment web --replicas=5",
+            "kubectl scale --replicas=5 deployment web",
+            vec![FlagOrder],
+        ),
+    ];
+    for (expected, submitted, differences) in cases {
+        let verdict = compare_commands(expected, submitted, true);
+        let wanted = if differences.is_empty() {
+            Verdict::Equal
+        } else {
+            Verdict::Equivalent(differences)
+        };
+        assert_eq!(verdict, wanted, "{} vs {}", expected, submitted);
+        assert!(verdict.is_correct());
+    }
+}
+
+#[test]
+fn different_commands() {
+    let cases = [
+        // Different values, arguments or flags
+        (
+            "kubectl run nginx --image=nginx:1.14",
+            "kubectl run nginx --image=nginx:1.15",
+        ),
+        (
+            "kubectl run nginx --image=nginx",
+            "kubectl run web --image=nginx",
+        ),
+        ("kubectl get pods", "kubectl get pods -o wide"),
+        ("kubectl get pods -o wide", "kubectl get pods"),
+        (
+            "kubectl create deployment web --image=nginx",
+            "kubectl create web deployment --image=nginx",
+        ),
+        ("kubectl get pods", "kubectl get svc"),
+        // A value can't move to another flag
+        (
+            "kubectl run a --image=x --port=80",
+            "kubectl run a --image=80 --port=x",
+        ),
+        // Arguments after -- are a command of their own
+        (
+            "kubectl exec web -- ls -l /tmp",
+            "kubectl exec web -- ls /tmp -l",
+        ),
+        ("kubectl exec web -- sh", "kubectl exec web sh"),
+        // Repeated flags must all be there
+        (
+            "kubectl create configmap app --from-literal=a=1 --from-literal=b=2",
+            "kubectl create configmap app --from-literal=a=1",
+        ),
+        // Shell operators and unclosed quotes only match as equal text
+        (
+            "kubectl get pods -o yaml | grep image",
+            "kubectl get pods | grep image -o yaml",
+        ),
+        (
+            "kubectl get pods; kubectl get svc",
+            "kubectl get svc; kubectl get pods",
+        ),
+        (
+            "kubectl label pod web 'app=web",
+            "kubectl label pod web app=web",
+        ),
+        ("", "kubectl get pods"),
+    ];
+    for (expected, submitted) in cases {
+        assert_eq!(
+            compare_commands(expected, submitted, true),
+            Verdict::Different,
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+}
+
+#[test]
+fn case_only_matters_when_case_sensitive() {
+    let expected = "kubectl expose deployment web --type=NodePort --port=80";
+    let submitted = "kubectl expose deployment web --port 80 --type nodeport";
+    assert_eq!(
+        compare_commands(expected, submitted, true),
+        Verdict::Different
+    );
+    assert!(compare_commands(expected, submitted, false).is_correct());
+}
+
+#[test]
+fn shell_operators_still_match_as_text() {
+    let command = "kubectl get pods -o yaml | grep image";
+    assert_eq!(
+        compare_commands(command, "kubectl get pods  -o yaml |  grep image", true),
+        Verdict::Equal
+    );
+}
+
+#[test]
+fn explanation_joins_the_differences() {
+    let verdict = compare_commands(
+        "kubectl get pods -l app=web -o yaml",
+        "kubectl get pods -o=yaml -l 'app=web'",
+        true,
+    );
+    assert_eq!(
+        verdict.explanation().as_deref(),
+        Some("flags in a different order; `-o yaml` written as `-o=yaml`; quotes around app=web differ")
+    );
+    assert_eq!(Verdict::Equal.explanation(), None);
+    assert_eq!(Verdict::Different.explanation(), None);
+}
+
+#[test]
+fn scorer_accepts_equivalent_commands_but_not_across_lines() {
+    let scorer = AnswerScorer::default();
+    let single = Question {
+        id: 1,
+        answer: "kubectl run nginx --image=nginx:1.14".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        scorer.grade(&single, "kubectl run nginx --image nginx:1.14"),
+        Verdict::Equivalent(vec![flag_syntax(
+            "--image=nginx:1.14",
+            "--image nginx:1.14"
+        )])
+    );
+
+    // Flags of one command can't satisfy another
+    let multi = Question {
+        id: 2,
+        answer:
+            "kubectl create deployment web --image=httpd\nkubectl scale deployment web --replicas=3"
+                .to_string(),
+        ..Default::default()
+    };
+    assert!(!scorer.is_correct(
+        &multi,
+        "kubectl create deployment web --replicas=3 kubectl scale deployment web --image=httpd"
+    ));
+}
+
+#[test]
+fn aliases_and_short_names_are_equivalent() {
+    let cases: Vec<(&str, &str, Vec<Difference>)> = vec![
+        (
+            "kubectl get pods",
+            "kubectl get po",
+            vec![alias("pods", "po")],
+        ),
+        (
+            "kubectl get pods",
+            "kubectl get pod",
+            vec![alias("pods", "pod")],
+        ),
+        (
+            "kubectl get pods",
+            "k get pods",
+            vec![alias("kubectl", "k")],
+        ),
+        (
+            "kubectl get services -n shop",
+            "kubectl get svc --namespace shop",
+            vec![alias("services", "svc"), alias("-n", "--namespace")],
+        ),
+        (
+            "kubectl scale deployment web --replicas=3",
+            "k scale deploy web --replicas=3",
+            vec![alias("kubectl", "k"), alias("deployment", "deploy")],
+        ),
+        (
+            "kubectl rollout undo deployment/web",
+            "kubectl rollout undo deploy/web",
+            vec![alias("deployment/web", "deploy/web")],
+        ),
+        (
+            "kubectl get pods,services",
+            "kubectl get po,svc",
+            vec![alias("pods,services", "po,svc")],
+        ),
+        (
+            "kubectl get configmap app -o yaml",
+            "kubectl get cm app --output=yaml",
+            vec![
+                alias("configmap", "cm"),
+                alias("-o", "--output"),
+                flag_syntax("-o yaml", "--output=yaml"),
+            ],
+        ),
+        (
+            "kubectl get pods -A -l app=web",
+            "kubectl get po --selector app=web --all-namespaces",
+            vec![
+                Difference::FlagOrder,
+                alias("pods", "po"),
+                alias("-l", "--selector"),
+                alias("-A", "--all-namespaces"),
+            ],
+        ),
+        (
+            "kubectl exec -it web -c app -- sh",
+            "kubectl exec --stdin --tty web --container app -- sh",
+            vec![
+                alias("-i", "--stdin"),
+                alias("-t", "--tty"),
+                alias("-c", "--container"),
+            ],
+        ),
+        (
+            "kubectl get networkpolicies -n shop",
+            "kubectl get netpol -n shop",
+            vec![alias("networkpolicies", "netpol")],
+        ),
+        (
+            "kubectl describe pvc data",
+            "kubectl describe persistentvolumeclaim data",
+            vec![alias("pvc", "persistentvolumeclaim")],
+        ),
+        (
+            "kubectl create serviceaccount build",
+            "kubectl create sa build",
+            vec![alias("serviceaccount", "sa")],
+        ),
+    ];
+    for (expected, submitted, differences) in cases {
+        assert_eq!(
+            compare_commands(expected, submitted, true),
+            Verdict::Equivalent(differences),
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+}
+
+#[test]
+fn short_names_never_stand_for_a_neighbouring_resource() {
+    for (expected, submitted) in [
+        ("kubectl get serviceaccounts", "kubectl get svc"),
+        ("kubectl get services", "kubectl get sa"),
+        ("kubectl get pv", "kubectl get pvc"),
+        ("kubectl get nodes", "kubectl get ns"),
+        ("kubectl get ds", "kubectl get deploy"),
+        ("kubectl get pods -n shop", "kubectl get pods -l shop"),
+    ] {
+        assert_eq!(
+            compare_commands(expected, submitted, true),
+            Verdict::Different,
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+}
+
+#[test]
+fn names_that_look_like_aliases_keep_their_spelling() {
+    for (expected, submitted) in [
+        // Object names: a pod called `pod` is not a pod called `pods`
+        (
+            "kubectl run pod --image=nginx",
+            "kubectl run pods --image=nginx",
+        ),
+        ("kubectl get pods pod", "kubectl get pods pods"),
+        ("kubectl delete pod svc", "kubectl delete pod services"),
+        ("kubectl logs po", "kubectl logs pods"),
+        // `create` subcommands are singular; the plurals are not commands
+        (
+            "kubectl create secret generic db --from-literal=a=b",
+            "kubectl create secrets generic db --from-literal=a=b",
+        ),
+        (
+            "kubectl create deployment web --image=nginx",
+            "kubectl create deployments web --image=nginx",
+        ),
+        (
+            "kubectl create configmap app",
+            "kubectl create configmaps app",
+        ),
+    ] {
+        assert_eq!(
+            compare_commands(expected, submitted, true),
+            Verdict::Different,
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+    // The resource argument and `type/name` forms still resolve
+    assert!(compare_commands("kubectl get pods pod", "kubectl get po pod", true).is_correct());
+    assert!(compare_commands(
+        "kubectl delete pod/web service/web",
+        "kubectl delete po/web svc/web",
+        true
+    )
+    .is_correct());
+    assert!(compare_commands(
+        "kubectl logs deployment/web",
+        "kubectl logs deploy/web",
+        true
+    )
+    .is_correct());
+    assert!(compare_commands(
+        "kubectl create deployment web --image=nginx",
+        "kubectl create deploy web --image=nginx",
+        true
+    )
+    .is_correct());
+}
+
+#[test]
+fn dash_l_is_labels_on_run_and_expose() {
+    for (expected, submitted) in [
+        (
+            "kubectl run web --image=nginx --labels=app=web",
+            "kubectl run web --image=nginx -l app=web",
+        ),
+        (
+            "kubectl expose deployment web --port=80 --labels=app=web",
+            "kubectl expose deployment web --port=80 -l app=web",
+        ),
+    ] {
+        assert!(
+            compare_commands(expected, submitted, true).is_correct(),
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+    assert_eq!(
+        compare_commands(
+            "kubectl run web --image=nginx --labels=app=web",
+            "kubectl run web --image=nginx --selector=app=web",
+            true
+        ),
+        Verdict::Different
+    );
+    // Elsewhere it is still the selector
+    assert!(compare_commands(
+        "kubectl get pods --selector app=web",
+        "kubectl get pods -l app=web",
+        true
+    )
+    .is_correct());
+}
+
+#[test]
+fn aliases_only_apply_to_kubectl_itself() {
+    // Unknown commands and words after `--` are left as written
+    for (expected, submitted) in [
+        ("helm get po", "helm get pods"),
+        (
+            "kubectl exec web -- ls -n",
+            "kubectl exec web -- ls --namespace",
+        ),
+        ("kubectl exec web -- cat po", "kubectl exec web -- cat pods"),
+        ("kubectl get pods web-po", "kubectl get pods web-pods"),
+    ] {
+        assert_eq!(
+            compare_commands(expected, submitted, true),
+            Verdict::Different,
+            "{} vs {}",
+            expected,
+            submitted
+        );
+    }
+}
+
+const DEPLOY_AND_EXPOSE: &str =
+    "kubectl create deployment web --image=httpd:2.4 --replicas=3\nkubectl expose deployment web --port=80";
+
+fn breakdown(matched: &[bool], extra: &[&str], extra_penalized: bool) -> CommandBreakdown {
+    let commands = split_commands(DEPLOY_AND_EXPOSE);
+    CommandBreakdown {
+        commands: commands
+            .into_iter()
+            .zip(matched)
+            .map(|(command, &matched)| CommandMatch { command, matched })
+            .collect(),
+        extra: extra.iter().map(ToString::to_string).collect(),
+        extra_penalized,
+    }
+}
+
+#[test]
+fn answers_split_into_commands_at_lines_and_double_ampersands() {
+    assert_eq!(
+        split_commands("kubectl create ns dev && kubectl get ns\n\n# then\n  kubectl get pods  "),
+        vec![
+            "kubectl create ns dev",
+            "kubectl get ns",
+            "kubectl get pods"
+        ]
+    );
+    // Quoted && belongs to the argument
+    assert_eq!(
+        split_commands("kubectl exec web -- sh -c 'true && false'"),
+        vec!["kubectl exec web -- sh -c 'true && false'"]
+    );
+}
+
+#[test]
+fn command_lists_earn_credit_per_matched_command() {
+    let deployment = "kubectl create deployment web --replicas 3 --image httpd:2.4";
+    let verdict = compare_command_lists(DEPLOY_AND_EXPOSE, deployment, true, false);
+    assert_eq!(
+        verdict,
+        Verdict::Partial(breakdown(&[true, false], &[], false))
+    );
+    assert!(!verdict.is_correct());
+    assert_eq!(verdict.credit(), 0.5);
+
+    assert_eq!(
+        compare_command_lists(DEPLOY_AND_EXPOSE, "kubectl get pods", true, false),
+        Verdict::Different
+    );
+    assert_eq!(Verdict::Different.credit(), 0.0);
+}
+
+#[test]
+fn command_lists_may_come_in_any_order() {
+    let verdict = compare_command_lists(
+        DEPLOY_AND_EXPOSE,
+        "kubectl expose deployment web --port=80 && k create deploy web --image=httpd:2.4 --replicas=3",
+        true,
+        false,
+    );
+    assert_eq!(
+        verdict,
+        Verdict::Equivalent(vec![
+            Difference::CommandOrder,
+            alias("kubectl", "k"),
+            alias("deployment", "deploy"),
+        ])
+    );
+    assert_eq!(verdict.credit(), 1.0);
+    assert_eq!(
+        compare_command_lists(
+            DEPLOY_AND_EXPOSE,
+            &DEPLOY_AND_EXPOSE.replace('\n', " && "),
+            true,
+            false
+        ),
+        Verdict::Equal
+    );
+}
+
+#[test]
+fn extra_commands_are_free_unless_penalized() {
+    let submitted = format!("kubectl get pods\n{}", DEPLOY_AND_EXPOSE);
+    assert_eq!(
+        compare_command_lists(DEPLOY_AND_EXPOSE, &submitted, true, false),
+        Verdict::Equivalent(vec![Difference::ExtraCommands(1)])
+    );
+
+    let verdict = compare_command_lists(DEPLOY_AND_EXPOSE, &submitted, true, true);
+    assert_eq!(
+        verdict,
+        Verdict::Partial(breakdown(&[true, true], &["kubectl get pods"], true))
+    );
+    assert!((verdict.credit() - 2.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn scorer_gives_partial_credit_on_multi_command_answers_only() {
+    let scorer = AnswerScorer::default();
+    let question = Question {
+        id: 2,
+        answer: DEPLOY_AND_EXPOSE.to_string(),
+        ..Default::default()
+    };
+    let verdict = scorer.grade(
+        &question,
+        "kubectl create deployment web --image=httpd:2.4 --replicas=3",
+    );
+    assert_eq!(verdict.credit(), 0.5);
+
+    let single = Question {
+        id: 1,
+        answer: "kubectl get pods".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        scorer.grade(&single, "kubectl get pods && kubectl get svc"),
+        Verdict::Different
+    );
+}
+
+#[test]
+fn diagnose_names_common_kubectl_mistakes() {
+    let cases = [
+        (
+            "kubectl create pod web --image=nginx",
+            "kubectl run web --image=nginx",
+            "You used `create pod` but need `run`: pods are started with `kubectl run`",
+        ),
+        (
+            "kubectl get pod web",
+            "kubectl describe pod web",
+            "You used `get` but need `describe`",
+        ),
+        (
+            "kubectl create deploy web --image=nginx",
+            "kubectl create job web --image=nginx",
+            "Wrong resource type: this asks for jobs, not deployments",
+        ),
+        (
+            "kubectl delete po/web",
+            "kubectl delete svc/web",
+            "Wrong resource type: this asks for services, not pods",
+        ),
+        (
+            "kubectl run web",
+            "kubectl run web --image=nginx",
+            "Missing `--image`: say which container image to run",
+        ),
+        (
+            "kubectl create deployment web --image=nginx",
+            "kubectl create deployment web --image=nginx --replicas=3",
+            "Missing `--replicas`: without it you get a single replica",
+        ),
+        (
+            "kubectl create deployment web --image=nginx --replicas 2",
+            "kubectl create deployment web --image=nginx --replicas=3",
+            "Wrong replica count: `--replicas` should be 3",
+        ),
+        (
+            "kubectl run web --image=nginx",
+            "kubectl run web --image=nginx -n prod",
+            "Missing `-n`: without a namespace the command uses the current one",
+        ),
+        (
+            "kubectl run web --image=nginx -o yaml",
+            "kubectl run web --image=nginx --dry-run=client -o yaml",
+            "Missing `--dry-run`: without it the command changes the cluster",
+        ),
+        (
+            "kubectl expose deploy web --port=80",
+            "kubectl expose deploy web --port=80 --target-port=8080",
+            "Missing `--target-port`",
+        ),
+    ];
+    for (submitted, expected, tip) in cases {
+        assert_eq!(
+            diagnose(submitted, expected).as_deref(),
+            Some(tip),
+            "{} for {}",
+            submitted,
+            expected
+        );
+    }
+}
+
+#[test]
+fn diagnose_resolves_aliases_before_comparing() {
+    assert_eq!(
+        diagnose(
+            "k create deploy web --image=nginx",
+            "kubectl create deployment web --image=nginx --replicas=2"
+        ),
+        Some("Missing `--replicas`: without it you get a single replica".to_string())
+    );
+    assert_eq!(
+        diagnose(
+            "k run web --image=nginx --namespace=prod",
+            "kubectl run web --image=nginx -n dev"
+        ),
+        None,
+        "a wrong namespace value is left to the answer"
+    );
+}
+
+#[test]
+fn diagnose_has_no_tip_outside_single_kubectl_commands() {
+    let cases = [
+        (
+            "kubectl run web --image=nginx",
+            "kubectl run web --image=nginx",
+        ),
+        ("helm install web", "kubectl run web --image=nginx"),
+        ("kubectl run web", "kubectl get pods | grep web"),
+        (
+            "kubectl create ns prod",
+            "kubectl create ns prod\nkubectl run web --image=nginx -n prod",
+        ),
+        ("kubectl get pods web", "kubectl get pods api"),
+    ];
+    for (submitted, expected) in cases {
+        assert_eq!(diagnose(submitted, expected), None, "{}", submitted);
+    }
+}
@@ -0,0 +1,90 @@
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::{JumpError, QuestionStatus, QuizState};
+
+/// Question 3 depends on question 2; question 1 is where the session starts
+fn questions() -> Vec<Question> {
+    (1..=3)
+        .map(|id| Question {
+            id,
+            question: format!("Question {}", id),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 60,
+            depends_on: (id == 3).then(|| vec![2]),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn guided() -> QuizState {
+    let config = QuizConfig {
+        guided: true,
+        ..QuizConfig::default()
+    };
+    QuizState::new(questions(), &config)
+}
+
+fn blocked() -> Result<(), JumpError> {
+    Err(JumpError::Blocked {
+        id: 3,
+        pending: vec![2],
+    })
+}
+
+#[test]
+fn a_jump_waits_for_the_prerequisite_to_pass() {
+    let mut quiz = guided();
+    assert_eq!(quiz.jump_to_id(3), blocked());
+    assert_eq!(quiz.current_index(), 0);
+
+    quiz.jump_to_id(2).unwrap();
+    quiz.submit_answer("kubectl get pods".to_string(), true);
+    quiz.jump_to_id(3).unwrap();
+    assert_eq!(quiz.current_index(), 2);
+}
+
+#[test]
+fn skipping_the_prerequisite_does_not_unlock_it() {
+    let mut quiz = guided();
+    // Jumping away leaves question 2 skipped
+    quiz.jump_to_id(2).unwrap();
+    quiz.jump_to_id(1).unwrap();
+    assert_eq!(quiz.records()[1].status, QuestionStatus::Skipped);
+    assert_eq!(quiz.jump_to_id(3), blocked());
+    assert_eq!(quiz.pending_prerequisites(2), [2]);
+
+    quiz.jump_to_id(2).unwrap();
+    quiz.submit_answer("kubectl get svc".to_string(), false);
+    assert_eq!(quiz.jump_to_id(3), blocked());
+}
+
+#[test]
+fn a_revealed_prerequisite_counts_as_done() {
+    let config = QuizConfig {
+        guided: true,
+        mode: Mode::Study,
+        ..QuizConfig::default()
+    };
+    let mut quiz = QuizState::new(questions(), &config);
+    quiz.jump_to_id(2).unwrap();
+    quiz.jump_to_id(1).unwrap();
+    assert_eq!(quiz.records()[1].status, QuestionStatus::Revealed);
+    assert!(quiz.pending_prerequisites(2).is_empty());
+    quiz.jump_to_id(3).unwrap();
+}
+
+#[test]
+fn without_guided_mode_any_jump_goes_through() {
+    let mut quiz = QuizState::new(questions(), &QuizConfig::default());
+    quiz.jump_to_id(3).unwrap();
+    assert_eq!(quiz.current_index(), 2);
+    assert_eq!(quiz.jump_to_id(9), Err(JumpError::UnknownId(9)));
+}
+
+#[test]
+fn the_refusal_names_the_pending_ids() {
+    assert_eq!(
+        blocked().unwrap_err().to_string(),
+        "question #3 needs #2 completed first"
+    );
+}
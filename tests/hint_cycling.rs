@@ -0,0 +1,82 @@
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::HintState;
+use ckad_practitioner::session::{Session, SessionAction};
+
+fn shown(max_hints: usize, requests: usize) -> HintState {
+    let mut state = HintState::new(true);
+    state.enable_hints();
+    for _ in 0..requests {
+        state.cycle_hint(max_hints);
+    }
+    state
+}
+
+#[test]
+fn cycling_wraps_from_the_last_hint_to_the_first() {
+    let indexes: Vec<usize> = (0..7)
+        .map(|requests| shown(3, requests).hint_index())
+        .collect();
+    assert_eq!(indexes, [0, 1, 2, 0, 1, 2, 0]);
+}
+
+#[test]
+fn a_single_hint_cycles_onto_itself() {
+    assert_eq!(shown(1, 1).hint_index(), 0);
+    assert_eq!(shown(1, 4).hint_index(), 0);
+}
+
+#[test]
+fn cycling_without_hints_stays_put() {
+    let state = shown(0, 3);
+    assert_eq!(state.hint_index(), 0);
+    assert_eq!(state.hints_viewed(), 1);
+}
+
+#[test]
+fn next_hint_stops_at_the_last_instead() {
+    let mut state = HintState::new(true);
+    state.enable_hints();
+    for _ in 0..5 {
+        state.next_hint(3);
+    }
+    assert_eq!(state.hint_index(), 2);
+    state.next_hint(0);
+    assert_eq!(state.hint_index(), 2);
+}
+
+fn question(hints: usize) -> Question {
+    Question {
+        id: 1,
+        question: "List the pods".to_string(),
+        answer: "kubectl get pods".to_string(),
+        hints: (1..=hints).map(|hint| format!("Hint {}", hint)).collect(),
+        time_limit_secs: 60,
+        ..Default::default()
+    }
+}
+
+fn hint_indexes(hints: usize, wrap_hints: bool, requests: usize) -> Vec<usize> {
+    let config = QuizConfig {
+        wrap_hints,
+        ..QuizConfig::default()
+    };
+    let mut session = Session::new(vec![question(hints)], config).unwrap();
+    (0..requests)
+        .map(|_| {
+            let _ = session.apply(SessionAction::Hint);
+            session.hint_state().hint_index()
+        })
+        .collect()
+}
+
+#[test]
+fn wrap_hints_cycles_the_session_back_to_the_first_hint() {
+    assert_eq!(hint_indexes(2, true, 5), [0, 1, 0, 1, 0]);
+    assert_eq!(hint_indexes(2, false, 5), [0, 1, 1, 1, 1]);
+}
+
+#[test]
+fn a_question_without_hints_has_nothing_to_cycle() {
+    assert_eq!(hint_indexes(0, true, 3), [0, 0, 0]);
+}
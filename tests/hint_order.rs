@@ -0,0 +1,102 @@
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::shuffle_hints;
+use ckad_practitioner::scheduler::{seeded_rng, select_questions};
+
+const GIVEAWAY: &str = "Run: kubectl run web --image=nginx";
+
+fn question(answer: &str) -> Question {
+    Question {
+        id: 1,
+        question: "Run a pod named web".to_string(),
+        answer: answer.to_string(),
+        hints: vec![
+            GIVEAWAY.to_string(),
+            "Use kubectl run".to_string(),
+            "The image is nginx".to_string(),
+            "Name it web".to_string(),
+            "Pods are the smallest unit".to_string(),
+        ],
+        time_limit_secs: 60,
+        ..Default::default()
+    }
+}
+
+fn shuffled(answer: &str, seed: u64) -> Vec<String> {
+    let mut question = question(answer);
+    shuffle_hints(&mut question, &mut seeded_rng(Some(seed)));
+    question.hints
+}
+
+fn sorted(mut hints: Vec<String>) -> Vec<String> {
+    hints.sort();
+    hints
+}
+
+#[test]
+fn shuffling_keeps_every_hint_once() {
+    let original = question("kubectl run web --image=nginx").hints;
+    for seed in 0..20 {
+        let hints = shuffled("kubectl run web --image=nginx", seed);
+        assert_eq!(sorted(hints), sorted(original.clone()), "seed {}", seed);
+    }
+}
+
+#[test]
+fn the_hint_that_gives_the_answer_away_stays_last() {
+    for seed in 0..20 {
+        let hints = shuffled("kubectl run web --image=nginx", seed);
+        assert_eq!(
+            hints.last().map(String::as_str),
+            Some(GIVEAWAY),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn the_same_seed_gives_the_same_order() {
+    let answer = "kubectl run web --image=nginx";
+    assert_eq!(shuffled(answer, 5), shuffled(answer, 5));
+    let orders: Vec<Vec<String>> = (0..20).map(|seed| shuffled(answer, seed)).collect();
+    assert!(orders.iter().any(|order| order != &orders[0]));
+}
+
+#[test]
+fn an_empty_answer_gives_nothing_away() {
+    let original = question("").hints;
+    for answer in ["", "   "] {
+        let orders: Vec<Vec<String>> = (0..20).map(|seed| shuffled(answer, seed)).collect();
+        for order in &orders {
+            assert_eq!(sorted(order.clone()), sorted(original.clone()));
+        }
+        // Every hint moves, not just the ones that don't contain ""
+        assert!(
+            orders.iter().any(|order| order.last() != original.last()),
+            "{:?}",
+            answer
+        );
+        assert!(orders.iter().any(|order| order[0] != original[0]));
+    }
+}
+
+#[test]
+fn the_scheduler_shuffles_hints_when_asked() {
+    let config = QuizConfig {
+        shuffle_hints: true,
+        seed: Some(5),
+        ..QuizConfig::default()
+    };
+    let selected = select_questions(vec![question("kubectl run web --image=nginx")], &config);
+    assert_eq!(
+        selected[0].hints,
+        shuffled("kubectl run web --image=nginx", 5)
+    );
+
+    let unshuffled = select_questions(
+        vec![question("kubectl run web --image=nginx")],
+        &QuizConfig::default(),
+    );
+    assert_eq!(unshuffled[0].hints, question("").hints);
+}
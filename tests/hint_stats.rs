@@ -0,0 +1,157 @@
+mod common;
+
+use chrono::Utc;
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::keymap::Action;
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::results::{QuestionResult, SessionResult};
+use ckad_practitioner::settings::{FileConfig, Settings};
+use ckad_practitioner::stats::{self, HintStats, HintUsage};
+use clap::Parser;
+use common::{key_event, new_app, run_script};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+
+fn attempt(uid: &str, tags: &[&str], hints_viewed: Vec<usize>) -> QuestionResult {
+    QuestionResult {
+        id: 1,
+        uid: uid.to_string(),
+        question: format!("Question {}", uid),
+        expected_answer: "kubectl get pods".to_string(),
+        domain: None,
+        outcome: QuestionStatus::Fail,
+        time_spent_secs: 30,
+        hints_used: hints_viewed.len(),
+        hints_viewed,
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        answer_submitted: None,
+        attempts: Vec::new(),
+        partial_credit: None,
+        points: 0.0,
+        confidence: None,
+    }
+}
+
+fn session(questions: Vec<QuestionResult>) -> SessionResult {
+    SessionResult {
+        started_at: Utc::now(),
+        complete: true,
+        questions,
+        ..new_app().session_result()
+    }
+}
+
+#[test]
+fn hint_usage_is_counted_per_question_and_per_tag() {
+    let history = vec![
+        session(vec![
+            attempt("netpol-1", &["networkpolicy"], vec![0, 1]),
+            attempt("pods-1", &["pods"], vec![]),
+        ]),
+        session(vec![
+            attempt("netpol-1", &["networkpolicy"], vec![0]),
+            attempt("netpol-2", &["networkpolicy"], vec![]),
+        ]),
+    ];
+    let stats = HintStats::from_history(&history);
+
+    let netpol = &stats.questions["netpol-1"];
+    assert_eq!(
+        netpol.usage,
+        HintUsage {
+            attempts: 2,
+            with_hints: 2
+        }
+    );
+    assert_eq!(netpol.by_hint.get(&0), Some(&2));
+    assert_eq!(netpol.by_hint.get(&1), Some(&1));
+    assert_eq!(stats.tags["networkpolicy"].with_hints, 2);
+    assert_eq!(stats.tags["networkpolicy"].attempts, 3);
+    assert_eq!(stats.tags["pods"].rate(), 0.0);
+    assert_eq!(stats.question("unknown"), HintUsage::default());
+}
+
+#[test]
+fn results_without_hint_positions_count_their_first_hints() {
+    let mut old = attempt("pods-1", &[], vec![]);
+    old.hints_used = 2;
+    let mut pending = attempt("pods-1", &[], vec![0]);
+    pending.outcome = QuestionStatus::Pending;
+
+    let stats = HintStats::from_history(&[session(vec![old, pending])]);
+    let pods = &stats.questions["pods-1"];
+    assert_eq!(pods.usage.attempts, 1);
+    assert_eq!(pods.by_hint.keys().copied().collect::<Vec<_>>(), vec![0, 1]);
+}
+
+#[test]
+fn the_report_leads_with_the_tags_that_needed_hints_most() {
+    let history = vec![session(vec![
+        attempt("netpol-1", &["networkpolicy"], vec![0]),
+        attempt("netpol-2", &["networkpolicy"], vec![1]),
+        attempt("pods-1", &["pods"], vec![]),
+    ])];
+    let mut out = Vec::new();
+    stats::write_hint_report(&mut out, &HintStats::from_history(&history)).unwrap();
+    let report = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "Hint usage by tag");
+    assert_eq!(
+        lines[1],
+        "  You used hints on 100% of networkpolicy attempts (2 of 2)"
+    );
+    assert_eq!(lines[2], "  You used hints on 0% of pods attempts (0 of 1)");
+    assert!(
+        report.contains("Question netpol-2  (hints #2 ×1)"),
+        "{}",
+        report
+    );
+    assert!(!report.contains("Question pods-1"));
+}
+
+#[test]
+fn an_empty_history_says_so() {
+    let mut out = Vec::new();
+    stats::write_hint_report(&mut out, &HintStats::default()).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "No saved attempts yet\n");
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_session_records_which_hints_were_shown() {
+    let mut app = new_app();
+    app.dispatch(Action::Hint);
+    app.dispatch(Action::Hint);
+    // Already shown, so it isn't counted twice
+    app.dispatch(Action::Hint);
+    app.dispatch(Action::Skip);
+
+    let result = app.session_result();
+    assert_eq!(result.questions[0].hints_viewed, vec![0, 1]);
+    assert_eq!(result.questions[0].hints_used, 2);
+    assert!(result.questions[1].hints_viewed.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_summary_shows_each_questions_hint_rate() {
+    let mut earlier = new_app().session_result();
+    earlier.complete = true;
+    earlier.questions[0].outcome = QuestionStatus::Fail;
+    earlier.questions[0].hints_viewed = vec![];
+    let mut app = new_app().with_history(&[earlier]);
+    app.dispatch(Action::Hint);
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let skips = vec![Event::Key(key_event('s')), Event::Key(key_event('s'))];
+    let screen = run_script(&mut app, &mut terminal, skips).await;
+    assert!(screen.contains("Hints"), "{}", screen);
+    // One of two attempts at the first question used a hint, none of the second's
+    assert!(screen.contains("1 · 50%"), "{}", screen);
+    assert!(screen.contains("0 · 0%"), "{}", screen);
+}
+
+#[test]
+fn stats_is_a_command() {
+    let cli = Cli::parse_from(["ckad-practitioner", "stats"]);
+    let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+    assert!(settings.stats);
+}
@@ -0,0 +1,98 @@
+use ckad_practitioner::i18n::{self, Messages, LOCALES};
+use std::collections::BTreeSet;
+
+/// Placeholder names such as "number" in "Question {number} of {total}"
+fn placeholders(text: &str) -> BTreeSet<&str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn every_english_key_exists_in_every_shipped_locale() {
+    let english = i18n::parse_catalog(i18n::catalog_source("en").unwrap()).unwrap();
+    assert!(!english.is_empty());
+    for (language, contents) in LOCALES {
+        let catalog = i18n::parse_catalog(contents)
+            .unwrap_or_else(|err| panic!("{} does not parse: {}", language, err));
+        for (key, text) in &english {
+            let translated = catalog
+                .get(key)
+                .unwrap_or_else(|| panic!("{} is missing {}", language, key));
+            assert_eq!(
+                placeholders(translated),
+                placeholders(text),
+                "{} has different placeholders for {}",
+                language,
+                key
+            );
+        }
+        let extra: Vec<_> = catalog
+            .keys()
+            .filter(|key| !english.contains_key(*key))
+            .collect();
+        assert!(
+            extra.is_empty(),
+            "{} has unknown keys {:?}",
+            language,
+            extra
+        );
+    }
+}
+
+#[test]
+fn locale_names_select_the_language() {
+    assert_eq!(Messages::for_locale("de_DE.UTF-8").language(), "de");
+    assert_eq!(Messages::for_locale("de").language(), "de");
+    assert_eq!(Messages::for_locale("DE-at").language(), "de");
+    // Unshipped languages and the C locale fall back to English
+    assert_eq!(Messages::for_locale("fr_FR.UTF-8").language(), "en");
+    assert_eq!(Messages::for_locale("C").language(), "en");
+    assert_eq!(Messages::for_locale("").language(), "en");
+    assert_eq!(i18n::language_code("POSIX"), None);
+    assert_eq!(i18n::language_code("pt_BR@latin"), Some("pt".to_string()));
+}
+
+#[test]
+fn placeholders_are_filled_in() {
+    let messages = Messages::english();
+    assert_eq!(
+        messages.format(
+            "question_heading",
+            &[("number", &2), ("total", &5), ("question", &"Create a pod")]
+        ),
+        "Question 2 of 5: Create a pod"
+    );
+    let german = Messages::for_locale("de");
+    assert_eq!(
+        german.format("summary_score", &[("correct", &3), ("total", &4)]),
+        "Punkte: 3/4 richtig"
+    );
+}
+
+#[test]
+fn substituted_values_are_not_expanded_again() {
+    let messages = Messages::english();
+    assert_eq!(
+        messages.format(
+            "question_heading",
+            &[
+                ("number", &1),
+                ("total", &1),
+                ("question", &"Use {{.metadata.name}} and {total}")
+            ]
+        ),
+        "Question 1 of 1: Use {{.metadata.name}} and {total}"
+    );
+    // A placeholder without a value is left as written
+    assert_eq!(
+        messages.format("time_remaining", &[]),
+        "Time remaining: {time}"
+    );
+}
+
+#[test]
+fn unknown_keys_show_the_key() {
+    assert_eq!(Messages::english().get("no_such_key"), "no_such_key");
+}
@@ -0,0 +1,162 @@
+use chrono::Utc;
+use ckad_practitioner::anki;
+use ckad_practitioner::config::{DuplicateIds, Mode, QuizConfig};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::question_repository::{
+    DirectoryQuestionRepository, QuestionRepository, RepositoryError,
+};
+use ckad_practitioner::quiz_state::QuizState;
+use ckad_practitioner::results::SessionResult;
+use ckad_practitioner::scheduler;
+use ckad_practitioner::stats::{self, PersonalBests};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PODS: &str = "- id: 1\n  question: Create a pod named web\n  hints: []\n  answer: kubectl run web --image=nginx\n  time_limit_secs: 60\n";
+const SERVICES: &str = "- id: 1\n  question: Expose the web deployment on port 80\n  hints: []\n  answer: kubectl expose deployment web --port=80\n  time_limit_secs: 60\n";
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ckad-identity-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Loads a directory of packs the way a session does, duplicate ids renumbered
+fn load(dir: &Path) -> Vec<Question> {
+    let mut questions = DirectoryQuestionRepository::new(dir.to_path_buf())
+        .get_questions()
+        .unwrap();
+    scheduler::check_ids(&mut questions, DuplicateIds::Renumber).unwrap();
+    questions
+}
+
+/// A session passing the questions in `passed` and failing the rest
+fn session(questions: &[Question], passed: &[&str]) -> SessionResult {
+    let mut quiz = QuizState::new(questions.to_vec(), &QuizConfig::default());
+    for question in questions {
+        quiz.submit_answer(String::new(), passed.contains(&question.question.as_str()));
+        quiz.next_question();
+    }
+    SessionResult::from_quiz(&quiz, Mode::Practice, Utc::now(), true)
+}
+
+#[test]
+fn packs_with_overlapping_ids_keep_separate_stats() {
+    let dir = temp_dir("packs");
+    fs::write(dir.join("a-pods.yaml"), PODS).unwrap();
+    fs::write(dir.join("b-services.yaml"), SERVICES).unwrap();
+    let questions = load(&dir);
+    assert_eq!(
+        questions
+            .iter()
+            .map(|question| question.id)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_ne!(questions[0].identity(), questions[1].identity());
+
+    let history = vec![session(&questions, &["Create a pod named web"])];
+    let bests = PersonalBests::from_history(&history);
+    assert!(bests.best(&questions[0]).is_some());
+    assert!(bests.best(&questions[1]).is_none());
+
+    // Loaded the other way round the ids swap, but the history follows the question
+    fs::rename(dir.join("a-pods.yaml"), dir.join("c-pods.yaml")).unwrap();
+    let reordered = load(&dir);
+    assert_eq!(
+        reordered[0].question,
+        "Expose the web deployment on port 80"
+    );
+    assert_eq!(reordered[0].id, 1);
+    let bank = DirectoryQuestionRepository::new(dir.clone());
+    let (missed, gone) = anki::fetch_missed(&bank, &stats::missed_questions(&history)).unwrap();
+    assert!(gone.is_empty());
+    assert_eq!(missed.len(), 1);
+    assert_eq!(missed[0].id, 1);
+    assert_eq!(missed[0].question, "Expose the web deployment on port 80");
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn a_uid_outlives_edits_to_the_question_text() {
+    let before = Question {
+        id: 4,
+        uid: Some("pods/web".to_string()),
+        question: "Create a pod named web".to_string(),
+        time_limit_secs: 60,
+        ..Default::default()
+    };
+    let after = Question {
+        id: 9,
+        question: "Create a pod called web using nginx".to_string(),
+        ..before.clone()
+    };
+    let history = vec![session(std::slice::from_ref(&before), &[])];
+    assert_eq!(history[0].questions[0].uid, "pods/web");
+    assert!(stats::missed_questions(&history).contains(&after.identity()));
+
+    let untagged = Question {
+        uid: None,
+        question: "Create  a pod named\nweb".to_string(),
+        ..before.clone()
+    };
+    assert_eq!(
+        untagged.identity(),
+        Question {
+            uid: None,
+            ..before
+        }
+        .identity(),
+        "whitespace doesn't change a text identity"
+    );
+    assert!(untagged.identity().starts_with("text:"));
+}
+
+#[test]
+fn sessions_saved_before_identities_are_rekeyed_by_question_text() {
+    let dir = temp_dir("migrate");
+    let legacy = r#"{"started_at":"2024-05-01T10:00:00Z","finished_at":"2024-05-01T10:05:00Z","mode":"practice","complete":true,
+        "questions":[
+          {"id":1,"question":"Create a pod named web","expected_answer":"","domain":null,"outcome":"fail","time_spent_secs":30,"hints_used":0,"answer_submitted":null},
+          {"id":2,"question":"A question no bank has any more","expected_answer":"","domain":null,"outcome":"fail","time_spent_secs":30,"hints_used":0,"answer_submitted":null}
+        ],
+        "score":{"correct":0,"total":2,"percent":0.0},"domains":[]}"#;
+    let path = dir.join("session-20240501T100000Z.json");
+    fs::write(&path, legacy).unwrap();
+    let bank = vec![Question {
+        id: 7,
+        uid: Some("pods/web".to_string()),
+        question: "Create a pod named web".to_string(),
+        ..Default::default()
+    }];
+
+    assert_eq!(stats::migrate_history(&dir, &bank).unwrap(), 1);
+    let history = stats::load_history(&dir);
+    let rekeyed = &history[0].questions;
+    assert_eq!(rekeyed[0].uid, "pods/web");
+    assert_eq!(
+        rekeyed[1].uid, "",
+        "left alone until its question is loaded"
+    );
+    assert!(stats::missed_questions(&history).contains("pods/web"));
+
+    assert_eq!(stats::migrate_history(&dir, &bank).unwrap(), 0);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn a_uid_used_twice_refuses_to_load() {
+    let mut questions: Vec<Question> = (1..=3)
+        .map(|id| Question {
+            id,
+            uid: Some(if id == 2 { "other" } else { "shared" }.to_string()),
+            ..Default::default()
+        })
+        .collect();
+    let err = scheduler::check_ids(&mut questions, DuplicateIds::Renumber).unwrap_err();
+    assert!(matches!(&err, RepositoryError::DuplicateUids(uids) if uids == &["shared"]));
+    assert!(err
+        .to_string()
+        .starts_with("more than one question uses uid shared;"));
+}
@@ -0,0 +1,205 @@
+use ckad_practitioner::keymap::{Action, KeyBinding, KeyBindings, KeymapError, KeymapPreset};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+fn parse(descriptor: &str) -> KeyBinding {
+    KeyBinding::parse(descriptor).unwrap_or_else(|err| panic!("{}: {}", descriptor, err))
+}
+
+fn keys(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(action, key)| (action.to_string(), key.to_string()))
+        .collect()
+}
+
+fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+#[test]
+fn modifiers_combine_in_any_order_and_case() {
+    let expected = KeyBinding::new(
+        KeyCode::Char('n'),
+        KeyModifiers::CONTROL | KeyModifiers::ALT,
+    );
+    assert_eq!(parse("ctrl+alt+n"), expected);
+    assert_eq!(parse("alt+ctrl+n"), expected);
+    assert_eq!(parse("Control+ALT+n"), expected);
+    assert_eq!(
+        parse("ctrl+Up"),
+        KeyBinding::new(KeyCode::Up, KeyModifiers::CONTROL)
+    );
+}
+
+#[test]
+fn named_keys_ignore_case_and_have_aliases() {
+    for (descriptors, code) in [
+        (&["Esc", "escape", "ESC"][..], KeyCode::Esc),
+        (&["Enter", "return"], KeyCode::Enter),
+        (&["space", "Space"], KeyCode::Char(' ')),
+        (&["PageUp", "pgup"], KeyCode::PageUp),
+        (&["pagedown", "PgDn"], KeyCode::PageDown),
+        (&["del", "Delete"], KeyCode::Delete),
+        (&["F1", "f1"], KeyCode::F(1)),
+        (&["F12"], KeyCode::F(12)),
+    ] {
+        for descriptor in descriptors {
+            assert_eq!(parse(descriptor), KeyBinding::plain(code), "{}", descriptor);
+        }
+    }
+}
+
+#[test]
+fn single_characters_keep_their_case() {
+    assert_eq!(parse("j"), KeyBinding::plain(KeyCode::Char('j')));
+    assert_eq!(parse("J"), KeyBinding::plain(KeyCode::Char('J')));
+    assert_ne!(parse("j"), parse("J"));
+}
+
+#[test]
+fn shift_folds_into_the_key() {
+    assert_eq!(parse("shift+j"), parse("J"));
+    assert_eq!(parse("shift+Tab"), parse("BackTab"));
+    assert!(parse("J").matches(&press(KeyCode::Char('J'), KeyModifiers::SHIFT)));
+    assert!(parse("BackTab").matches(&press(KeyCode::BackTab, KeyModifiers::SHIFT)));
+    // Shift stays on keys without a shifted form
+    assert_eq!(
+        parse("shift+Up"),
+        KeyBinding::new(KeyCode::Up, KeyModifiers::SHIFT)
+    );
+}
+
+#[test]
+fn the_plus_key_is_written_plainly_or_after_a_double_plus() {
+    assert_eq!(parse("+"), KeyBinding::plain(KeyCode::Char('+')));
+    assert_eq!(
+        parse("ctrl++"),
+        KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+    );
+}
+
+#[test]
+fn invalid_descriptors_are_rejected_whole() {
+    for descriptor in [
+        "",
+        "ctrl+",
+        "hyper+n",
+        "ctrl+ctrl+n",
+        "F0",
+        "F13",
+        "Fx",
+        "jk",
+        "ctrl+nope",
+    ] {
+        assert_eq!(
+            KeyBinding::parse(descriptor),
+            Err(KeymapError::InvalidKey(descriptor.to_string())),
+            "{:?}",
+            descriptor
+        );
+    }
+}
+
+#[test]
+fn bindings_print_the_way_they_are_written() {
+    assert_eq!(parse("ctrl+alt+n").to_string(), "ctrl+alt+n");
+    assert_eq!(parse("space").to_string(), "Space");
+    assert_eq!(parse("shift+tab").to_string(), "Shift+Tab");
+    assert_eq!(parse("pgdn").to_string(), "PgDn");
+    assert_eq!(parse("f5").to_string(), "F5");
+}
+
+#[test]
+fn an_override_replaces_the_preset_keys_for_its_action() {
+    let bindings = KeyBindings::default()
+        .with_overrides(&keys(&[("hint", "?")]))
+        .unwrap();
+    let question = press(KeyCode::Char('?'), KeyModifiers::NONE);
+    assert_eq!(bindings.action_for(&question), Some(Action::Hint));
+    assert_eq!(
+        bindings.action_for(&press(KeyCode::Char('h'), KeyModifiers::NONE)),
+        None
+    );
+    assert_eq!(bindings.label(Action::Hint).as_deref(), Some("?"));
+}
+
+#[test]
+fn a_preset_key_taken_by_another_action_moves_over() {
+    let bindings = KeyBindings::preset(KeymapPreset::Vim)
+        .with_overrides(&keys(&[("hint", "j")]))
+        .unwrap();
+    let j = press(KeyCode::Char('j'), KeyModifiers::NONE);
+    assert_eq!(bindings.action_for(&j), Some(Action::Hint));
+    // Scrolling keeps its other keys
+    assert_eq!(bindings.label(Action::ScrollDown).as_deref(), Some("Down"));
+}
+
+#[test]
+fn none_unbinds_an_action() {
+    let bindings = KeyBindings::default()
+        .with_overrides(&keys(&[("shell", "None")]))
+        .unwrap();
+    assert_eq!(bindings.label(Action::Shell), None);
+    assert_eq!(
+        bindings.action_for(&press(KeyCode::Char('!'), KeyModifiers::NONE)),
+        None
+    );
+}
+
+#[test]
+fn two_actions_on_one_key_are_a_conflict() {
+    let err = KeyBindings::default()
+        .with_overrides(&keys(&[("hint", "x"), ("skip", "x")]))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        KeymapError::Duplicate {
+            key: "x".to_string(),
+            first: "hint",
+            second: "skip",
+        }
+    );
+    // Spellings of the same key conflict too
+    let err = KeyBindings::default()
+        .with_overrides(&keys(&[("hint", "J"), ("skip", "shift+j")]))
+        .unwrap_err();
+    assert!(matches!(err, KeymapError::Duplicate { .. }), "{:?}", err);
+    assert_eq!(
+        err.to_string(),
+        "`shift+j` is bound to both `hint` and `skip`"
+    );
+}
+
+#[test]
+fn ctrl_c_stays_reserved_for_quitting() {
+    let err = KeyBindings::default()
+        .with_overrides(&keys(&[("hint", "ctrl+c")]))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        KeymapError::Reserved {
+            action: "hint".to_string(),
+            key: "ctrl+c".to_string(),
+        }
+    );
+    assert!(KeyBindings::default()
+        .with_overrides(&keys(&[("quit", "Ctrl+C")]))
+        .is_ok());
+}
+
+#[test]
+fn unknown_actions_and_bad_keys_are_errors() {
+    assert_eq!(
+        KeyBindings::default()
+            .with_overrides(&keys(&[("teleport", "t")]))
+            .unwrap_err(),
+        KeymapError::UnknownAction("teleport".to_string())
+    );
+    assert_eq!(
+        KeyBindings::default()
+            .with_overrides(&keys(&[("hint", "ctrl+")]))
+            .unwrap_err(),
+        KeymapError::InvalidKey("ctrl+".to_string())
+    );
+}
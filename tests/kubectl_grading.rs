@@ -0,0 +1,291 @@
+use ckad_practitioner::grading::{Difference, Verdict};
+use ckad_practitioner::kubectl::{
+    server_dry_run_args, split_command, ConfirmedContexts, KubectlError, KubectlRunner,
+};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::scorer::AnswerScorer;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Canned `kubectl --dry-run=client -o yaml` output keyed on the image
+#[derive(Debug, Default)]
+struct FakeKubectl {
+    installed: bool,
+    calls: AtomicUsize,
+}
+
+impl FakeKubectl {
+    fn installed() -> Arc<Self> {
+        Arc::new(Self {
+            installed: true,
+            ..Self::default()
+        })
+    }
+}
+
+impl KubectlRunner for FakeKubectl {
+    fn run(&self, args: &[String]) -> Result<String, KubectlError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        if !self.installed {
+            return Err(KubectlError::NotInstalled);
+        }
+        assert!(args.ends_with(&["--dry-run=client".into(), "-o".into(), "yaml".into()]));
+        let image = args
+            .iter()
+            .zip(args.iter().skip(1))
+            .find_map(|(flag, next)| match flag.strip_prefix("--image") {
+                Some("") => Some(next.as_str()),
+                Some(value) => value.strip_prefix('='),
+                None => None,
+            })
+            .ok_or_else(|| KubectlError::Failed("required flag(s) \"image\" not set".into()))?;
+        // Timestamps differ per call, as they do for real
+        Ok(format!(
+            "apiVersion: v1\nkind: Pod\nmetadata:\n  creationTimestamp: \"2024-01-01T00:00:0{}Z\"\n  \
+             labels:\n    run: nginx\n  name: nginx\nspec:\n  containers:\n  - image: {}\n    \
+             name: nginx\n    resources: {{}}\nstatus: {{}}\n",
+            call % 10,
+            image
+        ))
+    }
+}
+
+fn pod_question() -> Question {
+    Question {
+        id: 1,
+        question: "Create a Pod named nginx using the nginx:1.14 image".to_string(),
+        answer: "kubectl run nginx --image=nginx:1.14".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn equivalent_flag_spellings_render_the_same_manifest() {
+    let question = pod_question();
+    // run=nginx is the label kubectl run sets anyway
+    let submitted = "kubectl run nginx --image nginx:1.14 --labels=run=nginx";
+    assert!(!AnswerScorer::default().is_correct(&question, submitted));
+
+    let scorer = AnswerScorer::default().with_kubectl(FakeKubectl::installed());
+    assert_eq!(
+        scorer.grade(&question, submitted),
+        Verdict::Equivalent(vec![Difference::SameManifest])
+    );
+}
+
+#[test]
+fn a_different_manifest_is_still_wrong() {
+    let scorer = AnswerScorer::default().with_kubectl(FakeKubectl::installed());
+    assert!(!scorer.is_correct(&pod_question(), "kubectl run nginx --image nginx:latest"));
+}
+
+#[test]
+fn kubectl_errors_fall_back_to_text_comparison() {
+    let kubectl = Arc::new(FakeKubectl::default());
+    let scorer = AnswerScorer::default().with_kubectl(kubectl.clone());
+    let question = pod_question();
+
+    assert!(scorer.is_correct(&question, "kubectl  run nginx --image=nginx:1.14"));
+    assert!(scorer.is_correct(&question, "kubectl run nginx --image nginx:1.14"));
+    assert_eq!(kubectl.calls.load(Ordering::SeqCst), 0);
+    assert!(!scorer.is_correct(
+        &question,
+        "kubectl run nginx --image=nginx:1.14 --labels=run=nginx"
+    ));
+    assert_eq!(kubectl.calls.load(Ordering::SeqCst), 1);
+
+    let installed = AnswerScorer::default().with_kubectl(FakeKubectl::installed());
+    assert!(!installed.is_correct(&question, "kubectl run nginx"));
+}
+
+#[test]
+fn only_plain_dry_runnable_commands_reach_kubectl() {
+    let kubectl = FakeKubectl::installed();
+    let scorer = AnswerScorer::default().with_kubectl(kubectl.clone());
+    let question = pod_question();
+
+    for submitted in [
+        "kubectl run nginx --image=nginx:1.14; rm -rf /",
+        "kubectl run nginx --image=$(whoami)",
+        "kubectl delete pod nginx",
+        "docker run nginx:1.14",
+    ] {
+        assert!(!scorer.is_correct(&question, submitted), "{}", submitted);
+    }
+    assert_eq!(kubectl.calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn split_command_handles_quotes_and_rejects_shell_syntax() {
+    assert_eq!(
+        split_command(r#"kubectl create cm app --from-literal='greeting=hello world' -l "a=b""#),
+        Some(
+            [
+                "kubectl",
+                "create",
+                "cm",
+                "app",
+                "--from-literal=greeting=hello world",
+                "-l",
+                "a=b"
+            ]
+            .map(String::from)
+            .to_vec()
+        )
+    );
+    assert_eq!(split_command("kubectl get pods | wc -l"), None);
+    assert_eq!(split_command("kubectl run 'unterminated"), None);
+}
+
+/// `kubectl apply --dry-run=server` against a pretend API server
+#[derive(Debug)]
+struct FakeServer {
+    reply: fn(&str) -> Result<String, KubectlError>,
+    /// Every manifest file it was given, with the contents at the time
+    seen: Mutex<Vec<(PathBuf, String)>>,
+}
+
+impl FakeServer {
+    fn new(reply: fn(&str) -> Result<String, KubectlError>) -> Arc<Self> {
+        Arc::new(Self {
+            reply,
+            seen: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        self.seen.lock().unwrap().len()
+    }
+}
+
+impl KubectlRunner for FakeServer {
+    fn run(&self, args: &[String]) -> Result<String, KubectlError> {
+        assert_eq!(&args[..3], ["apply", "--dry-run=server", "-f"]);
+        let path = PathBuf::from(&args[3]);
+        let manifest = std::fs::read_to_string(&path).expect("manifest file exists during the run");
+        self.seen.lock().unwrap().push((path, manifest.clone()));
+        (self.reply)(&manifest)
+    }
+}
+
+/// Rejects pods without a container image, like a validating webhook might
+fn validating(manifest: &str) -> Result<String, KubectlError> {
+    if manifest.contains("image:") {
+        Ok("pod/web created (server dry run)\n".to_string())
+    } else {
+        Err(KubectlError::Failed(
+            "Error from server (Forbidden): error when creating \"pod.yaml\":\n  admission webhook \"images.example.com\" denied the request: image is required\n".to_string(),
+        ))
+    }
+}
+
+fn unreachable(_: &str) -> Result<String, KubectlError> {
+    Err(KubectlError::Failed(
+        "The connection to the server localhost:8080 was refused - did you specify the right host or port?".to_string(),
+    ))
+}
+
+fn manifest_question() -> Question {
+    Question {
+        id: 7,
+        question: "Write a pod named web".to_string(),
+        answer: "apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\nspec:\n  containers:\n  - name: web\n    image: nginx\n".to_string(),
+        yaml_answer: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn server_rejections_fail_the_answer_with_the_servers_message() {
+    let server = FakeServer::new(validating);
+    let scorer = AnswerScorer::default().with_server_dry_run(server.clone());
+    let submitted =
+        "apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\nspec:\n  containers:\n  - name: web\n";
+    let verdict = scorer.grade(&manifest_question(), submitted);
+    assert_eq!(
+        verdict,
+        Verdict::Rejected(
+            "Error from server (Forbidden): error when creating \"pod.yaml\": admission webhook \"images.example.com\" denied the request: image is required".to_string()
+        )
+    );
+    assert!(!verdict.is_correct());
+
+    // The manifest went through a temporary file, gone afterwards
+    let seen = server.seen.lock().unwrap();
+    assert_eq!(seen[0].1, submitted);
+    assert!(!seen[0].0.exists());
+}
+
+#[test]
+fn accepted_manifests_are_still_compared_with_the_model_answer() {
+    let server = FakeServer::new(validating);
+    let scorer = AnswerScorer::default().with_server_dry_run(server.clone());
+    let question = manifest_question();
+    let reordered = "kind: Pod\napiVersion: v1\nmetadata: {name: web}\nspec:\n  containers:\n  - {image: nginx, name: web}\n";
+    assert_eq!(scorer.grade(&question, reordered), Verdict::Equal);
+    assert_eq!(
+        scorer.grade(&question, &question.answer.replace("nginx", "httpd")),
+        Verdict::Different
+    );
+    assert_eq!(server.calls(), 2);
+}
+
+#[test]
+fn no_cluster_or_no_kubectl_falls_back_to_yaml_comparison() {
+    let question = manifest_question();
+    let unreachable = AnswerScorer::default().with_server_dry_run(FakeServer::new(unreachable));
+    assert_eq!(
+        unreachable.grade(&question, &question.answer),
+        Verdict::Equal
+    );
+
+    let missing = AnswerScorer::default().with_server_dry_run(Arc::new(FakeKubectl::default()));
+    assert_eq!(missing.grade(&question, &question.answer), Verdict::Equal);
+}
+
+#[test]
+fn only_manifest_answers_reach_the_server() {
+    let server = FakeServer::new(validating);
+    let scorer = AnswerScorer::default().with_server_dry_run(server.clone());
+    assert!(scorer.is_correct(&pod_question(), "kubectl run nginx --image=nginx:1.14"));
+    // Not YAML at all
+    assert!(!scorer.is_correct(&manifest_question(), ""));
+    assert_eq!(server.calls(), 0);
+}
+
+#[test]
+fn dry_run_args_always_stay_dry_runs() {
+    let args = server_dry_run_args(Path::new("/tmp/pod.yaml"));
+    assert_eq!(args, ["apply", "--dry-run=server", "-f", "/tmp/pod.yaml"]);
+}
+
+#[test]
+fn contexts_are_confirmed_once_and_remembered() {
+    let dir = std::env::temp_dir().join(format!("ckad-contexts-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let contexts = ConfirmedContexts::new(&dir);
+
+    let mut out = Vec::new();
+    let declined = contexts
+        .confirm("prod", &mut Cursor::new("n\n"), &mut out)
+        .unwrap();
+    assert!(!declined);
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .contains("against context 'prod'"));
+    assert!(!contexts.contains("prod"));
+
+    assert!(contexts
+        .confirm("kind-ckad", &mut Cursor::new("y\n"), &mut Vec::new())
+        .unwrap());
+    // Remembered: no question the second time
+    let mut out = Vec::new();
+    assert!(contexts
+        .confirm("kind-ckad", &mut Cursor::new(""), &mut out)
+        .unwrap());
+    assert!(out.is_empty());
+    assert!(!contexts.contains("prod"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,116 @@
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::list::{self, ListOptions, ListSort};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::print::PrintFormat;
+use ckad_practitioner::scheduler;
+use ckad_practitioner::settings::{FileConfig, Settings};
+use clap::Parser;
+
+const BANK: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/list-bank.yaml");
+
+/// Parses `args` after the program name and returns the bank as `list` sees it
+fn listed(args: &[&str]) -> (Vec<Question>, ListOptions) {
+    let cli = Cli::try_parse_from(
+        ["ckad-practitioner"]
+            .into_iter()
+            .chain(args.iter().copied())
+            .chain(["--file", BANK]),
+    )
+    .expect("arguments parse");
+    let settings = Settings::resolve(&cli, &FileConfig::default()).unwrap();
+    let questions = scheduler::build_session(settings.repository().as_ref(), &settings.quiz)
+        .expect("fixture bank loads");
+    (questions, settings.list.expect("list command"))
+}
+
+fn render(questions: &[Question], options: ListOptions) -> String {
+    let mut out = Vec::new();
+    list::write_list(&mut out, questions, options).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn table_lists_every_column_and_a_summary() {
+    let (questions, options) = listed(&["list"]);
+    assert_eq!(
+        render(&questions, options),
+        "\
+ID  QUESTION                                            TAGS                 DIFFICULTY  TIME
+1   Create a pod named alpha running nginx              pods                 easy        1:00
+2   Create a CronJob named report that runs every f...  jobs,pods            hard        3:00
+3   Expose the deployment web on port 80 using a Cl...  services,networking  medium      2:00
+4   Create a namespace named beta                       namespaces           -           0:30
+
+4 questions (1 easy, 1 medium, 1 hard, 1 unrated), 6:30 total
+"
+    );
+}
+
+#[test]
+fn tags_and_limit_filter_like_the_quiz() {
+    let (questions, options) = listed(&["list", "--tags", "pods"]);
+    let table = render(&questions, options);
+    assert!(table.contains("alpha"));
+    assert!(table.contains("CronJob"));
+    assert!(!table.contains("namespace named beta"));
+    assert!(table.ends_with("2 questions (1 easy, 1 hard), 4:00 total\n"));
+
+    // The limit applies in bank order, before the listing is sorted
+    let (questions, _) = listed(&["list", "--limit", "2"]);
+    let ids: Vec<usize> = questions.iter().map(|question| question.id).collect();
+    assert_eq!(ids, vec![3, 1]);
+}
+
+#[test]
+fn sort_orders_by_time_or_difficulty() {
+    let ids = |sort: &str| {
+        let (mut questions, options) = listed(&["list", "--sort", sort]);
+        list::sort_questions(&mut questions, options.sort);
+        questions
+            .iter()
+            .map(|question| question.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids("id"), vec![1, 2, 3, 4]);
+    assert_eq!(ids("time"), vec![4, 1, 3, 2]);
+    // Unrated questions go last
+    assert_eq!(ids("difficulty"), vec![1, 3, 2, 4]);
+}
+
+#[test]
+fn json_keeps_the_full_question_text() {
+    let (questions, options) = listed(&["list-questions", "--format", "json", "--sort", "time"]);
+    assert_eq!(
+        options,
+        ListOptions {
+            format: PrintFormat::Json,
+            sort: ListSort::Time,
+        }
+    );
+    let json: serde_json::Value = serde_json::from_str(&render(&questions, options)).unwrap();
+    let rows = json.as_array().unwrap();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0]["id"], 4);
+    assert_eq!(rows[0]["difficulty"], serde_json::Value::Null);
+    assert_eq!(rows[2]["difficulty"], "medium");
+    assert_eq!(
+        rows[2]["question"],
+        "Expose the deployment web on port 80 using a ClusterIP service named web-svc in the shop namespace"
+    );
+    assert_eq!(rows[3]["tags"], serde_json::json!(["jobs", "pods"]));
+}
+
+#[test]
+fn summary_leaves_out_an_all_unrated_breakdown() {
+    let question = |id| Question {
+        id,
+        question: "Create a pod".to_string(),
+        time_limit_secs: 45,
+        ..Default::default()
+    };
+    assert_eq!(
+        list::summary(&[question(1), question(2)]),
+        "2 questions, 1:30 total"
+    );
+    assert_eq!(list::summary(&[question(1)]), "1 question, 0:45 total");
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::events::ScriptedEvents;
+use ckad_practitioner::logging::{self, LogSettings};
+use ckad_practitioner::settings::{FileConfig, Settings};
+use clap::Parser;
+use common::{key_event, new_app};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::Level;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ckad-log-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn log_settings(args: &[&str]) -> LogSettings {
+    let cli = Cli::parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()));
+    Settings::resolve(&cli, &FileConfig::default()).unwrap().log
+}
+
+#[test]
+fn logging_is_off_without_a_flag_or_rust_log() {
+    assert_eq!(log_settings(&[]).directives(None), None);
+}
+
+#[test]
+fn debug_and_log_file_log_this_crate_at_debug() {
+    for args in [&["--debug"][..], &["--log-file", "/tmp/quiz.log"]] {
+        assert_eq!(
+            log_settings(args).directives(None).as_deref(),
+            Some("ckad_practitioner=debug"),
+            "{:?}",
+            args
+        );
+    }
+}
+
+#[test]
+fn rust_log_is_used_as_given() {
+    let settings = log_settings(&[]);
+    assert_eq!(
+        settings.directives(Some("hyper=trace")).as_deref(),
+        Some("hyper=trace")
+    );
+    assert_eq!(
+        log_settings(&["--debug"])
+            .directives(Some("ckad_practitioner=trace"))
+            .as_deref(),
+        Some("ckad_practitioner=trace")
+    );
+}
+
+#[test]
+fn log_level_overrides_rust_log() {
+    let settings = log_settings(&["--log-level", "warn"]);
+    assert_eq!(settings.level, Some(Level::WARN));
+    assert_eq!(
+        settings.directives(Some("trace")).as_deref(),
+        Some("ckad_practitioner=warn")
+    );
+    assert_eq!(
+        settings.directives(None).as_deref(),
+        Some("ckad_practitioner=warn")
+    );
+}
+
+#[test]
+fn the_log_goes_to_the_data_dir_unless_a_file_is_given() {
+    let data_dir = Path::new("/data");
+    assert_eq!(
+        log_settings(&["--debug"]).path(data_dir),
+        Path::new("/data/debug.log")
+    );
+    assert_eq!(
+        log_settings(&["--log-file", "/tmp/quiz.log"]).path(data_dir),
+        Path::new("/tmp/quiz.log")
+    );
+}
+
+// The subscriber is process-wide, so everything that installs it is one test
+#[tokio::test(start_paused = true)]
+async fn the_log_file_is_written_only_when_enabled() {
+    let disabled = temp_dir("disabled");
+    assert_eq!(
+        logging::init_with(&LogSettings::default(), &disabled, None).unwrap(),
+        None
+    );
+    assert!(!disabled.exists());
+
+    let data_dir = temp_dir("enabled");
+    let settings = log_settings(&["--debug"]);
+    let path = logging::init_with(&settings, &data_dir, None)
+        .unwrap()
+        .expect("a log file");
+    assert_eq!(path, data_dir.join("debug.log"));
+
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    app.run_with(
+        &mut terminal,
+        ScriptedEvents::new(vec![Event::Key(key_event('h'))]),
+    )
+    .await
+    .unwrap();
+    tracing::debug!(target: "hyper::proto", "dependency record");
+
+    let log = fs::read_to_string(&path).unwrap();
+    assert!(log.contains("ckad_practitioner::app: key event"), "{}", log);
+    assert!(log.contains("action=\"hint\""), "{}", log);
+    assert!(!log.contains("dependency record"), "{}", log);
+    let _ = fs::remove_dir_all(&data_dir);
+}
@@ -0,0 +1,113 @@
+use ckad_practitioner::markdown_import::{self, ImportProblem};
+use ckad_practitioner::question_repository::{FileQuestionRepository, QuestionRepository};
+use std::fs;
+use std::path::Path;
+
+const FIXTURES: &str = "tests/fixtures/markdown";
+
+fn import(name: &str) -> markdown_import::Import {
+    markdown_import::import_path(&Path::new(FIXTURES).join(name)).unwrap()
+}
+
+fn problems(import: &markdown_import::Import) -> Vec<(usize, ImportProblem)> {
+    import
+        .issues
+        .iter()
+        .map(|issue| (issue.line, issue.problem.clone()))
+        .collect()
+}
+
+#[test]
+fn details_blocks_hold_the_answer_under_question_headings() {
+    let import = import("core-concepts.md");
+    let ids: Vec<usize> = import.questions.iter().map(|q| q.id).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    let first = &import.questions[0];
+    assert_eq!(
+        first.question,
+        "Create a namespace called 'mynamespace' and a pod with image nginx called nginx on this namespace"
+    );
+    assert_eq!(
+        first.answer,
+        "kubectl create namespace mynamespace\nkubectl run nginx --image=nginx --restart=Never -n mynamespace"
+    );
+    assert_eq!(first.category.as_deref(), Some("Core Concepts (13%)"));
+    assert_eq!(first.source.as_deref(), Some("core-concepts.md"));
+    assert_eq!(first.time_limit_secs, 120);
+    assert!(first.hints.is_empty());
+
+    let second = &import.questions[1];
+    assert_eq!(
+        second.hints,
+        vec![
+            "Generate the manifest with --dry-run=client -o yaml",
+            "Apply it with kubectl create -f",
+        ]
+    );
+    assert!(!second.yaml_answer);
+    assert!(second.answer.starts_with("kubectl run nginx"));
+    let notes = second.notes.as_deref().unwrap();
+    assert!(
+        notes.starts_with("Easily generate YAML with:\napiVersion: v1"),
+        "{}",
+        notes
+    );
+
+    assert_eq!(import.questions[2].answer, "kubectl get po nginx -o wide");
+    assert_eq!(
+        problems(&import),
+        vec![
+            (19, ImportProblem::MixedAnswer),
+            (48, ImportProblem::NoSolutionMarker),
+            (54, ImportProblem::NoAnswer),
+        ]
+    );
+}
+
+#[test]
+fn labelled_headings_take_the_question_from_the_paragraphs_below() {
+    let import = import("simulator.md");
+    assert_eq!(import.questions.len(), 2);
+
+    let first = &import.questions[0];
+    assert_eq!(
+        first.question,
+        "The DevOps team would like to get the list of all Namespaces in the cluster. Save it to /opt/course/1/namespaces."
+    );
+    assert_eq!(first.answer, "k get ns > /opt/course/1/namespaces");
+    assert_eq!(first.category.as_deref(), Some("CKAD Simulator"));
+
+    let second = &import.questions[1];
+    assert_eq!(second.hints.len(), 2);
+    assert!(second.yaml_answer);
+    assert!(second.answer.starts_with("apiVersion: v1\nkind: Pod"));
+    assert_eq!(
+        second.notes.as_deref(),
+        Some("Then run k create -f 2.yaml.")
+    );
+
+    assert_eq!(problems(&import), vec![(38, ImportProblem::NoQuestionText)]);
+    assert!(import.issues[0].problem.is_skipped());
+}
+
+#[test]
+fn a_directory_imports_to_a_bank_the_quiz_loads() {
+    let import = markdown_import::import_path(Path::new(FIXTURES)).unwrap();
+    let ids: Vec<usize> = import.questions.iter().map(|q| q.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    assert_eq!(import.questions[3].source.as_deref(), Some("simulator.md"));
+
+    let path = std::env::temp_dir().join(format!("ckad-import-md-{}.yaml", std::process::id()));
+    fs::write(&path, markdown_import::to_yaml(&import.questions).unwrap()).unwrap();
+    let loaded = FileQuestionRepository::new(path.display().to_string())
+        .get_questions()
+        .unwrap();
+    fs::remove_file(&path).ok();
+    assert_eq!(loaded.len(), 5);
+    for (loaded, imported) in loaded.iter().zip(&import.questions) {
+        assert_eq!(loaded.answer, imported.answer);
+        assert_eq!(loaded.hints, imported.hints);
+        assert_eq!(loaded.yaml_answer, imported.yaml_answer);
+    }
+}
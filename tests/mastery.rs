@@ -0,0 +1,159 @@
+mod common;
+
+use ckad_practitioner::app::App;
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::{Mastery, QuizConfig};
+use ckad_practitioner::mastery::{MasteryLoop, MasteryState};
+use ckad_practitioner::question_repository::RepositoryError;
+use ckad_practitioner::settings::{self, Settings};
+use clap::Parser;
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+use std::time::Duration;
+
+const RIGHT: &str = "kubectl run alpha --image=nginx";
+
+/// Drills question 1 of the mock bank until it's right `streak` times in a row
+fn drill(streak: usize) -> App {
+    new_app_with(QuizConfig {
+        mastery: Some(Mastery { id: 1, streak }),
+        ..QuizConfig::default()
+    })
+}
+
+/// Presses `a`, types `text` and submits it
+fn answer(text: &str) -> Vec<Event> {
+    let mut events = vec![Event::Key(key_event('a'))];
+    events.extend(text.chars().map(|c| Event::Key(key_event(c))));
+    events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+    events
+}
+
+#[test]
+fn a_wrong_attempt_resets_the_streak() {
+    let mut drill = MasteryLoop::new(3);
+    assert_eq!(drill.record(true), MasteryState::Passed);
+    assert!(drill.next_attempt());
+    assert_eq!(drill.record(true), MasteryState::Passed);
+    assert_eq!(drill.streak(), 2);
+    assert!(drill.next_attempt());
+    assert_eq!(drill.record(false), MasteryState::Failed);
+    assert_eq!(drill.streak(), 0);
+    assert_eq!(drill.attempts(), 3);
+}
+
+#[test]
+fn the_target_streak_ends_the_drill() {
+    let mut drill = MasteryLoop::new(2);
+    drill.record(true);
+    drill.next_attempt();
+    assert_eq!(drill.record(true), MasteryState::Mastered);
+    assert!(drill.is_mastered());
+    assert!(!drill.next_attempt());
+    assert_eq!(drill.attempts(), 2);
+}
+
+#[test]
+fn each_attempt_is_recorded_once() {
+    let mut drill = MasteryLoop::new(2);
+    drill.record(false);
+    assert_eq!(drill.record(true), MasteryState::Failed);
+    assert_eq!(drill.streak(), 0);
+    assert!(!MasteryLoop::new(0).is_mastered());
+    assert_eq!(MasteryLoop::new(0).target(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_miss_shows_the_answer_and_next_tries_again_with_a_fresh_clock() {
+    let mut app = drill(2);
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    assert_eq!(app.session().quiz_state().total_questions(), 1);
+
+    run_script(&mut app, &mut terminal, answer(RIGHT)).await;
+    assert_eq!(app.mastery().unwrap().streak(), 1);
+    assert!(!app.session().quiz_state().is_resolved());
+
+    tokio::time::advance(Duration::from_secs(12)).await;
+    let missed = run_script(&mut app, &mut terminal, answer("kubectl get pods")).await;
+    assert_eq!(app.mastery().unwrap().streak(), 0);
+    assert!(missed.contains(RIGHT), "{}", missed);
+    assert!(missed.contains("n: try again"), "{}", missed);
+    assert!(!missed.contains("Quiz complete"));
+
+    let retry = run_script(&mut app, &mut terminal, vec![Event::Key(key_event('n'))]).await;
+    assert!(!app.session().is_finished());
+    assert_eq!(app.session().quiz_state().timer().remaining().as_secs(), 30);
+    assert!(retry.contains("Streak 0 of 2 · attempt 3"), "{}", retry);
+}
+
+#[tokio::test(start_paused = true)]
+async fn skipping_and_running_out_of_time_count_as_misses() {
+    let mut app = drill(1);
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    run_script(&mut app, &mut terminal, vec![Event::Key(key_event('s'))]).await;
+    assert_eq!(app.mastery().unwrap().state(), MasteryState::Failed);
+    assert!(!app.session().is_finished());
+
+    run_script(&mut app, &mut terminal, vec![Event::Key(key_event('n'))]).await;
+    tokio::time::advance(Duration::from_secs(31)).await;
+    run_script(&mut app, &mut terminal, vec![]).await;
+    assert_eq!(app.mastery().unwrap().state(), MasteryState::Failed);
+    assert_eq!(app.mastery().unwrap().attempts(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn reaching_the_streak_ends_the_drill_on_the_summary() {
+    let mut app = drill(2);
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    run_script(&mut app, &mut terminal, answer(RIGHT)).await;
+    let mastered = run_script(&mut app, &mut terminal, answer(RIGHT)).await;
+    assert!(app.mastery().unwrap().is_mastered());
+    assert!(
+        mastered.contains("Mastered: 2 in a row after 2 attempts"),
+        "{}",
+        mastered
+    );
+
+    run_script(&mut app, &mut terminal, vec![Event::Key(key_event('n'))]).await;
+    assert!(app.session().is_finished());
+}
+
+#[tokio::test(start_paused = true)]
+async fn ordinary_sessions_have_no_drill() {
+    let app = new_app();
+    assert!(app.mastery().is_none());
+}
+
+#[test]
+fn an_unknown_question_id_matches_nothing() {
+    let repository = common::MockQuestionRepository;
+    let config = QuizConfig {
+        mastery: Some(Mastery { id: 9, streak: 3 }),
+        ..QuizConfig::default()
+    };
+    match ckad_practitioner::scheduler::build_session(&repository, &config) {
+        Err(RepositoryError::NoMatch { filters, .. }) => {
+            assert_eq!(filters, vec!["--master 9".to_string()])
+        }
+        other => panic!("expected no match, got {:?}", other),
+    }
+}
+
+#[test]
+fn the_streak_comes_from_the_flag_then_the_defaults_section() {
+    let (file, warnings) = settings::parse_file_config("[defaults]\nmastery_streak = 5\n").unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let cli = Cli::parse_from(["ckad-practitioner", "--master", "4"]);
+    let quiz = Settings::resolve(&cli, &file).unwrap().quiz;
+    assert_eq!(quiz.mastery, Some(Mastery { id: 4, streak: 5 }));
+
+    let cli = Cli::parse_from(["ckad-practitioner", "--master", "4", "--streak", "2"]);
+    let quiz = Settings::resolve(&cli, &file).unwrap().quiz;
+    assert_eq!(quiz.mastery, Some(Mastery { id: 4, streak: 2 }));
+
+    let cli = Cli::parse_from(["ckad-practitioner"]);
+    assert_eq!(Settings::resolve(&cli, &file).unwrap().quiz.mastery, None);
+    assert!(Cli::try_parse_from(["ckad-practitioner", "--streak", "2"]).is_err());
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::events::ScriptedEvents;
+use ckad_practitioner::stats::FinishProjection;
+use common::{key_event, new_app, new_app_with, screen_text};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+use std::time::Duration;
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn the_projection_extends_the_average_pace_to_the_questions_left() {
+    let projection = FinishProjection::project(&[secs(60), secs(120)], 4, secs(900)).unwrap();
+    assert_eq!(projection.estimated, secs(540));
+    assert!(!projection.is_over_budget());
+    assert_eq!(projection.margin(), secs(360));
+}
+
+#[test]
+fn a_slow_pace_runs_over_the_budget() {
+    let projection = FinishProjection::project(&[secs(300)], 2, secs(600)).unwrap();
+    assert_eq!(projection.estimated, secs(900));
+    assert!(projection.is_over_budget());
+    assert_eq!(projection.margin(), secs(300));
+}
+
+#[test]
+fn nothing_is_projected_before_a_question_is_completed() {
+    assert_eq!(FinishProjection::project(&[], 10, secs(600)), None);
+}
+
+#[test]
+fn with_every_question_done_the_projection_is_the_time_spent() {
+    let projection = FinishProjection::project(&[secs(30), secs(45)], 0, secs(120)).unwrap();
+    assert_eq!(projection.estimated, secs(75));
+    assert_eq!(projection.margin(), secs(45));
+}
+
+#[tokio::test(start_paused = true)]
+async fn exam_mode_shows_the_projection_after_the_first_question() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Exam,
+        ..QuizConfig::default()
+    });
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    app.run_with(&mut terminal, ScriptedEvents::new(vec![]))
+        .await
+        .unwrap();
+    assert!(!screen_text(&terminal).contains("At this pace"));
+
+    tokio::time::advance(secs(10)).await;
+    let skip = vec![Event::Key(key_event('s'))];
+    app.run_with(&mut terminal, ScriptedEvents::new(skip))
+        .await
+        .unwrap();
+    let screen = screen_text(&terminal);
+    // 10s for one question, so 20s of the 60s budget for both
+    assert!(
+        screen.contains("At this pace you'll finish with 0:40 to spare"),
+        "{}",
+        screen
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn practice_mode_has_no_projection() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let skip = vec![Event::Key(key_event('s'))];
+    app.run_with(&mut terminal, ScriptedEvents::new(skip))
+        .await
+        .unwrap();
+    assert!(!screen_text(&terminal).contains("At this pace"));
+}
@@ -0,0 +1,357 @@
+mod common;
+
+use ckad_practitioner::app::App;
+use ckad_practitioner::config::{DuplicateIds, QuizConfig};
+use ckad_practitioner::keymap::{Action, KeyBindings};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::paging::{self, PAGE_SIZE};
+use ckad_practitioner::question_repository::{
+    FileQuestionRepository, QuestionRepository, RepositoryError,
+};
+use ckad_practitioner::scheduler;
+use ckad_practitioner::stats::{self, PersonalBests};
+use ckad_practitioner::theme::Theme;
+use common::screen_text;
+use crossterm::event::Event;
+use futures_util::stream;
+use ratatui::{backend::TestBackend, Terminal};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Generates questions on demand, like a database would return them; loading
+/// the whole bank at once is a test failure. Pages after the first wait until
+/// `open` is set.
+struct SyntheticBank {
+    total: usize,
+    open: Arc<AtomicBool>,
+}
+
+impl SyntheticBank {
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            open: Arc::new(AtomicBool::new(true)),
+        }
+    }
+}
+
+impl QuestionRepository for SyntheticBank {
+    fn get_questions(&self) -> Result<Vec<Question>, RepositoryError> {
+        panic!("a paged bank must not be loaded whole");
+    }
+
+    fn source_description(&self) -> String {
+        "synthetic".to_string()
+    }
+
+    fn supports_paging(&self) -> bool {
+        true
+    }
+
+    fn count(&self) -> Result<usize, RepositoryError> {
+        Ok(self.total)
+    }
+
+    fn get_page(&self, offset: usize, len: usize) -> Result<Vec<Question>, RepositoryError> {
+        while offset > 0 && !self.open.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let end = self.total.min(offset + len);
+        Ok((offset..end)
+            .map(|index| Question {
+                id: index + 1,
+                question: format!("Synthetic question {}", index + 1),
+                hints: vec!["A hint".to_string()],
+                answer: "kubectl get pods".to_string(),
+                time_limit_secs: 60,
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+fn new_app(bank: SyntheticBank, config: QuizConfig) -> App {
+    App::new(
+        Box::new(bank),
+        Theme::new(false),
+        KeyBindings::default(),
+        config,
+        PersonalBests::default(),
+    )
+    .unwrap()
+}
+
+/// Runs the loop for `wait` of real time, so background pages can arrive
+async fn run_for(app: &mut App, terminal: &mut Terminal<TestBackend>, wait: Duration) -> String {
+    let idle = stream::once(async move {
+        tokio::time::sleep(wait).await;
+        Ok(Event::FocusGained)
+    });
+    app.run_with(terminal, Box::pin(idle)).await.unwrap();
+    screen_text(terminal)
+}
+
+#[tokio::test]
+async fn ten_thousand_questions_start_on_the_first_page() {
+    let started = Instant::now();
+    let app = new_app(SyntheticBank::new(10_000), QuizConfig::default());
+    let startup = started.elapsed();
+
+    assert!(
+        startup < Duration::from_millis(500),
+        "startup took {:?}",
+        startup
+    );
+    let quiz = app.session().quiz_state();
+    assert_eq!(quiz.questions().len(), PAGE_SIZE);
+    assert_eq!(quiz.total_questions(), 10_000);
+}
+
+#[tokio::test]
+async fn the_rest_loads_in_bank_order_up_to_the_limit() {
+    let config = QuizConfig {
+        limit: Some(1_050),
+        ..QuizConfig::default()
+    };
+    let bank: Arc<dyn QuestionRepository> = Arc::new(SyntheticBank::new(10_000));
+    let mut start = paging::start(bank, &config).unwrap();
+    assert_eq!(start.total, 1_050);
+
+    let mut ids: Vec<usize> = start.first.iter().map(|question| question.id).collect();
+    while let Some(page) = start.rest.recv().await {
+        ids.extend(page.unwrap().iter().map(|question| question.id));
+    }
+    assert_eq!(ids, (1..=1_050).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn the_header_says_loading_until_the_next_question_arrives() {
+    let bank = SyntheticBank::new(250);
+    let open = Arc::clone(&bank.open);
+    open.store(false, Ordering::SeqCst);
+    let mut app = new_app(bank, QuizConfig::default());
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+    for _ in 1..PAGE_SIZE {
+        app.dispatch(Action::Skip);
+    }
+    let last_resident = app.session().quiz_state().current_index();
+    assert_eq!(last_resident, PAGE_SIZE - 1);
+    app.dispatch(Action::Skip);
+    assert_eq!(
+        app.session().quiz_state().current_index(),
+        last_resident,
+        "nothing to move on to yet"
+    );
+    let waiting = run_for(&mut app, &mut terminal, Duration::from_millis(20)).await;
+    assert!(
+        waiting.contains("synthetic (250 Qs) · loading…"),
+        "{}",
+        waiting
+    );
+
+    open.store(true, Ordering::SeqCst);
+    let loaded = run_for(&mut app, &mut terminal, Duration::from_millis(300)).await;
+    assert!(!loaded.contains("loading…"), "{}", loaded);
+    assert_eq!(app.session().quiz_state().questions().len(), 250);
+    app.dispatch(Action::Skip);
+    assert_eq!(app.session().quiz_state().current_index(), PAGE_SIZE);
+}
+
+#[tokio::test]
+async fn filtered_sessions_still_load_the_whole_bank() {
+    let bank = SyntheticBank::new(10);
+    let shuffled = QuizConfig {
+        shuffle_questions: true,
+        ..QuizConfig::default()
+    };
+    assert!(paging::can_start_early(&bank, &QuizConfig::default()));
+    assert!(!paging::can_start_early(&bank, &shuffled));
+    assert!(!paging::can_start_early(
+        &ckad_practitioner::question_repository::InMemoryQuestionRepository,
+        &QuizConfig::default()
+    ));
+}
+
+#[test]
+fn reading_history_at_startup_leaves_a_paged_bank_unloaded() {
+    let dir = std::env::temp_dir().join(format!("ckad-paging-{}-history", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let saved = r#"{"started_at":"2024-05-01T10:00:00Z","finished_at":"2024-05-01T10:05:00Z","mode":"practice","complete":true,
+        "questions":[{"id":1,"question":"Synthetic question 1","expected_answer":"","domain":null,"outcome":"pass","time_spent_secs":30,"hints_used":0,"answer_submitted":null}],
+        "score":{"correct":1,"total":1,"percent":100.0},"domains":[]}"#;
+    fs::write(dir.join("session-20240501T100000Z.json"), saved).unwrap();
+
+    // `SyntheticBank::get_questions` panics, so this fails if the rekeying
+    // loads the bank
+    let history = stats::load_history_for(&dir, &SyntheticBank::new(10_000));
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].questions[0].uid, "");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// A `.jsonl` bank of `total` questions in the temp dir, with a blank line in
+/// the middle
+fn json_lines_bank(name: &str, total: usize) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("ckad-paging-{}-{}.jsonl", std::process::id(), name));
+    let mut contents = String::new();
+    for id in 1..=total {
+        let question = Question {
+            id,
+            question: format!("Line question {}", id),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 60,
+            ..Default::default()
+        };
+        contents.push_str(&serde_json::to_string(&question).unwrap());
+        contents.push('\n');
+        if id == total / 2 {
+            contents.push('\n');
+        }
+    }
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn a_json_lines_file_pages_by_line() {
+    let path = json_lines_bank("pages", 250);
+    let bank = FileQuestionRepository::new(path.to_string_lossy().into_owned());
+    assert!(bank.supports_paging());
+    assert_eq!(bank.count().unwrap(), 250);
+
+    let page = bank.get_page(120, 10).unwrap();
+    let ids: Vec<usize> = page.iter().map(|question| question.id).collect();
+    assert_eq!(ids, (121..=130).collect::<Vec<_>>());
+    assert_eq!(bank.get_page(245, 10).unwrap().len(), 5);
+    assert_eq!(bank.get_questions().unwrap().len(), 250);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn pages_read_in_order_read_on_from_the_previous_one() {
+    let path = json_lines_bank("in-order", 250);
+    let bank = FileQuestionRepository::new(path.to_string_lossy().into_owned());
+    assert_eq!(bank.get_page(0, 100).unwrap().len(), 100);
+    // The file stays open after a page, so the next one doesn't reopen it
+    fs::remove_file(&path).unwrap();
+    let ids: Vec<usize> = bank
+        .get_page(100, 100)
+        .unwrap()
+        .iter()
+        .map(|question| question.id)
+        .collect();
+    assert_eq!(ids, (101..=200).collect::<Vec<_>>());
+    assert!(matches!(
+        bank.get_page(0, 10),
+        Err(RepositoryError::Io { .. })
+    ));
+}
+
+#[test]
+fn a_question_is_found_by_uid_without_reading_past_it() {
+    let path = std::env::temp_dir().join(format!("ckad-paging-{}-uid.jsonl", std::process::id()));
+    let lines = [
+        r#"{"id": 1, "question": "First", "hints": [], "answer": "A", "time_limit_secs": 10}"#,
+        r#"{"id": 2, "uid": "pods/web", "question": "Second", "hints": [], "answer": "B", "time_limit_secs": 10}"#,
+        "not json",
+    ];
+    fs::write(&path, lines.join("\n")).unwrap();
+    let bank = FileQuestionRepository::new(path.to_string_lossy().into_owned());
+    assert_eq!(bank.get_by_uid("pods/web").unwrap().unwrap().id, 2);
+    let first = Question {
+        id: 1,
+        question: "First".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        bank.get_by_uid(&first.identity()).unwrap().unwrap().answer,
+        "A"
+    );
+    let err = bank.get_by_uid("missing").unwrap_err().to_string();
+    assert!(err.contains("line 3: "), "{}", err);
+    fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn duplicate_ids_get_the_same_new_ids_paged_as_loaded_whole() {
+    let path = std::env::temp_dir().join(format!("ckad-paging-{}-dupes.jsonl", std::process::id()));
+    // A duplicate on the second page, with the bank's highest id further on
+    let ids = (1..=150).map(|line| match line {
+        120 | 125 => 5,
+        140 => 900,
+        line => line,
+    });
+    let contents: String = ids
+        .map(|id| {
+            let question = Question {
+                id,
+                question: format!("Question on its own line {}", id),
+                answer: "kubectl get pods".to_string(),
+                time_limit_secs: 60,
+                ..Default::default()
+            };
+            serde_json::to_string(&question).unwrap() + "\n"
+        })
+        .collect();
+    fs::write(&path, contents).unwrap();
+    let bank = Arc::new(FileQuestionRepository::new(
+        path.to_string_lossy().into_owned(),
+    ));
+
+    let mut whole = bank.get_questions().unwrap();
+    scheduler::check_ids(&mut whole, DuplicateIds::Renumber).unwrap();
+    let whole: Vec<usize> = whole.iter().map(|question| question.id).collect();
+    assert_eq!((whole[119], whole[124]), (901, 902));
+
+    let mut start = paging::start(bank, &QuizConfig::default()).unwrap();
+    let mut paged: Vec<usize> = start.first.iter().map(|question| question.id).collect();
+    while let Some(page) = start.rest.recv().await {
+        paged.extend(page.unwrap().iter().map(|question| question.id));
+    }
+    assert_eq!(paged, whole);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn a_bad_line_is_named_in_the_error() {
+    let path = std::env::temp_dir().join(format!("ckad-paging-{}-bad.jsonl", std::process::id()));
+    let line = r#"{"id": 1, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}"#;
+    fs::write(&path, format!("{}\n\nnot json\n", line)).unwrap();
+    let bank = FileQuestionRepository::new(path.to_string_lossy().into_owned());
+    assert_eq!(bank.count().unwrap(), 2);
+    assert_eq!(bank.get_page(0, 1).unwrap().len(), 1);
+    let err = bank.get_page(0, 2).unwrap_err().to_string();
+    assert!(err.contains("line 3: "), "{}", err);
+    fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn the_app_starts_a_json_lines_bank_on_its_first_page() {
+    let path = json_lines_bank("app", 250);
+    let app = App::new(
+        Box::new(FileQuestionRepository::new(
+            path.to_string_lossy().into_owned(),
+        )),
+        Theme::new(false),
+        KeyBindings::default(),
+        QuizConfig::default(),
+        PersonalBests::default(),
+    )
+    .unwrap();
+    let quiz = app.session().quiz_state();
+    assert_eq!(quiz.questions().len(), PAGE_SIZE);
+    assert_eq!(quiz.total_questions(), 250);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn json_and_yaml_files_load_whole() {
+    let file = FileQuestionRepository::new("questions.json".to_string());
+    assert!(!file.supports_paging());
+    assert!(!FileQuestionRepository::new("bank.yaml".to_string()).supports_paging());
+}
@@ -0,0 +1,110 @@
+use chrono::Utc;
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::results::{QuestionResult, Score, SessionResult};
+use ckad_practitioner::stats::PersonalBests;
+use std::time::Duration;
+
+fn attempt(uid: &str, outcome: QuestionStatus, time_spent_secs: u64) -> QuestionResult {
+    QuestionResult {
+        id: 1,
+        uid: uid.to_string(),
+        question: format!("Question {}", uid),
+        expected_answer: "kubectl get pods".to_string(),
+        domain: None,
+        outcome,
+        time_spent_secs,
+        hints_used: 0,
+        hints_viewed: Vec::new(),
+        tags: Vec::new(),
+        answer_submitted: None,
+        attempts: Vec::new(),
+        partial_credit: None,
+        points: 0.0,
+        confidence: None,
+    }
+}
+
+fn session(questions: Vec<QuestionResult>) -> SessionResult {
+    SessionResult {
+        started_at: Utc::now(),
+        finished_at: Utc::now(),
+        mode: Mode::Exam,
+        complete: true,
+        score: Score {
+            correct: 0,
+            total: questions.len(),
+            points: 0.0,
+            possible_points: 0,
+            percent: 0.0,
+        },
+        questions,
+        domains: Vec::new(),
+        break_secs: 0,
+        elapsed_secs: 0,
+    }
+}
+
+fn question(uid: &str) -> Question {
+    Question {
+        id: 1,
+        uid: Some(uid.to_string()),
+        question: format!("Question {}", uid),
+        answer: "kubectl get pods".to_string(),
+        time_limit_secs: 120,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn the_fastest_pass_across_sessions_is_the_best() {
+    let history = vec![
+        session(vec![
+            attempt("pods-run", QuestionStatus::Pass, 70),
+            attempt("svc-expose", QuestionStatus::Pass, 40),
+        ]),
+        session(vec![attempt("pods-run", QuestionStatus::Pass, 45)]),
+        session(vec![attempt("pods-run", QuestionStatus::Pass, 90)]),
+    ];
+    let bests = PersonalBests::from_history(&history);
+    assert_eq!(
+        bests.best(&question("pods-run")),
+        Some(Duration::from_secs(45))
+    );
+    assert_eq!(
+        bests.best(&question("svc-expose")),
+        Some(Duration::from_secs(40))
+    );
+}
+
+#[test]
+fn only_passes_count() {
+    let history = vec![session(vec![
+        attempt("pods-run", QuestionStatus::Fail, 10),
+        attempt("pods-run", QuestionStatus::Skipped, 5),
+        attempt("pods-run", QuestionStatus::Revealed, 8),
+        attempt("pods-run", QuestionStatus::Pass, 60),
+        attempt("svc-expose", QuestionStatus::Fail, 20),
+    ])];
+    let bests = PersonalBests::from_history(&history);
+    assert_eq!(
+        bests.best(&question("pods-run")),
+        Some(Duration::from_secs(60))
+    );
+    assert_eq!(bests.best(&question("svc-expose")), None);
+}
+
+#[test]
+fn a_question_without_a_record_has_no_best() {
+    let history = vec![session(vec![attempt("pods-run", QuestionStatus::Pass, 30)])];
+    assert_eq!(
+        PersonalBests::from_history(&history).best(&question("cm-create")),
+        None
+    );
+    assert_eq!(
+        PersonalBests::from_history(&[]).best(&question("pods-run")),
+        None
+    );
+    assert_eq!(PersonalBests::default().best(&question("pods-run")), None);
+}
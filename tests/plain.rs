@@ -0,0 +1,171 @@
+use chrono::{TimeZone, Utc};
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::goal::DailyGoal;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::plain;
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::session::{Session, SessionAction};
+use std::time::Duration;
+use tokio::io::{AsyncWriteExt, BufReader};
+
+fn questions() -> Vec<Question> {
+    ["kubectl get pods", "kubectl get svc", "kubectl get nodes"]
+        .into_iter()
+        .enumerate()
+        .map(|(index, answer)| Question {
+            id: index + 1,
+            question: format!("Question {}", index + 1),
+            answer: answer.to_string(),
+            time_limit_secs: 30,
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn session() -> Session {
+    Session::new(questions(), QuizConfig::default()).unwrap()
+}
+
+/// Runs the session on `typed`, then leaves the input open without another
+/// line, so the question after the last answer waits out its clock
+async fn run_typed(session: &mut Session, typed: &str) -> String {
+    let (mut keyboard, input) = tokio::io::duplex(1024);
+    keyboard.write_all(typed.as_bytes()).await.unwrap();
+    let mut out = Vec::new();
+    plain::run(session, BufReader::new(input), &mut out)
+        .await
+        .unwrap();
+    drop(keyboard);
+    String::from_utf8(out).unwrap()
+}
+
+fn statuses(session: &Session) -> Vec<QuestionStatus> {
+    session
+        .quiz_state()
+        .records()
+        .iter()
+        .map(|record| record.status)
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_pass_a_fail_and_a_deadline_run_to_the_score() {
+    let mut session = session();
+    let output = run_typed(&mut session, "kubectl get pods\nkubectl get pv\n").await;
+
+    assert!(session.is_finished());
+    assert_eq!(
+        statuses(&session),
+        [
+            QuestionStatus::Pass,
+            QuestionStatus::Fail,
+            QuestionStatus::Revealed
+        ]
+    );
+    assert!(
+        output.contains("[PASS] Answer:\n  kubectl get pods\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("[FAIL] Answer:\n  kubectl get svc\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("Time expired.\n[REVEALED] Answer:\n  kubectl get nodes\n"),
+        "{}",
+        output
+    );
+    assert!(output.ends_with("\nScore: 1/3\n"), "{}", output);
+}
+
+#[tokio::test(start_paused = true)]
+async fn an_answer_after_the_deadline_is_not_graded() {
+    let mut session = session();
+    let mut out = Vec::new();
+    let input = "kubectl get pods\n".as_bytes();
+    // The clock runs out before the first line is read
+    session.apply(SessionAction::Start).unwrap();
+    tokio::time::advance(Duration::from_secs(31)).await;
+    plain::run(&mut session, input, &mut out).await.unwrap();
+    let output = String::from_utf8(out).unwrap();
+    assert_eq!(
+        session.quiz_state().records()[0].status,
+        QuestionStatus::Revealed
+    );
+    assert!(
+        output.contains("Time expired before the answer arrived."),
+        "{}",
+        output
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn quitting_and_running_out_of_input_stop_early() {
+    let mut session = session();
+    let output = run_typed(&mut session, "kubectl get pods\n:q\n").await;
+    assert!(!session.is_finished());
+    assert_eq!(
+        statuses(&session),
+        [
+            QuestionStatus::Pass,
+            QuestionStatus::Pending,
+            QuestionStatus::Pending
+        ]
+    );
+    assert!(!output.contains("Score:"), "{}", output);
+
+    let mut session = self::session();
+    let mut out = Vec::new();
+    plain::run(&mut session, "kubectl get pods\n".as_bytes(), &mut out)
+        .await
+        .unwrap();
+    assert_eq!(session.quiz_state().current_index(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_session_with_a_goal_prints_the_week_and_today() {
+    let goal = DailyGoal {
+        questions: Some(2),
+        minutes: None,
+    };
+    let now = Utc.with_ymd_and_hms(2024, 5, 10, 9, 30, 0).unwrap();
+    let mut session = session();
+    let mut out = Vec::new();
+    let input = "kubectl get pods\nkubectl get pv\n:s\n".as_bytes();
+    let result = plain::run_session(&mut session, input, &mut out, &goal, &[], now)
+        .await
+        .unwrap();
+    let output = String::from_utf8(out).unwrap();
+
+    assert!(
+        output.starts_with("Goal met 0 of the last 7 days\n"),
+        "{}",
+        output
+    );
+    // The skip doesn't count toward the goal
+    assert!(output.ends_with("Goal met: 2/2 questions\n"), "{}", output);
+    assert_eq!(result.questions.len(), 3);
+    assert_eq!(result.questions[2].outcome, QuestionStatus::Skipped);
+}
+
+#[tokio::test(start_paused = true)]
+async fn without_a_goal_only_the_quiz_is_printed() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 10, 9, 30, 0).unwrap();
+    let mut session = session();
+    let mut out = Vec::new();
+    plain::run_session(
+        &mut session,
+        ":s\n:s\n:s\n".as_bytes(),
+        &mut out,
+        &DailyGoal::default(),
+        &[],
+        now,
+    )
+    .await
+    .unwrap();
+    let output = String::from_utf8(out).unwrap();
+    assert!(!output.contains("Goal"), "{}", output);
+    assert!(output.ends_with("\nScore: 0/3\n"), "{}", output);
+}
@@ -0,0 +1,151 @@
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::playlist::{self, PlaylistError, PlaylistRepository};
+use ckad_practitioner::question_repository::{
+    InMemoryQuestionRepository, QuestionRepository, RepositoryError,
+};
+use ckad_practitioner::quiz_state::QuizState;
+use std::fs;
+use std::path::PathBuf;
+
+const PLAN: &str = r#"
+name: Two-day plan
+sections:
+  - title: "Day 1: pods"
+    questions:
+      - pods/run
+      - uid: pods/logs
+        time_limit_secs: 45
+  - title: "Day 2: networking"
+    questions:
+      - net/expose
+"#;
+
+fn bank() -> Vec<Question> {
+    ["pods/logs", "net/expose", "pods/run", "pods/unused"]
+        .iter()
+        .enumerate()
+        .map(|(index, uid)| Question {
+            id: index + 1,
+            uid: Some(uid.to_string()),
+            question: format!("Question {}", uid),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 120,
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn write_playlist(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "ckad-playlist-{}-{}.yaml",
+        name,
+        std::process::id()
+    ));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn entries_are_uids_with_optional_time_limits() {
+    let plan = playlist::parse(PLAN).unwrap();
+    assert_eq!(plan.name.as_deref(), Some("Two-day plan"));
+    let entries: Vec<(&str, Option<u64>)> = plan
+        .entries()
+        .map(|entry| (entry.uid.as_str(), entry.time_limit_secs))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            ("pods/run", None),
+            ("pods/logs", Some(45)),
+            ("net/expose", None)
+        ]
+    );
+    assert!(playlist::parse("sections:\n  - questions: [a]\n    shuffle: true\n").is_err());
+}
+
+#[test]
+fn resolving_keeps_the_playlist_order_and_its_time_limits() {
+    let plan = playlist::parse(PLAN).unwrap();
+    let questions = plan.resolve(&bank()).unwrap();
+    let uids: Vec<&str> = questions
+        .iter()
+        .map(|question| question.uid.as_deref().unwrap())
+        .collect();
+    assert_eq!(uids, vec!["pods/run", "pods/logs", "net/expose"]);
+    let limits: Vec<u64> = questions
+        .iter()
+        .map(|question| question.time_limit_secs)
+        .collect();
+    assert_eq!(limits, vec![120, 45, 120]);
+
+    let starts = plan.section_starts();
+    assert_eq!(starts.get(&0).map(String::as_str), Some("Day 1: pods"));
+    assert_eq!(
+        starts.get(&2).map(String::as_str),
+        Some("Day 2: networking")
+    );
+    assert_eq!(starts.len(), 2);
+}
+
+#[test]
+fn unknown_uids_are_listed_when_the_session_loads() {
+    let path = write_playlist(
+        "unknown",
+        "sections:\n  - questions: [pods/run, pods/gone, net/gone]\n",
+    );
+    let plan = playlist::load(&path).unwrap();
+    let repository = PlaylistRepository::new(Box::new(InMemoryQuestionRepository), plan);
+    let err = repository.get_questions().unwrap_err();
+    assert!(matches!(
+        &err,
+        RepositoryError::Playlist(PlaylistError::UnknownUids { uids, .. })
+            if uids == &["pods/run", "pods/gone", "net/gone"]
+    ));
+    assert!(err
+        .to_string()
+        .ends_with("refers to questions the bank doesn't have: pods/run, pods/gone, net/gone"));
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn a_playlist_must_list_each_question_once() {
+    let repeated = write_playlist(
+        "repeated",
+        "sections:\n  - questions: [a, b]\n  - questions: [a]\n",
+    );
+    let err = playlist::load(&repeated).unwrap_err();
+    assert!(matches!(&err, PlaylistError::Repeated { uids, .. } if uids == &["a"]));
+
+    let empty = write_playlist(
+        "empty",
+        "sections:\n  - title: Nothing yet\n    questions: []\n",
+    );
+    assert!(matches!(
+        playlist::load(&empty),
+        Err(PlaylistError::Empty(_))
+    ));
+    let _ = fs::remove_file(&repeated);
+    let _ = fs::remove_file(&empty);
+}
+
+#[test]
+fn the_quiz_holds_at_each_section_start() {
+    let plan = playlist::parse(PLAN).unwrap();
+    let config = QuizConfig {
+        sections: plan.section_starts(),
+        ..QuizConfig::default()
+    };
+    let mut quiz = QuizState::new(plan.resolve(&bank()).unwrap(), &config);
+    assert_eq!(quiz.section_title(), Some("Day 1: pods"));
+    assert!(quiz.start_timer());
+    assert_eq!(quiz.section_title(), None);
+
+    quiz.submit_answer(String::new(), true);
+    quiz.next_question();
+    assert!(!quiz.is_armed(), "no section starts at the second question");
+    quiz.submit_answer(String::new(), true);
+    quiz.next_question();
+    assert_eq!(quiz.section_title(), Some("Day 2: networking"));
+}
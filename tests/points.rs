@@ -0,0 +1,118 @@
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::results::format_points;
+use ckad_practitioner::session::{Session, SessionAction};
+
+fn question(id: usize, answer: &str, points: Option<u32>) -> Question {
+    Question {
+        id,
+        question: format!("Question {}", id),
+        answer: answer.to_string(),
+        time_limit_secs: 60,
+        points,
+        ..Default::default()
+    }
+}
+
+fn weighted_session() -> Session {
+    Session::new(
+        vec![
+            question(1, "kubectl get pods", None),
+            question(2, "kubectl create namespace dev", Some(5)),
+            question(3, "kubectl get svc", Some(2)),
+        ],
+        QuizConfig::default(),
+    )
+    .unwrap()
+}
+
+fn answer(session: &mut Session, answer: &str) {
+    session
+        .apply(SessionAction::Submit(answer.to_string()))
+        .unwrap();
+    session.apply(SessionAction::Next).unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_correct_high_point_question_earns_its_full_value() {
+    let mut session = weighted_session();
+    let quiz_state = session.quiz_state();
+    assert_eq!(quiz_state.total_points(), 8);
+    assert_eq!(quiz_state.earned_points(), 0.0);
+    assert!(quiz_state.is_weighted());
+
+    answer(&mut session, "kubectl get po");
+    answer(&mut session, "kubectl create namespace dev");
+    answer(&mut session, "kubectl get pods");
+    assert!(session.is_finished());
+
+    let quiz_state = session.quiz_state();
+    assert_eq!(quiz_state.correct_count(), 2);
+    assert_eq!(quiz_state.earned_points(), 6.0);
+
+    let score = session.result().score;
+    assert_eq!((score.correct, score.total), (2, 3));
+    assert_eq!((score.points, score.possible_points), (6.0, 8));
+    assert_eq!(score.percent, 75.0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn unweighted_questions_are_worth_a_point_each() {
+    let mut session = Session::new(
+        vec![
+            question(1, "kubectl get pods", None),
+            question(2, "kubectl get svc", Some(1)),
+        ],
+        QuizConfig::default(),
+    )
+    .unwrap();
+    assert!(!session.quiz_state().is_weighted());
+    answer(&mut session, "kubectl get pods");
+    answer(&mut session, "wrong");
+    let score = session.result().score;
+    assert_eq!((score.points, score.possible_points), (1.0, 2));
+    assert_eq!(score.percent, 50.0);
+}
+
+#[test]
+fn points_default_to_one_and_are_read_from_banks() {
+    let json = r#"[
+        {"id": 1, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10, "points": 7},
+        {"id": 2, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}
+    ]"#;
+    let questions: Vec<Question> = serde_json::from_str(json).unwrap();
+    assert_eq!(questions[0].points(), 7);
+    assert_eq!(questions[1].points(), 1);
+    assert!(!serde_json::to_string(&questions[1])
+        .unwrap()
+        .contains("points"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn partial_credit_counts_its_share_of_the_points() {
+    let mut session = Session::new(
+        vec![
+            question(
+                1,
+                "kubectl create deployment web --image=httpd\nkubectl expose deployment web --port=80",
+                Some(4),
+            ),
+            question(2, "kubectl get pods", None),
+        ],
+        QuizConfig::default(),
+    )
+    .unwrap();
+    answer(&mut session, "kubectl create deployment web --image=httpd");
+    answer(&mut session, "kubectl get pods");
+
+    let quiz_state = session.quiz_state();
+    assert_eq!(quiz_state.correct_count(), 1);
+    assert_eq!(quiz_state.records()[0].partial_credit, Some(0.5));
+    assert_eq!(quiz_state.earned_points(), 3.0);
+    assert!(quiz_state.scores_by_points());
+    let score = session.result().score;
+    assert_eq!((score.points, score.possible_points), (3.0, 5));
+    assert_eq!(score.percent, 60.0);
+    assert_eq!(format_points(2.5), "2.5");
+    assert_eq!(format_points(18.0), "18");
+}
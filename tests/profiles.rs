@@ -0,0 +1,107 @@
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::profile::{self, ProfileError, ProfileName};
+use ckad_practitioner::results;
+use ckad_practitioner::session::{Session, SessionAction};
+use ckad_practitioner::settings::{self, Settings, SettingsError};
+use ckad_practitioner::stats;
+use clap::Parser;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fresh data directory under the system temp dir
+fn temp_data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ckad-profiles-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn settings_for(data_dir: &Path, args: &[&str]) -> Result<Settings, SettingsError> {
+    let config = format!("[stats]\ndata_dir = {:?}\n", data_dir.display().to_string());
+    let (file, _) = settings::parse_file_config(&config).expect("config parses");
+    let cli = Cli::try_parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()))
+        .expect("arguments parse");
+    Settings::resolve(&cli, &file)
+}
+
+/// Saves a one-question session with the given id, as main does
+fn save_session(settings: &Settings, id: usize) {
+    let question = Question {
+        id,
+        question: "Create a pod".to_string(),
+        answer: "kubectl run pod".to_string(),
+        time_limit_secs: 30,
+        ..Default::default()
+    };
+    let mut session = Session::new(vec![question], QuizConfig::default()).unwrap();
+    session
+        .apply(SessionAction::Submit("kubectl run pod".to_string()))
+        .unwrap();
+    let result = session.result();
+    let path = results::results_dir(&settings.data_dir).join(result.file_name());
+    results::write(&path, &result).unwrap();
+}
+
+fn saved_question_ids(settings: &Settings) -> Vec<usize> {
+    stats::load_history(&results::results_dir(&settings.data_dir))
+        .iter()
+        .flat_map(|result| result.questions.iter().map(|question| question.id))
+        .collect()
+}
+
+#[tokio::test]
+async fn profiles_never_see_each_others_sessions() {
+    let data_dir = temp_data_dir("isolation");
+    let alice = settings_for(&data_dir, &["--profile", "alice"]).unwrap();
+    let bob = settings_for(&data_dir, &["--profile", "bob"]).unwrap();
+    let shared = settings_for(&data_dir, &[]).unwrap();
+    assert_eq!(alice.data_dir, data_dir.join("profiles").join("alice"));
+    assert_eq!(shared.data_dir, data_dir);
+
+    save_session(&alice, 1);
+    save_session(&bob, 2);
+    save_session(&shared, 3);
+
+    assert_eq!(saved_question_ids(&alice), vec![1]);
+    assert_eq!(saved_question_ids(&bob), vec![2]);
+    assert_eq!(saved_question_ids(&shared), vec![3]);
+    assert_eq!(profile::list(&data_dir).unwrap(), vec!["alice", "bob"]);
+
+    fs::remove_dir_all(&data_dir).unwrap();
+}
+
+#[test]
+fn names_that_could_escape_the_profiles_dir_are_refused() {
+    assert!(ProfileName::parse("work_2024-b").is_ok());
+    for name in ["../bob", "a/b", "a\\b", "..", ".hidden", "two words"] {
+        assert!(
+            matches!(
+                ProfileName::parse(name),
+                Err(ProfileError::InvalidCharacter { .. })
+            ),
+            "{}",
+            name
+        );
+    }
+    assert_eq!(ProfileName::parse(""), Err(ProfileError::Empty));
+    assert!(matches!(
+        ProfileName::parse(&"x".repeat(65)),
+        Err(ProfileError::TooLong(_))
+    ));
+
+    let data_dir = temp_data_dir("invalid");
+    assert!(matches!(
+        settings_for(&data_dir, &["--profile", "../alice"]),
+        Err(SettingsError::Profile(_))
+    ));
+}
+
+#[test]
+fn listing_without_profiles_is_empty() {
+    let data_dir = temp_data_dir("empty");
+    assert!(profile::list(&data_dir).unwrap().is_empty());
+
+    let settings = settings_for(&data_dir, &["profiles", "list"]).unwrap();
+    assert!(settings.list_profiles);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use ckad_practitioner::app::App;
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::print::{self, PrintFormat, PrintOptions};
+use ckad_practitioner::question_repository::{InMemoryQuestionRepository, QuestionRepository};
+use ckad_practitioner::session::Session;
+use ckad_practitioner::stats::PersonalBests;
+use ckad_practitioner::theme::Theme;
+use common::{key_event, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+
+const LINK: &str = "https://kubernetes.io/docs/concepts/workloads/pods/";
+
+fn question() -> Question {
+    Question {
+        id: 1,
+        question: "List the pods".to_string(),
+        answer: "kubectl get pods".to_string(),
+        hints: vec!["Use kubectl get".to_string()],
+        references: Some(vec![LINK.to_string()]),
+        time_limit_secs: 60,
+        ..Default::default()
+    }
+}
+
+fn app() -> App {
+    let session = Session::new(vec![question()], QuizConfig::default()).unwrap();
+    App::from_session(
+        session,
+        "test".to_string(),
+        Theme::new(false),
+        KeyBindings::default(),
+        PersonalBests::default(),
+    )
+}
+
+fn key(c: char) -> Event {
+    Event::Key(key_event(c))
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_references_tab_is_locked_until_the_answer_is_revealed() {
+    let mut app = app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![key('3')]).await;
+    assert!(screen.contains("References [locked]"), "{}", screen);
+    assert!(
+        screen.contains("[locked] References are shown once the answer is revealed"),
+        "{}",
+        screen
+    );
+    assert!(!screen.contains(LINK), "{}", screen);
+
+    let mut events = vec![key('a')];
+    events.extend("kubectl get pods".chars().map(key));
+    events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+    events.push(key('3'));
+    let screen = run_script(&mut app, &mut terminal, events).await;
+    assert!(!screen.contains("References [locked]"), "{}", screen);
+    assert!(screen.contains(LINK), "{}", screen);
+}
+
+#[tokio::test(start_paused = true)]
+async fn references_are_not_hints() {
+    let mut app = app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![key('h'), key('h')]).await;
+    assert!(screen.contains("Use kubectl get"), "{}", screen);
+    assert!(!screen.contains(LINK), "{}", screen);
+    assert_eq!(app.session().quiz_state().records()[0].hints_used, 1);
+}
+
+#[test]
+fn the_built_in_bank_keeps_its_links_out_of_the_hints() {
+    let questions = InMemoryQuestionRepository.get_questions().unwrap();
+    for question in &questions {
+        assert!(
+            question.hints.iter().all(|hint| !hint.contains("https://")),
+            "question {}: {:?}",
+            question.id,
+            question.hints
+        );
+    }
+    let first = &questions[0];
+    assert_eq!(first.hints.len(), 2);
+    assert_eq!(
+        first.reference_links(),
+        ["https://kubernetes.io/docs/reference/kubectl/generated/kubectl-run/"]
+    );
+}
+
+#[test]
+fn references_round_trip_and_print_with_the_answers() {
+    let json = serde_json::to_string(&question()).unwrap();
+    let parsed: Question = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.reference_links(), [LINK]);
+    // Absent in a bank file means none
+    let bare: Question = serde_json::from_str(
+        r#"{"id": 1, "question": "q", "hints": [], "answer": "a", "time_limit_secs": 60}"#,
+    )
+    .unwrap();
+    assert!(bare.reference_links().is_empty());
+
+    let mut out = Vec::new();
+    let options = PrintOptions {
+        format: PrintFormat::Text,
+        answers: true,
+    };
+    print::write_questions(&mut out, &[question()], options).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(
+        text.contains(&format!("   Reference: {}", LINK)),
+        "{}",
+        text
+    );
+
+    let mut out = Vec::new();
+    let options = PrintOptions {
+        answers: false,
+        ..options
+    };
+    print::write_questions(&mut out, &[question()], options).unwrap();
+    assert!(!String::from_utf8(out).unwrap().contains(LINK));
+}
@@ -0,0 +1,237 @@
+use chrono::{TimeZone, Utc};
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::report::{self, ReportFormat};
+use ckad_practitioner::results::{DomainResult, QuestionResult, Score, SessionResult};
+
+fn question(id: usize, domain: Option<&str>, outcome: QuestionStatus) -> QuestionResult {
+    QuestionResult {
+        id,
+        uid: String::new(),
+        question: "Create a pod, then \"expose\" it".into(),
+        expected_answer: "kubectl run web --image=nginx".into(),
+        domain: domain.map(str::to_string),
+        outcome,
+        time_spent_secs: 42,
+        hints_used: 1,
+        hints_viewed: vec![0],
+        tags: Vec::new(),
+        answer_submitted: None,
+        attempts: Vec::new(),
+        partial_credit: None,
+        points: 0.0,
+        confidence: None,
+    }
+}
+
+fn session(questions: Vec<QuestionResult>) -> SessionResult {
+    SessionResult {
+        started_at: Utc::now(),
+        finished_at: Utc::now(),
+        mode: Mode::Practice,
+        complete: false,
+        score: Score {
+            correct: 0,
+            total: questions.len(),
+            points: 0.0,
+            possible_points: questions.len() as u32,
+            percent: 0.0,
+        },
+        questions,
+        domains: Vec::new(),
+        break_secs: 0,
+        elapsed_secs: 0,
+    }
+}
+
+#[test]
+fn timing_csv_has_one_row_per_answered_question() {
+    let passed = QuestionResult {
+        points: 2.0,
+        ..question(1, Some("Pod Design"), QuestionStatus::Pass)
+    };
+    let partial = QuestionResult {
+        partial_credit: Some(0.5),
+        points: 0.5,
+        ..question(2, None, QuestionStatus::Fail)
+    };
+    let csv = report::to_timing_csv(&session(vec![
+        passed,
+        partial,
+        question(3, Some("Pod Design"), QuestionStatus::Pending),
+    ]));
+    assert_eq!(
+        csv,
+        "id,category,status,time_spent_secs,hints_used,points\n\
+         1,Pod Design,pass,42,1,2\n\
+         2,,fail,42,1,0.5\n"
+    );
+}
+
+#[test]
+fn timing_csv_quotes_categories_with_separators_and_quotes() {
+    let csv = report::to_timing_csv(&session(vec![question(
+        7,
+        Some("Services, \"Networking\""),
+        QuestionStatus::Skipped,
+    )]));
+    let row = csv.lines().nth(1).unwrap();
+    assert_eq!(row, "7,\"Services, \"\"Networking\"\"\",skipped,42,1,0");
+}
+
+#[test]
+fn csv_fields_are_quoted_only_when_needed() {
+    assert_eq!(report::csv_field("plain"), "plain");
+    assert_eq!(report::csv_field("a\nb"), "\"a\nb\"");
+    assert_eq!(report::csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+}
+
+/// A finished exam with one question of each kind the report tells apart
+fn exam() -> SessionResult {
+    let started_at = Utc.with_ymd_and_hms(2024, 5, 10, 9, 30, 0).unwrap();
+    let passed = QuestionResult {
+        question: "Run a pod | named web".into(),
+        answer_submitted: Some("kubectl run web --image=nginx".into()),
+        hints_used: 0,
+        hints_viewed: Vec::new(),
+        time_spent_secs: 65,
+        points: 1.0,
+        ..question(1, Some("Pod Design"), QuestionStatus::Pass)
+    };
+    let failed = QuestionResult {
+        question: "Expose web\non port 80".into(),
+        expected_answer: "kubectl expose pod web --port=80".into(),
+        answer_submitted: Some("kubectl expose pod web".into()),
+        time_spent_secs: 120,
+        ..question(2, Some("Services"), QuestionStatus::Fail)
+    };
+    let skipped = QuestionResult {
+        question: "Create a configmap".into(),
+        expected_answer: "kubectl create cm app --from-literal=a=b".into(),
+        hints_used: 2,
+        hints_viewed: vec![0, 1],
+        time_spent_secs: 9,
+        ..question(3, None, QuestionStatus::Skipped)
+    };
+    SessionResult {
+        started_at,
+        finished_at: started_at,
+        mode: Mode::Exam,
+        complete: true,
+        score: Score {
+            correct: 1,
+            total: 3,
+            points: 1.0,
+            possible_points: 3,
+            percent: 100.0 / 3.0,
+        },
+        domains: vec![
+            DomainResult {
+                domain: "Pod Design".into(),
+                correct: 1,
+                total: 1,
+            },
+            DomainResult {
+                domain: "Services".into(),
+                correct: 0,
+                total: 1,
+            },
+        ],
+        elapsed_secs: 754,
+        ..session(vec![passed, failed, skipped])
+    }
+}
+
+#[test]
+fn the_markdown_report_matches_its_snapshot() {
+    let expected = "\
+# CKAD practice session 2024-05-10 09:30 UTC
+
+Mode: exam | Complete: yes | Elapsed: 12:34
+
+## Summary
+
+| # | Question | Outcome | Time | Hints |
+|---|----------|---------|------|-------|
+| 1 | Run a pod \\| named web | pass | 1:05 | 0 |
+| 2 | Expose web<br>on port 80 | fail | 2:00 | 1 |
+| 3 | Create a configmap | skipped | 0:09 | 2 |
+
+**Score:** 1/3 correct, 1/3 points (33%)
+
+## Domains
+
+| Domain | Correct | Total |
+|--------|---------|-------|
+| Pod Design | 1 | 1 |
+| Services | 0 | 1 |
+
+## Missed questions
+
+### Question 2 (fail)
+
+Expose web
+on port 80
+
+Your answer:
+
+```
+kubectl expose pod web
+```
+
+Correct answer:
+
+```
+kubectl expose pod web --port=80
+```
+
+Hints used: 1
+
+### Question 3 (skipped)
+
+Create a configmap
+
+Your answer: _none_
+
+Correct answer:
+
+```
+kubectl create cm app --from-literal=a=b
+```
+
+Hints used: 2
+";
+    assert_eq!(report::to_markdown(&exam()), expected);
+    assert_eq!(ReportFormat::Md.render(&exam()), expected);
+    assert_eq!(ReportFormat::Md.extension(), "md");
+}
+
+#[test]
+fn an_unfinished_session_without_misses_or_a_clock_leaves_those_out() {
+    let result = SessionResult {
+        started_at: Utc.with_ymd_and_hms(2024, 5, 10, 9, 30, 0).unwrap(),
+        ..session(vec![question(1, None, QuestionStatus::Pass)])
+    };
+    let markdown = report::to_markdown(&result);
+    assert!(
+        markdown.contains("Mode: practice | Complete: no\n\n"),
+        "{}",
+        markdown
+    );
+    assert!(!markdown.contains("Elapsed"), "{}", markdown);
+    assert!(!markdown.contains("## Missed questions"), "{}", markdown);
+}
+
+#[test]
+fn the_csv_report_matches_its_snapshot() {
+    let expected = "\
+id,domain,outcome,time_spent_secs,hints_used,question,answer_submitted,expected_answer
+1,Pod Design,pass,65,0,Run a pod | named web,kubectl run web --image=nginx,kubectl run web --image=nginx
+2,Services,fail,120,1,\"Expose web
+on port 80\",kubectl expose pod web,kubectl expose pod web --port=80
+3,,skipped,9,2,Create a configmap,,kubectl create cm app --from-literal=a=b
+";
+    assert_eq!(report::to_csv(&exam()), expected);
+    assert_eq!(ReportFormat::Csv.render(&exam()), expected);
+    assert_eq!(ReportFormat::Csv.extension(), "csv");
+}
@@ -0,0 +1,320 @@
+use ckad_practitioner::app::{describe_source, App};
+use ckad_practitioner::config::QuizConfig;
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::models::{Difficulty, Question, QuestionGrading, RubricItem};
+use ckad_practitioner::question_repository::{
+    CompositeQuestionRepository, DirectoryQuestionRepository, FileQuestionRepository,
+    InMemoryQuestionRepository, QuestionRepository, RepositoryError,
+};
+use ckad_practitioner::results;
+use ckad_practitioner::session::{Session, SessionAction};
+use ckad_practitioner::stats::{self, PersonalBests};
+use ckad_practitioner::theme::Theme;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+
+#[test]
+fn built_in_questions_describe_themselves_with_a_count() {
+    let repository = InMemoryQuestionRepository;
+    assert_eq!(repository.source_description(), "built-in");
+    let count = repository.get_questions().unwrap().len();
+    assert_eq!(
+        describe_source(&repository, count),
+        format!("built-in ({} Qs)", count)
+    );
+}
+
+#[test]
+fn files_and_directories_are_described_by_path() {
+    let file = FileQuestionRepository::new("banks/pods.yaml".to_string());
+    assert_eq!(file.source_description(), "banks/pods.yaml");
+    assert_eq!(describe_source(&file, 12), "banks/pods.yaml (12 Qs)");
+
+    let dir = DirectoryQuestionRepository::new(PathBuf::from("banks"));
+    assert_eq!(dir.source_description(), format!("banks{}", MAIN_SEPARATOR));
+    let dir = DirectoryQuestionRepository::new(PathBuf::from(format!("banks{}", MAIN_SEPARATOR)));
+    assert_eq!(dir.source_description(), format!("banks{}", MAIN_SEPARATOR));
+}
+
+#[test]
+fn composites_list_every_source() {
+    let composite = CompositeQuestionRepository::new(vec![
+        Box::new(InMemoryQuestionRepository),
+        Box::new(FileQuestionRepository::new("extra.json".to_string())),
+    ]);
+    assert_eq!(composite.source_description(), "built-in, extra.json");
+}
+
+#[test]
+fn files_that_do_not_parse_are_skipped_and_reported() {
+    let dir = std::env::temp_dir().join(format!("ckad-skipped-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("a.json"),
+        r#"[{"id": 1, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}]"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("b.json"), "not a bank").unwrap();
+
+    let repository = DirectoryQuestionRepository::new(dir.clone());
+    assert!(repository.skipped_sources().is_empty());
+    assert_eq!(repository.get_questions().unwrap().len(), 1);
+    let skipped = repository.skipped_sources();
+    assert_eq!(skipped.len(), 1, "{:?}", skipped);
+    assert!(skipped[0].contains("b.json"), "{:?}", skipped);
+
+    // A strict composite fails instead of skipping
+    let strict = CompositeQuestionRepository::new(vec![Box::new(FileQuestionRepository::new(
+        dir.join("b.json").to_string_lossy().into_owned(),
+    ))]);
+    assert!(matches!(
+        strict.get_questions(),
+        Err(RepositoryError::Parse { .. })
+    ));
+    assert!(strict.skipped_sources().is_empty());
+
+    let app = App::new(
+        Box::new(DirectoryQuestionRepository::new(dir.clone())),
+        Theme::new(false),
+        KeyBindings::default(),
+        QuizConfig::default(),
+        PersonalBests::default(),
+    )
+    .unwrap();
+    assert_eq!(app.skipped_sources(), skipped);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn author_and_source_round_trip_through_json() {
+    let json = r#"[
+        {"id": 1, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10,
+         "author": "Jane Doe", "source": "https://example.com/bank"},
+        {"id": 2, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}
+    ]"#;
+    let questions: Vec<Question> = serde_json::from_str(json).unwrap();
+    assert_eq!(questions[0].author.as_deref(), Some("Jane Doe"));
+    assert_eq!(
+        questions[0].source.as_deref(),
+        Some("https://example.com/bank")
+    );
+    assert_eq!(
+        questions[0].attribution().as_deref(),
+        Some("Jane Doe - https://example.com/bank")
+    );
+    assert_eq!(questions[1].author, None);
+    assert_eq!(questions[1].attribution(), None);
+
+    let written = serde_json::to_value(&questions).unwrap();
+    assert_eq!(written[0]["author"], "Jane Doe");
+    assert_eq!(written[0]["source"], "https://example.com/bank");
+    // Unset fields are left out rather than written as null
+    assert!(written[1].get("author").is_none());
+
+    let built_in = InMemoryQuestionRepository.get_questions().unwrap();
+    assert!(built_in[0].attribution().is_some());
+}
+
+#[test]
+fn rubric_questions_need_items_worth_points() {
+    let question = |rubric: &str, grading: &str| {
+        format!(
+            "- id: 3\n  question: Q\n  hints: []\n  answer: A\n  time_limit_secs: 10\n{}{}",
+            rubric, grading
+        )
+    };
+    let cases = [
+        ("  rubric:\n  - item: app-config\n", "", None),
+        (
+            "  rubric:\n  - item: app-config\n    points: 0\n",
+            "  grading: answer\n",
+            None,
+        ),
+        ("", "  grading: rubric\n", Some("the rubric has no items")),
+        (
+            "  rubric:\n  - item: ' '\n",
+            "",
+            Some("rubric item 1 is empty"),
+        ),
+        (
+            "  rubric:\n  - item: app-config\n    points: 0\n",
+            "",
+            Some("worth no points"),
+        ),
+    ];
+    let path = std::env::temp_dir().join(format!("ckad-rubric-{}.yaml", std::process::id()));
+    for (rubric, grading, error) in cases {
+        std::fs::write(&path, question(rubric, grading)).unwrap();
+        let loaded =
+            FileQuestionRepository::new(path.to_string_lossy().into_owned()).get_questions();
+        match error {
+            None => assert!(loaded.is_ok(), "{:?}", loaded.err()),
+            Some(error) => {
+                let err = loaded.unwrap_err().to_string();
+                assert!(err.contains("question 3 has an invalid rubric"), "{}", err);
+                assert!(err.contains(error), "{}", err);
+            }
+        }
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn invalid_answer_patterns_fail_the_load_with_the_question_id() {
+    let path = std::env::temp_dir().join(format!("ckad-pattern-{}.yaml", std::process::id()));
+    std::fs::write(
+        &path,
+        "- id: 1\n  question: Q\n  hints: []\n  answer: A\n  answer_pattern: 'kubectl run \\w+'\n  time_limit_secs: 10\n\
+         - id: 7\n  question: Q\n  hints: []\n  answer: A\n  answer_pattern: 'kubectl run (\\w+'\n  time_limit_secs: 10\n",
+    )
+    .unwrap();
+    let err = FileQuestionRepository::new(path.to_string_lossy().into_owned())
+        .get_questions()
+        .unwrap_err()
+        .to_string();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        err.contains("question 7 has an invalid answer_pattern"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn questions_by_uid_come_in_the_order_asked_for() {
+    let repository = InMemoryQuestionRepository;
+    let bank = repository.get_questions().unwrap();
+    let uid = |id: usize| {
+        bank.iter()
+            .find(|question| question.id == id)
+            .unwrap()
+            .identity()
+    };
+    let questions = repository
+        .get_questions_by_uids(&[uid(3), uid(1), uid(3)])
+        .unwrap();
+    let ids: Vec<usize> = questions.iter().map(|question| question.id).collect();
+    assert_eq!(ids, vec![3, 1, 3]);
+
+    let missing = |name: &str| name.to_string();
+    let err = repository
+        .get_questions_by_uids(&[
+            uid(1),
+            missing("gone/b"),
+            missing("gone/a"),
+            missing("gone/b"),
+        ])
+        .unwrap_err();
+    assert!(matches!(&err, RepositoryError::MissingUids(uids) if uids == &["gone/b", "gone/a"]));
+    assert_eq!(
+        err.to_string(),
+        "no question with uid gone/b, gone/a in the source"
+    );
+}
+
+/// A question with every optional field set, next to the built-in bank
+fn bank() -> Vec<Question> {
+    let mut questions = InMemoryQuestionRepository.get_questions().unwrap();
+    questions.push(Question {
+        id: 99,
+        uid: Some("pods/labelled".to_string()),
+        question: "Run a pod named web labelled app=web".to_string(),
+        context: Some("namespace: shop".to_string()),
+        setup: Some("apiVersion: v1\nkind: Namespace\nmetadata:\n  name: shop\n".to_string()),
+        hints: vec!["Use --labels".to_string()],
+        answer: "kubectl run web --image=nginx --labels=app=web".to_string(),
+        accepted_answers: Some(vec!["kubectl run web --image nginx -l app=web".to_string()]),
+        answer_pattern: Some("kubectl run \\S+ .*".to_string()),
+        rubric: Some(vec![RubricItem {
+            item: "--labels=app=web".to_string(),
+            points: 2,
+        }]),
+        grading: Some(QuestionGrading::Answer),
+        case_sensitive: Some(false),
+        time_limit_secs: 90,
+        points: Some(2),
+        category: Some("Design and Build".to_string()),
+        tags: vec!["pods".to_string(), "labels".to_string()],
+        difficulty: Some(Difficulty::Medium),
+        depends_on: Some(vec![1]),
+        priority: Some(3),
+        related: Some(vec!["kubectl get pods -l app=web".to_string()]),
+        references: Some(vec![
+            "https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/".to_string(),
+        ]),
+        notes: Some("Labels select pods for services.".to_string()),
+        author: Some("Jane Doe".to_string()),
+        source: Some("labels bank".to_string()),
+        ..Default::default()
+    });
+    questions
+}
+
+fn saved_and_loaded(path: &Path, contents: String) -> Vec<Question> {
+    std::fs::write(path, contents).unwrap();
+    let loaded = FileQuestionRepository::new(path.to_string_lossy().into_owned()).get_questions();
+    std::fs::remove_file(path).unwrap();
+    loaded.unwrap()
+}
+
+#[test]
+fn questions_survive_a_save_and_load_in_every_format() {
+    let bank = bank();
+    let expected = serde_json::to_value(&bank).unwrap();
+    let path = |extension: &str| {
+        std::env::temp_dir().join(format!(
+            "ckad-round-trip-{}.{}",
+            std::process::id(),
+            extension
+        ))
+    };
+
+    let json = saved_and_loaded(&path("json"), serde_json::to_string_pretty(&bank).unwrap());
+    assert_eq!(serde_json::to_value(&json).unwrap(), expected);
+
+    let yaml = saved_and_loaded(&path("yaml"), serde_yaml::to_string(&bank).unwrap());
+    assert_eq!(serde_json::to_value(&yaml).unwrap(), expected);
+
+    let lines: Vec<String> = bank
+        .iter()
+        .map(|question| serde_json::to_string(question).unwrap())
+        .collect();
+    let jsonl = saved_and_loaded(&path("jsonl"), lines.join("\n"));
+    assert_eq!(serde_json::to_value(&jsonl).unwrap(), expected);
+}
+
+#[test]
+fn unset_fields_are_left_out_when_saved() {
+    let question = Question {
+        id: 1,
+        question: "List the pods".to_string(),
+        answer: "kubectl get pods".to_string(),
+        time_limit_secs: 30,
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_string(&question).unwrap(),
+        r#"{"id":1,"question":"List the pods","hints":[],"answer":"kubectl get pods","time_limit_secs":30}"#
+    );
+}
+
+#[test]
+fn saved_session_results_load_back_unchanged() {
+    let mut session = Session::new(bank(), QuizConfig::default()).unwrap();
+    session
+        .apply(SessionAction::Submit(
+            "kubectl run nginx --image=nginx:1.14".to_string(),
+        ))
+        .unwrap();
+    session.apply(SessionAction::Next).unwrap();
+    session.apply(SessionAction::Hint).unwrap();
+    session.apply(SessionAction::Skip).unwrap();
+    let result = session.result();
+
+    let dir = std::env::temp_dir().join(format!("ckad-results-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    results::write(&dir.join(result.file_name()), &result).unwrap();
+    let history = stats::load_history(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(history, [result]);
+}
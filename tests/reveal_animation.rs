@@ -0,0 +1,76 @@
+use ckad_practitioner::quiz_state::{revealed_chars, RevealAnimation};
+use ckad_practitioner::timer::MockClock;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn nothing_is_revealed_at_the_start() {
+    assert_eq!(revealed_chars(40, Duration::ZERO, ms(1000)), 0);
+}
+
+#[test]
+fn partway_reveals_the_elapsed_share_rounded_down() {
+    assert_eq!(revealed_chars(40, ms(250), ms(1000)), 10);
+    assert_eq!(revealed_chars(40, ms(500), ms(1000)), 20);
+    assert_eq!(revealed_chars(3, ms(500), ms(1000)), 1);
+    assert_eq!(revealed_chars(40, ms(999), ms(1000)), 39);
+}
+
+#[test]
+fn the_full_duration_and_beyond_reveal_everything() {
+    assert_eq!(revealed_chars(40, ms(1000), ms(1000)), 40);
+    assert_eq!(revealed_chars(40, ms(5000), ms(1000)), 40);
+}
+
+#[test]
+fn a_zero_duration_reveals_everything_at_once() {
+    assert_eq!(revealed_chars(40, Duration::ZERO, Duration::ZERO), 40);
+    assert_eq!(revealed_chars(40, ms(10), Duration::ZERO), 40);
+    assert_eq!(revealed_chars(0, Duration::ZERO, ms(1000)), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_animation_follows_the_clock_until_done_or_skipped() {
+    let mut reveal = RevealAnimation::new();
+    assert_eq!(reveal.visible_chars(), None);
+
+    reveal.start(40);
+    assert_eq!(reveal.visible_chars(), Some(0));
+    tokio::time::advance(RevealAnimation::DURATION / 2).await;
+    reveal.advance();
+    assert_eq!(reveal.visible_chars(), Some(20));
+    tokio::time::advance(RevealAnimation::DURATION).await;
+    reveal.advance();
+    assert!(!reveal.is_running());
+    assert_eq!(reveal.visible_chars(), None);
+
+    reveal.start(40);
+    reveal.skip();
+    assert!(reveal.has_started());
+    assert_eq!(reveal.visible_chars(), None);
+}
+
+#[test]
+fn the_animation_reads_the_clock_it_was_given() {
+    let clock = MockClock::new();
+    let mut reveal = RevealAnimation::with_clock(Arc::new(clock.clone()));
+    reveal.start(10);
+    clock.advance(ms(300));
+    reveal.advance();
+    assert_eq!(reveal.visible_chars(), Some(3));
+    clock.advance(ms(700));
+    reveal.advance();
+    assert!(!reveal.is_running());
+
+    // A reset keeps the clock for the next question's reveal
+    reveal.reset();
+    assert!(!reveal.has_started());
+    reveal.start(10);
+    clock.advance(ms(500));
+    reveal.advance();
+    assert_eq!(reveal.visible_chars(), Some(5));
+}
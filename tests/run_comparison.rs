@@ -0,0 +1,106 @@
+mod common;
+
+use chrono::{Duration as Days, Utc};
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::QuizState;
+use ckad_practitioner::results::SessionResult;
+use ckad_practitioner::stats::{self, RunComparison};
+use common::{key_event, new_app, run_script};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+
+fn questions() -> Vec<Question> {
+    (1..=4)
+        .map(|id| Question {
+            id,
+            uid: Some(format!("q{}", id)),
+            question: format!("Question {}", id),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 60,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// A complete session passing the questions with the uids in `passed`, each
+/// taking `secs`
+fn session(questions: &[Question], passed: &[&str], secs: u64) -> SessionResult {
+    let mut quiz = QuizState::new(questions.to_vec(), &QuizConfig::default());
+    for question in questions {
+        let pass = passed.contains(&question.uid.as_deref().unwrap());
+        quiz.submit_answer(String::new(), pass);
+        quiz.next_question();
+    }
+    let mut result = SessionResult::from_quiz(&quiz, Mode::Practice, Utc::now(), true);
+    for question in &mut result.questions {
+        question.time_spent_secs = secs;
+    }
+    result
+}
+
+#[test]
+fn deltas_and_newly_correct_questions() {
+    let bank = questions();
+    let previous = session(&bank, &["q1"], 30);
+    let current = session(&bank, &["q1", "q2", "q4"], 20);
+
+    let comparison = RunComparison::between(&previous, &current);
+    assert_eq!(comparison.score_delta, 50.0);
+    assert_eq!(comparison.time_delta_secs, -40);
+    assert_eq!(comparison.newly_correct, vec![2, 4]);
+
+    let back = RunComparison::between(&current, &previous);
+    assert_eq!(back.score_delta, -50.0);
+    assert_eq!(back.time_delta_secs, 40);
+    assert!(back.newly_correct.is_empty());
+}
+
+#[test]
+fn questions_are_matched_by_identity_not_id() {
+    let bank = questions();
+    let previous = session(&bank, &[], 30);
+    // The next run loads the bank in another order, so the ids move
+    let mut reordered = bank.clone();
+    reordered.reverse();
+    for (index, question) in reordered.iter_mut().enumerate() {
+        question.id = index + 1;
+    }
+    let current = session(&reordered, &["q4"], 30);
+
+    let comparison = RunComparison::between(&previous, &current);
+    assert_eq!(comparison.newly_correct, vec![1]);
+    assert_eq!(comparison.time_delta_secs, 0);
+}
+
+#[test]
+fn the_previous_run_is_the_latest_complete_session() {
+    let bank = questions();
+    let mut older = session(&bank, &["q1"], 30);
+    older.started_at = Utc::now() - Days::days(2);
+    let latest = session(&bank, &["q1", "q2"], 30);
+    let mut abandoned = session(&bank, &[], 30);
+    abandoned.complete = false;
+
+    let history = vec![latest.clone(), older, abandoned];
+    assert_eq!(stats::previous_run(&history), Some(&latest));
+    assert_eq!(stats::previous_run(&[]), None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_summary_shows_the_comparison_with_arrows() {
+    // Yesterday's run of the same two questions, both skipped
+    let previous = SessionResult {
+        started_at: Utc::now() - Days::days(1),
+        complete: true,
+        ..new_app().session_result()
+    };
+    let mut app = new_app().with_history(&[previous]);
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let skips = vec![Event::Key(key_event('s')), Event::Key(key_event('s'))];
+    let screen = run_script(&mut app, &mut terminal, skips).await;
+    assert!(app.session().is_finished());
+    assert!(screen.contains("Compared with your last run"), "{}", screen);
+    assert!(screen.contains("Score = 0.0%"));
+    assert!(screen.contains("No questions missed last time were right this time"));
+}
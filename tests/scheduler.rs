@@ -0,0 +1,441 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use ckad_practitioner::app::App;
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::{DuplicateIds, Freshness, Mode, QuizConfig};
+use ckad_practitioner::keymap::KeyBindings;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::print::{self, PrintFormat, PrintOptions};
+use ckad_practitioner::question_repository::{self, RepositoryError};
+use ckad_practitioner::quiz_state::QuizState;
+use ckad_practitioner::results::SessionResult;
+use ckad_practitioner::scheduler::{
+    self, check_ids, daily_index, exclude_recent, order_by_priority, select_questions,
+};
+use ckad_practitioner::settings::{self, Settings};
+use ckad_practitioner::stats::{self, PersonalBests};
+use ckad_practitioner::theme::Theme;
+use clap::Parser;
+
+fn questions(count: usize) -> Vec<Question> {
+    (1..=count)
+        .map(|id| Question {
+            id,
+            question: format!("Question {}", id),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 30,
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn ids_with_limit(limit: Option<usize>, loaded: usize) -> Vec<usize> {
+    let config = QuizConfig {
+        limit,
+        ..QuizConfig::default()
+    };
+    select_questions(questions(loaded), &config)
+        .iter()
+        .map(|question| question.id)
+        .collect()
+}
+
+#[test]
+fn limit_keeps_the_first_questions_in_order() {
+    assert_eq!(ids_with_limit(Some(3), 5), vec![1, 2, 3]);
+}
+
+#[test]
+fn limit_larger_than_the_set_keeps_everything() {
+    assert_eq!(ids_with_limit(Some(10), 5), vec![1, 2, 3, 4, 5]);
+    assert_eq!(ids_with_limit(Some(5), 5), vec![1, 2, 3, 4, 5]);
+    assert_eq!(ids_with_limit(None, 5), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn limit_applies_after_tag_filtering() {
+    let mut loaded = questions(4);
+    loaded[1].tags = vec!["pods".to_string()];
+    loaded[3].tags = vec!["pods".to_string()];
+    let config = QuizConfig {
+        tags: vec!["pods".to_string()],
+        limit: Some(1),
+        ..QuizConfig::default()
+    };
+    let selected = select_questions(loaded, &config);
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].id, 2);
+}
+
+#[test]
+fn zero_limit_is_rejected_on_the_command_line() {
+    assert!(Cli::try_parse_from(["ckad-practitioner", "--limit", "0"]).is_err());
+    let cli = Cli::try_parse_from(["ckad-practitioner", "--limit", "2"]).unwrap();
+    assert_eq!(cli.limit, Some(2));
+}
+
+/// Questions with these ids, in this order
+fn with_ids(ids: &[usize]) -> Vec<Question> {
+    ids.iter()
+        .map(|&id| Question {
+            id,
+            question: format!("Question {}", id),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[test]
+fn duplicate_ids_are_reported_once_each_in_order() {
+    assert_eq!(
+        question_repository::duplicate_ids(&with_ids(&[1, 2, 3])),
+        Vec::<usize>::new()
+    );
+    assert_eq!(
+        question_repository::duplicate_ids(&with_ids(&[7, 2, 7, 2, 7, 4])),
+        vec![2, 7]
+    );
+}
+
+#[test]
+fn strict_loading_fails_naming_the_duplicate_id() {
+    let mut questions = with_ids(&[1, 3, 2, 3]);
+    let err = check_ids(&mut questions, DuplicateIds::Error).unwrap_err();
+    assert!(matches!(&err, RepositoryError::DuplicateIds(ids) if ids == &[3]));
+    assert!(err.to_string().contains("more than one question uses id 3"));
+}
+
+#[test]
+fn lenient_loading_renumbers_later_duplicates_past_the_highest_id() {
+    let mut questions = with_ids(&[1, 3, 2, 3, 1]);
+    check_ids(&mut questions, DuplicateIds::Renumber).unwrap();
+    let ids: Vec<usize> = questions.iter().map(|question| question.id).collect();
+    assert_eq!(ids, vec![1, 3, 2, 4, 5]);
+    // The first question with an id keeps it
+    assert_eq!(questions[1].question, "Question 3");
+    assert_eq!(questions[3].question, "Question 3");
+    assert!(question_repository::duplicate_ids(&questions).is_empty());
+}
+
+#[test]
+fn duplicate_ids_policy_comes_from_the_config_file() {
+    let (file, _) = settings::parse_file_config("[defaults]\nduplicate_ids = \"error\"\n").unwrap();
+    let settings = Settings::resolve(&Cli::parse_from(["ckad-practitioner"]), &file).unwrap();
+    assert_eq!(settings.quiz.duplicate_ids, DuplicateIds::Error);
+    assert_eq!(QuizConfig::default().duplicate_ids, DuplicateIds::Renumber);
+}
+
+fn with_priorities(priorities: &[Option<u8>]) -> Vec<Question> {
+    priorities
+        .iter()
+        .enumerate()
+        .map(|(index, &priority)| Question {
+            id: index + 1,
+            priority,
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[test]
+fn priority_ordering_puts_higher_values_first_and_keeps_ties_in_order() {
+    let mut questions = with_priorities(&[Some(1), None, Some(3), Some(1), Some(3), Some(0)]);
+    order_by_priority(&mut questions);
+    let ids: Vec<usize> = questions.iter().map(|question| question.id).collect();
+    assert_eq!(ids, vec![3, 5, 1, 4, 2, 6]);
+}
+
+#[test]
+fn priority_only_keeps_prioritized_questions_before_the_limit() {
+    let config = QuizConfig {
+        priority_only: true,
+        limit: Some(2),
+        ..QuizConfig::default()
+    };
+    let selected = select_questions(
+        with_priorities(&[None, Some(2), Some(0), Some(5), Some(2)]),
+        &config,
+    );
+    let ids: Vec<usize> = selected.iter().map(|question| question.id).collect();
+    assert_eq!(ids, vec![4, 2]);
+}
+
+#[test]
+fn the_question_of_the_day_is_stable_for_a_date() {
+    let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    let index = daily_index(date, 40);
+    assert!(index < 40);
+    assert_eq!(daily_index(date, 40), index);
+
+    let config = QuizConfig {
+        daily: Some(date),
+        ..QuizConfig::default()
+    };
+    let picked = select_questions(questions(40), &config);
+    assert_eq!(picked.len(), 1);
+    assert_eq!(picked[0].id, index + 1);
+
+    // Load order doesn't change the pick
+    let mut reversed = questions(40);
+    reversed.reverse();
+    assert_eq!(select_questions(reversed, &config)[0].id, picked[0].id);
+}
+
+#[test]
+fn the_question_of_the_day_moves_around_the_bank_over_time() {
+    let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    let days: Vec<usize> = start
+        .iter_days()
+        .take(365)
+        .map(|date| daily_index(date, 20))
+        .collect();
+
+    let mut seen = [0; 20];
+    for &index in &days {
+        seen[index] += 1;
+    }
+    // About 18 days each; no question is left out or picked far too often
+    assert!(
+        seen.iter().all(|&count| (10..=30).contains(&count)),
+        "{:?}",
+        seen
+    );
+    let repeats = days.windows(2).filter(|pair| pair[0] == pair[1]).count();
+    assert!(repeats < 40, "{} repeats on consecutive days", repeats);
+}
+
+fn now() -> DateTime<Utc> {
+    "2024-05-10T12:00:00Z".parse().unwrap()
+}
+
+/// A session that answered `questions` and finished `ago` before `now()`
+fn attempted(questions: &[Question], ago: Duration) -> SessionResult {
+    let mut quiz = QuizState::new(questions.to_vec(), &QuizConfig::default());
+    for _ in questions {
+        quiz.submit_answer(String::new(), true);
+        quiz.next_question();
+    }
+    let finished_at = now() - ago;
+    SessionResult {
+        finished_at,
+        ..SessionResult::from_quiz(&quiz, Mode::Practice, finished_at, true)
+    }
+}
+
+fn fresh_ids(history: &[SessionResult], days: u32, wanted: usize) -> Vec<usize> {
+    let freshness = Freshness {
+        days,
+        now: now(),
+        last_seen: stats::last_seen(history),
+    };
+    exclude_recent(questions(5), &freshness, wanted)
+        .iter()
+        .map(|question| question.id)
+        .collect()
+}
+
+#[test]
+fn questions_attempted_inside_the_window_are_left_out() {
+    let bank = questions(5);
+    let history = vec![
+        // Just inside three days, and exactly on the boundary
+        attempted(&bank[0..1], Duration::days(3) - Duration::seconds(1)),
+        attempted(&bank[1..2], Duration::days(3)),
+        attempted(&bank[2..3], Duration::hours(1)),
+    ];
+    assert_eq!(fresh_ids(&history, 3, 1), vec![2, 4, 5]);
+    assert_eq!(fresh_ids(&history, 1, 1), vec![1, 2, 4, 5]);
+    assert_eq!(fresh_ids(&[], 3, 1), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn the_latest_attempt_counts() {
+    let bank = questions(5);
+    let history = vec![
+        attempted(&bank[0..1], Duration::days(10)),
+        attempted(&bank[0..1], Duration::days(1)),
+    ];
+    assert_eq!(fresh_ids(&history, 3, 1), vec![2, 3, 4, 5]);
+}
+
+#[test]
+fn too_small_a_pool_takes_back_the_least_recently_seen() {
+    let bank = questions(5);
+    let history = vec![
+        attempted(&bank[0..2], Duration::hours(2)),
+        attempted(&bank[2..3], Duration::days(2)),
+        attempted(&bank[3..4], Duration::hours(30)),
+    ];
+    // Only question 5 is fresh; 3 then 4 were seen longest ago
+    assert_eq!(fresh_ids(&history, 3, 1), vec![5]);
+    assert_eq!(fresh_ids(&history, 3, 2), vec![3, 5]);
+    assert_eq!(fresh_ids(&history, 3, 3), vec![3, 4, 5]);
+    assert_eq!(fresh_ids(&history, 3, 10).len(), 5);
+}
+
+#[test]
+fn the_limit_is_the_session_size_the_pool_must_reach() {
+    let bank = questions(5);
+    let config = QuizConfig {
+        limit: Some(4),
+        fresh: Some(Freshness {
+            days: 3,
+            now: now(),
+            last_seen: stats::last_seen(&[attempted(&bank[0..3], Duration::days(1))]),
+        }),
+        ..QuizConfig::default()
+    };
+    let ids: Vec<usize> = select_questions(questions(5), &config)
+        .iter()
+        .map(|question| question.id)
+        .collect();
+    assert_eq!(ids.len(), 4);
+    assert!(ids.contains(&4) && ids.contains(&5));
+}
+
+#[test]
+fn fresh_days_cannot_be_combined_with_the_question_of_the_day() {
+    let parsed = Cli::try_parse_from(["ckad-practitioner", "--fresh-days", "3", "--daily"]);
+    assert!(parsed.is_err());
+}
+
+/// The ids `--print` writes for these flags, read back from its JSON output
+fn printed_ids(settings: &Settings) -> Vec<usize> {
+    let questions =
+        scheduler::build_session(settings.repository().as_ref(), &settings.quiz).unwrap();
+    let options = PrintOptions {
+        format: PrintFormat::Json,
+        answers: false,
+    };
+    let mut out = Vec::new();
+    print::write_questions(&mut out, &questions, options).unwrap();
+    let printed: Vec<serde_json::Value> = serde_json::from_slice(&out).unwrap();
+    printed
+        .iter()
+        .map(|question| question["id"].as_u64().unwrap() as usize)
+        .collect()
+}
+
+/// The ids the TUI session runs for these flags, in order
+fn session_ids(settings: &Settings) -> Vec<usize> {
+    let app = App::new(
+        settings.repository(),
+        Theme::new(false),
+        KeyBindings::default(),
+        settings.quiz.clone(),
+        PersonalBests::default(),
+    )
+    .unwrap();
+    app.session()
+        .quiz_state()
+        .questions()
+        .iter()
+        .map(|question| question.id)
+        .collect()
+}
+
+#[test]
+fn print_and_the_tui_select_the_same_questions() {
+    let file = settings::FileConfig::default();
+    for args in [
+        &[][..],
+        &["--tags", "pods"],
+        &["--tags", "pods,configuration", "--limit", "2"],
+        &["--shuffle", "--seed", "7"],
+        &["--shuffle", "--seed", "7", "--tags", "pods", "--limit", "2"],
+        &["--sort-by-id", "--limit", "3"],
+    ] {
+        let cli = Cli::parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()));
+        let settings = Settings::resolve(&cli, &file).unwrap();
+        let printed = printed_ids(&settings);
+        assert!(!printed.is_empty(), "{:?}", args);
+        assert_eq!(printed, session_ids(&settings), "{:?}", args);
+    }
+}
+
+#[test]
+fn the_seed_fixes_the_order_for_both() {
+    let file = settings::FileConfig::default();
+    let resolve = |seed: &str| {
+        let cli = Cli::parse_from(["ckad-practitioner", "--shuffle", "--seed", seed]);
+        Settings::resolve(&cli, &file).unwrap()
+    };
+    assert_eq!(printed_ids(&resolve("7")), printed_ids(&resolve("7")));
+    assert_eq!(session_ids(&resolve("7")), session_ids(&resolve("7")));
+    assert_ne!(printed_ids(&resolve("7")), printed_ids(&resolve("8")));
+}
+
+/// Questions 1-5 in the order a directory listing might load them
+fn loaded_out_of_order() -> Vec<Question> {
+    let mut loaded = questions(5);
+    loaded.swap(0, 3);
+    loaded.swap(1, 4);
+    loaded
+}
+
+fn selected_ids(loaded: Vec<Question>, config: &QuizConfig) -> Vec<usize> {
+    select_questions(loaded, config)
+        .iter()
+        .map(|question| question.id)
+        .collect()
+}
+
+#[test]
+fn sort_by_id_orders_the_session_before_the_limit() {
+    let sorted = QuizConfig {
+        sort_by_id: true,
+        ..QuizConfig::default()
+    };
+    assert_eq!(
+        selected_ids(loaded_out_of_order(), &sorted),
+        [1, 2, 3, 4, 5]
+    );
+    // Load order is kept without it
+    assert_eq!(
+        selected_ids(loaded_out_of_order(), &QuizConfig::default()),
+        [4, 5, 3, 1, 2]
+    );
+
+    let limited = QuizConfig {
+        limit: Some(2),
+        ..sorted
+    };
+    assert_eq!(selected_ids(loaded_out_of_order(), &limited), [1, 2]);
+}
+
+#[test]
+fn sort_by_id_keeps_duplicate_ids_in_load_order() {
+    let mut loaded = questions(3);
+    loaded[0].id = 2;
+    loaded[0].question = "First loaded".to_string();
+    let config = QuizConfig {
+        sort_by_id: true,
+        ..QuizConfig::default()
+    };
+    let selected = select_questions(loaded, &config);
+    let texts: Vec<&str> = selected
+        .iter()
+        .map(|question| question.question.as_str())
+        .collect();
+    assert_eq!(texts, ["First loaded", "Question 2", "Question 3"]);
+}
+
+#[test]
+fn a_seeded_shuffle_after_sorting_ignores_load_order() {
+    let config = QuizConfig {
+        sort_by_id: true,
+        shuffle_questions: true,
+        seed: Some(11),
+        limit: Some(3),
+        ..QuizConfig::default()
+    };
+    let shuffled = selected_ids(loaded_out_of_order(), &config);
+    assert_eq!(shuffled, selected_ids(questions(5), &config));
+    assert_eq!(shuffled.len(), 3);
+    assert_ne!(shuffled, [1, 2, 3]);
+}
+
+#[test]
+fn sort_by_id_and_shuffle_conflict_on_the_command_line() {
+    let parsed = Cli::try_parse_from(["ckad-practitioner", "--sort-by-id", "--shuffle"]);
+    assert!(parsed.is_err());
+}
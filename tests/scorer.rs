@@ -0,0 +1,421 @@
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::grading::{Difference, Verdict};
+use ckad_practitioner::models::{Question, QuestionGrading, RubricItem};
+use ckad_practitioner::question_repository::{InMemoryQuestionRepository, QuestionRepository};
+use ckad_practitioner::scorer::{AnswerScorer, Strictness};
+use ckad_practitioner::settings::{self, Settings};
+use clap::Parser;
+
+fn service_type_question(case_sensitive: Option<bool>) -> Question {
+    Question {
+        id: 1,
+        question: "Which Service type is only reachable inside the cluster?".to_string(),
+        answer: "ClusterIP".to_string(),
+        case_sensitive,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn case_sensitive_scoring_rejects_a_case_mismatch() {
+    let scorer = AnswerScorer::default();
+    let question = service_type_question(None);
+    assert!(scorer.is_correct(&question, "ClusterIP"));
+    assert!(!scorer.is_correct(&question, "clusterip"));
+}
+
+#[test]
+fn case_insensitive_scoring_accepts_a_case_mismatch() {
+    let scorer = AnswerScorer::new(false);
+    let question = service_type_question(None);
+    assert!(scorer.is_correct(&question, "clusterip"));
+    assert!(scorer.is_correct(&question, "  CLUSTERIP "));
+    assert!(!scorer.is_correct(&question, "NodePort"));
+}
+
+#[test]
+fn question_setting_overrides_the_global_one() {
+    let lenient = service_type_question(Some(false));
+    assert!(AnswerScorer::new(true).is_correct(&lenient, "clusterip"));
+
+    let strict = service_type_question(Some(true));
+    assert!(!AnswerScorer::new(false).is_correct(&strict, "clusterip"));
+}
+
+fn pattern_question(answer: &str, pattern: &str) -> Question {
+    Question {
+        id: 1,
+        answer: answer.to_string(),
+        answer_pattern: Some(pattern.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn answer_patterns_accept_any_matching_submission() {
+    let scorer = AnswerScorer::default();
+    let question = pattern_question(
+        "kubectl run box --image=busybox",
+        r"kubectl run [a-z][a-z0-9-]* --image=busybox",
+    );
+    assert_eq!(
+        scorer.grade(&question, "kubectl run box --image=busybox"),
+        Verdict::Equal
+    );
+    assert_eq!(
+        scorer.grade(&question, "  kubectl  run my-pod --image=busybox "),
+        Verdict::Equivalent(vec![Difference::Pattern])
+    );
+    // Anchored: extra words on either side don't match
+    assert!(!scorer.is_correct(&question, "kubectl run my-pod --image=busybox --rm"));
+    assert!(!scorer.is_correct(&question, "echo kubectl run my-pod --image=busybox"));
+    // The pattern replaces the text comparison, flag order included
+    assert!(!scorer.is_correct(&question, "kubectl run --image=busybox my-pod"));
+}
+
+#[test]
+fn answer_patterns_escape_flag_syntax() {
+    let scorer = AnswerScorer::default();
+    let question = pattern_question(
+        "kubectl get pods -o=jsonpath='{.items[*].metadata.name}'",
+        r"kubectl get pods? -o=jsonpath='\{\.items\[\*\]\.metadata\.name\}'",
+    );
+    assert!(scorer.is_correct(
+        &question,
+        "kubectl get pod -o=jsonpath='{.items[*].metadata.name}'"
+    ));
+    assert!(!scorer.is_correct(
+        &question,
+        "kubectl get pods -o=jsonpath='{.itemsX*].metadata.name}'"
+    ));
+}
+
+#[test]
+fn answer_patterns_match_multi_line_submissions_line_by_line() {
+    let question = pattern_question(
+        "kubectl create ns dev\nkubectl run web --image=nginx -n dev",
+        r"kubectl create (ns|namespace) [a-z]+$\n^kubectl run \w+ --image=nginx -n [a-z]+",
+    );
+    let scorer = AnswerScorer::default();
+    assert!(scorer.is_correct(
+        &question,
+        "kubectl create namespace dev\n\n  kubectl run web --image=nginx -n dev  \n"
+    ));
+    assert!(scorer.is_correct(
+        &question,
+        "kubectl create ns dev\r\nkubectl run api --image=nginx -n dev"
+    ));
+    // Both lines are needed, in order
+    assert!(!scorer.is_correct(&question, "kubectl create ns dev"));
+    assert!(!scorer.is_correct(
+        &question,
+        "kubectl run web --image=nginx -n dev\nkubectl create ns dev"
+    ));
+}
+
+#[test]
+fn answer_patterns_follow_case_sensitivity() {
+    let question = pattern_question("ClusterIP", "Cluster(IP)?");
+    assert!(!AnswerScorer::default().is_correct(&question, "clusterip"));
+    assert!(AnswerScorer::new(false).is_correct(&question, "clusterip"));
+}
+
+fn rubric_question(items: &[(&str, u32)]) -> Question {
+    Question {
+        id: 1,
+        answer: "kubectl create configmap app-config --from-literal=database.url=db:5432"
+            .to_string(),
+        rubric: Some(
+            items
+                .iter()
+                .map(|(item, points)| RubricItem {
+                    item: item.to_string(),
+                    points: *points,
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn rubric_grading_scores_the_items_found() {
+    let question = rubric_question(&[
+        ("create configmap", 1),
+        ("app-config", 1),
+        ("--from-literal", 2),
+        ("database.url", 2),
+    ]);
+    // (submission, items found in rubric order, correct)
+    let cases: &[(&str, [bool; 4], bool)] = &[
+        (
+            "kubectl create configmap app-config --from-literal=database.url=db:5432",
+            [true, true, true, true],
+            true,
+        ),
+        (
+            "k create cm app-config --from-literal database.url=x",
+            [false, true, true, true],
+            false,
+        ),
+        (
+            "kubectl create configmap app-config --from-file=database.url",
+            [true, true, false, true],
+            false,
+        ),
+        (
+            "kubectl create configmap other --from-literal='database.url=db'",
+            [true, false, true, true],
+            false,
+        ),
+        (
+            "kubectl configmap create app-config-v2",
+            [false, false, false, false],
+            false,
+        ),
+        ("", [false, false, false, false], false),
+    ];
+    let scorer = AnswerScorer::default();
+    for (submitted, found, correct) in cases {
+        let verdict = scorer.grade(&question, submitted);
+        let rubric = verdict.rubric().expect("a rubric verdict");
+        let matched: Vec<bool> = rubric.items.iter().map(|item| item.matched).collect();
+        assert_eq!(matched, found, "{}", submitted);
+        assert_eq!(verdict.is_correct(), *correct, "{}", submitted);
+        let earned: u32 = question
+            .rubric_items()
+            .iter()
+            .zip(found.iter())
+            .filter(|(_, found)| **found)
+            .map(|(item, _)| item.points)
+            .sum();
+        assert_eq!(verdict.credit(), earned as f64 / 6.0, "{}", submitted);
+    }
+}
+
+#[test]
+fn rubric_items_match_across_commands_but_phrases_within_one() {
+    let question = rubric_question(&[("create namespace", 1), ("-n dev", 1)]);
+    let scorer = AnswerScorer::default();
+    let cases: &[(&str, [bool; 2])] = &[
+        (
+            "kubectl create namespace dev\nkubectl run web --image=nginx -n dev",
+            [true, true],
+        ),
+        (
+            "kubectl create ns dev && kubectl get pods -n dev",
+            [false, true],
+        ),
+        ("kubectl create\nnamespace dev -n\ndev", [false, false]),
+        ("kubectl get pods -n dev | grep web", [false, true]),
+    ];
+    for (submitted, found) in cases {
+        let verdict = scorer.grade(&question, submitted);
+        let matched: Vec<bool> = verdict
+            .rubric()
+            .unwrap()
+            .items
+            .iter()
+            .map(|item| item.matched)
+            .collect();
+        assert_eq!(matched, found, "{}", submitted);
+    }
+}
+
+#[test]
+fn rubric_grading_follows_case_sensitivity() {
+    let question = rubric_question(&[("ConfigMap", 1)]);
+    let cases = [
+        (true, "kind: ConfigMap", true),
+        (true, "kind: configmap", false),
+        (false, "kind: configmap", true),
+    ];
+    for (case_sensitive, submitted, correct) in cases {
+        assert_eq!(
+            AnswerScorer::new(case_sensitive).is_correct(&question, submitted),
+            correct,
+            "{} (case sensitive: {})",
+            submitted,
+            case_sensitive
+        );
+    }
+}
+
+#[test]
+fn the_grading_selector_chooses_between_rubric_and_answer() {
+    let scorer = AnswerScorer::default();
+    let exact = "kubectl create configmap app-config --from-literal=database.url=db:5432";
+    let loose = "kubectl create configmap app-config --from-literal=database.url=other";
+    let mut question = rubric_question(&[("app-config", 3), ("database.url", 1)]);
+    assert_eq!(question.grading(), QuestionGrading::Rubric);
+    assert_eq!(question.points(), 4, "worth the rubric's total");
+    assert!(scorer.is_correct(&question, loose));
+
+    question.grading = Some(QuestionGrading::Answer);
+    assert_eq!(question.points(), 1);
+    assert_eq!(scorer.grade(&question, exact), Verdict::Equal);
+    assert_eq!(scorer.grade(&question, loose), Verdict::Different);
+
+    let plain = Question {
+        answer: exact.to_string(),
+        ..Default::default()
+    };
+    assert_eq!(plain.grading(), QuestionGrading::Answer);
+    let weighted = Question {
+        points: Some(10),
+        ..rubric_question(&[("app-config", 3)])
+    };
+    assert_eq!(weighted.points(), 10, "explicit points win");
+}
+
+fn resources_question() -> Question {
+    InMemoryQuestionRepository
+        .get_questions()
+        .unwrap()
+        .into_iter()
+        .find(|question| question.id == 3)
+        .unwrap()
+}
+
+#[test]
+fn quoting_a_yaml_value_does_not_matter() {
+    let scorer = AnswerScorer::default();
+    let question = resources_question();
+    assert!(question.yaml_answer);
+
+    let unquoted = "resources:\n  requests:\n    memory: 256Mi\n    cpu: 100m\n  limits:\n    memory: 512Mi\n    cpu: 200m";
+    let mixed = "resources:\n  limits: {cpu: '200m', memory: 512Mi}\n  requests: {cpu: 100m, memory: \"256Mi\"}";
+    assert_eq!(scorer.grade(&question, unquoted), Verdict::Equal);
+    assert_eq!(scorer.grade(&question, mixed), Verdict::Equal);
+
+    let wrong = unquoted.replace("512Mi", "512M");
+    assert_eq!(scorer.grade(&question, &wrong), Verdict::Different);
+}
+
+#[test]
+fn quoted_numbers_and_booleans_match_their_plain_spelling() {
+    let scorer = AnswerScorer::default();
+    let question = Question {
+        id: 1,
+        answer: "spec:\n  replicas: 3\n  cpu: \"0.5\"\n  paused: false\n  ports:\n    80: web"
+            .to_string(),
+        yaml_answer: true,
+        ..Default::default()
+    };
+    let quoted =
+        "spec:\n  replicas: \"3\"\n  cpu: 0.5\n  paused: \"false\"\n  ports:\n    \"80\": web";
+    assert_eq!(scorer.grade(&question, quoted), Verdict::Equal);
+
+    for different in [
+        "spec:\n  replicas: 3.0\n  cpu: 0.5\n  paused: false\n  ports:\n    80: web",
+        "spec:\n  replicas: 3\n  cpu: \".5\"\n  paused: false\n  ports:\n    80: web",
+        "spec:\n  replicas: 3\n  cpu: 0.5\n  paused: \"null\"\n  ports:\n    80: web",
+    ] {
+        assert_eq!(
+            scorer.grade(&question, different),
+            Verdict::Different,
+            "{}",
+            different
+        );
+    }
+    let unset = Question {
+        answer: "paused: null".to_string(),
+        ..question
+    };
+    assert_eq!(scorer.grade(&unset, "paused: ~"), Verdict::Equal);
+    assert_eq!(scorer.grade(&unset, "paused: \"null\""), Verdict::Different);
+}
+
+fn namespaced_question() -> Question {
+    Question {
+        id: 1,
+        answer: "kubectl create deployment web --image=nginx --replicas=2 -n shop".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn each_strictness_preset_accepts_what_it_promises() {
+    let question = namespaced_question();
+    let exact = "kubectl create deployment web --image=nginx --replicas=2 -n shop";
+    let spaced = "  kubectl create  deployment web --image=nginx --replicas=2 -n shop ";
+    let reordered = "kubectl create deploy web -n shop --replicas 2 --image=nginx";
+    let shouting = "KUBECTL create deployment web --image=nginx --replicas=2 -n shop";
+    let no_namespace = "kubectl create deployment web --image=nginx --replicas=2";
+    let wrong_namespace = "kubectl create deployment web --image=nginx --replicas=2 -n dev";
+    let wrong_image = "kubectl create deployment web --image=httpd --replicas=2 -n shop";
+
+    let cases = [
+        (exact, [true, true, true]),
+        (spaced, [false, true, true]),
+        (reordered, [false, true, true]),
+        (shouting, [false, false, true]),
+        (no_namespace, [false, false, true]),
+        (wrong_namespace, [false, false, false]),
+        (wrong_image, [false, false, false]),
+    ];
+    let presets = [Strictness::Strict, Strictness::Normal, Strictness::Lenient];
+    for (submitted, accepted) in cases {
+        for (strictness, accepted) in presets.into_iter().zip(accepted) {
+            let scorer = AnswerScorer::new(true).with_strictness(strictness);
+            assert_eq!(
+                scorer.is_correct(&question, submitted),
+                accepted,
+                "{:?}: {}",
+                strictness,
+                submitted
+            );
+        }
+    }
+}
+
+#[test]
+fn lenient_grading_says_when_the_namespace_was_forgiven() {
+    let scorer = AnswerScorer::new(true).with_strictness(Strictness::Lenient);
+    let question = Question {
+        answer: "kubectl get pods".to_string(),
+        ..namespaced_question()
+    };
+    assert_eq!(
+        scorer.grade(&question, "kubectl get pods --namespace=default"),
+        Verdict::Equivalent(vec![Difference::Namespace])
+    );
+    // A question that insists on case keeps it under any preset
+    let insists = Question {
+        case_sensitive: Some(true),
+        ..question
+    };
+    assert!(!scorer.is_correct(&insists, "kubectl get PODS"));
+}
+
+#[test]
+fn strict_grading_still_accepts_listed_alternatives() {
+    let scorer = AnswerScorer::new(true).with_strictness(Strictness::Strict);
+    let question = Question {
+        accepted_answers: Some(vec![
+            "k create deploy web --image=nginx --replicas=2 -n shop".to_string(),
+        ]),
+        ..namespaced_question()
+    };
+    assert!(scorer.is_correct(
+        &question,
+        "k create deploy web --image=nginx --replicas=2 -n shop\n"
+    ));
+    assert!(!scorer.is_correct(
+        &question,
+        "k create deploy web --replicas=2 --image=nginx -n shop"
+    ));
+    assert!(!scorer.is_correct(&question, "   "));
+}
+
+#[test]
+fn the_strictness_flag_overrides_the_config_file() {
+    let (file, _) = settings::parse_file_config("[defaults]\nstrictness = \"lenient\"\n").unwrap();
+    let from_file = Settings::resolve(&Cli::parse_from(["ckad-practitioner"]), &file).unwrap();
+    assert_eq!(from_file.strictness, Strictness::Lenient);
+    let cli = Cli::parse_from(["ckad-practitioner", "--strictness", "strict"]);
+    assert_eq!(
+        Settings::resolve(&cli, &file).unwrap().strictness,
+        Strictness::Strict
+    );
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::quiz_state::{Confidence, QuestionStatus};
+use ckad_practitioner::results::{DomainResult, QuestionResult, Score, SessionResult};
+use ckad_practitioner::serve;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+fn question(id: usize, outcome: QuestionStatus, submitted: Option<&str>) -> QuestionResult {
+    QuestionResult {
+        id,
+        uid: String::new(),
+        question: format!("Question {} <b>text</b>\nsecond line", id),
+        expected_answer: format!("kubectl run pod{} --image=nginx", id),
+        domain: Some("Design & Build".to_string()),
+        outcome,
+        time_spent_secs: 75,
+        hints_used: 1,
+        hints_viewed: vec![0],
+        tags: Vec::new(),
+        answer_submitted: submitted.map(str::to_string),
+        attempts: Vec::new(),
+        partial_credit: None,
+        points: if outcome == QuestionStatus::Pass {
+            1.0
+        } else {
+            0.0
+        },
+        confidence: Some(Confidence::High),
+    }
+}
+
+fn session(started_at: &str, percent: f64) -> SessionResult {
+    let started_at: DateTime<Utc> = started_at.parse().unwrap();
+    SessionResult {
+        started_at,
+        finished_at: started_at,
+        mode: Mode::Practice,
+        complete: true,
+        questions: vec![
+            question(
+                1,
+                QuestionStatus::Pass,
+                Some("kubectl run pod1 --image=nginx"),
+            ),
+            question(
+                2,
+                QuestionStatus::Fail,
+                Some("kubectl run pod2 --image=<nginx>"),
+            ),
+            question(3, QuestionStatus::Skipped, None),
+        ],
+        score: Score {
+            correct: 1,
+            total: 3,
+            points: 1.0,
+            possible_points: 3,
+            percent,
+        },
+        domains: vec![
+            DomainResult {
+                domain: "Design & Build".to_string(),
+                correct: 1,
+                total: 2,
+            },
+            DomainResult {
+                domain: "Deployment".to_string(),
+                correct: 0,
+                total: 1,
+            },
+        ],
+        break_secs: 0,
+        elapsed_secs: 0,
+    }
+}
+
+fn sessions() -> Vec<SessionResult> {
+    vec![
+        session("2024-05-01T09:00:00Z", 33.3),
+        session("2024-05-03T18:30:00Z", 66.7),
+    ]
+}
+
+#[test]
+fn the_index_lists_sessions_newest_first_with_links() {
+    let page = serve::route("GET", "/", &sessions());
+    assert_eq!(page.status, 200);
+    let newer = page
+        .body
+        .find("<a href=\"/sessions/1\">2024-05-03 18:30 UTC</a>")
+        .expect("newer session row");
+    let older = page
+        .body
+        .find("<a href=\"/sessions/0\">2024-05-01 09:00 UTC</a>")
+        .expect("older session row");
+    assert!(newer < older);
+    assert!(page
+        .body
+        .contains("<td>practice</td><td>1/3 (67%)</td><td>1/3</td><td>yes</td>"));
+}
+
+#[test]
+fn a_session_page_has_domain_bars_and_a_detail_per_question() {
+    let page = serve::route("GET", "/sessions/0", &sessions());
+    assert_eq!(page.status, 200);
+    let body = &page.body;
+    assert!(body.contains("<h1>Session 2024-05-01 09:00 UTC</h1>"));
+    assert!(body.contains(
+        "<tr><td>Design &amp; Build</td><td><div class=\"bar\"><span style=\"width: 50%\"></span></div></td><td>1/2</td></tr>"
+    ));
+    assert!(body.contains("<td>Deployment</td><td><div class=\"bar\"><span style=\"width: 0%\"></span></div></td><td>0/1</td>"));
+    assert_eq!(body.matches("<details>").count(), 3);
+    assert!(body.contains(
+        "<summary>#2 Question 2 &lt;b&gt;text&lt;/b&gt; &middot; <span class=\"fail\">fail</span> &middot; 1:15 &middot; 1 hint</summary>"
+    ));
+    assert!(body.contains("<h3>Your answer</h3><pre>kubectl run pod2 --image=&lt;nginx&gt;</pre>"));
+    assert!(body.contains("<h3>Model answer</h3><pre>kubectl run pod2 --image=nginx</pre>"));
+    assert!(body.contains("<h3>Your answer</h3><p><em>none</em></p>"));
+    assert!(body.contains("Confidence: high"));
+}
+
+#[test]
+fn unknown_paths_and_other_methods_are_refused() {
+    assert_eq!(serve::route("GET", "/sessions/2", &sessions()).status, 404);
+    assert_eq!(serve::route("GET", "/sessions/x", &sessions()).status, 404);
+    assert_eq!(serve::route("GET", "/etc/passwd", &sessions()).status, 404);
+    assert_eq!(serve::route("POST", "/", &sessions()).status, 405);
+    assert!(serve::route("GET", "/", &[])
+        .body
+        .contains("No saved sessions yet."));
+}
+
+#[tokio::test]
+async fn the_server_answers_on_localhost_until_shut_down() {
+    let listener = serve::bind().await.unwrap();
+    let address = listener.local_addr().unwrap();
+    assert!(address.ip().is_loopback());
+    assert_ne!(address.port(), 0);
+
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(serve::run(listener, sessions(), async {
+        let _ = stopped.await;
+    }));
+
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    stream
+        .write_all(b"GET /sessions/1 HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+    assert!(response.contains("content-type: text/html; charset=utf-8"));
+    assert!(response.contains("<h1>Session 2024-05-03 18:30 UTC</h1>"));
+
+    stop.send(()).unwrap();
+    server.await.unwrap().unwrap();
+}
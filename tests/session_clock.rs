@@ -0,0 +1,106 @@
+mod common;
+
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::models::Question;
+use ckad_practitioner::quiz_state::QuizState;
+use ckad_practitioner::timer::MockClock;
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::Event;
+use ratatui::{backend::TestBackend, Terminal};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn questions() -> Vec<Question> {
+    (1..=3)
+        .map(|id| Question {
+            id,
+            question: format!("Question {}", id),
+            answer: "kubectl get pods".to_string(),
+            time_limit_secs: 60,
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[test]
+fn the_session_clock_runs_across_questions_but_not_pauses() {
+    let clock = MockClock::new();
+    let mut quiz = QuizState::new(questions(), &QuizConfig::default());
+    quiz.set_clock(Arc::new(clock.clone()));
+
+    clock.advance(Duration::from_secs(20));
+    quiz.submit_answer(String::new(), true);
+    quiz.next_question();
+    clock.advance(Duration::from_secs(15));
+    assert_eq!(quiz.session_elapsed().as_secs(), 35);
+    assert_eq!(quiz.timer().elapsed().as_secs(), 15);
+
+    quiz.pause_timer();
+    clock.advance(Duration::from_secs(100));
+    quiz.resume_timer();
+    assert_eq!(quiz.session_elapsed().as_secs(), 35);
+
+    // A break the clocks ran through, as with strict exam breaks
+    clock.advance(Duration::from_secs(10));
+    quiz.exclude_break(Duration::from_secs(10));
+    assert_eq!(quiz.session_elapsed().as_secs(), 35);
+}
+
+#[test]
+fn the_session_clock_runs_while_a_question_waits_for_the_start_key() {
+    let clock = MockClock::new();
+    let config = QuizConfig {
+        ready_gate: true,
+        ..QuizConfig::default()
+    };
+    let mut quiz = QuizState::new(questions(), &config);
+    quiz.set_clock(Arc::new(clock.clone()));
+    assert!(quiz.is_armed());
+
+    clock.advance(Duration::from_secs(8));
+    assert_eq!(quiz.timer().elapsed().as_secs(), 0);
+    assert_eq!(quiz.session_elapsed().as_secs(), 8);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_header_shows_the_session_time_opposite_the_question_clock() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+    tokio::time::advance(Duration::from_secs(12)).await;
+    run_script(&mut app, &mut terminal, vec![Event::Key(key_event('s'))]).await;
+    tokio::time::advance(Duration::from_secs(71)).await;
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    let header = screen
+        .lines()
+        .find(|line| line.contains("TIME EXPIRED"))
+        .expect("header line");
+    assert!(
+        header.trim_end().ends_with("Session: 1:23 elapsed │"),
+        "{}",
+        header
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn narrow_terminals_drop_the_session_time_first() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(50, 30)).unwrap();
+    let screen = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(screen.contains("Time remaining: 0:30"), "{}", screen);
+    assert!(!screen.contains("Session:"), "{}", screen);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_results_record_the_session_time() {
+    let mut app = new_app_with(QuizConfig {
+        mode: Mode::Exam,
+        ..QuizConfig::default()
+    });
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    tokio::time::advance(Duration::from_secs(40)).await;
+    run_script(&mut app, &mut terminal, vec![Event::Key(key_event('s'))]).await;
+    tokio::time::advance(Duration::from_secs(5)).await;
+    run_script(&mut app, &mut terminal, vec![Event::Key(key_event('s'))]).await;
+    assert_eq!(app.session_result().elapsed_secs, 45);
+}
@@ -0,0 +1,185 @@
+use chrono::{Local, TimeZone, Utc};
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::playlist;
+use ckad_practitioner::settings::{self, FileConfig, Settings};
+use clap::Parser;
+
+const FILE: &str = r#"
+[defaults]
+mode = "drill"
+shuffle = true
+wrap_hints = true
+guided = true
+retry_wrong = true
+case_sensitive = false
+limit = 5
+tags = ["pods"]
+
+[timer]
+bell = true
+ready_gate = true
+reveal_delay_secs = 2
+start_screen = false
+"#;
+
+fn file() -> FileConfig {
+    let (file, warnings) = settings::parse_file_config(FILE).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    file
+}
+
+fn resolve(args: &[&str], file: &FileConfig) -> Settings {
+    let cli = Cli::parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()));
+    Settings::resolve(&cli, file).unwrap()
+}
+
+#[test]
+fn without_a_file_or_flags_the_built_in_defaults_apply() {
+    let settings = resolve(&[], &FileConfig::default());
+    let builtin = QuizConfig::default();
+    assert_eq!(settings.quiz.mode, builtin.mode);
+    assert_eq!(settings.quiz.shuffle_questions, builtin.shuffle_questions);
+    assert_eq!(settings.quiz.case_sensitive, builtin.case_sensitive);
+    assert_eq!(settings.quiz.bell, builtin.bell);
+    assert_eq!(settings.quiz.limit, None);
+    assert!(settings.start_countdown.is_some());
+}
+
+#[test]
+fn the_file_overrides_the_built_in_defaults() {
+    let settings = resolve(&[], &file());
+    assert_eq!(settings.quiz.mode, Mode::Drill);
+    assert!(settings.quiz.shuffle_questions);
+    assert!(settings.quiz.wrap_hints);
+    assert!(settings.quiz.guided);
+    assert!(settings.quiz.retry_wrong);
+    assert!(!settings.quiz.case_sensitive);
+    assert!(settings.quiz.bell);
+    assert!(settings.quiz.ready_gate);
+    assert_eq!(settings.quiz.limit, Some(5));
+    assert_eq!(settings.quiz.tags, ["pods"]);
+    assert_eq!(settings.quiz.reveal_delay_secs, 2);
+    assert_eq!(settings.start_countdown, None);
+}
+
+#[test]
+fn flags_override_the_file() {
+    let settings = resolve(
+        &[
+            "--mode",
+            "exam",
+            "--limit",
+            "2",
+            "--tags",
+            "secrets",
+            "--reveal-delay",
+            "0",
+        ],
+        &file(),
+    );
+    assert_eq!(settings.quiz.mode, Mode::Exam);
+    assert_eq!(settings.quiz.limit, Some(2));
+    assert_eq!(settings.quiz.tags, ["secrets"]);
+    assert_eq!(settings.quiz.reveal_delay_secs, 0);
+}
+
+#[test]
+fn no_flags_turn_off_what_the_file_turns_on() {
+    let settings = resolve(
+        &[
+            "--no-shuffle",
+            "--no-wrap-hints",
+            "--no-guided",
+            "--no-retry-wrong",
+            "--no-ignore-case",
+            "--no-bell",
+            "--no-ready-gate",
+            "--countdown",
+        ],
+        &file(),
+    );
+    assert!(!settings.quiz.shuffle_questions);
+    assert!(!settings.quiz.wrap_hints);
+    assert!(!settings.quiz.guided);
+    assert!(!settings.quiz.retry_wrong);
+    assert!(settings.quiz.case_sensitive);
+    assert!(!settings.quiz.bell);
+    assert!(!settings.quiz.ready_gate);
+    assert!(settings.start_countdown.is_some());
+}
+
+#[test]
+fn flags_turn_on_what_the_file_leaves_off() {
+    let settings = resolve(
+        &["--shuffle-hints", "--accommodations", "--confidence"],
+        &file(),
+    );
+    assert!(settings.quiz.shuffle_hints);
+    assert!(settings.quiz.accommodations);
+    assert!(settings.quiz.confidence_prompt);
+}
+
+#[test]
+fn the_last_of_a_flag_and_its_no_form_wins() {
+    let file = FileConfig::default();
+    assert!(!resolve(&["--bell", "--no-bell"], &file).quiz.bell);
+    assert!(resolve(&["--no-bell", "--bell"], &file).quiz.bell);
+    assert!(resolve(&["--no-countdown", "--countdown"], &file)
+        .start_countdown
+        .is_some());
+}
+
+#[test]
+fn resolving_reads_no_playlist_history_or_clock() {
+    // A playlist that doesn't exist, and a data dir that isn't there, resolve fine
+    let settings = resolve(
+        &["--playlist", "/nonexistent/plan.yaml"],
+        &FileConfig::default(),
+    );
+    assert_eq!(
+        settings.playlist_file.as_deref(),
+        Some(std::path::Path::new("/nonexistent/plan.yaml"))
+    );
+    assert!(settings.playlist.is_none());
+
+    let settings = resolve(&["--fresh-days", "3"], &FileConfig::default());
+    assert_eq!(settings.fresh_days, Some(3));
+    assert!(settings.quiz.fresh.is_none());
+
+    let settings = resolve(&["--daily"], &FileConfig::default());
+    assert!(settings.daily);
+    assert_eq!(settings.quiz.daily, None);
+}
+
+#[test]
+fn the_clock_dates_the_daily_question_and_the_fresh_window() {
+    let now = Local.with_ymd_and_hms(2024, 5, 10, 9, 30, 0).unwrap();
+    let settings = resolve(&["--daily"], &FileConfig::default()).with_clock(now, &[]);
+    assert_eq!(settings.quiz.daily, now.date_naive().into());
+    assert!(settings.quiz.fresh.is_none());
+
+    let settings = resolve(&["--fresh-days", "3"], &FileConfig::default()).with_clock(now, &[]);
+    let fresh = settings.quiz.fresh.expect("a fresh-days window");
+    assert_eq!(fresh.days, 3);
+    assert_eq!(fresh.now, now.with_timezone(&Utc));
+    assert!(fresh.last_seen.is_empty());
+    assert_eq!(settings.quiz.daily, None);
+}
+
+#[test]
+fn a_playlist_overrides_ordering_and_the_fresh_window() {
+    let plan = playlist::parse("sections:\n  - title: Day 1\n    questions: [pods/run]\n").unwrap();
+    let now = Local.with_ymd_and_hms(2024, 5, 10, 9, 30, 0).unwrap();
+    let file = settings::parse_file_config("[defaults]\nshuffle = true\nfresh_days = 3\n")
+        .unwrap()
+        .0;
+    let settings = resolve(&[], &file).with_playlist(plan).with_clock(now, &[]);
+    assert!(!settings.quiz.shuffle_questions);
+    assert!(settings.quiz.tags.is_empty());
+    assert_eq!(
+        settings.quiz.sections.get(&0).map(String::as_str),
+        Some("Day 1")
+    );
+    assert!(settings.quiz.fresh.is_none());
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use ckad_practitioner::app::{App, ExitReason};
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::events::ScriptedEvents;
+use ckad_practitioner::settings::{self, Settings};
+use clap::Parser;
+use common::{key_event, new_app, new_app_with, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+use std::time::Duration;
+
+fn new_terminal() -> Terminal<TestBackend> {
+    Terminal::new(TestBackend::new(120, 40)).expect("test terminal")
+}
+
+fn enter() -> Event {
+    Event::Key(KeyEvent::from(KeyCode::Enter))
+}
+
+fn remaining(app: &App) -> Duration {
+    app.session().quiz_state().timer().remaining()
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_first_clock_waits_for_enter_and_the_countdown() {
+    let mut app = new_app().with_start_screen(3);
+    let mut terminal = new_terminal();
+
+    // Long enough for the first question to have expired without the screen
+    tokio::time::advance(Duration::from_secs(60)).await;
+    let waiting = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(waiting.contains("Ready?"), "{}", waiting);
+    assert!(waiting.contains("2 questions, practice mode, 1:00 in total"));
+    assert!(waiting.contains("Press Enter to begin"));
+    assert!(!waiting.contains("Filters:"), "no filters are active");
+    assert!(!waiting.contains("Create a pod named alpha"));
+    assert_eq!(remaining(&app), Duration::from_secs(30));
+
+    let counting = run_script(&mut app, &mut terminal, vec![enter()]).await;
+    assert!(counting.contains("Starting in 3…"), "{}", counting);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    let counting = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(counting.contains("Starting in 2…"));
+    assert!(app.is_starting());
+
+    tokio::time::advance(Duration::from_secs(2)).await;
+    let started = run_script(&mut app, &mut terminal, vec![]).await;
+    assert!(!app.is_starting());
+    assert!(started.contains("Create a pod named alpha"), "{}", started);
+    assert_eq!(remaining(&app), Duration::from_secs(30));
+
+    tokio::time::advance(Duration::from_secs(5)).await;
+    run_script(&mut app, &mut terminal, vec![]).await;
+    assert_eq!(remaining(&app), Duration::from_secs(25));
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_start_screen_ignores_other_keys_but_quits() {
+    let mut app = new_app().with_start_screen(3);
+    let mut terminal = new_terminal();
+
+    let screen = run_script(&mut app, &mut terminal, vec![Event::Key(key_event('h'))]).await;
+    assert!(!screen.contains("First hint"));
+    assert!(app.is_starting());
+
+    let exit = app
+        .run_with(
+            &mut terminal,
+            ScriptedEvents::new(vec![Event::Key(key_event('q'))]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(exit, ExitReason::Quit);
+}
+
+#[tokio::test(start_paused = true)]
+async fn study_mode_has_no_clock_to_hold() {
+    let app = new_app_with(QuizConfig {
+        mode: Mode::Study,
+        ..QuizConfig::default()
+    })
+    .with_start_screen(3);
+    assert!(!app.is_starting());
+}
+
+fn start_countdown(config: &str, args: &[&str]) -> Option<u64> {
+    let (file, warnings) = settings::parse_file_config(config).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let cli = Cli::parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()));
+    Settings::resolve(&cli, &file).unwrap().start_countdown
+}
+
+#[test]
+fn the_countdown_is_on_by_default_and_no_countdown_skips_the_screen() {
+    assert_eq!(start_countdown("", &[]), Some(3));
+    assert_eq!(start_countdown("", &["--no-countdown"]), None);
+    assert_eq!(
+        start_countdown("[timer]\ncountdown_secs = 0\n", &[]),
+        Some(0)
+    );
+    assert_eq!(
+        start_countdown("[timer]\nstart_screen = false\n", &[]),
+        None
+    );
+}
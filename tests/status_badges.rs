@@ -0,0 +1,72 @@
+mod common;
+
+use ckad_practitioner::i18n::Messages;
+use ckad_practitioner::quiz_state::QuestionStatus;
+use ckad_practitioner::theme::Theme;
+use ckad_practitioner::ui::status_badge;
+use common::{key_event, new_app, run_script};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::style::{Color, Modifier};
+use ratatui::{backend::TestBackend, Terminal};
+
+const STATUSES: [(QuestionStatus, &str, Color); 5] = [
+    (QuestionStatus::Pass, "PASS", Color::Green),
+    (QuestionStatus::Fail, "FAIL", Color::Red),
+    (QuestionStatus::Skipped, "SKIP", Color::Yellow),
+    (QuestionStatus::Revealed, "REVEALED", Color::Blue),
+    (QuestionStatus::Pending, "PENDING", Color::DarkGray),
+];
+
+#[test]
+fn each_status_has_its_own_colored_badge() {
+    let messages = Messages::english();
+    for (status, text, color) in STATUSES {
+        let badge = status_badge(status, &Theme::new(true), &messages);
+        assert_eq!(badge.content, text, "{:?}", status);
+        assert_eq!(badge.style.fg, Some(color), "{:?}", status);
+        assert!(badge.style.add_modifier.contains(Modifier::BOLD));
+    }
+}
+
+#[test]
+fn without_colors_the_badge_is_bracketed() {
+    let messages = Messages::english();
+    for (status, text, _) in STATUSES {
+        let badge = status_badge(status, &Theme::new(false), &messages);
+        assert_eq!(badge.content, format!("[{}]", text), "{:?}", status);
+        assert_eq!(badge.style.fg, None);
+    }
+}
+
+#[test]
+fn badges_follow_the_locale() {
+    let messages = Messages::for_locale("de");
+    let badge = status_badge(QuestionStatus::Pass, &Theme::new(false), &messages);
+    assert_eq!(badge.content, "[RICHTIG]");
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_summary_table_shows_a_badge_per_question() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let mut events = vec![Event::Key(key_event('a'))];
+    events.extend(
+        "kubectl run alpha --image=nginx"
+            .chars()
+            .map(|c| Event::Key(key_event(c))),
+    );
+    events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+    events.push(Event::Key(key_event('n')));
+    events.push(Event::Key(key_event('s')));
+    let screen = run_script(&mut app, &mut terminal, events).await;
+    assert!(app.session().is_finished());
+
+    let row_of = |text: &str| {
+        screen
+            .lines()
+            .find(|row| row.contains(text))
+            .unwrap_or_else(|| panic!("no row with {:?} in\n{}", text, screen))
+    };
+    assert!(row_of("Create a pod named alpha").contains("PASS"));
+    assert!(row_of("Create a namespace named beta").contains("SKIP"));
+}
@@ -0,0 +1,51 @@
+use ckad_practitioner::theme::Theme;
+use ratatui::style::Style;
+use std::collections::HashSet;
+
+#[test]
+fn each_exam_domain_gets_its_own_tag_and_color() {
+    let theme = Theme::new(true);
+    let domains = [
+        "Application Design and Build",
+        "Application Deployment",
+        "Application Observability and Maintenance",
+        "Application Environment, Configuration and Security",
+        "Services and Networking",
+        "State Persistence",
+        "Core Concepts",
+    ];
+    let styles: Vec<_> = domains
+        .iter()
+        .map(|domain| theme.category_style(domain))
+        .collect();
+    let tags: HashSet<&str> = styles.iter().map(|style| style.tag.as_str()).collect();
+    let colors: HashSet<_> = styles.iter().map(|style| style.style.fg).collect();
+    assert_eq!(tags.len(), domains.len());
+    assert_eq!(colors.len(), domains.len());
+    assert!(styles.iter().all(|style| style.style.fg.is_some()));
+}
+
+#[test]
+fn short_and_differently_cased_names_map_like_the_full_domain() {
+    let theme = Theme::new(true);
+    assert_eq!(
+        theme.category_style("configuration"),
+        theme.category_style("Application Environment, Configuration and Security")
+    );
+    assert_eq!(theme.category_style("Design and Build").tag, "BUILD");
+    assert_eq!(theme.category_style("Observability").tag, "OBS");
+}
+
+#[test]
+fn unknown_categories_keep_their_name_and_the_default_style() {
+    let style = Theme::new(true).category_style(" Helm ");
+    assert_eq!(style.tag, "Helm");
+    assert_eq!(style.style, Style::default());
+}
+
+#[test]
+fn no_color_keeps_the_tags_but_drops_the_colors() {
+    let style = Theme::new(false).category_style("Configuration");
+    assert_eq!(style.tag, "CONF");
+    assert_eq!(style.style, Style::default());
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use ckad_practitioner::config::{Mode, QuizConfig};
+use ckad_practitioner::events::ScriptedEvents;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::plain;
+use ckad_practitioner::quiz_state::QuizState;
+use ckad_practitioner::session::Session;
+use ckad_practitioner::timer::{MockClock, TimeUse};
+use common::{key_event, new_app, new_app_with, screen_text};
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{backend::TestBackend, Terminal};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+fn question() -> Question {
+    Question {
+        id: 1,
+        question: "List the pods".to_string(),
+        answer: "kubectl get pods".to_string(),
+        time_limit_secs: 100,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn well_under_the_limit_reports_the_share_left() {
+    assert_eq!(TimeUse::classify(secs(60), secs(100)), TimeUse::Spare(40));
+    assert_eq!(TimeUse::classify(secs(0), secs(100)), TimeUse::Spare(100));
+    // The share left is rounded down
+    assert_eq!(
+        TimeUse::classify(Duration::from_millis(59_500), secs(100)),
+        TimeUse::Spare(40)
+    );
+}
+
+#[test]
+fn under_a_tenth_left_is_just_under() {
+    assert_eq!(TimeUse::classify(secs(90), secs(100)), TimeUse::Spare(10));
+    assert_eq!(
+        TimeUse::classify(Duration::from_millis(90_001), secs(100)),
+        TimeUse::JustUnder(Duration::from_millis(9_999))
+    );
+    assert_eq!(
+        TimeUse::classify(secs(99), secs(100)),
+        TimeUse::JustUnder(secs(1))
+    );
+}
+
+#[test]
+fn at_or_over_the_limit_used_all_the_time() {
+    assert_eq!(TimeUse::classify(secs(100), secs(100)), TimeUse::UsedAll);
+    assert_eq!(TimeUse::classify(secs(130), secs(100)), TimeUse::UsedAll);
+    assert_eq!(TimeUse::classify(secs(0), secs(0)), TimeUse::UsedAll);
+}
+
+#[test]
+fn the_feedback_reads_as_a_sentence() {
+    assert_eq!(
+        TimeUse::Spare(40).to_string(),
+        "Finished with 40% of the time to spare"
+    );
+    assert_eq!(
+        TimeUse::JustUnder(secs(4)).to_string(),
+        "Just made it, with 0:04 left"
+    );
+    assert_eq!(TimeUse::UsedAll.to_string(), "Used all the time");
+}
+
+#[test]
+fn only_resolved_questions_have_feedback() {
+    let clock = MockClock::new();
+    let mut quiz = QuizState::new(vec![question(), question()], &QuizConfig::default());
+    quiz.set_clock(Arc::new(clock.clone()));
+    // Half a second short, as the mock clock starts a moment behind
+    clock.advance(Duration::from_millis(29_500));
+    assert_eq!(quiz.time_use(0), None);
+
+    quiz.submit_answer("kubectl get pods".to_string(), true);
+    assert_eq!(quiz.time_use(0), Some(TimeUse::Spare(70)));
+    quiz.next_question();
+    assert_eq!(quiz.time_use(1), None);
+
+    clock.advance(secs(100));
+    assert_eq!(quiz.time_use(1), Some(TimeUse::UsedAll));
+
+    let study = QuizConfig {
+        mode: Mode::Study,
+        ..QuizConfig::default()
+    };
+    assert_eq!(QuizState::new(vec![question()], &study).time_use(0), None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn the_answer_panel_shows_the_feedback_until_the_next_question() {
+    let mut app = new_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    tokio::time::advance(secs(12)).await;
+    let mut events = vec![Event::Key(key_event('a'))];
+    events.extend(
+        "kubectl run alpha --image=nginx"
+            .chars()
+            .map(|c| Event::Key(key_event(c))),
+    );
+    events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+    app.run_with(&mut terminal, ScriptedEvents::new(events))
+        .await
+        .unwrap();
+    let screen = screen_text(&terminal);
+    // 12 of 30 seconds used
+    assert!(
+        screen.contains("Finished with 60% of the time to spare"),
+        "{}",
+        screen
+    );
+
+    app.run_with(
+        &mut terminal,
+        ScriptedEvents::new(vec![Event::Key(key_event('n'))]),
+    )
+    .await
+    .unwrap();
+    assert!(!screen_text(&terminal).contains("to spare"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn running_out_of_time_says_so() {
+    let mut app = new_app_with(QuizConfig::default());
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    tokio::time::advance(secs(31)).await;
+    app.run_with(&mut terminal, ScriptedEvents::new(vec![]))
+        .await
+        .unwrap();
+    let screen = screen_text(&terminal);
+    assert!(screen.contains("Used all the time"), "{}", screen);
+}
+
+#[tokio::test(start_paused = true)]
+async fn plain_mode_prints_the_feedback_after_the_answer() {
+    let mut session = Session::new(vec![question()], QuizConfig::default()).unwrap();
+    let mut out = Vec::new();
+    plain::run(&mut session, "kubectl get pods\n".as_bytes(), &mut out)
+        .await
+        .unwrap();
+    let output = String::from_utf8(out).unwrap();
+    assert!(
+        output.contains(
+            "[PASS] Answer:\n  kubectl get pods\nFinished with 100% of the time to spare"
+        ),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,211 @@
+use ckad_practitioner::cli::Cli;
+use ckad_practitioner::settings::{self, Settings};
+use ckad_practitioner::timer::{flash_phase, FrameRate, IdleWatch, MockClock, Timer, FLASH_SECS};
+use clap::Parser;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn flash_phase_alternates_each_second() {
+    let phases: Vec<Option<bool>> = (0..FLASH_SECS)
+        .map(|secs| flash_phase(Duration::from_millis(secs * 1000 + 500)))
+        .collect();
+    for pair in phases.windows(2) {
+        assert!(pair[0].is_some() && pair[1].is_some());
+        assert_ne!(pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn flash_phase_is_off_outside_the_last_seconds() {
+    assert_eq!(flash_phase(Duration::from_secs(FLASH_SECS)), None);
+    assert_eq!(flash_phase(Duration::from_secs(30)), None);
+    assert_eq!(flash_phase(Duration::ZERO), None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn paused_timer_does_not_count_the_break() {
+    let mut timer = Timer::new(60);
+    tokio::time::advance(Duration::from_secs(10)).await;
+    timer.pause();
+    tokio::time::advance(Duration::from_secs(120)).await;
+    assert_eq!(timer.elapsed(), Duration::from_secs(10));
+    assert!(!timer.is_expired());
+
+    timer.resume();
+    tokio::time::advance(Duration::from_secs(5)).await;
+    assert_eq!(timer.remaining(), Duration::from_secs(45));
+}
+
+#[tokio::test(start_paused = true)]
+async fn reveal_is_pending_until_the_delay_has_passed() {
+    let timer = Timer::new(30);
+    assert!(
+        !timer.reveal_pending(5),
+        "nothing to hold back before expiry"
+    );
+
+    tokio::time::advance(Duration::from_secs(30)).await;
+    assert!(timer.reveal_pending(5));
+    assert!(
+        !timer.reveal_pending(0),
+        "no delay shows the answer at expiry"
+    );
+
+    tokio::time::advance(Duration::from_millis(4_999)).await;
+    assert!(timer.reveal_pending(5));
+    tokio::time::advance(Duration::from_millis(1)).await;
+    assert!(!timer.reveal_pending(5));
+}
+
+#[test]
+fn mock_clock_drives_expiry_and_remaining_time() {
+    let clock = MockClock::new();
+    let timer = Timer::with_clock(30, Arc::new(clock.clone()));
+    assert_eq!(timer.remaining(), Duration::from_secs(30));
+    assert!(!timer.is_expired());
+
+    clock.advance(Duration::from_millis(29_999));
+    assert_eq!(timer.remaining(), Duration::from_millis(1));
+    assert!(!timer.is_expired());
+    assert_eq!(timer.flash_phase(), Some(true));
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(timer.remaining(), Duration::ZERO);
+    assert!(timer.is_expired());
+
+    clock.advance(Duration::from_secs(4));
+    assert_eq!(timer.overtime(), Duration::from_secs(4));
+}
+
+#[test]
+fn reset_restarts_the_mock_clock_with_a_new_limit() {
+    let clock = MockClock::new();
+    let mut timer = Timer::with_clock(10, Arc::new(clock.clone()));
+    clock.advance(Duration::from_secs(15));
+    assert!(timer.is_expired());
+
+    timer.reset(20);
+    assert!(!timer.is_expired());
+    assert_eq!(timer.limit(), Duration::from_secs(20));
+    assert_eq!(timer.remaining(), Duration::from_secs(20));
+    clock.advance(Duration::from_secs(20));
+    assert!(timer.is_expired());
+}
+
+#[test]
+fn pause_and_backdating_follow_the_mock_clock() {
+    let clock = MockClock::new();
+    let mut timer =
+        Timer::with_clock(60, Arc::new(clock.clone())).backdated(Duration::from_secs(50));
+    assert_eq!(timer.remaining(), Duration::from_secs(10));
+
+    timer.pause();
+    clock.advance(Duration::from_secs(300));
+    assert!(!timer.is_expired());
+
+    timer.resume();
+    clock.advance(Duration::from_secs(10));
+    assert!(timer.is_expired());
+}
+
+#[test]
+fn extending_a_running_timer_adds_to_the_limit_without_restarting() {
+    let clock = MockClock::new();
+    let mut timer = Timer::with_clock(30, Arc::new(clock.clone()));
+    clock.advance(Duration::from_secs(20));
+
+    timer.extend(60);
+    assert_eq!(timer.limit(), Duration::from_secs(90));
+    assert_eq!(timer.elapsed(), Duration::from_secs(20));
+    assert_eq!(timer.remaining(), Duration::from_secs(70));
+
+    clock.advance(Duration::from_secs(69));
+    assert!(!timer.is_expired());
+    clock.advance(Duration::from_secs(1));
+    assert!(timer.is_expired());
+}
+
+#[test]
+fn extending_an_expired_timer_reopens_it_only_past_the_overtime() {
+    let clock = MockClock::new();
+    let mut timer = Timer::with_clock(30, Arc::new(clock.clone()));
+    clock.advance(Duration::from_secs(45));
+    assert_eq!(timer.overtime(), Duration::from_secs(15));
+
+    // Not enough to cover the overtime
+    timer.extend(10);
+    assert!(timer.is_expired());
+    assert_eq!(timer.overtime(), Duration::from_secs(5));
+
+    timer.extend(10);
+    assert!(!timer.is_expired());
+    assert_eq!(timer.remaining(), Duration::from_secs(5));
+    assert_eq!(timer.overtime(), Duration::ZERO);
+}
+
+#[test]
+fn idle_watch_flips_once_after_the_threshold_without_input() {
+    let clock = MockClock::new();
+    let mut idle = IdleWatch::with_clock(Duration::from_secs(120), Arc::new(clock.clone()));
+
+    clock.advance(Duration::from_secs(119));
+    assert!(!idle.check());
+    assert!(!idle.is_idle());
+
+    clock.advance(Duration::from_secs(1));
+    assert!(idle.check());
+    assert!(idle.is_idle());
+    clock.advance(Duration::from_secs(600));
+    assert!(!idle.check(), "reported once per idle stretch");
+
+    assert!(idle.input(), "the next key press ends the stretch");
+    assert!(!idle.is_idle());
+    clock.advance(Duration::from_secs(119));
+    assert!(!idle.check());
+}
+
+#[test]
+fn input_before_the_threshold_restarts_the_countdown() {
+    let clock = MockClock::new();
+    let mut idle = IdleWatch::with_clock(Duration::from_secs(120), Arc::new(clock.clone()));
+    clock.advance(Duration::from_secs(100));
+    assert!(!idle.input());
+    clock.advance(Duration::from_secs(100));
+    assert!(!idle.check());
+    clock.advance(Duration::from_secs(20));
+    assert!(idle.check());
+}
+
+#[test]
+fn frame_rates_convert_to_frame_periods_and_clamp() {
+    assert_eq!(
+        FrameRate::default().frame_period(),
+        Duration::from_millis(50)
+    );
+    assert_eq!(
+        FrameRate::new(30).frame_period(),
+        Duration::from_nanos(33_333_333)
+    );
+    assert_eq!(
+        FrameRate::new(60).frame_period(),
+        Duration::from_nanos(16_666_666)
+    );
+
+    assert_eq!(FrameRate::new(0).fps(), FrameRate::MIN_FPS);
+    assert_eq!(FrameRate::new(1).frame_period(), Duration::from_millis(200));
+    assert_eq!(FrameRate::new(240).fps(), FrameRate::MAX_FPS);
+}
+
+#[test]
+fn the_fps_flag_overrides_the_config() {
+    let frame_rate = |config: &str, args: &[&str]| {
+        let (file, _) = settings::parse_file_config(config).unwrap();
+        let cli = Cli::parse_from(std::iter::once("ckad-practitioner").chain(args.iter().copied()));
+        Settings::resolve(&cli, &file).unwrap().frame_rate
+    };
+    assert_eq!(frame_rate("", &[]), FrameRate::default());
+    assert_eq!(frame_rate("[ui]\nfps = 10\n", &[]).fps(), 10);
+    assert_eq!(frame_rate("[ui]\nfps = 10\n", &["--fps", "30"]).fps(), 30);
+    assert_eq!(frame_rate("", &["--fps", "1000"]).fps(), 60);
+}
@@ -0,0 +1,232 @@
+use chrono::Utc;
+use ckad_practitioner::app::{ExitReason, Outcome};
+use ckad_practitioner::events::ScriptedEvents;
+use ckad_practitioner::models::Question;
+use ckad_practitioner::stats::{self, TypingTrend};
+use ckad_practitioner::theme::Theme;
+use ckad_practitioner::typing::{
+    self, CharState, TypingAttempt, TypingResult, TypingRun, TypingTarget,
+};
+use ckad_practitioner::typing_app::TypingApp;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use std::fs;
+use std::time::Duration;
+
+fn target(command: &str) -> TypingTarget {
+    TypingTarget {
+        question_id: 1,
+        command: command.to_string(),
+    }
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// Types `text` one character a second
+async fn type_slowly(run: &mut TypingRun, text: &str) {
+    for c in text.chars() {
+        run.push(c);
+        tokio::time::advance(Duration::from_secs(1)).await;
+    }
+}
+
+#[test]
+fn comparison_marks_each_position() {
+    use CharState::*;
+    assert_eq!(typing::compare("kubectl", ""), vec![Pending; 7]);
+    assert_eq!(
+        typing::compare("get po", "gat"),
+        vec![Correct, Wrong, Correct, Pending, Pending, Pending]
+    );
+    assert_eq!(typing::compare("ls", "lsx"), vec![Correct, Correct, Extra]);
+    // Characters, not bytes
+    assert_eq!(typing::compare("→a", "→b"), vec![Correct, Wrong]);
+}
+
+#[test]
+fn uncorrected_errors_count_wrong_missing_and_extra_characters() {
+    assert_eq!(typing::uncorrected_errors("kubectl", "kubectl"), 0);
+    assert_eq!(typing::uncorrected_errors("kubectl", "kubactl"), 1);
+    assert_eq!(typing::uncorrected_errors("kubectl", "kube"), 3);
+    assert_eq!(typing::uncorrected_errors("kubectl", "kubectl  "), 2);
+}
+
+#[test]
+fn wpm_counts_five_characters_as_a_word() {
+    assert_eq!(typing::wpm(50, Duration::from_secs(60)), 10.0);
+    assert_eq!(typing::wpm(25, Duration::from_secs(15)), 20.0);
+    assert_eq!(typing::wpm(10, Duration::ZERO), 0.0);
+}
+
+#[test]
+fn accuracy_is_the_share_of_right_keystrokes() {
+    assert_eq!(typing::accuracy(0, 0), 100.0);
+    assert_eq!(typing::accuracy(20, 5), 75.0);
+    assert_eq!(typing::accuracy(3, 7), 0.0);
+}
+
+#[test]
+fn targets_are_the_command_lines_of_non_manifest_answers() {
+    let questions = vec![
+        Question {
+            id: 1,
+            answer: "kubectl create deployment web --image=nginx\n\n# then\nkubectl expose deployment web --port=80".into(),
+            ..Default::default()
+        },
+        Question {
+            id: 2,
+            answer: "apiVersion: v1\nkind: Pod".into(),
+            yaml_answer: true,
+            ..Default::default()
+        },
+        Question {
+            id: 3,
+            answer: "  kubectl get pods  ".into(),
+            ..Default::default()
+        },
+    ];
+    let targets = typing::targets(&questions);
+    let listed: Vec<(usize, &str)> = targets
+        .iter()
+        .map(|target| (target.question_id, target.command.as_str()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            (1, "kubectl create deployment web --image=nginx"),
+            (1, "kubectl expose deployment web --port=80"),
+            (3, "kubectl get pods"),
+        ]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_run_times_each_command_from_its_first_keystroke() {
+    let mut run = TypingRun::new(vec![target("get po"), target("ls")]);
+    tokio::time::advance(Duration::from_secs(30)).await;
+    assert_eq!(run.elapsed(), Duration::ZERO, "reading time doesn't count");
+
+    // One typo, corrected
+    type_slowly(&mut run, "gx").await;
+    run.backspace();
+    type_slowly(&mut run, "et po").await;
+    assert_eq!(
+        run.current_index(),
+        1,
+        "an exact match finishes the command"
+    );
+
+    let attempt = &run.attempts()[0];
+    assert_eq!(attempt.typed, "get po");
+    assert_eq!(attempt.typos, 1);
+    assert_eq!(attempt.keystrokes, 7);
+    assert_eq!(attempt.errors, 0);
+    // The last keystroke ends the clock: 6 seconds between 7 keystrokes
+    assert_eq!(attempt.elapsed(), Duration::from_secs(6));
+    assert!((attempt.wpm() - 12.0).abs() < 1e-9);
+
+    // Enter finishes early, leaving the missing character as an error
+    run.finish_current();
+    assert_eq!(run.attempts().len(), 1, "Enter before typing does nothing");
+    type_slowly(&mut run, "l").await;
+    run.finish_current();
+    assert!(run.is_finished());
+    assert_eq!(run.attempts()[1].errors, 1);
+    assert_eq!(run.result().errors(), 1);
+}
+
+#[test]
+fn results_aggregate_speed_over_all_commands() {
+    let attempt = |typed: &str, secs: u64, typos, errors| TypingAttempt {
+        question_id: 1,
+        command: typed.to_string(),
+        typed: typed.to_string(),
+        elapsed_ms: secs * 1000,
+        typos,
+        keystrokes: typed.len(),
+        errors,
+    };
+    let result = TypingResult {
+        started_at: Utc::now(),
+        attempts: vec![attempt("0123456789", 6, 1, 0), attempt("01234", 3, 0, 2)],
+    };
+    // 15 characters in 9 seconds
+    assert_eq!(result.wpm(), 20.0);
+    assert_eq!(result.errors(), 2);
+    assert!((result.accuracy() - 93.33).abs() < 0.01);
+    assert_eq!(
+        result.summary(),
+        "20 WPM, 93% accuracy, 2 errors over 2 commands"
+    );
+}
+
+#[test]
+fn typing_trend_tracks_best_and_recent_speed() {
+    let dir = std::env::temp_dir().join(format!("ckad-typing-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(
+        TypingTrend::from_history(&stats::load_typing_history(&dir)),
+        TypingTrend::default()
+    );
+
+    // 60 characters over (12 - n) seconds for n in 0..7: 60 up to 120 WPM
+    for n in 0..7u64 {
+        let result = TypingResult {
+            started_at: Utc::now() + chrono::Duration::seconds(n as i64),
+            attempts: vec![TypingAttempt {
+                question_id: 1,
+                command: "x".repeat(60),
+                typed: "x".repeat(60),
+                elapsed_ms: (12 - n) * 1000,
+                typos: 0,
+                keystrokes: 60,
+                errors: 0,
+            }],
+        };
+        typing::write(&dir.join(result.file_name()), &result).unwrap();
+    }
+    let trend = TypingTrend::from_history(&stats::load_typing_history(&dir));
+    assert_eq!(trend.sessions, 7);
+    assert_eq!(trend.best_wpm, 120.0);
+    // The last five: 12 - n seconds for n = 2..=6
+    let recent: f64 = (2..=6).map(|n| 720.0 / (12 - n) as f64).sum::<f64>() / 5.0;
+    assert!((trend.recent_wpm - recent).abs() < 1e-9);
+}
+
+#[tokio::test(start_paused = true)]
+async fn typing_screen_marks_mistakes_and_ends_on_a_summary() {
+    let mut app = TypingApp::new(
+        vec![target("kubectl get po")],
+        "mock (1 Qs)".into(),
+        Theme::new(false),
+        TypingTrend::default(),
+    );
+    let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+    let typed: Vec<Event> = "kubectx"
+        .chars()
+        .map(|c| Event::Key(key(KeyCode::Char(c))))
+        .collect();
+    app.run_with(&mut terminal, ScriptedEvents::new(typed))
+        .await
+        .unwrap();
+    let screen = format!("{:?}", terminal.backend().buffer());
+    assert!(screen.contains("Typing benchmark - command 1 of 1"));
+    assert!(screen.contains("Type: kubectx_"));
+    assert!(screen.contains("1 typos"));
+    assert!(screen.contains("First benchmark: no history yet"));
+
+    assert_eq!(app.handle_key(key(KeyCode::Enter)), Outcome::Redraw);
+    assert!(app.typing_run().is_finished());
+    let reason = app
+        .run_with(&mut terminal, ScriptedEvents::new(vec![]))
+        .await
+        .unwrap();
+    assert_eq!(reason, ExitReason::Quit);
+    let screen = format!("{:?}", terminal.backend().buffer());
+    assert!(screen.contains("kubectl get po"));
+    assert!(screen.contains("errors left"));
+    assert_eq!(app.result().attempts[0].errors, 8);
+    assert_eq!(app.handle_key(key(KeyCode::Enter)), Outcome::Exit);
+}
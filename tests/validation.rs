@@ -0,0 +1,167 @@
+use ckad_practitioner::models::{Difficulty, Question, QuestionGrading, RubricItem};
+use ckad_practitioner::question_repository::{
+    InMemoryQuestionRepository, QuestionFormat, QuestionRepository,
+};
+use ckad_practitioner::validation::{self, ValidationError};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+fn errors(yaml: &str) -> Vec<String> {
+    validation::validate_str(yaml, QuestionFormat::Yaml)
+        .iter()
+        .map(ValidationError::to_string)
+        .collect()
+}
+
+fn keys(object: &Value) -> BTreeSet<String> {
+    object.as_object().unwrap().keys().cloned().collect()
+}
+
+#[test]
+fn the_schema_describes_every_question_field() {
+    let question = Question {
+        uid: Some(String::new()),
+        context: Some(String::new()),
+        setup: Some(String::new()),
+        yaml_answer: true,
+        accepted_answers: Some(Vec::new()),
+        answer_pattern: Some(String::new()),
+        rubric: Some(vec![RubricItem {
+            item: "run".to_string(),
+            points: 1,
+        }]),
+        grading: Some(QuestionGrading::Rubric),
+        case_sensitive: Some(true),
+        points: Some(1),
+        category: Some(String::new()),
+        tags: vec![String::new()],
+        difficulty: Some(Difficulty::Easy),
+        depends_on: Some(Vec::new()),
+        priority: Some(1),
+        related: Some(Vec::new()),
+        references: Some(Vec::new()),
+        notes: Some(String::new()),
+        author: Some(String::new()),
+        source: Some(String::new()),
+        ..Default::default()
+    };
+    let serialized = serde_json::to_value(&question).unwrap();
+    let schema = validation::schema();
+    let item = &schema["items"];
+    assert_eq!(keys(&item["properties"]), keys(&serialized));
+    assert_eq!(
+        keys(&item["properties"]["rubric"]["items"]["properties"]),
+        keys(&serialized["rubric"][0])
+    );
+}
+
+#[test]
+fn the_built_in_bank_and_the_fixture_bank_are_valid() {
+    let questions = InMemoryQuestionRepository.get_questions().unwrap();
+    let bank = serde_json::to_value(&questions).unwrap();
+    assert_eq!(validation::validate_value(&bank), Vec::new());
+    assert_eq!(
+        validation::validate_file(Path::new("tests/fixtures/list-bank.yaml")).unwrap(),
+        Vec::new()
+    );
+}
+
+#[test]
+fn schema_errors_point_at_the_offending_value() {
+    let yaml = r#"
+- id: 1
+  question: Create a pod
+  hints: "use kubectl run"
+  answer: kubectl run web --image=nginx
+  time_limit_secs: 0
+  difficulty: extreme
+  tag: [pods]
+- id: 2
+  question: ""
+  hints: [one, 2]
+  time_limit_secs: 60
+"#;
+    assert_eq!(
+        errors(yaml),
+        vec![
+            "$[0].difficulty: expected one of \"easy\", \"medium\", \"hard\", found \"extreme\"",
+            "$[0].hints: expected an array, found a string",
+            "$[0].tag: unknown field",
+            "$[0].time_limit_secs: must be at least 1",
+            "$[1]: missing required field `answer`",
+            "$[1].hints[1]: expected a string, found an integer",
+            "$[1].question: must not be empty",
+        ]
+    );
+}
+
+#[test]
+fn entries_that_fit_the_schema_get_the_quiz_rules_and_hint_lint() {
+    let yaml = r#"
+- id: 1
+  question: Create a pod
+  hints: [kubectl run web --image=nginx, "  ", Use kubectl run, Use kubectl run]
+  answer: kubectl run web --image=nginx
+  answer_pattern: "kubectl run ("
+  time_limit_secs: 60
+  depends_on: [1, 7]
+- id: 1
+  question: Write the manifest
+  hints: []
+  answer: "kind: [Pod"
+  yaml_answer: true
+  time_limit_secs: 60
+"#;
+    let errors = errors(yaml);
+    assert_eq!(errors[0], "$[1].id: id 1 is already used by $[0]");
+    assert!(errors[1].starts_with("$[0].answer_pattern: the answer pattern is not a valid regex"));
+    assert_eq!(
+        errors[2..5],
+        [
+            "$[0].hints[0]: the first hint is the whole answer",
+            "$[0].hints[1]: the hint is blank",
+            "$[0].hints[3]: the hint repeats hints[2]",
+        ]
+    );
+    assert_eq!(
+        errors[5..7],
+        [
+            "$[0].depends_on[0]: a question can't depend on itself",
+            "$[0].depends_on[1]: no question with id 7",
+        ]
+    );
+    assert!(errors[7].starts_with("$[1].answer: the answer is not valid YAML"));
+    assert_eq!(errors.len(), 8, "{:#?}", errors);
+}
+
+#[test]
+fn unparseable_files_are_one_error_at_the_root() {
+    let errors = validation::validate_str("[{\"id\": 1,", QuestionFormat::Json);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, "$");
+}
+
+#[test]
+fn json_lines_banks_are_checked_as_a_list_of_their_lines() {
+    let bank = concat!(
+        r#"{"id": 1, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}"#,
+        "\n\n",
+        r#"{"id": 1, "question": "Q", "hints": [], "answer": "A", "time_limit_secs": 10}"#,
+        "\n",
+    );
+    let errors = validation::validate_str(bank, QuestionFormat::JsonLines);
+    assert_eq!(errors.len(), 1, "{:#?}", errors);
+    assert_eq!(
+        errors[0].to_string(),
+        "$[1].id: id 1 is already used by $[0]"
+    );
+
+    let errors = validation::validate_str("{\"id\": 1}\n[", QuestionFormat::JsonLines);
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].to_string().starts_with("$: line 2: "),
+        "{:?}",
+        errors
+    );
+}
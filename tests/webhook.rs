@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use ckad_practitioner::config::Mode;
+use ckad_practitioner::results::{Score, SessionResult};
+use ckad_practitioner::webhook::{self, Posted, WebhookSettings};
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// A request the mock endpoint received
+#[derive(Debug, Clone)]
+struct Received {
+    authorization: Option<String>,
+    body: String,
+}
+
+/// Answers with `statuses` in turn (the last one repeats) and records every
+/// request; returns the endpoint URL
+async fn mock_endpoint(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<Received>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/results", listener.local_addr().unwrap());
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&received);
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (log, statuses) = (Arc::clone(&log), statuses.clone());
+            tokio::spawn(async move {
+                let service = service_fn(move |request: Request<Incoming>| {
+                    let (log, statuses) = (Arc::clone(&log), statuses.clone());
+                    async move {
+                        let authorization = request
+                            .headers()
+                            .get(hyper::header::AUTHORIZATION)
+                            .map(|value| value.to_str().unwrap().to_string());
+                        let body = request.into_body().collect().await.unwrap().to_bytes();
+                        let mut log = log.lock().unwrap();
+                        let status = statuses[log.len().min(statuses.len() - 1)];
+                        log.push(Received {
+                            authorization,
+                            body: String::from_utf8(body.to_vec()).unwrap(),
+                        });
+                        let mut response = Response::new(Full::new(Bytes::new()));
+                        *response.status_mut() = StatusCode::from_u16(status).unwrap();
+                        Ok::<_, Infallible>(response)
+                    }
+                });
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    });
+    (url, received)
+}
+
+fn settings(url: &str) -> WebhookSettings {
+    WebhookSettings::new(url)
+        .with_token(Some("study-group".to_string()))
+        .with_backoff(Duration::from_millis(1))
+}
+
+fn session() -> SessionResult {
+    let started_at: DateTime<Utc> = "2024-05-01T10:00:00Z".parse().unwrap();
+    SessionResult {
+        started_at,
+        finished_at: started_at,
+        mode: Mode::Practice,
+        complete: true,
+        questions: Vec::new(),
+        score: Score {
+            correct: 0,
+            total: 0,
+            points: 0.0,
+            possible_points: 0,
+            percent: 0.0,
+        },
+        domains: Vec::new(),
+        break_secs: 0,
+        elapsed_secs: 0,
+    }
+}
+
+fn temp_outbox(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ckad-webhook-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[tokio::test]
+async fn results_are_posted_as_json_with_the_bearer_token() {
+    let (url, received) = mock_endpoint(vec![200]).await;
+    let outbox = temp_outbox("success");
+
+    let posted = webhook::post_session(&settings(&url), &session(), &outbox)
+        .await
+        .unwrap();
+
+    assert!(matches!(posted, Posted::Delivered), "{:?}", posted);
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        received[0].authorization.as_deref(),
+        Some("Bearer study-group")
+    );
+    let body: SessionResult = serde_json::from_str(&received[0].body).unwrap();
+    assert_eq!(body.started_at, session().started_at);
+    assert!(!outbox.exists());
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_until_the_post_succeeds() {
+    let (url, received) = mock_endpoint(vec![500, 503, 200]).await;
+    let outbox = temp_outbox("retry");
+
+    let posted = webhook::post_session(&settings(&url), &session(), &outbox)
+        .await
+        .unwrap();
+
+    assert!(matches!(posted, Posted::Delivered), "{:?}", posted);
+    assert_eq!(received.lock().unwrap().len(), 3);
+    assert!(!outbox.exists());
+}
+
+#[tokio::test]
+async fn undeliverable_results_wait_in_the_outbox_until_flushed() {
+    let (down, received) = mock_endpoint(vec![500]).await;
+    let outbox = temp_outbox("outbox");
+
+    let posted = webhook::post_session(&settings(&down), &session(), &outbox)
+        .await
+        .unwrap();
+
+    let Posted::Queued { path, error } = posted else {
+        panic!("expected the payload to be queued");
+    };
+    assert_eq!(error.to_string(), "the webhook answered HTTP 500");
+    assert_eq!(received.lock().unwrap().len(), 3, "one try and two retries");
+    assert_eq!(path, outbox.join("session-20240501T100000Z.json"));
+    let queued = std::fs::read_to_string(&path).unwrap();
+
+    let (up, received) = mock_endpoint(vec![200]).await;
+    let report = webhook::flush_outbox(&settings(&up), &outbox)
+        .await
+        .unwrap();
+
+    assert_eq!(report.delivered, vec![path.clone()]);
+    assert!(report.failed.is_empty());
+    assert_eq!(received.lock().unwrap()[0].body, queued);
+    assert!(!path.exists());
+    let _ = std::fs::remove_dir_all(&outbox);
+}